@@ -1,10 +1,16 @@
 // blockchain-core/src/block.rs
-use crate::{types::*, transaction::Transaction, BlockchainError, BlockchainResult};
-use blockchain_crypto::{hash::Hashable, Address, Hash, MerkleTree};
+use crate::{clock::{Clock, SystemClock}, config, evidence::SignedHeader, types::*, transaction::Transaction, BlockchainError, BlockchainResult};
+use blockchain_crypto::{hash::Hashable, Address, Hash, HashAlgorithm, KeyPair, MerkleTree, PublicKey, Signature};
 use serde::{Deserialize, Serialize};
 
+/// Domain separator for header signatures
+const HEADER_DOMAIN: &[u8] = b"POAS_BLOCK_HEADER_V1";
+
+/// Domain separator for vote signatures
+const VOTE_DOMAIN: &[u8] = b"POAS_FINALITY_VOTE_V1";
+
 /// Block header containing metadata
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct BlockHeader {
     /// Block number/height
     pub number: BlockNumber,
@@ -16,6 +22,8 @@ pub struct BlockHeader {
     pub state_root: Hash,
     /// Block timestamp
     pub timestamp: Timestamp,
+    /// Slot the block was proposed in, counted from genesis
+    pub slot: u64,
     /// Block proposer (validator)
     pub proposer: Address,
     /// Gas limit for this block
@@ -24,13 +32,24 @@ pub struct BlockHeader {
     pub gas_used: Gas,
     /// Extra data (can include validator signatures, etc.)
     pub extra_data: Vec<u8>,
+    /// Proof that the proposer was elected for this block's slot
+    pub election: Option<ProposerElection>,
+    /// Hash of the next epoch's validator set, committed in the last block of each epoch
+    pub next_validators_hash: Option<Hash>,
 }
 
 impl BlockHeader {
-    /// Calculate header hash
+    /// Calculate header hash with the chain's hash algorithm
     pub fn hash(&self) -> Hash {
-        let bytes = bincode::serialize(self).unwrap();
-        bytes.hash()
+        self.hash_with(config::hash_algorithm())
+    }
+
+    /// Bytes the proposer signs for this header
+    pub fn signing_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(HEADER_DOMAIN.len() + 32);
+        bytes.extend_from_slice(HEADER_DOMAIN);
+        bytes.extend_from_slice(self.hash().as_bytes());
+        bytes
     }
 
     /// Validate header basic properties
@@ -68,6 +87,16 @@ impl BlockHeader {
     }
 }
 
+impl Hashable for BlockHeader {
+    fn hash(&self) -> Hash {
+        self.hash_with(config::hash_algorithm())
+    }
+
+    fn hash_with(&self, algorithm: HashAlgorithm) -> Hash {
+        config::hash_encoded(self, algorithm)
+    }
+}
+
 /// Complete block structure
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Block {
@@ -75,8 +104,10 @@ pub struct Block {
     pub header: BlockHeader,
     /// List of transactions
     pub transactions: Vec<Transaction>,
-    /// Validator signatures (for PoAS consensus)
+    /// Precommit signatures over the parent block (its quorum certificate)
     pub validator_signatures: Vec<ValidatorSignature>,
+    /// Proposer's signature over the header's signing bytes
+    pub proposer_signature: Option<Signature>,
 }
 
 impl Block {
@@ -88,6 +119,19 @@ impl Block {
         proposer: Address,
         transactions: Vec<Transaction>,
         gas_limit: Gas,
+    ) -> BlockchainResult<Self> {
+        Self::new_with_clock(number, parent_hash, state_root, proposer, transactions, gas_limit, &SystemClock)
+    }
+
+    /// Create a new block timestamped by the given clock
+    pub fn new_with_clock(
+        number: BlockNumber,
+        parent_hash: Hash,
+        state_root: Hash,
+        proposer: Address,
+        transactions: Vec<Transaction>,
+        gas_limit: Gas,
+        clock: &dyn Clock,
     ) -> BlockchainResult<Self> {
         // Calculate transactions root
         let tx_hashes: Vec<Hash> = transactions.iter().map(|tx| tx.hash()).collect();
@@ -108,20 +152,21 @@ impl Block {
             parent_hash,
             transactions_root,
             state_root,
-            timestamp: std::time::SystemTime::now()
-                .duration_since(std::time::UNIX_EPOCH)
-                .unwrap()
-                .as_secs(),
+            timestamp: clock.now(),
+            slot: 0,
             proposer,
             gas_limit,
             gas_used,
             extra_data: Vec::new(),
+            election: None,
+            next_validators_hash: None,
         };
 
         Ok(Self {
             header,
             transactions,
             validator_signatures: Vec::new(),
+            proposer_signature: None,
         })
     }
 
@@ -135,15 +180,63 @@ impl Block {
         self.header.number
     }
 
+    /// Sign the header as its proposer; the header must not change afterwards
+    pub fn sign(&mut self, keypair: &KeyPair) -> BlockchainResult<()> {
+        if keypair.public_key().to_address() != self.header.proposer {
+            return Err(BlockchainError::InvalidBlock(
+                "Signing key does not match proposer".into()
+            ));
+        }
+        self.proposer_signature = Some(keypair.sign(&self.header.signing_bytes())?);
+        Ok(())
+    }
+
+    /// Check the proposer signed the block with `public_key`
+    pub fn verify_proposer_signature(&self, public_key: &PublicKey) -> BlockchainResult<()> {
+        let signature = self.proposer_signature.as_ref().ok_or_else(|| {
+            BlockchainError::InvalidBlock("Block is not signed by its proposer".into())
+        })?;
+        if public_key.to_address() != self.header.proposer {
+            return Err(BlockchainError::InvalidBlock(
+                "Key does not match proposer".into()
+            ));
+        }
+        if !public_key.verify(&self.header.signing_bytes(), signature)? {
+            return Err(BlockchainError::InvalidSignature);
+        }
+        Ok(())
+    }
+
+    /// The header with the proposer's signature, usable as double-sign evidence
+    pub fn signed_header(&self, public_key: &PublicKey) -> Option<SignedHeader> {
+        self.proposer_signature.clone().map(|signature| SignedHeader {
+            header: self.header.clone(),
+            public_key: public_key.clone(),
+            signature,
+        })
+    }
+
+    /// Serialized size in bytes
+    pub fn size(&self) -> usize {
+        bincode::serialized_size(self).map(|s| s as usize).unwrap_or(usize::MAX)
+    }
+
     /// Validate block structure and content
     pub fn validate(&self, parent: &Block) -> BlockchainResult<()> {
         // Validate header
         self.header.validate(&parent.header)?;
 
+        // Check serialized size
+        let size = self.size();
+        if size > MAX_BLOCK_BYTES {
+            return Err(BlockchainError::BlockTooLarge { size, max: MAX_BLOCK_BYTES });
+        }
+
         // Validate transactions
         for tx in &self.transactions {
             tx.validate_basic()?;
         }
+        crate::transaction::verify_signatures(&self.transactions)?;
 
         // Verify transactions merkle root
         let tx_hashes: Vec<Hash> = self.transactions.iter().map(|tx| tx.hash()).collect();
@@ -176,16 +269,20 @@ impl Block {
             transactions_root: Hash::zero(),
             state_root: genesis_state_root,
             timestamp: 0,
+            slot: 0,
             proposer: Address::zero(),
             gas_limit: 10_000_000,
             gas_used: 0,
             extra_data: b"Genesis Block".to_vec(),
+            election: None,
+            next_validators_hash: None,
         };
 
         Self {
             header,
             transactions: Vec::new(),
             validator_signatures: Vec::new(),
+            proposer_signature: None,
         }
     }
 
@@ -195,10 +292,43 @@ impl Block {
     }
 }
 
+/// Proposer's leader election proof for a slot
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProposerElection {
+    /// Proposer's public key, which must hash to the header's `proposer`
+    pub public_key: blockchain_crypto::PublicKey,
+    /// VRF proof over the slot's election input
+    pub proof: blockchain_crypto::VrfProof,
+}
+
+/// Finality vote phase
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum VoteType {
+    /// First phase; a prevote quorum justifies a block
+    Prevote,
+    /// Second phase; a precommit quorum finalizes a block
+    Precommit,
+}
+
+/// Bytes signed by a finality vote
+pub fn vote_signing_bytes(vote_type: VoteType, height: BlockNumber, block_hash: &Hash) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(VOTE_DOMAIN.len() + 1 + 8 + 32);
+    bytes.extend_from_slice(VOTE_DOMAIN);
+    bytes.push(match vote_type {
+        VoteType::Prevote => 0,
+        VoteType::Precommit => 1,
+    });
+    bytes.extend_from_slice(&height.to_le_bytes());
+    bytes.extend_from_slice(block_hash.as_bytes());
+    bytes
+}
+
 /// Validator signature for consensus
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ValidatorSignature {
     pub validator: Address,
+    /// Signer's public key, which must hash to `validator`
+    pub public_key: blockchain_crypto::PublicKey,
     pub signature: blockchain_crypto::Signature,
     pub timestamp: Timestamp,
 }
@@ -237,6 +367,38 @@ mod tests {
         assert_eq!(block1.hash(), block2.hash());
     }
 
+    #[test]
+    fn test_header_hash_algorithm() {
+        let header = Block::genesis(Hash::zero()).header;
+        let encoded = bincode::serialize(&header).unwrap();
+
+        assert_eq!(header.hash(), encoded.hash());
+        assert_eq!(
+            header.hash_with(HashAlgorithm::Blake3),
+            encoded.hash_with(HashAlgorithm::Blake3)
+        );
+        assert_ne!(header.hash_with(HashAlgorithm::Blake3), header.hash());
+    }
+
+    #[test]
+    fn test_proposer_signature() {
+        let keypair = KeyPair::generate(blockchain_crypto::SignatureScheme::Ed25519).unwrap();
+        let other = KeyPair::generate(blockchain_crypto::SignatureScheme::Ed25519).unwrap();
+        let proposer = keypair.public_key().to_address();
+        let mut block = Block::new(1, Hash::zero(), Hash::zero(), proposer, vec![], 10_000_000).unwrap();
+        assert!(block.verify_proposer_signature(keypair.public_key()).is_err());
+
+        // Only the proposer's key can sign
+        assert!(block.sign(&other).is_err());
+        block.sign(&keypair).unwrap();
+        assert!(block.verify_proposer_signature(keypair.public_key()).is_ok());
+        assert!(block.verify_proposer_signature(other.public_key()).is_err());
+
+        // Changing the header invalidates the signature
+        block.header.gas_limit += 1;
+        assert!(block.verify_proposer_signature(keypair.public_key()).is_err());
+    }
+
     #[test]
     fn test_header_validation() {
         let genesis = Block::genesis(Hash::zero());
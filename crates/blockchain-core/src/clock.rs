@@ -0,0 +1,95 @@
+// blockchain-core/src/clock.rs
+
+use crate::types::Timestamp;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+/// Source of the current time for timestamps, delays and expiry checks
+pub trait Clock: Send + Sync + std::fmt::Debug {
+    /// Current Unix time in seconds
+    fn now(&self) -> Timestamp;
+}
+
+/// Clock shared between components
+pub type SharedClock = Arc<dyn Clock>;
+
+/// Wall clock backed by `SystemTime`
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Timestamp {
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+    }
+}
+
+/// Manually driven clock for tests and simulations
+///
+/// Clones share the same time, so one handle can fast-forward every
+/// component holding another.
+#[derive(Debug, Clone, Default)]
+pub struct ManualClock {
+    now: Arc<AtomicU64>,
+}
+
+impl ManualClock {
+    /// Create a clock starting at the given time
+    pub fn new(start: Timestamp) -> Self {
+        Self {
+            now: Arc::new(AtomicU64::new(start)),
+        }
+    }
+
+    /// Set the current time
+    pub fn set(&self, now: Timestamp) {
+        self.now.store(now, Ordering::SeqCst);
+    }
+
+    /// Move the clock forward, returning the new time
+    pub fn advance(&self, seconds: u64) -> Timestamp {
+        self.now.fetch_add(seconds, Ordering::SeqCst) + seconds
+    }
+
+    /// Wrap in a shared handle
+    pub fn shared(&self) -> SharedClock {
+        Arc::new(self.clone())
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> Timestamp {
+        self.now.load(Ordering::SeqCst)
+    }
+}
+
+/// Shared system clock, the default for all components
+pub fn system_clock() -> SharedClock {
+    Arc::new(SystemClock)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_manual_clock_shared_between_clones() {
+        let clock = ManualClock::new(1_000);
+        let shared = clock.shared();
+
+        assert_eq!(shared.now(), 1_000);
+        assert_eq!(clock.advance(30), 1_030);
+        assert_eq!(shared.now(), 1_030);
+
+        clock.set(5);
+        assert_eq!(shared.now(), 5);
+    }
+
+    #[test]
+    fn test_system_clock_is_recent() {
+        // 2020-01-01
+        assert!(SystemClock.now() > 1_577_836_800);
+    }
+}
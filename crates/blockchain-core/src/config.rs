@@ -0,0 +1,82 @@
+// blockchain-core/src/config.rs
+
+use crate::{BlockchainError, BlockchainResult};
+use blockchain_crypto::{Hash, HashAlgorithm, Hasher};
+use serde::{Deserialize, Serialize};
+use std::sync::OnceLock;
+
+/// Chain-wide parameters that every node on a network must agree on
+///
+/// Block, transaction and state hashes are computed with `hash_algorithm`,
+/// so changing it produces an incompatible chain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChainConfig {
+    /// Algorithm for block, transaction and state hashing
+    pub hash_algorithm: HashAlgorithm,
+}
+
+impl Default for ChainConfig {
+    fn default() -> Self {
+        Self {
+            hash_algorithm: HashAlgorithm::Sha256,
+        }
+    }
+}
+
+static CHAIN_CONFIG: OnceLock<ChainConfig> = OnceLock::new();
+
+impl ChainConfig {
+    /// Make this the configuration of the running process
+    ///
+    /// Must happen before anything is hashed: the first hash fixes the
+    /// default configuration, after which only an identical one is accepted.
+    pub fn install(self) -> BlockchainResult<()> {
+        let installed = *CHAIN_CONFIG.get_or_init(|| self);
+        if installed != self {
+            return Err(BlockchainError::InvalidChain(format!(
+                "Chain config already set to {:?}",
+                installed
+            )));
+        }
+        Ok(())
+    }
+
+    /// Configuration of the running process
+    pub fn current() -> ChainConfig {
+        *CHAIN_CONFIG.get_or_init(ChainConfig::default)
+    }
+}
+
+/// Hash algorithm of the running chain
+pub fn hash_algorithm() -> HashAlgorithm {
+    ChainConfig::current().hash_algorithm
+}
+
+/// Hash the bincode encoding of a value without materialising it
+pub fn hash_encoded<T: Serialize + ?Sized>(value: &T, algorithm: HashAlgorithm) -> Hash {
+    let mut hasher = algorithm.hasher();
+    bincode::serialize_into(&mut hasher, value).expect("hasher writes never fail");
+    hasher.finalize()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use blockchain_crypto::Hashable;
+
+    #[test]
+    fn test_default_config_is_installed_implicitly() {
+        assert_eq!(hash_algorithm(), HashAlgorithm::Sha256);
+        assert!(ChainConfig::default().install().is_ok());
+        assert!(ChainConfig { hash_algorithm: HashAlgorithm::Blake3 }.install().is_err());
+    }
+
+    #[test]
+    fn test_hash_encoded_matches_serialized_bytes() {
+        let value = (7u64, vec![1u8, 2, 3], "payload".to_string());
+        for algorithm in [HashAlgorithm::Sha256, HashAlgorithm::Blake3] {
+            let bytes = bincode::serialize(&value).unwrap();
+            assert_eq!(hash_encoded(&value, algorithm), bytes.hash_with(algorithm));
+        }
+    }
+}
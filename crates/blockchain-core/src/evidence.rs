@@ -0,0 +1,338 @@
+// blockchain-core/src/evidence.rs
+
+//! Double-sign evidence
+//!
+//! A validator that signs two different headers for the same slot, or two
+//! different votes of the same type at the same height, has equivocated, and
+//! anyone holding both signed messages can prove it. Evidence is submitted in
+//! a `SubmitEvidence` transaction; executing it slashes the offender's stake,
+//! credits the insurance fund and pays the submitter the whistleblower share.
+
+use crate::{
+    block::{vote_signing_bytes, BlockHeader, VoteType},
+    config,
+    types::*,
+    BlockchainError, BlockchainResult,
+};
+use blockchain_crypto::{Address, Hash, KeyPair, PublicKey, Signature};
+use serde::{Deserialize, Serialize};
+
+/// Share of the offender's stake slashed for double-signing (5%)
+pub const DOUBLE_SIGN_SLASH_BPS: u64 = 500;
+
+/// Seconds before unbonded stake is released (14 days)
+///
+/// Evidence older than this is rejected: the offender may have withdrawn
+/// the stake it was bonded with.
+pub const DEFAULT_UNBONDING_PERIOD: u64 = 14 * 24 * 3600;
+
+/// Account credited with the insurance share of slashed stake
+pub const INSURANCE_FUND_ADDRESS: Address = Address::new(*b"POAS_INSURANCE_FUND\0");
+
+/// A header signed by its proposer
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignedHeader {
+    pub header: BlockHeader,
+    /// Proposer's public key, which must hash to `header.proposer`
+    pub public_key: PublicKey,
+    pub signature: Signature,
+}
+
+impl SignedHeader {
+    /// Sign a header as its proposer
+    pub fn sign(header: BlockHeader, keypair: &KeyPair) -> BlockchainResult<Self> {
+        let signature = keypair.sign(&header.signing_bytes())?;
+        Ok(Self {
+            header,
+            public_key: keypair.public_key().clone(),
+            signature,
+        })
+    }
+
+    /// Check the key belongs to the proposer and the signature is valid
+    pub fn verify(&self) -> BlockchainResult<()> {
+        if self.public_key.to_address() != self.header.proposer {
+            return Err(BlockchainError::InvalidEvidence(
+                "Header key does not match proposer".into()
+            ));
+        }
+        if !self.public_key.verify(&self.header.signing_bytes(), &self.signature)? {
+            return Err(BlockchainError::InvalidSignature);
+        }
+        Ok(())
+    }
+}
+
+/// A finality vote with its signature
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignedVote {
+    pub vote_type: VoteType,
+    pub height: BlockNumber,
+    pub block_hash: Hash,
+    pub public_key: PublicKey,
+    pub signature: Signature,
+}
+
+impl SignedVote {
+    /// Check the signature over the vote
+    pub fn verify(&self) -> BlockchainResult<()> {
+        let message = vote_signing_bytes(self.vote_type, self.height, &self.block_hash);
+        if !self.public_key.verify(&message, &self.signature)? {
+            return Err(BlockchainError::InvalidSignature);
+        }
+        Ok(())
+    }
+
+    /// Address of the voter
+    pub fn validator(&self) -> Address {
+        self.public_key.to_address()
+    }
+}
+
+/// Proof that a validator signed two conflicting messages
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DoubleSignEvidence {
+    /// Two different headers proposed for the same slot
+    ///
+    /// Headers at the same height in different slots are not an offense: a
+    /// validator may propose at a height and, if that block is lost, again
+    /// as a fallback in a later slot.
+    ConflictingHeaders {
+        first: Box<SignedHeader>,
+        second: Box<SignedHeader>,
+    },
+    /// Two votes of the same type for different blocks at the same height
+    ConflictingVotes {
+        first: Box<SignedVote>,
+        second: Box<SignedVote>,
+    },
+}
+
+impl DoubleSignEvidence {
+    /// Validator that equivocated
+    pub fn offender(&self) -> Address {
+        match self {
+            DoubleSignEvidence::ConflictingHeaders { first, .. } => first.header.proposer,
+            DoubleSignEvidence::ConflictingVotes { first, .. } => first.validator(),
+        }
+    }
+
+    /// Height at which the offense happened
+    pub fn height(&self) -> BlockNumber {
+        match self {
+            DoubleSignEvidence::ConflictingHeaders { first, .. } => first.header.number,
+            DoubleSignEvidence::ConflictingVotes { first, .. } => first.height,
+        }
+    }
+
+    /// Slot of conflicting headers; votes are not tied to a slot
+    pub fn slot(&self) -> Option<u64> {
+        match self {
+            DoubleSignEvidence::ConflictingHeaders { first, .. } => Some(first.header.slot),
+            DoubleSignEvidence::ConflictingVotes { .. } => None,
+        }
+    }
+
+    /// Identifier of the offense, shared by every piece of evidence for it
+    ///
+    /// Depends only on the offender, height, slot and kind of message, so the
+    /// same offense cannot be slashed twice with a different pair of messages.
+    pub fn id(&self) -> Hash {
+        let kind: u8 = match self {
+            DoubleSignEvidence::ConflictingHeaders { .. } => 0,
+            DoubleSignEvidence::ConflictingVotes { first, .. } => match first.vote_type {
+                VoteType::Prevote => 1,
+                VoteType::Precommit => 2,
+            },
+        };
+        config::hash_encoded(
+            &(kind, self.offender(), self.height(), self.slot()),
+            config::hash_algorithm(),
+        )
+    }
+
+    /// Check both messages are validly signed by the same validator and conflict
+    pub fn verify(&self) -> BlockchainResult<()> {
+        match self {
+            DoubleSignEvidence::ConflictingHeaders { first, second } => {
+                if first.header.proposer != second.header.proposer {
+                    return Err(BlockchainError::InvalidEvidence("Headers from different proposers".into()));
+                }
+                if first.header.number != second.header.number {
+                    return Err(BlockchainError::InvalidEvidence("Headers at different heights".into()));
+                }
+                if first.header.slot != second.header.slot {
+                    return Err(BlockchainError::InvalidEvidence("Headers for different slots".into()));
+                }
+                if first.header.hash() == second.header.hash() {
+                    return Err(BlockchainError::InvalidEvidence("Headers are identical".into()));
+                }
+                first.verify()?;
+                second.verify()?;
+            }
+            DoubleSignEvidence::ConflictingVotes { first, second } => {
+                if first.validator() != second.validator() {
+                    return Err(BlockchainError::InvalidEvidence("Votes from different validators".into()));
+                }
+                if first.vote_type != second.vote_type || first.height != second.height {
+                    return Err(BlockchainError::InvalidEvidence("Votes for different rounds".into()));
+                }
+                if first.block_hash == second.block_hash {
+                    return Err(BlockchainError::InvalidEvidence("Votes are for the same block".into()));
+                }
+                first.verify()?;
+                second.verify()?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Fund distribution for slashed tokens
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SlashingDistribution {
+    /// Amount to burn (50%)
+    pub burn: StakeAmount,
+    /// Amount to insurance fund (30%)
+    pub insurance: StakeAmount,
+    /// Amount to whistleblower reward (20%)
+    pub whistleblower: StakeAmount,
+}
+
+impl SlashingDistribution {
+    /// Calculate distribution from slashed amount
+    pub fn from_slashed_amount(amount: &StakeAmount) -> Self {
+        let total = amount.inner();
+
+        // Calculate percentages
+        let burn_amount = (total * 50u64) / 100u64;
+        let insurance_amount = (total * 30u64) / 100u64;
+        let whistleblower_amount = (total * 20u64) / 100u64;
+
+        Self {
+            burn: StakeAmount::new(burn_amount),
+            insurance: StakeAmount::new(insurance_amount),
+            whistleblower: StakeAmount::new(whistleblower_amount),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Block;
+    use blockchain_crypto::SignatureScheme;
+
+    fn header(keypair: &KeyPair, number: BlockNumber, state_root: Hash) -> BlockHeader {
+        let address = keypair.public_key().to_address();
+        Block::new(number, Hash::zero(), state_root, address, vec![], 10_000_000).unwrap().header
+    }
+
+    fn vote(keypair: &KeyPair, vote_type: VoteType, height: BlockNumber, block_hash: Hash) -> Box<SignedVote> {
+        Box::new(SignedVote {
+            vote_type,
+            height,
+            block_hash,
+            public_key: keypair.public_key().clone(),
+            signature: keypair.sign(&vote_signing_bytes(vote_type, height, &block_hash)).unwrap(),
+        })
+    }
+
+    #[test]
+    fn test_conflicting_headers() {
+        let keypair = KeyPair::generate(SignatureScheme::Ed25519).unwrap();
+        let first = Box::new(SignedHeader::sign(header(&keypair, 5, Hash::zero()), &keypair).unwrap());
+        let second = Box::new(SignedHeader::sign(header(&keypair, 5, Hash::new([1u8; 32])), &keypair).unwrap());
+
+        let evidence = DoubleSignEvidence::ConflictingHeaders { first: first.clone(), second: second.clone() };
+        assert!(evidence.verify().is_ok());
+        assert_eq!(evidence.offender(), keypair.public_key().to_address());
+        assert_eq!(evidence.height(), 5);
+
+        // The same header twice is not an offense
+        let same = DoubleSignEvidence::ConflictingHeaders { first: first.clone(), second: first.clone() };
+        assert!(same.verify().is_err());
+
+        // Different heights are not an offense
+        let later = Box::new(SignedHeader::sign(header(&keypair, 6, Hash::zero()), &keypair).unwrap());
+        let apart = DoubleSignEvidence::ConflictingHeaders { first: first.clone(), second: later };
+        assert!(apart.verify().is_err());
+
+        // A tampered header no longer matches its signature
+        let mut tampered = second;
+        tampered.header.gas_limit += 1;
+        let forged = DoubleSignEvidence::ConflictingHeaders { first: first.clone(), second: tampered };
+        assert!(forged.verify().is_err());
+
+        // Proposing the same height again as a fallback in a later slot is not an offense
+        let mut retry = header(&keypair, 5, Hash::new([2u8; 32]));
+        retry.slot = first.header.slot + 1;
+        let retry = Box::new(SignedHeader::sign(retry, &keypair).unwrap());
+        let fallback = DoubleSignEvidence::ConflictingHeaders { first, second: retry };
+        assert!(matches!(fallback.verify(), Err(BlockchainError::InvalidEvidence(_))));
+    }
+
+    #[test]
+    fn test_conflicting_votes() {
+        let keypair = KeyPair::generate(SignatureScheme::Ed25519).unwrap();
+        let other = KeyPair::generate(SignatureScheme::Ed25519).unwrap();
+        let first = vote(&keypair, VoteType::Precommit, 9, Hash::new([1u8; 32]));
+        let second = vote(&keypair, VoteType::Precommit, 9, Hash::new([2u8; 32]));
+
+        let evidence = DoubleSignEvidence::ConflictingVotes { first: first.clone(), second: second.clone() };
+        assert!(evidence.verify().is_ok());
+
+        // Prevote and precommit for different blocks are not conflicting
+        let prevote = vote(&keypair, VoteType::Prevote, 9, Hash::new([2u8; 32]));
+        let phases = DoubleSignEvidence::ConflictingVotes { first: first.clone(), second: prevote };
+        assert!(phases.verify().is_err());
+
+        // Votes from two validators are not conflicting
+        let theirs = vote(&other, VoteType::Precommit, 9, Hash::new([2u8; 32]));
+        let pair = DoubleSignEvidence::ConflictingVotes { first: first.clone(), second: theirs };
+        assert!(pair.verify().is_err());
+
+        // A signature moved onto another block is invalid
+        let mut moved = second;
+        moved.block_hash = Hash::new([3u8; 32]);
+        let forged = DoubleSignEvidence::ConflictingVotes { first, second: moved };
+        assert!(forged.verify().is_err());
+    }
+
+    #[test]
+    fn test_evidence_id_identifies_offense() {
+        let keypair = KeyPair::generate(SignatureScheme::Ed25519).unwrap();
+        let a = vote(&keypair, VoteType::Precommit, 9, Hash::new([1u8; 32]));
+        let b = vote(&keypair, VoteType::Precommit, 9, Hash::new([2u8; 32]));
+        let c = vote(&keypair, VoteType::Precommit, 9, Hash::new([3u8; 32]));
+
+        let ab = DoubleSignEvidence::ConflictingVotes { first: a.clone(), second: b.clone() };
+        let ba = DoubleSignEvidence::ConflictingVotes { first: b, second: a.clone() };
+        let ac = DoubleSignEvidence::ConflictingVotes { first: a, second: c };
+        assert_eq!(ab.id(), ba.id());
+        assert_eq!(ab.id(), ac.id());
+
+        let prevotes = DoubleSignEvidence::ConflictingVotes {
+            first: vote(&keypair, VoteType::Prevote, 9, Hash::new([1u8; 32])),
+            second: vote(&keypair, VoteType::Prevote, 9, Hash::new([2u8; 32])),
+        };
+        assert_ne!(ab.id(), prevotes.id());
+
+        // Conflicting headers are one offense per slot
+        let signed = |slot: u64, state_root: Hash| {
+            let mut header = header(&keypair, 9, state_root);
+            header.slot = slot;
+            Box::new(SignedHeader::sign(header, &keypair).unwrap())
+        };
+        let slot3 = DoubleSignEvidence::ConflictingHeaders {
+            first: signed(3, Hash::new([1u8; 32])),
+            second: signed(3, Hash::new([2u8; 32])),
+        };
+        let slot4 = DoubleSignEvidence::ConflictingHeaders {
+            first: signed(4, Hash::new([1u8; 32])),
+            second: signed(4, Hash::new([2u8; 32])),
+        };
+        assert_eq!(slot3.slot(), Some(3));
+        assert_ne!(slot3.id(), slot4.id());
+    }
+}
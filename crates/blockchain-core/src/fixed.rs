@@ -0,0 +1,395 @@
+// blockchain-core/src/fixed.rs
+
+//! Deterministic fixed-point arithmetic
+//!
+//! Consensus-critical formulas (selection weights, rewards, minting, slashing,
+//! the utility index) must give bit-identical results on every node, which
+//! `f64` does not guarantee across platforms and compilers. `Fixed` is a
+//! signed decimal with 18 fractional digits stored in an `i128`; every
+//! operation is integer arithmetic and truncates toward zero.
+
+use num_bigint::BigInt;
+use num_traits::ToPrimitive;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::ops::{Add, Mul, Neg, Sub};
+
+/// Number of fractional decimal digits
+pub const FIXED_DECIMALS: u32 = 18;
+
+const SCALE: i128 = 1_000_000_000_000_000_000;
+
+/// ln(2) at 18 decimals
+const LN_2: i128 = 693_147_180_559_945_309;
+
+/// Below this, e^x rounds to zero at 18 decimals
+const EXP_MIN: i128 = -42 * SCALE;
+
+/// Above this, e^x does not fit
+const EXP_MAX: i128 = 46 * SCALE;
+
+/// Signed fixed-point number with 18 decimal places
+///
+/// Arithmetic operators saturate at the representable range instead of
+/// panicking; use the `checked_*` methods to detect overflow and division by
+/// zero. JSON and other human-readable formats encode the value as a decimal
+/// string, e.g. `"1.5"`.
+#[derive(Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Fixed(i128);
+
+impl Fixed {
+    pub const ZERO: Fixed = Fixed(0);
+    pub const ONE: Fixed = Fixed(SCALE);
+    pub const MAX: Fixed = Fixed(i128::MAX);
+    pub const MIN: Fixed = Fixed(i128::MIN);
+
+    /// Create from the raw value in units of 10^-18
+    pub const fn from_raw(raw: i128) -> Self {
+        Self(raw)
+    }
+
+    /// Raw value in units of 10^-18
+    pub const fn raw(&self) -> i128 {
+        self.0
+    }
+
+    pub const fn from_int(value: i64) -> Self {
+        Self(value as i128 * SCALE)
+    }
+
+    /// `numerator / denominator`; `None` if the denominator is zero
+    pub fn from_ratio(numerator: u64, denominator: u64) -> Option<Self> {
+        if denominator == 0 {
+            return None;
+        }
+        Some(Self(numerator as i128 * SCALE / denominator as i128))
+    }
+
+    /// Basis points as a fraction, e.g. 2,500 → 0.25
+    pub const fn from_bps(bps: u64) -> Self {
+        Self(bps as i128 * (SCALE / crate::types::BPS_DENOMINATOR as i128))
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.0 < 0
+    }
+
+    pub fn checked_add(self, other: Fixed) -> Option<Fixed> {
+        self.0.checked_add(other.0).map(Fixed)
+    }
+
+    pub fn checked_sub(self, other: Fixed) -> Option<Fixed> {
+        self.0.checked_sub(other.0).map(Fixed)
+    }
+
+    pub fn checked_mul(self, other: Fixed) -> Option<Fixed> {
+        mul_div(self.0, other.0, SCALE).map(Fixed)
+    }
+
+    /// Divide; `None` on division by zero or overflow
+    pub fn checked_div(self, other: Fixed) -> Option<Fixed> {
+        if other.0 == 0 {
+            return None;
+        }
+        mul_div(self.0, SCALE, other.0).map(Fixed)
+    }
+
+    /// e^self, saturating at `Fixed::MAX`
+    ///
+    /// Reduces the argument to r = x - k·ln2 with |r| ≤ ln2/2, sums the Taylor
+    /// series of e^r until terms vanish, then scales by 2^k.
+    pub fn exp(self) -> Fixed {
+        if self.0 < EXP_MIN {
+            return Fixed::ZERO;
+        }
+        if self.0 > EXP_MAX {
+            return Fixed::MAX;
+        }
+
+        let k = div_round_half_away(self.0, LN_2);
+        let r = Fixed(self.0 - k * LN_2);
+
+        let mut sum = Fixed::ONE;
+        let mut term = Fixed::ONE;
+        for n in 1..64 {
+            term = Fixed((term * r).raw() / n);
+            if term.is_zero() {
+                break;
+            }
+            sum = sum + term;
+        }
+
+        if k >= 0 {
+            sum.0.checked_mul(1i128 << k).map_or(Fixed::MAX, Fixed)
+        } else {
+            Fixed(sum.0 >> (-k).min(127))
+        }
+    }
+
+    /// Value in basis points, truncated; negative values give zero
+    pub fn to_bps(&self) -> u64 {
+        (self.0.max(0) / (SCALE / crate::types::BPS_DENOMINATOR as i128))
+            .to_u64()
+            .unwrap_or(u64::MAX)
+    }
+
+    /// Lossy conversion to `f64`, for display and non-consensus statistics only
+    pub fn to_f64_lossy(&self) -> f64 {
+        self.0 as f64 / SCALE as f64
+    }
+
+    fn parse(s: &str) -> Result<Fixed, String> {
+        let trimmed = s.trim();
+        let (negative, digits) = match trimmed.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, trimmed),
+        };
+        let (whole, frac) = digits.split_once('.').unwrap_or((digits, ""));
+
+        let is_digits = |s: &str| s.chars().all(|c| c.is_ascii_digit());
+        if (whole.is_empty() && frac.is_empty()) || !is_digits(whole) || !is_digits(frac) {
+            return Err(format!("Invalid fixed-point number: {}", s));
+        }
+        if frac.len() > FIXED_DECIMALS as usize {
+            return Err(format!("Too many decimal places: {}", s));
+        }
+
+        let whole: i128 = if whole.is_empty() { 0 } else { whole.parse().map_err(|_| format!("Out of range: {}", s))? };
+        let frac: i128 = format!("{:0<width$}", frac, width = FIXED_DECIMALS as usize)
+            .parse()
+            .map_err(|_| format!("Invalid fixed-point number: {}", s))?;
+
+        let raw = whole
+            .checked_mul(SCALE)
+            .and_then(|w| w.checked_add(frac))
+            .ok_or_else(|| format!("Out of range: {}", s))?;
+        Ok(Fixed(if negative { -raw } else { raw }))
+    }
+}
+
+/// `a * b / c` truncated toward zero, widening when the product overflows
+fn mul_div(a: i128, b: i128, c: i128) -> Option<i128> {
+    match a.checked_mul(b) {
+        Some(product) => product.checked_div(c),
+        None => (BigInt::from(a) * BigInt::from(b) / BigInt::from(c)).to_i128(),
+    }
+}
+
+/// `a / b` rounded to the nearest integer, ties away from zero
+fn div_round_half_away(a: i128, b: i128) -> i128 {
+    let quotient = a / b;
+    let remainder = a % b;
+    if remainder.abs() * 2 >= b.abs() {
+        quotient + a.signum() * b.signum()
+    } else {
+        quotient
+    }
+}
+
+impl Add for Fixed {
+    type Output = Fixed;
+
+    fn add(self, other: Fixed) -> Fixed {
+        Fixed(self.0.saturating_add(other.0))
+    }
+}
+
+impl Sub for Fixed {
+    type Output = Fixed;
+
+    fn sub(self, other: Fixed) -> Fixed {
+        Fixed(self.0.saturating_sub(other.0))
+    }
+}
+
+impl Mul for Fixed {
+    type Output = Fixed;
+
+    fn mul(self, other: Fixed) -> Fixed {
+        self.checked_mul(other).unwrap_or(if self.is_negative() != other.is_negative() {
+            Fixed::MIN
+        } else {
+            Fixed::MAX
+        })
+    }
+}
+
+impl Neg for Fixed {
+    type Output = Fixed;
+
+    fn neg(self) -> Fixed {
+        Fixed(self.0.saturating_neg())
+    }
+}
+
+impl std::iter::Sum for Fixed {
+    fn sum<I: Iterator<Item = Fixed>>(iter: I) -> Fixed {
+        iter.fold(Fixed::ZERO, |acc, f| acc + f)
+    }
+}
+
+impl std::str::FromStr for Fixed {
+    type Err = String;
+
+    /// Parse a decimal such as "1.5" or "-0.0001"
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Fixed::parse(s)
+    }
+}
+
+impl fmt::Display for Fixed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sign = if self.0 < 0 { "-" } else { "" };
+        let abs = self.0.unsigned_abs();
+        let whole = abs / SCALE as u128;
+        let frac = abs % SCALE as u128;
+
+        if frac == 0 {
+            return write!(f, "{}{}", sign, whole);
+        }
+        let frac = format!("{:0>width$}", frac, width = FIXED_DECIMALS as usize);
+        write!(f, "{}{}.{}", sign, whole, frac.trim_end_matches('0'))
+    }
+}
+
+impl fmt::Debug for Fixed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Fixed({})", self)
+    }
+}
+
+impl Serialize for Fixed {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.serialize_str(&self.to_string())
+        } else {
+            self.0.serialize(serializer)
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Fixed {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            let value = String::deserialize(deserializer)?;
+            value.parse().map_err(serde::de::Error::custom)
+        } else {
+            i128::deserialize(deserializer).map(Fixed)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    fn fixed(s: &str) -> Fixed {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_constructors_and_formatting() {
+        assert_eq!(Fixed::from_int(3).to_string(), "3");
+        assert_eq!(Fixed::from_ratio(3, 2).unwrap().to_string(), "1.5");
+        assert_eq!(Fixed::from_ratio(1, 3).unwrap().to_string(), "0.333333333333333333");
+        assert_eq!(Fixed::from_bps(2_500), fixed("0.25"));
+        assert_eq!(fixed("0.25").to_bps(), 2_500);
+        assert_eq!(fixed("-0.0001").to_string(), "-0.0001");
+        assert_eq!(fixed(".5"), fixed("0.5"));
+        assert!(Fixed::from_ratio(1, 0).is_none());
+        assert!("1.0000000000000000001".parse::<Fixed>().is_err());
+        assert!("abc".parse::<Fixed>().is_err());
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        assert_eq!(fixed("1.5") * fixed("1.2"), fixed("1.8"));
+        assert_eq!(fixed("-1.5") * fixed("2"), fixed("-3"));
+        assert_eq!(Fixed::ONE.checked_div(Fixed::from_int(3)), Fixed::from_ratio(1, 3));
+        assert_eq!(Fixed::ONE.checked_div(Fixed::ZERO), None);
+        assert_eq!(fixed("0.3") + fixed("0.25") + fixed("0.2") + fixed("0.15") + fixed("0.1"), Fixed::ONE);
+
+        // Wide products are exact
+        let big = Fixed::from_int(1_000_000_000_000);
+        assert_eq!(big * big.checked_div(Fixed::from_int(1_000_000)).unwrap(), Fixed::from_int(1_000_000_000_000_000_000));
+        assert_eq!(Fixed::MAX * Fixed::from_int(2), Fixed::MAX);
+        assert_eq!(Fixed::MAX * Fixed::from_int(-2), Fixed::MIN);
+    }
+
+    // Reference values from a 50-digit evaluation
+    #[test]
+    fn test_exp_reference_values() {
+        let cases = [
+            ("0", "1"),
+            ("1", "2.718281828459045235"),
+            ("-1", "0.367879441171442321"),
+            ("0.5", "1.648721270700128146"),
+            ("-0.1", "0.904837418035959573"),
+            ("10", "22026.465794806716516957"),
+            ("-20", "0.000000002061153622"),
+        ];
+        for (x, expected) in cases {
+            let actual = fixed(x).exp();
+            let expected = fixed(expected);
+            assert!(
+                (actual - expected).raw().abs() <= expected.raw() / 1_000_000_000_000_000 + 2,
+                "exp({}) = {}, expected {}", x, actual, expected
+            );
+        }
+        assert_eq!(fixed("-50").exp(), Fixed::ZERO);
+        assert_eq!(fixed("100").exp(), Fixed::MAX);
+    }
+
+    #[test]
+    fn test_serde() {
+        let value = fixed("-12.345");
+        let json = serde_json::to_string(&value).unwrap();
+        assert_eq!(json, "\"-12.345\"");
+        assert_eq!(serde_json::from_str::<Fixed>(&json).unwrap(), value);
+
+        let bytes = bincode::serialize(&value).unwrap();
+        assert_eq!(bincode::deserialize::<Fixed>(&bytes).unwrap(), value);
+    }
+
+    proptest! {
+        #[test]
+        fn prop_mul_matches_integer_reference(a in -1_000_000_000i64..1_000_000_000, b in -1_000_000_000i64..1_000_000_000) {
+            prop_assert_eq!(Fixed::from_int(a) * Fixed::from_int(b), Fixed::from_raw(a as i128 * b as i128 * SCALE));
+        }
+
+        #[test]
+        fn prop_ratio_matches_integer_reference(n in 0u64..u64::MAX, d in 1u64..u64::MAX) {
+            let ratio = Fixed::from_ratio(n, d).unwrap();
+            // Truncated: ratio * d ≤ n < (ratio + ulp) * d
+            let scaled = BigInt::from(n) * BigInt::from(SCALE);
+            prop_assert!(BigInt::from(ratio.raw()) * BigInt::from(d) <= scaled);
+            prop_assert!(BigInt::from(ratio.raw() + 1) * BigInt::from(d) > scaled);
+        }
+
+        #[test]
+        fn prop_div_inverts_mul(a in -1_000_000_000_000i64..1_000_000_000_000, b in 1i64..1_000_000) {
+            let product = Fixed::from_int(a) * Fixed::from_int(b);
+            prop_assert_eq!(product.checked_div(Fixed::from_int(b)), Some(Fixed::from_int(a)));
+        }
+
+        #[test]
+        fn prop_exp_matches_float_reference(raw in -30_000_000_000_000_000_000i128..30_000_000_000_000_000_000) {
+            let x = Fixed::from_raw(raw);
+            let reference = x.to_f64_lossy().exp();
+            let actual = x.exp().to_f64_lossy();
+            prop_assert!((actual - reference).abs() <= reference * 1e-12 + 1e-17,
+                "exp({}) = {}, reference {}", x, actual, reference);
+        }
+
+        #[test]
+        fn prop_display_roundtrip(raw in any::<i128>().prop_filter("not MIN", |r| *r != i128::MIN)) {
+            let value = Fixed::from_raw(raw);
+            prop_assert_eq!(value.to_string().parse::<Fixed>().unwrap(), value);
+        }
+    }
+}
@@ -1,9 +1,9 @@
 // blockchain-core/src/fork.rs
 
-use crate::{Block, BlockNumber, BlockchainError, BlockchainResult};
+use crate::{Block, BlockHeader, BlockNumber, BlockchainError, BlockchainResult};
 use blockchain_crypto::Hash;
 use serde::{Deserialize, Serialize};
-use std::collections::{HashMap, VecDeque};
+use std::collections::{HashMap, HashSet};
 
 /// Fork choice rule
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -41,6 +41,8 @@ pub struct ForkResolver {
     max_reorg_depth: u64,
     /// Fork history
     fork_history: Vec<ForkInfo>,
+    /// Index of every known header, canonical or not
+    tree: BlockTree,
 }
 
 impl ForkResolver {
@@ -50,9 +52,28 @@ impl ForkResolver {
             choice,
             max_reorg_depth,
             fork_history: Vec::new(),
+            tree: BlockTree::new(),
         }
     }
 
+    /// Track a block in the block tree with the given weight, e.g. its proposer's stake.
+    ///
+    /// The first block tracked becomes the tree root; every later block must
+    /// extend a block that is already known.
+    pub fn track_block(&mut self, block: &Block, weight: u128) -> BlockchainResult<()> {
+        self.tree.insert(&block.header, weight)
+    }
+
+    /// Get the block tree
+    pub fn tree(&self) -> &BlockTree {
+        &self.tree
+    }
+
+    /// Get mutable block tree
+    pub fn tree_mut(&mut self) -> &mut BlockTree {
+        &mut self.tree
+    }
+
     /// Detect if there's a fork
     pub fn detect_fork(
         &self,
@@ -90,10 +111,17 @@ impl ForkResolver {
                 Ok(fork_chain.len() > main_chain.len())
             }
             ForkChoice::HeaviestChain => {
-                // Calculate cumulative work (simplified)
-                let main_work = main_chain.len() as u64;
-                let fork_work = fork_chain.len() as u64;
-                Ok(fork_work > main_work)
+                // Prefer cumulative weight from the block tree when both tips are tracked
+                let main_tip = main_chain.last().map(|b| b.hash());
+                let fork_tip = fork_chain.last().map(|b| b.hash());
+                let tracked = main_tip
+                    .and_then(|h| self.tree.cumulative_weight(&h))
+                    .zip(fork_tip.and_then(|h| self.tree.cumulative_weight(&h)));
+
+                match tracked {
+                    Some((main_work, fork_work)) => Ok(fork_work > main_work),
+                    None => Ok(fork_chain.len() > main_chain.len()),
+                }
             }
             ForkChoice::LatestJustified => {
                 // Would check for justified checkpoints
@@ -103,7 +131,11 @@ impl ForkResolver {
         }
     }
 
-    /// Find common ancestor between two chains
+    /// Find common ancestor between two chains.
+    ///
+    /// Uses the block tree when both heads are tracked. Otherwise the maps are
+    /// walked, first lowering the higher head to the other's height and then
+    /// stepping both back in lockstep.
     pub fn find_common_ancestor(
         &self,
         chain_a: &HashMap<Hash, Block>,
@@ -111,40 +143,31 @@ impl ForkResolver {
         head_a: &Hash,
         head_b: &Hash,
     ) -> Option<Hash> {
-        let mut current_a = *head_a;
-        let mut current_b = *head_b;
-        
-        let mut visited_a = std::collections::HashSet::new();
-        let mut visited_b = std::collections::HashSet::new();
-
-        loop {
-            // Check if we've found common block
-            if visited_b.contains(&current_a) {
-                return Some(current_a);
-            }
-            if visited_a.contains(&current_b) {
-                return Some(current_b);
-            }
+        if let Some(ancestor) = self.tree.common_ancestor(head_a, head_b) {
+            return Some(ancestor);
+        }
 
-            // Mark as visited
-            visited_a.insert(current_a);
-            visited_b.insert(current_b);
+        let mut block_a = chain_a.get(head_a)?;
+        let mut block_b = chain_b.get(head_b)?;
 
-            // Move to parents
-            let block_a = chain_a.get(&current_a)?;
-            let block_b = chain_b.get(&current_b)?;
+        while block_a.number() > block_b.number() {
+            block_a = chain_a.get(&block_a.header.parent_hash)?;
+        }
+        while block_b.number() > block_a.number() {
+            block_b = chain_b.get(&block_b.header.parent_hash)?;
+        }
 
-            if block_a.is_genesis() && block_b.is_genesis() {
-                return Some(current_a); // Genesis is common ancestor
+        loop {
+            let hash_a = block_a.hash();
+            if hash_a == block_b.hash() {
+                return Some(hash_a);
             }
-
-            current_a = block_a.header.parent_hash;
-            current_b = block_b.header.parent_hash;
-
-            // Safety check - don't search forever
-            if visited_a.len() > 10000 || visited_b.len() > 10000 {
+            if block_a.is_genesis() || block_b.is_genesis() {
                 return None;
             }
+
+            block_a = chain_a.get(&block_a.header.parent_hash)?;
+            block_b = chain_b.get(&block_b.header.parent_hash)?;
         }
     }
 
@@ -160,6 +183,14 @@ impl ForkResolver {
                 "No common ancestor found".into()
             ))?;
 
+        // Reject deep reorgs up front when the tree knows both heights
+        if let (Some(old), Some(ancestor)) = (self.tree.get(old_head), self.tree.get(&common_ancestor)) {
+            let depth = old.number - ancestor.number;
+            if depth > self.max_reorg_depth {
+                return Err(BlockchainError::ReorgTooDeep { depth });
+            }
+        }
+
         // Build path from old_head to common ancestor (to be reverted)
         let mut revert = Vec::new();
         let mut current = *old_head;
@@ -187,11 +218,12 @@ impl ForkResolver {
         }
         apply.reverse(); // Apply in forward order
 
+        let depth = revert.len() as u64;
         Ok(ReorgPath {
             common_ancestor,
-            revert_blocks: revert.clone(),
+            revert_blocks: revert,
             apply_blocks: apply,
-            depth: revert.len() as u64,
+            depth,
         })
     }
 
@@ -246,6 +278,299 @@ impl ReorgPath {
     }
 }
 
+/// Entry in the block tree
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TreeNode {
+    /// Block hash
+    pub hash: Hash,
+    /// Parent block hash
+    pub parent: Hash,
+    /// Block number/height
+    pub number: BlockNumber,
+    /// Weight contributed by this block
+    pub weight: u128,
+    /// Total weight from the tree root up to and including this block
+    pub cumulative_weight: u128,
+    /// Binary lifting table: `ancestors[k]` is the ancestor 2^k blocks back.
+    /// Not persisted; [`BlockTree::restore`] rebuilds it
+    #[serde(skip)]
+    ancestors: Vec<Hash>,
+}
+
+/// Index of known headers (canonical and side branches).
+///
+/// Ancestor, common-ancestor and descendant queries use binary lifting and
+/// run in O(log n) of the height difference. The tree is persisted node by
+/// node and rebuilt with [`BlockTree::restore`].
+#[derive(Debug, Clone, Default)]
+pub struct BlockTree {
+    /// Nodes indexed by block hash
+    nodes: HashMap<Hash, TreeNode>,
+    /// Child hashes indexed by parent hash
+    children: HashMap<Hash, Vec<Hash>>,
+    /// Root of the tree (genesis or last pruning point)
+    root: Option<Hash>,
+}
+
+impl BlockTree {
+    /// Create an empty block tree
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Insert a header with the given weight.
+    ///
+    /// The first header inserted becomes the root. Later headers must extend a
+    /// known parent at the next height. Re-inserting a known header is a no-op.
+    pub fn insert(&mut self, header: &BlockHeader, weight: u128) -> BlockchainResult<()> {
+        let hash = header.hash();
+        if self.nodes.contains_key(&hash) {
+            return Ok(());
+        }
+
+        match self.root {
+            None => {
+                self.add_root(TreeNode {
+                    hash,
+                    parent: header.parent_hash,
+                    number: header.number,
+                    weight,
+                    cumulative_weight: weight,
+                    ancestors: Vec::new(),
+                });
+                Ok(())
+            }
+            Some(_) => self.link(hash, header.parent_hash, header.number, weight),
+        }
+    }
+
+    /// Rebuild a tree from persisted nodes, recomputing the lifting tables.
+    ///
+    /// The root keeps its stored cumulative weight. Nodes that no longer link
+    /// to the root, e.g. left over from an interrupted prune, are skipped.
+    pub fn restore(root: Hash, nodes: impl IntoIterator<Item = TreeNode>) -> BlockchainResult<Self> {
+        let mut tree = Self::new();
+        let mut rest = Vec::new();
+        for mut node in nodes {
+            if node.hash == root {
+                node.ancestors.clear();
+                tree.add_root(node);
+            } else {
+                rest.push(node);
+            }
+        }
+        if tree.root.is_none() {
+            return Err(BlockchainError::BlockNotFound(root));
+        }
+
+        rest.sort_by_key(|node| node.number);
+        for node in rest {
+            let _ = tree.link(node.hash, node.parent, node.number, node.weight);
+        }
+        Ok(tree)
+    }
+
+    fn add_root(&mut self, node: TreeNode) {
+        self.root = Some(node.hash);
+        self.children.entry(node.parent).or_default().push(node.hash);
+        self.nodes.insert(node.hash, node);
+    }
+
+    /// Attach a block under a known parent at the next height
+    fn link(&mut self, hash: Hash, parent_hash: Hash, number: BlockNumber, weight: u128) -> BlockchainResult<()> {
+        if self.nodes.contains_key(&hash) {
+            return Ok(());
+        }
+
+        let parent = self.nodes.get(&parent_hash)
+            .ok_or(BlockchainError::BlockNotFound(parent_hash))?;
+
+        if number != parent.number + 1 {
+            return Err(BlockchainError::InvalidBlock(
+                format!("Invalid block number: expected {}, got {}",
+                    parent.number + 1, number)
+            ));
+        }
+
+        let mut ancestors = vec![parent.hash];
+        loop {
+            let k = ancestors.len() - 1;
+            let next = self.nodes.get(&ancestors[k])
+                .and_then(|n| n.ancestors.get(k).copied());
+            match next {
+                Some(h) => ancestors.push(h),
+                None => break,
+            }
+        }
+
+        let node = TreeNode {
+            hash,
+            parent: parent.hash,
+            number,
+            weight,
+            cumulative_weight: parent.cumulative_weight.saturating_add(weight),
+            ancestors,
+        };
+
+        self.children.entry(node.parent).or_default().push(hash);
+        self.nodes.insert(hash, node);
+        Ok(())
+    }
+
+    /// Get a node by hash
+    pub fn get(&self, hash: &Hash) -> Option<&TreeNode> {
+        self.nodes.get(hash)
+    }
+
+    /// Check if a block is tracked
+    pub fn contains(&self, hash: &Hash) -> bool {
+        self.nodes.contains_key(hash)
+    }
+
+    /// Get the root hash
+    pub fn root(&self) -> Option<Hash> {
+        self.root
+    }
+
+    /// Number of tracked blocks
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Check if the tree is empty
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Get block height for a hash
+    pub fn height(&self, hash: &Hash) -> Option<BlockNumber> {
+        self.nodes.get(hash).map(|n| n.number)
+    }
+
+    /// Get cumulative weight for a hash
+    pub fn cumulative_weight(&self, hash: &Hash) -> Option<u128> {
+        self.nodes.get(hash).map(|n| n.cumulative_weight)
+    }
+
+    /// Get the children of a block
+    pub fn children(&self, hash: &Hash) -> &[Hash] {
+        self.children.get(hash).map(|c| c.as_slice()).unwrap_or(&[])
+    }
+
+    /// Get all tips (blocks without children)
+    pub fn tips(&self) -> Vec<Hash> {
+        self.nodes.keys()
+            .filter(|h| self.children(h).is_empty())
+            .copied()
+            .collect()
+    }
+
+    /// Get the ancestor of `hash` at height `number`
+    pub fn ancestor_at(&self, hash: &Hash, number: BlockNumber) -> Option<Hash> {
+        let mut current = self.nodes.get(hash)?;
+        if number > current.number {
+            return None;
+        }
+
+        while current.number > number {
+            let diff = current.number - number;
+            let k = (63 - diff.leading_zeros() as usize).min(current.ancestors.len().checked_sub(1)?);
+            current = self.nodes.get(&current.ancestors[k])?;
+        }
+
+        Some(current.hash)
+    }
+
+    /// Find the lowest common ancestor of two blocks
+    pub fn common_ancestor(&self, a: &Hash, b: &Hash) -> Option<Hash> {
+        let node_a = self.nodes.get(a)?;
+        let node_b = self.nodes.get(b)?;
+        let number = node_a.number.min(node_b.number);
+
+        let mut a = self.nodes.get(&self.ancestor_at(a, number)?)?;
+        let mut b = self.nodes.get(&self.ancestor_at(b, number)?)?;
+        if a.hash == b.hash {
+            return Some(a.hash);
+        }
+
+        // Both nodes sit at the same height, so their tables line up index by index
+        for k in (0..a.ancestors.len()).rev() {
+            if let (Some(x), Some(y)) = (a.ancestors.get(k), b.ancestors.get(k)) {
+                if x != y {
+                    a = self.nodes.get(x)?;
+                    b = self.nodes.get(y)?;
+                }
+            }
+        }
+
+        if a.parent == b.parent && self.nodes.contains_key(&a.parent) {
+            Some(a.parent)
+        } else {
+            None
+        }
+    }
+
+    /// Check whether `descendant` is `ancestor` or builds on it
+    pub fn is_descendant(&self, descendant: &Hash, ancestor: &Hash) -> bool {
+        match self.nodes.get(ancestor) {
+            Some(node) => self.ancestor_at(descendant, node.number) == Some(*ancestor),
+            None => false,
+        }
+    }
+
+    /// Weight of the branch from `ancestor` (exclusive) to `tip` (inclusive)
+    pub fn branch_weight(&self, tip: &Hash, ancestor: &Hash) -> Option<u128> {
+        if !self.is_descendant(tip, ancestor) {
+            return None;
+        }
+        let tip = self.nodes.get(tip)?;
+        let ancestor = self.nodes.get(ancestor)?;
+        Some(tip.cumulative_weight - ancestor.cumulative_weight)
+    }
+
+    /// Get the tip with the highest block number
+    pub fn longest_tip(&self) -> Option<Hash> {
+        self.nodes.values()
+            .filter(|n| self.children(&n.hash).is_empty())
+            .max_by_key(|n| (n.number, n.cumulative_weight))
+            .map(|n| n.hash)
+    }
+
+    /// Get the tip with the highest cumulative weight
+    pub fn heaviest_tip(&self) -> Option<Hash> {
+        self.nodes.values()
+            .filter(|n| self.children(&n.hash).is_empty())
+            .max_by_key(|n| (n.cumulative_weight, n.number))
+            .map(|n| n.hash)
+    }
+
+    /// Re-root the tree at `new_root`, dropping every block that does not descend from it.
+    ///
+    /// Returns the hashes of the blocks removed.
+    pub fn prune(&mut self, new_root: &Hash) -> BlockchainResult<Vec<Hash>> {
+        if !self.nodes.contains_key(new_root) {
+            return Err(BlockchainError::BlockNotFound(*new_root));
+        }
+
+        let mut keep = HashSet::new();
+        let mut stack = vec![*new_root];
+        while let Some(hash) = stack.pop() {
+            stack.extend_from_slice(self.children(&hash));
+            keep.insert(hash);
+        }
+
+        let removed: Vec<Hash> = self.nodes.keys()
+            .filter(|h| !keep.contains(h))
+            .copied()
+            .collect();
+        self.nodes.retain(|h, _| keep.contains(h));
+        self.children.retain(|h, _| keep.contains(h));
+        self.root = Some(*new_root);
+
+        Ok(removed)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -290,4 +615,171 @@ mod tests {
         let fork = resolver.detect_fork(&block1, &block2);
         assert!(fork.is_some());
     }
-}
\ No newline at end of file
+
+    /// Build a chain of `len` blocks on top of `parent`, tagging headers with `tag`
+    fn build_branch(parent: &Block, len: u64, tag: u8) -> Vec<Block> {
+        let mut blocks = Vec::new();
+        let mut prev = parent.clone();
+        for _ in 0..len {
+            let mut block = Block::new(
+                prev.number() + 1,
+                prev.hash(),
+                Hash::zero(),
+                blockchain_crypto::Address::zero(),
+                vec![],
+                10_000_000,
+            ).unwrap();
+            block.header.extra_data = vec![tag];
+            prev = block.clone();
+            blocks.push(block);
+        }
+        blocks
+    }
+
+    #[test]
+    fn test_block_tree_ancestors() {
+        let genesis = Block::genesis(Hash::zero());
+        let main = build_branch(&genesis, 100, 0);
+
+        let mut tree = BlockTree::new();
+        tree.insert(&genesis.header, 1).unwrap();
+        for block in &main {
+            tree.insert(&block.header, 1).unwrap();
+        }
+
+        let tip = main[99].hash();
+        assert_eq!(tree.height(&tip), Some(100));
+        assert_eq!(tree.ancestor_at(&tip, 0), Some(genesis.hash()));
+        assert_eq!(tree.ancestor_at(&tip, 37), Some(main[36].hash()));
+        assert_eq!(tree.ancestor_at(&tip, 101), None);
+        assert!(tree.is_descendant(&tip, &main[49].hash()));
+        assert!(!tree.is_descendant(&main[49].hash(), &tip));
+        assert_eq!(tree.branch_weight(&tip, &main[49].hash()), Some(50));
+        assert_eq!(tree.cumulative_weight(&tip), Some(101));
+    }
+
+    #[test]
+    fn test_block_tree_common_ancestor() {
+        let genesis = Block::genesis(Hash::zero());
+        let main = build_branch(&genesis, 40, 0);
+        let side = build_branch(&main[24], 7, 1);
+
+        let mut tree = BlockTree::new();
+        tree.insert(&genesis.header, 1).unwrap();
+        for block in &main {
+            tree.insert(&block.header, 1).unwrap();
+        }
+        for block in &side {
+            tree.insert(&block.header, 5).unwrap();
+        }
+
+        let main_tip = main[39].hash();
+        let side_tip = side[6].hash();
+        assert_eq!(tree.common_ancestor(&main_tip, &side_tip), Some(main[24].hash()));
+        assert_eq!(tree.common_ancestor(&side_tip, &main[10].hash()), Some(main[10].hash()));
+        assert_eq!(tree.tips().len(), 2);
+        assert_eq!(tree.longest_tip(), Some(main_tip));
+        assert_eq!(tree.heaviest_tip(), Some(side_tip));
+        assert!(!tree.is_descendant(&side_tip, &main[30].hash()));
+    }
+
+    #[test]
+    fn test_block_tree_rejects_orphans_and_prunes() {
+        let genesis = Block::genesis(Hash::zero());
+        let main = build_branch(&genesis, 10, 0);
+        let side = build_branch(&main[2], 3, 1);
+
+        let mut tree = BlockTree::new();
+        tree.insert(&genesis.header, 1).unwrap();
+        assert!(tree.insert(&main[1].header, 1).is_err());
+
+        for block in main.iter().chain(side.iter()) {
+            tree.insert(&block.header, 1).unwrap();
+        }
+
+        let removed = tree.prune(&main[4].hash()).unwrap();
+        assert_eq!(removed.len(), 8);
+        assert!(removed.contains(&side[0].hash()));
+        assert_eq!(tree.root(), Some(main[4].hash()));
+        assert!(!tree.contains(&side[0].hash()));
+        assert_eq!(tree.ancestor_at(&main[9].hash(), 5), Some(main[4].hash()));
+        assert_eq!(tree.ancestor_at(&main[9].hash(), 2), None);
+        assert_eq!(tree.common_ancestor(&main[9].hash(), &main[6].hash()), Some(main[6].hash()));
+    }
+
+    #[test]
+    fn test_block_tree_restore() {
+        let genesis = Block::genesis(Hash::zero());
+        let main = build_branch(&genesis, 40, 0);
+        let side = build_branch(&main[9], 12, 1);
+
+        let mut tree = BlockTree::new();
+        tree.insert(&genesis.header, 0).unwrap();
+        for block in main.iter().chain(side.iter()) {
+            tree.insert(&block.header, 3).unwrap();
+        }
+        tree.prune(&main[4].hash()).unwrap();
+
+        // Nodes round-trip one by one without their lifting tables
+        let stored: Vec<Vec<u8>> = std::iter::once(main[2].hash())
+            .chain(main.iter().chain(side.iter()).map(|b| b.hash()))
+            .filter_map(|hash| tree.get(&hash))
+            .map(|node| bincode::serialize(node).unwrap())
+            .collect();
+        let nodes = stored.iter().map(|bytes| bincode::deserialize::<TreeNode>(bytes).unwrap());
+        let restored = BlockTree::restore(main[4].hash(), nodes).unwrap();
+
+        assert_eq!(restored.len(), tree.len());
+        assert_eq!(restored.root(), Some(main[4].hash()));
+        assert_eq!(restored.cumulative_weight(&side[11].hash()), tree.cumulative_weight(&side[11].hash()));
+        assert_eq!(restored.ancestor_at(&main[39].hash(), 7), Some(main[6].hash()));
+        assert_eq!(restored.common_ancestor(&main[39].hash(), &side[11].hash()), Some(main[9].hash()));
+        assert_eq!(restored.heaviest_tip(), tree.heaviest_tip());
+
+        // The root must be among the nodes
+        assert!(BlockTree::restore(main[0].hash(), Vec::new()).is_err());
+    }
+
+    #[test]
+    fn test_reorg_path_uses_tree() {
+        let genesis = Block::genesis(Hash::zero());
+        let main = build_branch(&genesis, 6, 0);
+        let side = build_branch(&main[1], 8, 1);
+
+        let mut resolver = ForkResolver::new(ForkChoice::HeaviestChain, 3);
+        let mut blocks = HashMap::new();
+        for block in std::iter::once(&genesis).chain(main.iter()).chain(side.iter()) {
+            resolver.track_block(block, 1).unwrap();
+            blocks.insert(block.hash(), block.clone());
+        }
+
+        let ancestor = resolver.find_common_ancestor(&blocks, &blocks, &main[5].hash(), &side[7].hash());
+        assert_eq!(ancestor, Some(main[1].hash()));
+
+        // Reverting four blocks exceeds the limit of three
+        let err = resolver.calculate_reorg_path(&blocks, &main[5].hash(), &side[7].hash());
+        assert!(matches!(err, Err(BlockchainError::ReorgTooDeep { depth: 4 })));
+
+        let path = resolver.calculate_reorg_path(&blocks, &main[3].hash(), &side[7].hash()).unwrap();
+        assert_eq!(path.common_ancestor, main[1].hash());
+        assert_eq!(path.depth, 2);
+        assert_eq!(path.apply_blocks.len(), 8);
+        assert_eq!(path.apply_blocks[0].hash(), side[0].hash());
+    }
+
+    #[test]
+    fn test_common_ancestor_without_tree() {
+        let resolver = ForkResolver::new(ForkChoice::LongestChain, 100);
+        let genesis = Block::genesis(Hash::zero());
+        let main = build_branch(&genesis, 5, 0);
+        let side = build_branch(&main[0], 2, 1);
+
+        let mut blocks = HashMap::new();
+        for block in std::iter::once(&genesis).chain(main.iter()).chain(side.iter()) {
+            blocks.insert(block.hash(), block.clone());
+        }
+
+        let ancestor = resolver.find_common_ancestor(&blocks, &blocks, &main[4].hash(), &side[1].hash());
+        assert_eq!(ancestor, Some(main[0].hash()));
+    }
+}
@@ -10,15 +10,23 @@
 
 pub mod block;
 pub mod chain;
+pub mod clock;
+pub mod config;
 pub mod transaction;
 pub mod state;
 pub mod types;
+pub mod fixed;
+pub mod evidence;
 
-pub use block::{Block, BlockHeader};
+pub use block::{Block, BlockHeader, ProposerElection};
 pub use chain::Blockchain;
+pub use clock::{Clock, ManualClock, SharedClock, SystemClock};
+pub use config::ChainConfig;
 pub use transaction::{Transaction, TransactionType, TransactionReceipt};
 pub use state::{Account, WorldState};
 pub use types::*;
+pub use fixed::Fixed;
+pub use evidence::DoubleSignEvidence;
 
 
 // ADD these module declarations (around line 10, after existing modules):
@@ -29,7 +37,7 @@ pub mod metrics;
 // ADD these to pub use statements (around line 17):
 pub use mempool::{TransactionPool, PoolConfig, PoolMetrics};
 pub use fork::{ForkChoice, ForkResolver};
-pub use metrics::ChainMetrics;
+pub use metrics::{ChainMetrics, MetricsCalculator, BlockExecution, Percentiles};
 
 
 
@@ -87,8 +95,29 @@ pub enum BlockchainError {
     #[error("Gas limit exceeded")]
     GasLimitExceeded,
 
+    #[error("Intrinsic gas too low: required {required}, provided {provided}")]
+    IntrinsicGasTooLow { required: Gas, provided: Gas },
+
+    #[error("Transaction too large: {size} bytes (max {max})")]
+    TransactionTooLarge { size: usize, max: usize },
+
+    #[error("Block too large: {size} bytes (max {max})")]
+    BlockTooLarge { size: usize, max: usize },
+
+    #[error("Contract code too large: {size} bytes (max {max})")]
+    ContractCodeTooLarge { size: usize, max: usize },
+
     #[error("Invalid signature")]
     InvalidSignature,
+
+    #[error("Invalid signature on transaction {index}")]
+    InvalidTransactionSignature { index: usize },
+
+    #[error("Invalid evidence: {0}")]
+    InvalidEvidence(String),
+
+    #[error("Evidence already processed: {0}")]
+    DuplicateEvidence(Hash),
 }
 
 #[cfg(test)]
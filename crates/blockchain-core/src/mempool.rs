@@ -1,9 +1,16 @@
 // blockchain-core/src/mempool.rs
 
-use crate::{transaction::Transaction, BlockchainError, BlockchainResult, Gas};
+use crate::{
+    clock::{system_clock, SharedClock},
+    transaction::{verify_signatures, Transaction},
+    BlockchainError, BlockchainResult, Gas, MAX_BLOCK_BYTES,
+};
 use blockchain_crypto::{Address, Hash};
 use serde::{Deserialize, Serialize};
-use std::collections::{HashMap, BTreeMap};
+use std::collections::{HashMap, BTreeMap, VecDeque};
+
+/// Bytes reserved for the header and validator signatures when filling a block
+const BLOCK_OVERHEAD_BYTES: usize = 64 * 1024;
 
 /// Transaction pool configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -73,8 +80,14 @@ pub struct TransactionPool {
     by_hash: HashMap<Hash, Transaction>,
     /// Transaction count by sender
     by_sender: HashMap<Address, usize>,
+    /// Admission times of recently included transactions, for inclusion latency metrics
+    included: HashMap<Hash, crate::Timestamp>,
+    /// Insertion order of `included`, oldest first
+    included_order: VecDeque<Hash>,
     /// Metrics
     metrics: PoolMetrics,
+    /// Time source for admission and expiry
+    clock: SharedClock,
 }
 
 impl TransactionPool {
@@ -86,15 +99,55 @@ impl TransactionPool {
             queued: HashMap::new(),
             by_hash: HashMap::new(),
             by_sender: HashMap::new(),
+            included: HashMap::new(),
+            included_order: VecDeque::new(),
             metrics: PoolMetrics::default(),
+            clock: system_clock(),
         }
     }
 
+    /// Replace the time source
+    pub fn set_clock(&mut self, clock: SharedClock) {
+        self.clock = clock;
+    }
+
     /// Add transaction to pool
     pub fn add(&mut self, tx: Transaction, current_nonce: u64) -> BlockchainResult<()> {
         // Validate transaction
         tx.validate_basic()?;
+        tx.verify()?;
+
+        self.insert(tx, current_nonce)
+    }
+
+    /// Add many transactions at once, e.g. from a mempool sync response
+    ///
+    /// Signatures are checked in one batch. If the batch fails, each
+    /// transaction is checked on its own so only the bad ones are rejected.
+    /// Returns one result per transaction, in order.
+    pub fn add_batch<F>(&mut self, transactions: Vec<Transaction>, current_nonce: F) -> Vec<BlockchainResult<()>>
+    where
+        F: Fn(&Address) -> u64,
+    {
+        let basic: Vec<BlockchainResult<()>> = transactions.iter().map(|tx| tx.validate_basic()).collect();
+        let batch_ok = basic.iter().all(|r| r.is_ok()) && verify_signatures(&transactions).is_ok();
+
+        transactions
+            .into_iter()
+            .zip(basic)
+            .map(|(tx, basic)| {
+                basic?;
+                if !batch_ok {
+                    tx.verify()?;
+                }
+                let nonce = current_nonce(&tx.from);
+                self.insert(tx, nonce)
+            })
+            .collect()
+    }
 
+    /// Admit a transaction whose signature has already been checked
+    fn insert(&mut self, tx: Transaction, current_nonce: u64) -> BlockchainResult<()> {
         // Check if already exists
         let tx_hash = tx.hash();
         if self.by_hash.contains_key(&tx_hash) {
@@ -135,7 +188,7 @@ impl TransactionPool {
 
         let entry = PoolEntry {
             transaction: tx.clone(),
-            added_at: current_timestamp(),
+            added_at: self.clock.now(),
             status,
         };
 
@@ -169,6 +222,7 @@ impl TransactionPool {
     pub fn get_pending(&self, max_gas: Gas, max_count: usize) -> Vec<Transaction> {
         let mut transactions = Vec::new();
         let mut total_gas = 0u64;
+        let mut total_bytes = 0usize;
 
         // Iterate from highest to lowest gas price
         for (_, tx_map) in self.pending.iter().rev() {
@@ -177,11 +231,18 @@ impl TransactionPool {
                     continue;
                 }
 
+                // Leave room for the block header within the block size limit
+                let size = entry.transaction.size();
+                if total_bytes + size > MAX_BLOCK_BYTES - BLOCK_OVERHEAD_BYTES {
+                    continue;
+                }
+
                 if transactions.len() >= max_count {
                     return transactions;
                 }
 
                 total_gas += entry.transaction.gas_limit;
+                total_bytes += size;
                 transactions.push(entry.transaction.clone());
             }
         }
@@ -214,7 +275,11 @@ impl TransactionPool {
     /// Remove transactions that were included in a block
     pub fn remove_included(&mut self, transactions: &[Transaction]) {
         for tx in transactions {
-            self.remove(&tx.hash());
+            let tx_hash = tx.hash();
+            if let Some(added_at) = self.admission_time(&tx_hash) {
+                self.record_included(tx_hash, added_at);
+            }
+            self.remove(&tx_hash);
         }
 
         // Try to promote queued transactions
@@ -255,7 +320,7 @@ impl TransactionPool {
 
     /// Prune old transactions
     pub fn prune(&mut self) {
-        let now = current_timestamp();
+        let now = self.clock.now();
         let max_age = self.config.max_age;
         let mut to_remove = Vec::new();
 
@@ -288,6 +353,22 @@ impl TransactionPool {
         self.by_hash.get(hash)
     }
 
+    /// Get the time a transaction was admitted to the pool.
+    ///
+    /// Also answers for recently included transactions so block metrics can
+    /// compute inclusion latency after the pool has dropped them.
+    pub fn admission_time(&self, hash: &Hash) -> Option<crate::Timestamp> {
+        if let Some(added_at) = self.included.get(hash) {
+            return Some(*added_at);
+        }
+
+        let tx = self.by_hash.get(hash)?;
+        self.pending.get(&tx.gas_price)
+            .and_then(|tx_map| tx_map.get(hash))
+            .or_else(|| self.queued.get(&tx.from).and_then(|nonces| nonces.get(&tx.nonce)))
+            .map(|entry| entry.added_at)
+    }
+
     /// Get all transactions from sender
     pub fn get_by_sender(&self, sender: &Address) -> Vec<Transaction> {
         self.by_hash.values()
@@ -317,11 +398,26 @@ impl TransactionPool {
         self.queued.clear();
         self.by_hash.clear();
         self.by_sender.clear();
+        self.included.clear();
+        self.included_order.clear();
         self.metrics = PoolMetrics::default();
     }
 
     // Helper methods
 
+    fn record_included(&mut self, tx_hash: Hash, added_at: crate::Timestamp) {
+        if self.included.insert(tx_hash, added_at).is_none() {
+            self.included_order.push_back(tx_hash);
+        }
+
+        // Bound the record to the pool size
+        while self.included_order.len() > self.config.max_size {
+            if let Some(oldest) = self.included_order.pop_front() {
+                self.included.remove(&oldest);
+            }
+        }
+    }
+
     fn remove_from_pending(&mut self, tx_hash: &Hash, gas_price: u64) {
         if let Some(tx_map) = self.pending.get_mut(&gas_price) {
             if tx_map.remove(tx_hash).is_some() {
@@ -357,13 +453,6 @@ impl TransactionPool {
     }
 }
 
-fn current_timestamp() -> crate::Timestamp {
-    std::time::SystemTime::now()
-        .duration_since(std::time::UNIX_EPOCH)
-        .unwrap()
-        .as_secs()
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -422,4 +511,47 @@ mod tests {
         assert!(pending[0].gas_price >= pending[1].gas_price);
         assert!(pending[1].gas_price >= pending[2].gas_price);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_prune_in_virtual_time() {
+        let clock = crate::ManualClock::new(1_000);
+        let mut pool = TransactionPool::new(PoolConfig::default());
+        pool.set_clock(clock.shared());
+
+        let keypair = KeyPair::generate(SignatureScheme::Ed25519).unwrap();
+        let mut tx = create_test_tx(0, 10);
+        tx.from = keypair.public_key().to_address();
+        tx.sign(&keypair).unwrap();
+        pool.add(tx, 0).unwrap();
+
+        clock.advance(PoolConfig::default().max_age);
+        pool.prune();
+        assert_eq!(pool.metrics().total_transactions, 1);
+
+        clock.advance(1);
+        pool.prune();
+        assert_eq!(pool.metrics().total_transactions, 0);
+    }
+
+    #[test]
+    fn test_add_batch_rejects_only_bad_signatures() {
+        let mut pool = TransactionPool::new(PoolConfig::default());
+        let mut txs: Vec<Transaction> = (0..4)
+            .map(|_| {
+                let keypair = KeyPair::generate(SignatureScheme::Ed25519).unwrap();
+                let mut tx = create_test_tx(0, 10);
+                tx.from = keypair.public_key().to_address();
+                tx.sign(&keypair).unwrap();
+                tx
+            })
+            .collect();
+
+        // Tamper after signing
+        txs[2].gas_price = 20;
+
+        let results = pool.add_batch(txs, |_| 0);
+        assert!(results[0].is_ok() && results[1].is_ok() && results[3].is_ok());
+        assert!(matches!(results[2], Err(BlockchainError::InvalidSignature)));
+        assert_eq!(pool.pending_count(), 3);
+    }
+}
@@ -1,8 +1,12 @@
 // blockchain-core/src/metrics.rs
 
-use crate::{Block, BlockNumber, Amount, Gas};
+use crate::{
+    transaction::{ExecutionStatus, TransactionReceipt},
+    Amount, Block, BlockNumber, Gas, Timestamp,
+};
+use blockchain_crypto::Hash;
 use serde::{Deserialize, Serialize};
-use std::collections::VecDeque;
+use std::collections::{BTreeMap, HashMap, VecDeque};
 
 /// Chain metrics and statistics
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -25,6 +29,27 @@ pub struct ChainMetrics {
     pub block_size_stats: SizeStats,
     /// Gas usage statistics
     pub gas_stats: GasStats,
+    /// Rolling block time distribution (seconds)
+    #[serde(default)]
+    pub block_time_percentiles: Percentiles,
+    /// Rolling block fullness distribution (gas used as % of gas limit)
+    #[serde(default)]
+    pub block_fullness_percentiles: Percentiles,
+    /// Rolling inclusion latency distribution (seconds from mempool admission to block)
+    #[serde(default)]
+    pub inclusion_latency_percentiles: Percentiles,
+    /// Transactions processed per transaction type
+    #[serde(default)]
+    pub tx_type_counts: BTreeMap<String, u64>,
+    /// Fee and burn totals
+    #[serde(default)]
+    pub fee_stats: FeeStats,
+    /// Total failed or reverted transactions
+    #[serde(default)]
+    pub failed_transactions: u64,
+    /// Rolling failed transaction rate (%)
+    #[serde(default)]
+    pub failed_tx_rate: f64,
 }
 
 impl ChainMetrics {
@@ -40,6 +65,13 @@ impl ChainMetrics {
             avg_tx_per_block: 0.0,
             block_size_stats: SizeStats::default(),
             gas_stats: GasStats::default(),
+            block_time_percentiles: Percentiles::default(),
+            block_fullness_percentiles: Percentiles::default(),
+            inclusion_latency_percentiles: Percentiles::default(),
+            tx_type_counts: BTreeMap::new(),
+            fee_stats: FeeStats::default(),
+            failed_transactions: 0,
+            failed_tx_rate: 0.0,
         }
     }
 }
@@ -67,6 +99,80 @@ pub struct GasStats {
     pub utilization_rate: f64,
 }
 
+/// p50/p90/p99 summary of a sample window
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Percentiles {
+    pub p50: f64,
+    pub p90: f64,
+    pub p99: f64,
+}
+
+impl Percentiles {
+    /// Compute nearest-rank percentiles over the samples
+    pub fn from_samples(samples: &[f64]) -> Self {
+        if samples.is_empty() {
+            return Self::default();
+        }
+
+        let mut sorted = samples.to_vec();
+        sorted.sort_by(|a, b| a.total_cmp(b));
+
+        Self {
+            p50: nearest_rank(&sorted, 50),
+            p90: nearest_rank(&sorted, 90),
+            p99: nearest_rank(&sorted, 99),
+        }
+    }
+}
+
+/// Nearest-rank percentile of an ascending, non-empty slice
+fn nearest_rank(sorted: &[f64], percentile: usize) -> f64 {
+    let rank = (percentile * sorted.len()).div_ceil(100);
+    sorted[rank.saturating_sub(1)]
+}
+
+/// Fee and burn statistics
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeeStats {
+    /// Total fees paid (gas used * gas price)
+    pub total_fees: Amount,
+    /// Total fees burned
+    pub total_burned: Amount,
+    /// Average fee per transaction over the rolling window
+    pub avg_fee_per_tx: f64,
+}
+
+impl Default for FeeStats {
+    fn default() -> Self {
+        Self {
+            total_fees: Amount::zero(),
+            total_burned: Amount::zero(),
+            avg_fee_per_tx: 0.0,
+        }
+    }
+}
+
+/// Execution results for a block, used for fee, failure and latency metrics
+#[derive(Debug, Clone)]
+pub struct BlockExecution {
+    /// Receipts for the block's transactions
+    pub receipts: Vec<TransactionReceipt>,
+    /// Mempool admission time by transaction hash
+    pub added_at: HashMap<Hash, Timestamp>,
+    /// Amount burned while executing the block
+    pub burned: Amount,
+}
+
+impl Default for BlockExecution {
+    fn default() -> Self {
+        Self {
+            receipts: Vec::new(),
+            added_at: HashMap::new(),
+            burned: Amount::zero(),
+        }
+    }
+}
+
 /// Metrics calculator
 pub struct MetricsCalculator {
     /// Recent blocks for rolling statistics
@@ -89,12 +195,28 @@ impl MetricsCalculator {
 
     /// Update metrics with new block
     pub fn update(&mut self, block: &Block, prev_timestamp: u64) {
-        let block_stats = BlockStats::from_block(block, prev_timestamp);
+        self.update_with_execution(block, prev_timestamp, &BlockExecution::default());
+    }
+
+    /// Update metrics with new block and its execution results
+    pub fn update_with_execution(&mut self, block: &Block, prev_timestamp: u64, execution: &BlockExecution) {
+        let block_stats = BlockStats::from_block(block, prev_timestamp, execution);
         
         // Update cumulative metrics
         self.cumulative.height = block.number();
         self.cumulative.total_transactions += block.transactions.len() as u64;
         self.cumulative.total_gas_used += block.header.gas_used;
+        self.cumulative.failed_transactions += block_stats.failed_count as u64;
+
+        for tx in &block.transactions {
+            *self.cumulative.tx_type_counts
+                .entry(tx.tx_type.name().to_string())
+                .or_insert(0) += 1;
+        }
+
+        let fee_stats = &mut self.cumulative.fee_stats;
+        fee_stats.total_fees = fee_stats.total_fees.clone() + block_stats.fees.clone();
+        fee_stats.total_burned = fee_stats.total_burned.clone() + execution.burned.clone();
 
         // Add to recent blocks
         self.recent_blocks.push_back(block_stats);
@@ -167,6 +289,41 @@ impl MetricsCalculator {
                 0.0
             },
         };
+
+        // Distributions
+        let block_times: Vec<f64> = self.recent_blocks.iter()
+            .map(|b| b.block_time)
+            .collect();
+        self.cumulative.block_time_percentiles = Percentiles::from_samples(&block_times);
+
+        let fullness: Vec<f64> = self.recent_blocks.iter()
+            .map(|b| b.fullness)
+            .collect();
+        self.cumulative.block_fullness_percentiles = Percentiles::from_samples(&fullness);
+
+        let latencies: Vec<f64> = self.recent_blocks.iter()
+            .flat_map(|b| b.inclusion_latencies.iter().map(|l| *l as f64))
+            .collect();
+        self.cumulative.inclusion_latency_percentiles = Percentiles::from_samples(&latencies);
+
+        // Failure rate and fees over executed transactions only
+        let executed: usize = self.recent_blocks.iter()
+            .map(|b| b.executed_count)
+            .sum();
+        let failed: usize = self.recent_blocks.iter()
+            .map(|b| b.failed_count)
+            .sum();
+        let window_fees: f64 = self.recent_blocks.iter()
+            .map(|b| b.fees.to_f64_lossy())
+            .sum();
+
+        if executed > 0 {
+            self.cumulative.failed_tx_rate = (failed as f64 / executed as f64) * 100.0;
+            self.cumulative.fee_stats.avg_fee_per_tx = window_fees / executed as f64;
+        } else {
+            self.cumulative.failed_tx_rate = 0.0;
+            self.cumulative.fee_stats.avg_fee_per_tx = 0.0;
+        }
     }
 
     /// Get current metrics
@@ -192,10 +349,15 @@ struct BlockStats {
     gas_used: Gas,
     gas_limit: Gas,
     avg_gas_price: u64,
+    fullness: f64,
+    inclusion_latencies: Vec<u64>,
+    executed_count: usize,
+    failed_count: usize,
+    fees: Amount,
 }
 
 impl BlockStats {
-    fn from_block(block: &Block, prev_timestamp: u64) -> Self {
+    fn from_block(block: &Block, prev_timestamp: u64, execution: &BlockExecution) -> Self {
         let block_time = if prev_timestamp > 0 {
             block.header.timestamp.saturating_sub(prev_timestamp) as f64
         } else {
@@ -215,6 +377,31 @@ impl BlockStats {
             .map(|b| b.len())
             .unwrap_or(0);
 
+        let fullness = if block.header.gas_limit > 0 {
+            (block.header.gas_used as f64 / block.header.gas_limit as f64) * 100.0
+        } else {
+            0.0
+        };
+
+        let inclusion_latencies = block.transactions.iter()
+            .filter_map(|tx| execution.added_at.get(&tx.hash()))
+            .map(|added_at| block.header.timestamp.saturating_sub(*added_at))
+            .collect();
+
+        let gas_prices: HashMap<Hash, u64> = block.transactions.iter()
+            .map(|tx| (tx.hash(), tx.gas_price))
+            .collect();
+        let mut fees = Amount::zero();
+        let mut failed_count = 0;
+        for receipt in &execution.receipts {
+            if receipt.status != ExecutionStatus::Success {
+                failed_count += 1;
+            }
+            if let Some(gas_price) = gas_prices.get(&receipt.tx_hash) {
+                fees = fees + Amount::from_u64(receipt.gas_used.saturating_mul(*gas_price));
+            }
+        }
+
         Self {
             number: block.number(),
             timestamp: block.header.timestamp,
@@ -224,6 +411,11 @@ impl BlockStats {
             gas_used: block.header.gas_used,
             gas_limit: block.header.gas_limit,
             avg_gas_price,
+            fullness,
+            inclusion_latencies,
+            executed_count: execution.receipts.len(),
+            failed_count,
+            fees,
         }
     }
 }
@@ -266,4 +458,106 @@ mod tests {
         assert_eq!(metrics.height, 5);
         assert!(metrics.avg_block_time > 0.0);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_old_snapshot_deserializes() {
+        // Snapshot written before distribution, fee and failure metrics existed
+        let old = r#"{
+            "height": 42,
+            "total_transactions": 120,
+            "total_gas_used": 2520000,
+            "avg_block_time": 3.0,
+            "avg_gas_price": 10.0,
+            "tps": 1.5,
+            "avg_tx_per_block": 2.5,
+            "block_size_stats": { "min": 100, "max": 900, "avg": 450.0 },
+            "gas_stats": { "total_used": 2520000, "total_limit": 10000000, "avg_usage": 52500.0, "utilization_rate": 25.2 }
+        }"#;
+
+        let metrics: ChainMetrics = serde_json::from_str(old).unwrap();
+        assert_eq!(metrics.height, 42);
+        assert_eq!(metrics.gas_stats.total_used, 2520000);
+        assert_eq!(metrics.block_time_percentiles.p99, 0.0);
+        assert!(metrics.tx_type_counts.is_empty());
+        assert_eq!(metrics.fee_stats.total_burned, Amount::zero());
+        assert_eq!(metrics.failed_transactions, 0);
+    }
+
+    #[test]
+    fn test_percentiles() {
+        let samples: Vec<f64> = (1..=100).map(|i| i as f64).collect();
+        let p = Percentiles::from_samples(&samples);
+        assert_eq!(p.p50, 50.0);
+        assert_eq!(p.p90, 90.0);
+        assert_eq!(p.p99, 99.0);
+
+        let p = Percentiles::from_samples(&[7.0]);
+        assert_eq!(p.p50, 7.0);
+        assert_eq!(p.p99, 7.0);
+        assert_eq!(Percentiles::from_samples(&[]).p90, 0.0);
+    }
+
+    #[test]
+    fn test_execution_metrics() {
+        use crate::transaction::{Transaction, TransactionType};
+
+        let transfer = Transaction::new(
+            Address::zero(),
+            0,
+            TransactionType::Transfer { to: Address::zero(), amount: Amount::from_u64(5) },
+            10,
+            21000,
+        );
+        let stake = Transaction::new(
+            Address::zero(),
+            1,
+            TransactionType::Stake { amount: Amount::from_u64(5) },
+            20,
+            21000,
+        );
+
+        let mut block = Block::new(
+            1,
+            Hash::zero(),
+            Hash::zero(),
+            Address::zero(),
+            vec![transfer.clone(), stake.clone()],
+            84_000,
+        ).unwrap();
+        block.header.timestamp = 1_000;
+
+        let receipt = |tx: &Transaction, status| TransactionReceipt {
+            tx_hash: tx.hash(),
+            block_number: 1,
+            from: tx.from,
+            to: None,
+            gas_used: 21000,
+            status,
+            contract_address: None,
+            logs: Vec::new(),
+        };
+        let execution = BlockExecution {
+            receipts: vec![
+                receipt(&transfer, ExecutionStatus::Success),
+                receipt(&stake, ExecutionStatus::Failed),
+            ],
+            added_at: [(transfer.hash(), 990), (stake.hash(), 996)].into_iter().collect(),
+            burned: Amount::from_u64(1_000),
+        };
+
+        let mut calc = MetricsCalculator::new(10);
+        calc.update_with_execution(&block, 997, &execution);
+
+        let metrics = calc.metrics();
+        assert_eq!(metrics.tx_type_counts.get("transfer"), Some(&1));
+        assert_eq!(metrics.tx_type_counts.get("stake"), Some(&1));
+        assert_eq!(metrics.failed_transactions, 1);
+        assert_eq!(metrics.failed_tx_rate, 50.0);
+        assert_eq!(metrics.fee_stats.total_fees, Amount::from_u64(21000 * 10 + 21000 * 20));
+        assert_eq!(metrics.fee_stats.total_burned, Amount::from_u64(1_000));
+        assert_eq!(metrics.block_fullness_percentiles.p50, 50.0);
+        assert_eq!(metrics.inclusion_latency_percentiles.p50, 4.0);
+        assert_eq!(metrics.inclusion_latency_percentiles.p99, 10.0);
+        assert_eq!(metrics.block_time_percentiles.p90, 3.0);
+    }
+}
@@ -1,7 +1,7 @@
 // blockchain-core/src/state.rs
 
-use crate::{types::*, BlockchainError, BlockchainResult};
-use blockchain_crypto::{hash::Hashable, Address, Hash};
+use crate::{config, types::*, BlockchainError, BlockchainResult};
+use blockchain_crypto::{Address, Hash, HashAlgorithm, Hasher};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -83,6 +83,13 @@ impl Account {
         Ok(())
     }
 
+    /// Remove a share of the stake in basis points, returning the amount removed
+    pub fn slash(&mut self, bps: u64) -> StakeAmount {
+        let slashed = self.staked.mul_bps(bps);
+        self.staked = self.staked.saturating_sub(&slashed);
+        slashed
+    }
+
     /// Unstake tokens
     pub fn unstake(&mut self, amount: &StakeAmount) -> BlockchainResult<()> {
         if self.staked.inner() < amount.inner() {
@@ -200,24 +207,28 @@ impl WorldState {
         Ok(())
     }
 
-    /// Calculate state root hash
+    /// Calculate state root hash with the chain's hash algorithm
     pub fn state_root(&self) -> Hash {
+        self.state_root_with(config::hash_algorithm())
+    }
+
+    /// Calculate state root hash with the given algorithm
+    pub fn state_root_with(&self, algorithm: HashAlgorithm) -> Hash {
+        if self.accounts.is_empty() {
+            return Hash::zero();
+        }
+
         // Sort accounts by address for deterministic hashing
         let mut sorted_accounts: Vec<_> = self.accounts.iter().collect();
         sorted_accounts.sort_by_key(|(addr, _)| *addr);
 
-        // Serialize and hash
-        let mut combined = Vec::new();
+        // Stream each address and encoded account into the hasher
+        let mut hasher = algorithm.hasher();
         for (addr, account) in sorted_accounts {
-            combined.extend_from_slice(addr.as_bytes());
-            combined.extend_from_slice(&bincode::serialize(account).unwrap());
-        }
-
-        if combined.is_empty() {
-            Hash::zero()
-        } else {
-            combined.hash()
+            hasher.update(addr.as_bytes());
+            bincode::serialize_into(&mut hasher, account).expect("hasher writes never fail");
         }
+        hasher.finalize()
     }
 
     /// Begin transaction (checkpoint)
@@ -226,12 +237,16 @@ impl WorldState {
     }
 
     /// Commit transaction
+    ///
+    /// Inside an enclosing checkpoint the modifications are kept, so rolling
+    /// that checkpoint back still undoes them.
     pub fn commit(&mut self) {
-        // Remove modifications up to last checkpoint
-        while let Some(mod_type) = self.modifications.pop() {
-            if matches!(mod_type, StateModification::Checkpoint) {
-                break;
-            }
+        let is_checkpoint = |m: &StateModification| matches!(m, StateModification::Checkpoint);
+        if let Some(last) = self.modifications.iter().rposition(is_checkpoint) {
+            self.modifications.remove(last);
+        }
+        if !self.modifications.iter().any(is_checkpoint) {
+            self.modifications.clear();
         }
     }
 
@@ -348,6 +363,30 @@ mod tests {
         assert_eq!(state.get_balance(&addr2), Amount::from_u64(300));
     }
 
+    #[test]
+    fn test_nested_commit_rolls_back_with_outer_checkpoint() {
+        let mut state = WorldState::new();
+        let addr = Address::zero();
+        state.get_account_mut(&addr).add_balance(&Amount::from_u64(100)).unwrap();
+
+        state.checkpoint();
+        state.checkpoint();
+        state.get_account_mut(&addr).add_balance(&Amount::from_u64(50)).unwrap();
+        state.commit();
+        assert_eq!(state.get_balance(&addr), Amount::from_u64(150));
+
+        // The committed inner change is undone with the outer checkpoint
+        state.rollback();
+        assert_eq!(state.get_balance(&addr), Amount::from_u64(100));
+
+        // Without an enclosing checkpoint a commit is final
+        state.checkpoint();
+        state.get_account_mut(&addr).add_balance(&Amount::from_u64(50)).unwrap();
+        state.commit();
+        state.rollback();
+        assert_eq!(state.get_balance(&addr), Amount::from_u64(150));
+    }
+
     #[test]
     fn test_state_root() {
         let mut state = WorldState::new();
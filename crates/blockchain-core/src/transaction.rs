@@ -1,7 +1,7 @@
 // blockchain-core/src/transaction.rs
 
-use crate::{types::*, BlockchainError, BlockchainResult};
-use blockchain_crypto::{hash::Hashable, Address, Hash, PublicKey, Signature};
+use crate::{clock::{Clock, SystemClock}, config, evidence::DoubleSignEvidence, types::*, BlockchainError, BlockchainResult};
+use blockchain_crypto::{hash::Hashable, verify_batch, Address, BatchItem, Hash, HashAlgorithm, PublicKey, Signature};
 use serde::{Deserialize, Serialize};
 
 /// Transaction types supported by the protocol
@@ -40,6 +40,29 @@ pub enum TransactionType {
         contract: Address,
         data: Vec<u8>,
     },
+    /// Report a validator's double-signing; the sender receives the whistleblower share
+    SubmitEvidence {
+        evidence: Box<DoubleSignEvidence>,
+    },
+    /// Return the sending validator to the active set once its jail time is served
+    Unjail,
+}
+
+impl TransactionType {
+    /// Short name of the transaction type, used for metrics and logging
+    pub fn name(&self) -> &'static str {
+        match self {
+            TransactionType::Transfer { .. } => "transfer",
+            TransactionType::Stake { .. } => "stake",
+            TransactionType::Unstake { .. } => "unstake",
+            TransactionType::DeployLiquidity { .. } => "deploy_liquidity",
+            TransactionType::WithdrawLiquidity { .. } => "withdraw_liquidity",
+            TransactionType::ContractDeployment { .. } => "contract_deployment",
+            TransactionType::ContractCall { .. } => "contract_call",
+            TransactionType::SubmitEvidence { .. } => "submit_evidence",
+            TransactionType::Unjail => "unjail",
+        }
+    }
 }
 
 /// Complete transaction structure
@@ -57,6 +80,8 @@ pub struct Transaction {
     pub gas_limit: Gas,
     /// Transaction timestamp
     pub timestamp: Timestamp,
+    /// Signer's public key, set by `sign`
+    pub public_key: Option<PublicKey>,
     /// Digital signature
     pub signature: Option<Signature>,
 }
@@ -69,6 +94,18 @@ impl Transaction {
         tx_type: TransactionType,
         gas_price: GasPrice,
         gas_limit: Gas,
+    ) -> Self {
+        Self::new_with_clock(from, nonce, tx_type, gas_price, gas_limit, &SystemClock)
+    }
+
+    /// Create a new unsigned transaction timestamped by the given clock
+    pub fn new_with_clock(
+        from: Address,
+        nonce: Nonce,
+        tx_type: TransactionType,
+        gas_price: GasPrice,
+        gas_limit: Gas,
+        clock: &dyn Clock,
     ) -> Self {
         Self {
             from,
@@ -76,16 +113,15 @@ impl Transaction {
             tx_type,
             gas_price,
             gas_limit,
-            timestamp: std::time::SystemTime::now()
-                .duration_since(std::time::UNIX_EPOCH)
-                .unwrap()
-                .as_secs(),
+            timestamp: clock.now(),
+            public_key: None,
             signature: None,
         }
     }
 
     /// Sign the transaction
     pub fn sign(&mut self, keypair: &blockchain_crypto::KeyPair) -> BlockchainResult<()> {
+        self.public_key = Some(keypair.public_key().clone());
         let hash = self.hash_for_signing();
         let signature = keypair.sign(hash.as_bytes())?;
         self.signature = Some(signature);
@@ -101,18 +137,32 @@ impl Transaction {
         Ok(public_key.verify(hash.as_bytes(), signature)?)
     }
 
-    /// Calculate transaction hash
+    /// Verify the signature against the embedded public key, which must match `from`
+    pub fn verify(&self) -> BlockchainResult<()> {
+        verify_signatures(std::slice::from_ref(self))
+            .map_err(|_| BlockchainError::InvalidSignature)
+    }
+
+    /// Calculate transaction hash with the chain's hash algorithm
     pub fn hash(&self) -> Hash {
-        let bytes = bincode::serialize(self).unwrap();
-        bytes.hash()
+        self.hash_with(config::hash_algorithm())
     }
 
     /// Hash used for signing (excludes signature field)
+    ///
+    /// Encodes the fields exactly as the transaction with `signature: None`.
     fn hash_for_signing(&self) -> Hash {
-        let mut tx_copy = self.clone();
-        tx_copy.signature = None;
-        let bytes = bincode::serialize(&tx_copy).unwrap();
-        bytes.hash()
+        let unsigned = (
+            &self.from,
+            &self.nonce,
+            &self.tx_type,
+            &self.gas_price,
+            &self.gas_limit,
+            &self.timestamp,
+            &self.public_key,
+            &None::<Signature>,
+        );
+        config::hash_encoded(&unsigned, config::hash_algorithm())
     }
 
     /// Calculate transaction fee
@@ -120,12 +170,50 @@ impl Transaction {
         Amount::from_u64(gas_used * self.gas_price)
     }
 
+    /// Serialized size in bytes
+    pub fn size(&self) -> usize {
+        bincode::serialized_size(self).map(|s| s as usize).unwrap_or(usize::MAX)
+    }
+
+    /// Payload bytes charged per byte of intrinsic gas
+    pub fn payload(&self) -> Vec<u8> {
+        match &self.tx_type {
+            TransactionType::ContractDeployment { bytecode, constructor_args } => {
+                let mut data = bytecode.clone();
+                data.extend_from_slice(constructor_args);
+                data
+            }
+            TransactionType::ContractCall { data, .. } => data.clone(),
+            TransactionType::SubmitEvidence { evidence } => {
+                bincode::serialize(evidence).unwrap_or_default()
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    /// Whether the transaction creates a contract
+    pub fn is_create(&self) -> bool {
+        matches!(self.tx_type, TransactionType::ContractDeployment { .. })
+    }
+
+    /// Minimum gas charged before execution: base cost, creation cost and payload bytes
+    pub fn intrinsic_gas(&self) -> Gas {
+        let mut gas = TX_BASE_GAS;
+        if self.is_create() {
+            gas += TX_CREATE_GAS;
+        }
+        gas.saturating_add(data_gas(&self.payload()))
+    }
+
     /// Validate basic transaction properties
     pub fn validate_basic(&self) -> BlockchainResult<()> {
         // Check signature exists
         if self.signature.is_none() {
             return Err(BlockchainError::InvalidTransaction("Missing signature".into()));
         }
+        if self.public_key.is_none() {
+            return Err(BlockchainError::InvalidTransaction("Missing public key".into()));
+        }
 
         // Check gas limit is reasonable
         if self.gas_limit == 0 {
@@ -137,6 +225,18 @@ impl Transaction {
             return Err(BlockchainError::InvalidTransaction("Gas price cannot be zero".into()));
         }
 
+        // Check serialized size
+        let size = self.size();
+        if size > MAX_TX_BYTES {
+            return Err(BlockchainError::TransactionTooLarge { size, max: MAX_TX_BYTES });
+        }
+
+        // Check gas limit covers intrinsic gas (base + payload bytes)
+        let required = self.intrinsic_gas();
+        if self.gas_limit < required {
+            return Err(BlockchainError::IntrinsicGasTooLow { required, provided: self.gas_limit });
+        }
+
         // Validate transaction type specifics
         match &self.tx_type {
             TransactionType::Transfer { amount, .. } => {
@@ -168,12 +268,22 @@ impl Transaction {
                 if bytecode.is_empty() {
                     return Err(BlockchainError::InvalidTransaction("Contract bytecode cannot be empty".into()));
                 }
+                if bytecode.len() > MAX_CONTRACT_CODE_SIZE {
+                    return Err(BlockchainError::ContractCodeTooLarge {
+                        size: bytecode.len(),
+                        max: MAX_CONTRACT_CODE_SIZE,
+                    });
+                }
             }
             TransactionType::ContractCall { data, .. } => {
                 if data.is_empty() {
                     return Err(BlockchainError::InvalidTransaction("Contract call data cannot be empty".into()));
                 }
             }
+            TransactionType::SubmitEvidence { evidence } => {
+                evidence.verify()?;
+            }
+            TransactionType::Unjail => {}
         }
 
         Ok(())
@@ -201,6 +311,23 @@ impl Transaction {
     }
 }
 
+impl Hashable for Transaction {
+    fn hash(&self) -> Hash {
+        self.hash_with(config::hash_algorithm())
+    }
+
+    fn hash_with(&self, algorithm: HashAlgorithm) -> Hash {
+        config::hash_encoded(self, algorithm)
+    }
+}
+
+/// Intrinsic gas for payload bytes: zero bytes are cheaper than non-zero bytes
+pub fn data_gas(data: &[u8]) -> Gas {
+    data.iter()
+        .map(|b| if *b == 0 { TX_DATA_ZERO_GAS } else { TX_DATA_NON_ZERO_GAS })
+        .fold(0, Gas::saturating_add)
+}
+
 /// Transaction receipt after execution
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TransactionReceipt {
@@ -238,6 +365,41 @@ pub struct Log {
     pub data: Vec<u8>,
 }
 
+/// Verify the signatures of many transactions in one batch
+///
+/// Each transaction must carry a signature and a public key that derives to
+/// its `from` address. Fails with the index of the first bad transaction.
+pub fn verify_signatures(transactions: &[Transaction]) -> BlockchainResult<()> {
+    let hashes: Vec<Hash> = transactions.iter().map(|tx| tx.hash_for_signing()).collect();
+
+    // Items are only collected up to the first transaction lacking a usable
+    // key, since nothing after it can be the lowest failing index
+    let mut items = Vec::with_capacity(transactions.len());
+    let mut first_unusable = None;
+    for (index, (tx, hash)) in transactions.iter().zip(&hashes).enumerate() {
+        match (&tx.public_key, &tx.signature) {
+            (Some(public_key), Some(signature)) if public_key.to_address() == tx.from => {
+                items.push(BatchItem::new(hash.as_bytes(), public_key, signature));
+            }
+            _ => {
+                first_unusable = Some(index);
+                break;
+            }
+        }
+    }
+
+    match verify_batch(&items) {
+        Err(blockchain_crypto::CryptoError::BatchVerificationFailed { index }) => {
+            Err(BlockchainError::InvalidTransactionSignature { index })
+        }
+        Err(other) => Err(other.into()),
+        Ok(()) => match first_unusable {
+            Some(index) => Err(BlockchainError::InvalidTransactionSignature { index }),
+            None => Ok(()),
+        },
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -283,6 +445,33 @@ mod tests {
         assert!(tx.verify_signature(keypair.public_key()).unwrap());
     }
 
+    #[test]
+    fn test_streamed_hashes_match_encoding() {
+        let keypair = KeyPair::generate(SignatureScheme::Ed25519).unwrap();
+        let mut tx = Transaction::new(
+            keypair.public_key().to_address(),
+            3,
+            TransactionType::ContractCall {
+                contract: Address::zero(),
+                data: vec![1, 2, 3],
+            },
+            10,
+            50_000,
+        );
+        tx.sign(&keypair).unwrap();
+
+        let mut unsigned = tx.clone();
+        unsigned.signature = None;
+        assert_eq!(tx.hash_for_signing(), bincode::serialize(&unsigned).unwrap().hash());
+
+        let encoded = bincode::serialize(&tx).unwrap();
+        assert_eq!(tx.hash(), encoded.hash());
+        assert_eq!(
+            tx.hash_with(HashAlgorithm::Sha3_256),
+            encoded.hash_with(HashAlgorithm::Sha3_256)
+        );
+    }
+
     #[test]
     fn test_transaction_validation() {
         let keypair = KeyPair::generate(SignatureScheme::Ed25519).unwrap();
@@ -306,4 +495,102 @@ mod tests {
         tx.sign(&keypair).unwrap();
         assert!(tx.validate_basic().is_ok());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_intrinsic_gas_enforced() {
+        let keypair = KeyPair::generate(SignatureScheme::Ed25519).unwrap();
+        let from = keypair.public_key().to_address();
+
+        let data = vec![0u8, 1, 2, 3];
+        let mut tx = Transaction::new(
+            from,
+            1,
+            TransactionType::ContractCall { contract: Address::zero(), data },
+            10,
+            21000,
+        );
+        assert_eq!(tx.intrinsic_gas(), 21000 + 4 + 3 * 16);
+
+        tx.sign(&keypair).unwrap();
+        assert!(matches!(
+            tx.validate_basic(),
+            Err(BlockchainError::IntrinsicGasTooLow { required: 21052, provided: 21000 })
+        ));
+
+        tx.gas_limit = tx.intrinsic_gas();
+        tx.sign(&keypair).unwrap();
+        assert!(tx.validate_basic().is_ok());
+    }
+
+    #[test]
+    fn test_size_limits_enforced() {
+        let keypair = KeyPair::generate(SignatureScheme::Ed25519).unwrap();
+        let from = keypair.public_key().to_address();
+
+        let mut deploy = Transaction::new(
+            from,
+            1,
+            TransactionType::ContractDeployment {
+                bytecode: vec![1u8; MAX_CONTRACT_CODE_SIZE + 1],
+                constructor_args: Vec::new(),
+            },
+            10,
+            10_000_000,
+        );
+        deploy.sign(&keypair).unwrap();
+        assert!(matches!(
+            deploy.validate_basic(),
+            Err(BlockchainError::ContractCodeTooLarge { .. })
+        ));
+
+        let mut call = Transaction::new(
+            from,
+            1,
+            TransactionType::ContractCall { contract: Address::zero(), data: vec![1u8; MAX_TX_BYTES] },
+            10,
+            u64::MAX,
+        );
+        call.sign(&keypair).unwrap();
+        assert!(matches!(
+            call.validate_basic(),
+            Err(BlockchainError::TransactionTooLarge { .. })
+        ));
+    }
+
+    #[test]
+    fn test_verify_signatures_reports_index() {
+        let signed = |scheme| {
+            let keypair = KeyPair::generate(scheme).unwrap();
+            let mut tx = Transaction::new(
+                keypair.public_key().to_address(),
+                1,
+                TransactionType::Transfer { to: Address::zero(), amount: Amount::from_u64(100) },
+                10,
+                21000,
+            );
+            tx.sign(&keypair).unwrap();
+            tx
+        };
+
+        let mut txs: Vec<Transaction> = (0..6)
+            .map(|i| signed(if i % 2 == 0 { SignatureScheme::Ed25519 } else { SignatureScheme::Secp256k1 }))
+            .collect();
+        assert!(verify_signatures(&txs).is_ok());
+        assert!(txs[0].verify().is_ok());
+
+        // Signed by a key that does not own `from`
+        txs[4].from = Address::zero();
+        assert!(matches!(
+            verify_signatures(&txs),
+            Err(BlockchainError::InvalidTransactionSignature { index: 4 })
+        ));
+
+        // Modified after signing
+        txs[3].nonce = 2;
+        assert!(matches!(
+            verify_signatures(&txs),
+            Err(BlockchainError::InvalidTransactionSignature { index: 3 })
+        ));
+        assert!(txs[3].verify().is_err());
+    }
+}
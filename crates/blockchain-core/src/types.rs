@@ -1,7 +1,9 @@
 // blockchain-core/src/types.rs
 
+use crate::fixed::{Fixed, FIXED_DECIMALS};
 use num_bigint::BigUint;
-use serde::{Deserialize, Serialize};
+use num_traits::{Num, ToPrimitive, Zero};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::ops::{Add, Sub};
 
 /// Block number/height
@@ -19,8 +21,70 @@ pub type Gas = u64;
 /// Timestamp in Unix epoch seconds
 pub type Timestamp = u64;
 
-/// Token amount (using BigUint for arbitrary precision)
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+/// Number of decimal places in one KAI
+pub const KAI_DECIMALS: u32 = 18;
+
+/// Denomination symbol for whole tokens
+pub const KAI_SYMBOL: &str = "KAI";
+
+/// Basis points in 100%
+pub const BPS_DENOMINATOR: u64 = 10_000;
+
+/// Maximum serialized size of a single transaction (128 KiB)
+pub const MAX_TX_BYTES: usize = 128 * 1024;
+
+/// Maximum serialized size of a block (2 MiB)
+pub const MAX_BLOCK_BYTES: usize = 2 * 1024 * 1024;
+
+/// Maximum size of deployed contract bytecode (EIP-170)
+pub const MAX_CONTRACT_CODE_SIZE: usize = 24_576;
+
+/// Intrinsic gas charged for every transaction
+pub const TX_BASE_GAS: Gas = 21_000;
+
+/// Additional intrinsic gas for contract creation
+pub const TX_CREATE_GAS: Gas = 32_000;
+
+/// Intrinsic gas per zero byte of transaction payload
+pub const TX_DATA_ZERO_GAS: Gas = 4;
+
+/// Intrinsic gas per non-zero byte of transaction payload
+pub const TX_DATA_NON_ZERO_GAS: Gas = 16;
+
+/// Rounding mode for division
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Rounding {
+    /// Round toward zero
+    Down,
+    /// Round away from zero
+    Up,
+    /// Round to nearest, ties away from zero
+    HalfUp,
+    /// Round to nearest, ties to even
+    HalfEven,
+}
+
+/// Error parsing a human-readable amount
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum AmountParseError {
+    #[error("Empty amount")]
+    Empty,
+
+    #[error("Invalid digits in amount: {0}")]
+    InvalidDigits(String),
+
+    #[error("Unknown denomination: {0}")]
+    UnknownDenomination(String),
+
+    #[error("Too many decimal places: {given} (max {max})")]
+    TooManyDecimals { given: usize, max: u32 },
+}
+
+/// Token amount in base units (using BigUint for arbitrary precision).
+///
+/// JSON and other human-readable formats encode the amount as a decimal
+/// string of base units; binary formats keep the `BigUint` encoding.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Amount(BigUint);
 
 impl Amount {
@@ -29,24 +93,32 @@ impl Amount {
     }
 
     pub fn zero() -> Self {
-        Self(BigUint::from(0u64))
+        Self(BigUint::zero())
     }
 
     pub fn from_u64(value: u64) -> Self {
         Self(BigUint::from(value))
     }
 
+    pub fn from_u128(value: u128) -> Self {
+        Self(BigUint::from(value))
+    }
+
     pub fn from_tokens(tokens: u64) -> Self {
         // 1 token = 10^18 base units (similar to ETH/wei)
-        Self(BigUint::from(tokens) * BigUint::from(10u64).pow(18))
+        Self(BigUint::from(tokens) * Self::unit_scale(KAI_DECIMALS))
     }
 
     pub fn inner(&self) -> &BigUint {
         &self.0
     }
 
+    pub fn into_inner(self) -> BigUint {
+        self.0
+    }
+
     pub fn is_zero(&self) -> bool {
-        self.0 == BigUint::from(0u64)
+        self.0.is_zero()
     }
 
     pub fn checked_add(&self, other: &Amount) -> Option<Amount> {
@@ -54,19 +126,245 @@ impl Amount {
     }
 
     pub fn checked_sub(&self, other: &Amount) -> Option<Amount> {
-        if &self.0 < &other.0 {
+        if self.0 < other.0 {
             None
         } else {
             Some(Amount(&self.0 - &other.0))
         }
     }
+
+    pub fn saturating_sub(&self, other: &Amount) -> Amount {
+        self.checked_sub(other).unwrap_or_else(Amount::zero)
+    }
+
+    pub fn checked_mul(&self, other: &Amount) -> Option<Amount> {
+        Some(Amount(&self.0 * &other.0))
+    }
+
+    pub fn checked_mul_u64(&self, factor: u64) -> Option<Amount> {
+        Some(Amount(&self.0 * factor))
+    }
+
+    /// Divide rounding down; `None` on division by zero
+    pub fn checked_div(&self, other: &Amount) -> Option<Amount> {
+        if other.is_zero() {
+            None
+        } else {
+            Some(Amount(&self.0 / &other.0))
+        }
+    }
+
+    /// Divide by a `u64` rounding down; `None` on division by zero
+    pub fn checked_div_u64(&self, divisor: u64) -> Option<Amount> {
+        if divisor == 0 {
+            None
+        } else {
+            Some(Amount(&self.0 / divisor))
+        }
+    }
+
+    /// Compute `self * numerator / denominator` without intermediate loss
+    pub fn mul_div(&self, numerator: &Amount, denominator: &Amount, rounding: Rounding) -> Option<Amount> {
+        if denominator.is_zero() {
+            return None;
+        }
+        Some(Amount(div_round(&self.0 * &numerator.0, &denominator.0, rounding)))
+    }
+
+    /// Compute `self * numerator / denominator` for `u64` factors
+    pub fn mul_div_u64(&self, numerator: u64, denominator: u64, rounding: Rounding) -> Option<Amount> {
+        self.mul_div(&Amount::from_u64(numerator), &Amount::from_u64(denominator), rounding)
+    }
+
+    /// Take `bps` basis points of this amount, rounding down
+    pub fn mul_bps(&self, bps: u64) -> Amount {
+        self.mul_bps_rounded(bps, Rounding::Down)
+    }
+
+    /// Take `bps` basis points of this amount with the given rounding
+    pub fn mul_bps_rounded(&self, bps: u64, rounding: Rounding) -> Amount {
+        Amount(div_round(&self.0 * bps, &BigUint::from(BPS_DENOMINATOR), rounding))
+    }
+
+    /// Take `percent` percent of this amount, rounding down
+    pub fn percent(&self, percent: u64) -> Amount {
+        Amount(&self.0 * percent / 100u64)
+    }
+
+    /// This amount as a share of `total` in basis points, rounding down.
+    ///
+    /// Returns `None` if `total` is zero; the result may exceed 10,000.
+    pub fn ratio_bps(&self, total: &Amount) -> Option<u64> {
+        if total.is_zero() {
+            return None;
+        }
+        (&self.0 * BPS_DENOMINATOR / &total.0).to_u64()
+    }
+
+    /// This amount as a fraction of `total`, rounding down; `None` if `total`
+    /// is zero or the ratio does not fit
+    pub fn ratio_fixed(&self, total: &Amount) -> Option<Fixed> {
+        if total.is_zero() {
+            return None;
+        }
+        (&self.0 * BigUint::from(10u64).pow(FIXED_DECIMALS) / &total.0)
+            .to_i128()
+            .map(Fixed::from_raw)
+    }
+
+    /// Multiply by a fixed-point factor, rounding down; negative factors give zero
+    pub fn mul_fixed(&self, factor: Fixed) -> Amount {
+        if factor.is_negative() {
+            return Amount::zero();
+        }
+        let raw = BigUint::from(factor.raw() as u128);
+        Amount(&self.0 * raw / BigUint::from(10u64).pow(FIXED_DECIMALS))
+    }
+
+    /// Convert to `u64` if the value fits
+    pub fn to_u64(&self) -> Option<u64> {
+        self.0.to_u64()
+    }
+
+    /// Convert to `u64`, clamping at `u64::MAX`
+    pub fn saturating_to_u64(&self) -> u64 {
+        self.0.to_u64().unwrap_or(u64::MAX)
+    }
+
+    /// Convert to `u128` if the value fits
+    pub fn to_u128(&self) -> Option<u128> {
+        self.0.to_u128()
+    }
+
+    /// Convert to `u128`, clamping at `u128::MAX`
+    pub fn saturating_to_u128(&self) -> u128 {
+        self.0.to_u128().unwrap_or(u128::MAX)
+    }
+
+    /// Lossy conversion to `f64`, for display and non-consensus statistics only
+    pub fn to_f64_lossy(&self) -> f64 {
+        self.0.to_f64().unwrap_or(f64::MAX)
+    }
+
+    /// Format with `decimals` decimal places, trimming trailing zeros
+    pub fn format_units(&self, decimals: u32) -> String {
+        let scale = Self::unit_scale(decimals);
+        let whole = &self.0 / &scale;
+        let frac = &self.0 % &scale;
+
+        if frac.is_zero() {
+            return whole.to_string();
+        }
+
+        let frac = format!("{:0>width$}", frac.to_string(), width = decimals as usize);
+        format!("{}.{}", whole, frac.trim_end_matches('0'))
+    }
+
+    /// Format as whole tokens, e.g. "1.5 KAI"
+    pub fn to_kai_string(&self) -> String {
+        format!("{} {}", self.format_units(KAI_DECIMALS), KAI_SYMBOL)
+    }
+
+    /// Parse a decimal string with `decimals` decimal places, e.g. "1.5" with 18 decimals
+    pub fn parse_units(value: &str, decimals: u32) -> Result<Amount, AmountParseError> {
+        let value = value.trim().replace('_', "");
+        if value.is_empty() {
+            return Err(AmountParseError::Empty);
+        }
+
+        let (whole, frac) = match value.split_once('.') {
+            Some((whole, frac)) => (whole, frac),
+            None => (value.as_str(), ""),
+        };
+
+        let is_digits = |s: &str| s.chars().all(|c| c.is_ascii_digit());
+        if (whole.is_empty() && frac.is_empty()) || !is_digits(whole) || !is_digits(frac) {
+            return Err(AmountParseError::InvalidDigits(value.clone()));
+        }
+
+        let frac = frac.trim_end_matches('0');
+        if frac.len() > decimals as usize {
+            return Err(AmountParseError::TooManyDecimals { given: frac.len(), max: decimals });
+        }
+
+        let digits = format!("{}{:0<width$}", whole, frac, width = decimals as usize);
+        BigUint::from_str_radix(&digits, 10)
+            .map(Amount)
+            .map_err(|_| AmountParseError::InvalidDigits(value.clone()))
+    }
+
+    fn unit_scale(decimals: u32) -> BigUint {
+        BigUint::from(10u64).pow(decimals)
+    }
+}
+
+/// Divide `numerator` by a non-zero `denominator` with the given rounding
+fn div_round(numerator: BigUint, denominator: &BigUint, rounding: Rounding) -> BigUint {
+    let quotient = &numerator / denominator;
+    let remainder = numerator % denominator;
+    if remainder.is_zero() {
+        return quotient;
+    }
+
+    let round_up = match rounding {
+        Rounding::Down => false,
+        Rounding::Up => true,
+        Rounding::HalfUp => &remainder * 2u64 >= *denominator,
+        Rounding::HalfEven => {
+            let twice = &remainder * 2u64;
+            twice > *denominator || (twice == *denominator && quotient.bit(0))
+        }
+    };
+
+    if round_up {
+        quotient + 1u64
+    } else {
+        quotient
+    }
+}
+
+impl std::str::FromStr for Amount {
+    type Err = AmountParseError;
+
+    /// Parse "1.5 KAI" (whole tokens) or a plain integer of base units
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        match s.rsplit_once(char::is_whitespace) {
+            Some((value, unit)) if unit.eq_ignore_ascii_case(KAI_SYMBOL) => {
+                Amount::parse_units(value, KAI_DECIMALS)
+            }
+            Some((_, unit)) => Err(AmountParseError::UnknownDenomination(unit.to_string())),
+            None => Amount::parse_units(s, 0),
+        }
+    }
+}
+
+impl Serialize for Amount {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.serialize_str(&self.0.to_string())
+        } else {
+            self.0.serialize(serializer)
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Amount {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            let value = String::deserialize(deserializer)?;
+            value.parse().map_err(serde::de::Error::custom)
+        } else {
+            BigUint::deserialize(deserializer).map(Amount)
+        }
+    }
 }
 
 impl Add for Amount {
     type Output = Amount;
 
     fn add(self, other: Amount) -> Amount {
-        Amount(&self.0 + &other.0)
+        Amount(self.0 + other.0)
     }
 }
 
@@ -74,7 +372,19 @@ impl Sub for Amount {
     type Output = Amount;
 
     fn sub(self, other: Amount) -> Amount {
-        Amount(&self.0 - &other.0)
+        Amount(self.0 - other.0)
+    }
+}
+
+impl std::iter::Sum for Amount {
+    fn sum<I: Iterator<Item = Amount>>(iter: I) -> Amount {
+        iter.fold(Amount::zero(), |acc, a| acc + a)
+    }
+}
+
+impl From<u64> for Amount {
+    fn from(value: u64) -> Self {
+        Amount::from_u64(value)
     }
 }
 
@@ -142,4 +452,70 @@ mod tests {
         let score = UtilityScore::from_percentage(85.5);
         assert_eq!(score.to_percentage(), 85.5);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_amount_mul_div_rounding() {
+        let a = Amount::from_u64(10);
+        assert_eq!(a.mul_div_u64(1, 3, Rounding::Down), Some(Amount::from_u64(3)));
+        assert_eq!(a.mul_div_u64(1, 3, Rounding::Up), Some(Amount::from_u64(4)));
+        assert_eq!(a.mul_div_u64(1, 4, Rounding::HalfUp), Some(Amount::from_u64(3)));
+        assert_eq!(a.mul_div_u64(1, 4, Rounding::HalfEven), Some(Amount::from_u64(2)));
+        assert_eq!(Amount::from_u64(14).mul_div_u64(1, 4, Rounding::HalfEven), Some(Amount::from_u64(4)));
+        assert_eq!(a.mul_div_u64(1, 0, Rounding::Down), None);
+        assert_eq!(a.checked_div(&Amount::zero()), None);
+    }
+
+    #[test]
+    fn test_amount_large_values() {
+        // Values above 2^64 must not be truncated
+        let big = Amount::from_tokens(1_000_000_000);
+        assert!(big.to_u64().is_none());
+        assert_eq!(big.saturating_to_u64(), u64::MAX);
+        assert_eq!(big.mul_bps(2_500), Amount::from_tokens(250_000_000));
+        assert_eq!(big.percent(10), Amount::from_tokens(100_000_000));
+        assert_eq!(Amount::from_tokens(3).ratio_bps(&Amount::from_tokens(4)), Some(7_500));
+        assert_eq!(big.checked_mul_u64(2).unwrap().checked_div(&big), Some(Amount::from_u64(2)));
+    }
+
+    #[test]
+    fn test_amount_fixed_point() {
+        let a = Amount::from_tokens(1_000_000);
+        assert_eq!(a.mul_fixed("0.5".parse().unwrap()), Amount::from_tokens(500_000));
+        assert_eq!(a.mul_fixed(Fixed::from_ratio(6, 5).unwrap()), Amount::from_tokens(1_200_000));
+        assert_eq!(a.mul_fixed(-Fixed::ONE), Amount::zero());
+        assert_eq!(Amount::from_u64(10).mul_fixed(Fixed::from_ratio(1, 3).unwrap()), Amount::from_u64(3));
+
+        assert_eq!(Amount::from_u64(1).ratio_fixed(&Amount::from_u64(8)), Some("0.125".parse().unwrap()));
+        assert_eq!(Amount::from_tokens(3).ratio_fixed(&Amount::from_tokens(2)), Fixed::from_ratio(3, 2));
+        assert_eq!(a.ratio_fixed(&Amount::zero()), None);
+    }
+
+    #[test]
+    fn test_amount_parse_and_format() {
+        let a: Amount = "1.5 KAI".parse().unwrap();
+        assert_eq!(a, Amount::from_u64(1_500_000_000_000_000_000));
+        assert_eq!(a.to_kai_string(), "1.5 KAI");
+        assert_eq!(Amount::from_tokens(2).to_kai_string(), "2 KAI");
+        assert_eq!(Amount::from_u64(1).to_kai_string(), "0.000000000000000001 KAI");
+
+        assert_eq!("0.25 kai".parse::<Amount>().unwrap(), Amount::parse_units("0.25", 18).unwrap());
+        assert_eq!("42".parse::<Amount>().unwrap(), Amount::from_u64(42));
+        assert_eq!(".5 KAI".parse::<Amount>().unwrap(), Amount::from_u64(500_000_000_000_000_000));
+
+        assert!(matches!("1.5".parse::<Amount>(), Err(AmountParseError::TooManyDecimals { .. })));
+        assert!(matches!("1 ETH".parse::<Amount>(), Err(AmountParseError::UnknownDenomination(_))));
+        assert!(matches!("1e5".parse::<Amount>(), Err(AmountParseError::InvalidDigits(_))));
+        assert!(matches!("".parse::<Amount>(), Err(AmountParseError::Empty)));
+    }
+
+    #[test]
+    fn test_amount_serde() {
+        let a = Amount::from_tokens(100_000);
+        let json = serde_json::to_string(&a).unwrap();
+        assert_eq!(json, "\"100000000000000000000000\"");
+        assert_eq!(serde_json::from_str::<Amount>(&json).unwrap(), a);
+
+        let bytes = bincode::serialize(&a).unwrap();
+        assert_eq!(bincode::deserialize::<Amount>(&bytes).unwrap(), a);
+    }
+}
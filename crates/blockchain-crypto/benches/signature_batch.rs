@@ -0,0 +1,48 @@
+// blockchain-crypto/benches/signature_batch.rs
+
+use blockchain_crypto::{verify_batch, BatchItem, KeyPair, Signature, SignatureScheme};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+
+fn signed_votes(count: usize) -> (Vec<KeyPair>, Vec<Vec<u8>>, Vec<Signature>) {
+    let keys: Vec<KeyPair> = (0..count)
+        .map(|_| KeyPair::generate(SignatureScheme::Ed25519).unwrap())
+        .collect();
+    let messages: Vec<Vec<u8>> = (0..count).map(|i| format!("vote {}", i).into_bytes()).collect();
+    let signatures = keys
+        .iter()
+        .zip(&messages)
+        .map(|(key, message)| key.sign(message).unwrap())
+        .collect();
+    (keys, messages, signatures)
+}
+
+fn bench_ed25519_verification(c: &mut Criterion) {
+    let mut group = c.benchmark_group("ed25519_verify");
+
+    for count in [16, 128, 1024] {
+        let (keys, messages, signatures) = signed_votes(count);
+        let items: Vec<BatchItem<'_>> = keys
+            .iter()
+            .zip(&messages)
+            .zip(&signatures)
+            .map(|((key, message), signature)| BatchItem::new(message, key.public_key(), signature))
+            .collect();
+
+        // The loop block import used before batch verification
+        group.bench_with_input(BenchmarkId::new("one_by_one", count), &items, |b, items| {
+            b.iter(|| {
+                items
+                    .iter()
+                    .all(|item| item.signature.verify(item.message, item.public_key).unwrap())
+            })
+        });
+        group.bench_with_input(BenchmarkId::new("verify_batch", count), &items, |b, items| {
+            b.iter(|| verify_batch(items).unwrap())
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_ed25519_verification);
+criterion_main!(benches);
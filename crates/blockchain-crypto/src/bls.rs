@@ -0,0 +1,402 @@
+// blockchain-crypto/src/bls.rs
+
+//! BLS12-381 signatures and aggregation
+//!
+//! Uses the "minimal public key" variant: 48-byte public keys in G1 and
+//! 96-byte signatures in G2, with the proof-of-possession ciphersuite. Keys
+//! must come with a verified proof of possession before their signatures are
+//! aggregated, otherwise rogue-key attacks become possible.
+
+use crate::{CryptoError, CryptoResult, KeyPair, PublicKey, SecretKey, Signature, SignatureScheme};
+use blst::min_pk;
+use blst::BLST_ERROR;
+use serde::{Deserialize, Serialize};
+
+/// Domain separation tag for message signatures
+pub const SIGNATURE_DST: &[u8] = b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
+
+/// Domain separation tag for proofs of possession
+pub const POP_DST: &[u8] = b"BLS_POP_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
+
+/// Compressed public key size
+pub const PUBLIC_KEY_SIZE: usize = 48;
+
+/// Compressed signature size
+pub const SIGNATURE_SIZE: usize = 96;
+
+pub(crate) fn sign(secret_key: &SecretKey, message: &[u8]) -> CryptoResult<Signature> {
+    let sk = to_secret_key(secret_key)?;
+    Ok(from_signature(&sk.sign(message, SIGNATURE_DST, &[])))
+}
+
+pub(crate) fn verify(
+    message: &[u8],
+    public_key: &PublicKey,
+    signature: &Signature,
+) -> CryptoResult<bool> {
+    let pk = to_public_key(public_key)?;
+    let sig = to_signature(signature)?;
+    Ok(sig.verify(true, message, SIGNATURE_DST, &[], &pk, true) == BLST_ERROR::BLST_SUCCESS)
+}
+
+/// Prove ownership of a BLS key by signing its own public key
+pub fn prove_possession(keypair: &KeyPair) -> CryptoResult<Signature> {
+    if keypair.scheme() != SignatureScheme::Bls12381 {
+        return Err(CryptoError::InvalidSecretKey);
+    }
+    let sk = to_secret_key(keypair.secret_key())?;
+    Ok(from_signature(&sk.sign(keypair.public_key().as_bytes(), POP_DST, &[])))
+}
+
+/// Check a proof of possession, as required before registering a BLS key
+pub fn verify_possession(public_key: &PublicKey, proof: &Signature) -> CryptoResult<bool> {
+    let pk = to_public_key(public_key)?;
+    let sig = to_signature(proof)?;
+    Ok(sig.verify(true, public_key.as_bytes(), POP_DST, &[], &pk, true) == BLST_ERROR::BLST_SUCCESS)
+}
+
+/// Combine signatures into one
+pub fn aggregate_signatures(signatures: &[Signature]) -> CryptoResult<Signature> {
+    let sigs = signatures
+        .iter()
+        .map(to_signature)
+        .collect::<CryptoResult<Vec<_>>>()?;
+    let refs: Vec<&min_pk::Signature> = sigs.iter().collect();
+
+    min_pk::AggregateSignature::aggregate(&refs, true)
+        .map(|agg| from_signature(&agg.to_signature()))
+        .map_err(|_| CryptoError::InvalidSignature)
+}
+
+/// Combine public keys into one
+pub fn aggregate_public_keys(public_keys: &[PublicKey]) -> CryptoResult<PublicKey> {
+    let pks = public_keys
+        .iter()
+        .map(to_public_key)
+        .collect::<CryptoResult<Vec<_>>>()?;
+    let refs: Vec<&min_pk::PublicKey> = pks.iter().collect();
+
+    min_pk::AggregatePublicKey::aggregate(&refs, false)
+        .map(|agg| PublicKey::new(SignatureScheme::Bls12381, agg.to_public_key().to_bytes().to_vec()))
+        .map_err(|_| CryptoError::InvalidPublicKey)
+}
+
+/// Verify an aggregate signature where every signer signed the same message
+///
+/// One pairing check regardless of the number of signers. The keys must have
+/// verified proofs of possession.
+pub fn verify_aggregate(
+    message: &[u8],
+    public_keys: &[PublicKey],
+    signature: &Signature,
+) -> CryptoResult<bool> {
+    if public_keys.is_empty() {
+        return Ok(false);
+    }
+    let pks = public_keys
+        .iter()
+        .map(to_public_key)
+        .collect::<CryptoResult<Vec<_>>>()?;
+    let refs: Vec<&min_pk::PublicKey> = pks.iter().collect();
+    let sig = to_signature(signature)?;
+
+    Ok(sig.fast_aggregate_verify(true, message, SIGNATURE_DST, &refs) == BLST_ERROR::BLST_SUCCESS)
+}
+
+/// Verify an aggregate signature over a distinct message per signer
+pub fn verify_aggregate_multi(
+    messages: &[&[u8]],
+    public_keys: &[PublicKey],
+    signature: &Signature,
+) -> CryptoResult<bool> {
+    if public_keys.is_empty() || messages.len() != public_keys.len() {
+        return Ok(false);
+    }
+    let pks = public_keys
+        .iter()
+        .map(to_public_key)
+        .collect::<CryptoResult<Vec<_>>>()?;
+    let refs: Vec<&min_pk::PublicKey> = pks.iter().collect();
+    let sig = to_signature(signature)?;
+
+    Ok(sig.aggregate_verify(true, messages, SIGNATURE_DST, &refs, true) == BLST_ERROR::BLST_SUCCESS)
+}
+
+/// Bitfield of which committee members signed
+///
+/// Deserialization goes through `from_bytes`, so a bitfield received from a
+/// peer always has exactly `len` bits.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "RawSignerBitfield")]
+pub struct SignerBitfield {
+    bits: Vec<u8>,
+    len: usize,
+}
+
+/// Unchecked wire form of a `SignerBitfield`
+#[derive(Deserialize)]
+struct RawSignerBitfield {
+    bits: Vec<u8>,
+    len: usize,
+}
+
+impl TryFrom<RawSignerBitfield> for SignerBitfield {
+    type Error = CryptoError;
+
+    fn try_from(raw: RawSignerBitfield) -> CryptoResult<Self> {
+        Self::from_bytes(raw.bits, raw.len)
+    }
+}
+
+impl SignerBitfield {
+    /// Empty bitfield for a committee of `len` members
+    pub fn new(len: usize) -> Self {
+        Self {
+            bits: vec![0; len.div_ceil(8)],
+            len,
+        }
+    }
+
+    /// Bitfield from its bytes, rejecting a wrong byte count or bits set at or above `len`
+    pub fn from_bytes(bits: Vec<u8>, len: usize) -> CryptoResult<Self> {
+        if bits.len() != len.div_ceil(8) {
+            return Err(CryptoError::DeserializationError(format!(
+                "Bitfield of {} bytes for committee of {}",
+                bits.len(),
+                len
+            )));
+        }
+        if !len.is_multiple_of(8) {
+            if let Some(last) = bits.last() {
+                if last >> (len % 8) != 0 {
+                    return Err(CryptoError::DeserializationError(format!(
+                        "Bitfield has signers beyond committee of {}",
+                        len
+                    )));
+                }
+            }
+        }
+        Ok(Self { bits, len })
+    }
+
+    pub fn set(&mut self, index: usize) -> CryptoResult<()> {
+        if index >= self.len {
+            return Err(CryptoError::SerializationError(format!(
+                "Signer index {} out of range for committee of {}",
+                index, self.len
+            )));
+        }
+        self.bits[index / 8] |= 1 << (index % 8);
+        Ok(())
+    }
+
+    pub fn get(&self, index: usize) -> bool {
+        index < self.len && self.bits[index / 8] & (1 << (index % 8)) != 0
+    }
+
+    /// Committee size
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Number of signers
+    pub fn count(&self) -> usize {
+        self.bits.iter().map(|b| b.count_ones() as usize).sum()
+    }
+
+    /// Indices of signers, ascending
+    pub fn signers(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.len).filter(move |i| self.get(*i))
+    }
+}
+
+/// Aggregate signature plus the bitfield of committee members it covers
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AggregateSignature {
+    pub signature: Signature,
+    pub signers: SignerBitfield,
+}
+
+impl AggregateSignature {
+    /// Aggregate `(committee index, signature)` pairs for a committee of `committee_size`
+    pub fn aggregate(committee_size: usize, signatures: &[(usize, Signature)]) -> CryptoResult<Self> {
+        let mut signers = SignerBitfield::new(committee_size);
+        for (index, _) in signatures {
+            if signers.get(*index) {
+                return Err(CryptoError::SerializationError(format!(
+                    "Duplicate signer {}",
+                    index
+                )));
+            }
+            signers.set(*index)?;
+        }
+
+        let sigs: Vec<Signature> = signatures.iter().map(|(_, sig)| sig.clone()).collect();
+        Ok(Self {
+            signature: aggregate_signatures(&sigs)?,
+            signers,
+        })
+    }
+
+    /// Verify against the committee's public keys, in committee order
+    pub fn verify(&self, message: &[u8], committee: &[PublicKey]) -> CryptoResult<bool> {
+        if committee.len() != self.signers.len() {
+            return Ok(false);
+        }
+        let keys: Vec<PublicKey> = self
+            .signers
+            .signers()
+            .map(|i| committee[i].clone())
+            .collect();
+        verify_aggregate(message, &keys, &self.signature)
+    }
+
+    /// Number of signers
+    pub fn signer_count(&self) -> usize {
+        self.signers.count()
+    }
+}
+
+fn to_secret_key(secret_key: &SecretKey) -> CryptoResult<min_pk::SecretKey> {
+    if secret_key.scheme() != SignatureScheme::Bls12381 {
+        return Err(CryptoError::InvalidSecretKey);
+    }
+    min_pk::SecretKey::from_bytes(secret_key.as_bytes()).map_err(|_| CryptoError::InvalidSecretKey)
+}
+
+fn to_public_key(public_key: &PublicKey) -> CryptoResult<min_pk::PublicKey> {
+    if public_key.scheme() != SignatureScheme::Bls12381 {
+        return Err(CryptoError::InvalidPublicKey);
+    }
+    min_pk::PublicKey::key_validate(public_key.as_bytes()).map_err(|_| CryptoError::InvalidPublicKey)
+}
+
+fn to_signature(signature: &Signature) -> CryptoResult<min_pk::Signature> {
+    if signature.scheme() != SignatureScheme::Bls12381 {
+        return Err(CryptoError::InvalidSignature);
+    }
+    min_pk::Signature::from_bytes(signature.as_bytes()).map_err(|_| CryptoError::InvalidSignature)
+}
+
+fn from_signature(signature: &min_pk::Signature) -> Signature {
+    Signature::new(SignatureScheme::Bls12381, signature.to_bytes().to_vec())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn committee(n: usize) -> Vec<KeyPair> {
+        (0..n)
+            .map(|_| KeyPair::generate(SignatureScheme::Bls12381).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_key_and_signature_sizes() {
+        let keypair = KeyPair::generate(SignatureScheme::Bls12381).unwrap();
+        assert_eq!(keypair.public_key().as_bytes().len(), PUBLIC_KEY_SIZE);
+        assert_eq!(keypair.sign(b"msg").unwrap().as_bytes().len(), SIGNATURE_SIZE);
+
+        let restored =
+            KeyPair::from_secret_bytes(SignatureScheme::Bls12381, keypair.secret_key().as_bytes())
+                .unwrap();
+        assert_eq!(restored.public_key(), keypair.public_key());
+    }
+
+    #[test]
+    fn test_proof_of_possession() {
+        let keypair = KeyPair::generate(SignatureScheme::Bls12381).unwrap();
+        let other = KeyPair::generate(SignatureScheme::Bls12381).unwrap();
+        let proof = prove_possession(&keypair).unwrap();
+
+        assert!(verify_possession(keypair.public_key(), &proof).unwrap());
+        assert!(!verify_possession(other.public_key(), &proof).unwrap());
+
+        // A plain signature over the key is not a valid proof
+        let plain = keypair.sign(keypair.public_key().as_bytes()).unwrap();
+        assert!(!verify_possession(keypair.public_key(), &plain).unwrap());
+    }
+
+    #[test]
+    fn test_aggregate_same_message() {
+        let keys = committee(5);
+        let message = b"block 42";
+        let sigs: Vec<Signature> = keys.iter().map(|k| k.sign(message).unwrap()).collect();
+        let pks: Vec<PublicKey> = keys.iter().map(|k| k.public_key().clone()).collect();
+
+        let aggregate = aggregate_signatures(&sigs).unwrap();
+        assert!(verify_aggregate(message, &pks, &aggregate).unwrap());
+        assert!(!verify_aggregate(b"block 43", &pks, &aggregate).unwrap());
+        assert!(!verify_aggregate(message, &pks[..4], &aggregate).unwrap());
+
+        let aggregate_pk = aggregate_public_keys(&pks).unwrap();
+        assert!(aggregate.verify(message, &aggregate_pk).unwrap());
+    }
+
+    #[test]
+    fn test_aggregate_distinct_messages() {
+        let keys = committee(3);
+        let messages: Vec<Vec<u8>> = (0..3u8).map(|i| vec![i; 8]).collect();
+        let sigs: Vec<Signature> = keys
+            .iter()
+            .zip(&messages)
+            .map(|(k, m)| k.sign(m).unwrap())
+            .collect();
+        let pks: Vec<PublicKey> = keys.iter().map(|k| k.public_key().clone()).collect();
+        let refs: Vec<&[u8]> = messages.iter().map(|m| m.as_slice()).collect();
+
+        let aggregate = aggregate_signatures(&sigs).unwrap();
+        assert!(verify_aggregate_multi(&refs, &pks, &aggregate).unwrap());
+
+        let swapped: Vec<&[u8]> = vec![refs[1], refs[0], refs[2]];
+        assert!(!verify_aggregate_multi(&swapped, &pks, &aggregate).unwrap());
+    }
+
+    #[test]
+    fn test_aggregate_with_bitfield() {
+        let keys = committee(10);
+        let committee: Vec<PublicKey> = keys.iter().map(|k| k.public_key().clone()).collect();
+        let message = b"attestation";
+
+        let signed = [0usize, 3, 4, 9];
+        let sigs: Vec<(usize, Signature)> = signed
+            .iter()
+            .map(|&i| (i, keys[i].sign(message).unwrap()))
+            .collect();
+
+        let aggregate = AggregateSignature::aggregate(committee.len(), &sigs).unwrap();
+        assert_eq!(aggregate.signer_count(), 4);
+        assert_eq!(aggregate.signers.signers().collect::<Vec<_>>(), signed);
+        assert!(aggregate.verify(message, &committee).unwrap());
+
+        // Claiming an extra signer breaks verification
+        let mut forged = aggregate.clone();
+        forged.signers.set(1).unwrap();
+        assert!(!forged.verify(message, &committee).unwrap());
+
+        assert!(AggregateSignature::aggregate(10, &[sigs[0].clone(), sigs[0].clone()]).is_err());
+        assert!(AggregateSignature::aggregate(3, &sigs).is_err());
+    }
+
+    #[test]
+    fn test_malformed_bitfield_rejected() {
+        let mut bitfield = SignerBitfield::new(10);
+        bitfield.set(9).unwrap();
+        let json = serde_json::to_string(&bitfield).unwrap();
+        assert_eq!(serde_json::from_str::<SignerBitfield>(&json).unwrap(), bitfield);
+
+        // Too few bytes for the claimed committee would make `get` index out of bounds
+        let short = r#"{"bits":[1],"len":1000}"#;
+        assert!(serde_json::from_str::<SignerBitfield>(short).is_err());
+
+        // Bits above the committee size would inflate the signer count
+        let stray = r#"{"bits":[255,255],"len":10}"#;
+        assert!(serde_json::from_str::<SignerBitfield>(stray).is_err());
+        assert!(SignerBitfield::from_bytes(vec![255, 3], 10).is_ok());
+    }
+}
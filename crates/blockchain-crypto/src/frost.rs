@@ -0,0 +1,518 @@
+// blockchain-crypto/src/frost.rs
+
+//! FROST threshold Ed25519 signatures (RFC 9591, FROST(Ed25519, SHA-512))
+//!
+//! A trusted dealer splits an Ed25519 key into `n` shares with Feldman
+//! commitments so every holder can check its share. Any `t` holders then sign
+//! in two rounds:
+//!
+//! 1. each signer calls [`commit`] and publishes its [`SigningCommitments`],
+//!    keeping the [`SigningNonces`] private;
+//! 2. the coordinator bundles the message and commitments in a
+//!    [`SigningPackage`], each signer returns a [`SignatureShare`] from
+//!    [`sign`], and [`aggregate`] checks the shares and combines them.
+//!
+//! The result is an ordinary Ed25519 [`Signature`] under the original
+//! [`PublicKey`]; verifiers cannot tell it was produced by a group.
+
+use crate::{CryptoError, CryptoResult, KeyPair, PublicKey, Signature, SignatureScheme};
+use curve25519_dalek::edwards::{CompressedEdwardsY, EdwardsPoint};
+use curve25519_dalek::scalar::{clamp_integer, Scalar};
+use curve25519_dalek::traits::Identity;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha512};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use zeroize::Zeroize;
+
+const CONTEXT: &[u8] = b"FROST-ED25519-SHA512-v1";
+
+/// One signer's share of a group key
+#[derive(Clone, Serialize, Deserialize)]
+pub struct KeyShare {
+    identifier: u16,
+    threshold: u16,
+    secret: [u8; 32],
+    group_public_key: PublicKey,
+}
+
+impl KeyShare {
+    pub fn identifier(&self) -> u16 {
+        self.identifier
+    }
+
+    pub fn threshold(&self) -> u16 {
+        self.threshold
+    }
+
+    pub fn group_public_key(&self) -> &PublicKey {
+        &self.group_public_key
+    }
+
+    /// Public counterpart of this share
+    pub fn verifying_share(&self) -> [u8; 32] {
+        EdwardsPoint::mul_base(&self.scalar()).compress().to_bytes()
+    }
+
+    /// Check this share against the dealer's commitments
+    pub fn verify(&self, public: &PublicKeyPackage) -> CryptoResult<()> {
+        let x = Scalar::from(u64::from(self.identifier));
+        let mut expected = EdwardsPoint::identity();
+        for commitment in public.commitment.iter().rev() {
+            expected = expected * x + decode_point(commitment)?;
+        }
+
+        let verifying_share = self.verifying_share();
+        if self.group_public_key != public.group_public_key
+            || expected.compress().to_bytes() != verifying_share
+            || public.verifying_shares.get(&self.identifier) != Some(&verifying_share)
+        {
+            return Err(CryptoError::ThresholdError(format!(
+                "Key share {} does not match the group commitments",
+                self.identifier
+            )));
+        }
+        Ok(())
+    }
+
+    fn scalar(&self) -> Scalar {
+        Scalar::from_bytes_mod_order(self.secret)
+    }
+}
+
+impl Drop for KeyShare {
+    fn drop(&mut self) {
+        self.secret.zeroize();
+    }
+}
+
+impl fmt::Debug for KeyShare {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "KeyShare({}/{}, [REDACTED])", self.identifier, self.threshold)
+    }
+}
+
+/// Public data for a split key, shared by all participants
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PublicKeyPackage {
+    group_public_key: PublicKey,
+    threshold: u16,
+    /// Commitments to the dealer's polynomial coefficients, constant first
+    commitment: Vec<[u8; 32]>,
+    verifying_shares: BTreeMap<u16, [u8; 32]>,
+}
+
+impl PublicKeyPackage {
+    pub fn group_public_key(&self) -> &PublicKey {
+        &self.group_public_key
+    }
+
+    pub fn threshold(&self) -> u16 {
+        self.threshold
+    }
+
+    pub fn verifying_share(&self, identifier: u16) -> Option<&[u8; 32]> {
+        self.verifying_shares.get(&identifier)
+    }
+}
+
+/// Split an Ed25519 key into `signers` shares with threshold `threshold`
+pub fn split_key(
+    keypair: &KeyPair,
+    threshold: u16,
+    signers: u16,
+) -> CryptoResult<(Vec<KeyShare>, PublicKeyPackage)> {
+    if keypair.scheme() != SignatureScheme::Ed25519 {
+        return Err(CryptoError::InvalidSecretKey);
+    }
+    if threshold < 2 || threshold > signers {
+        return Err(CryptoError::ThresholdError(format!(
+            "Invalid {}-of-{} split",
+            threshold, signers
+        )));
+    }
+
+    let mut seed: [u8; 32] = keypair
+        .secret_key()
+        .as_bytes()
+        .try_into()
+        .map_err(|_| CryptoError::InvalidSecretKey)?;
+    let mut expanded: [u8; 64] = Sha512::digest(seed).into();
+    let mut scalar_bytes = [0u8; 32];
+    scalar_bytes.copy_from_slice(&expanded[..32]);
+    let mut coefficients = vec![Scalar::from_bytes_mod_order(clamp_integer(scalar_bytes))];
+    seed.zeroize();
+    expanded.zeroize();
+    scalar_bytes.zeroize();
+
+    for _ in 1..threshold {
+        coefficients.push(random_scalar()?);
+    }
+
+    let commitment = coefficients
+        .iter()
+        .map(|c| EdwardsPoint::mul_base(c).compress().to_bytes())
+        .collect();
+
+    let shares: Vec<KeyShare> = (1..=signers)
+        .map(|identifier| {
+            let x = Scalar::from(u64::from(identifier));
+            let secret = coefficients.iter().rev().fold(Scalar::ZERO, |acc, c| acc * x + c);
+            KeyShare {
+                identifier,
+                threshold,
+                secret: secret.to_bytes(),
+                group_public_key: keypair.public_key().clone(),
+            }
+        })
+        .collect();
+    coefficients.zeroize();
+
+    let public = PublicKeyPackage {
+        group_public_key: keypair.public_key().clone(),
+        threshold,
+        commitment,
+        verifying_shares: shares.iter().map(|s| (s.identifier, s.verifying_share())).collect(),
+    };
+
+    Ok((shares, public))
+}
+
+/// Round one secret nonces; use once, then drop
+pub struct SigningNonces {
+    identifier: u16,
+    hiding: [u8; 32],
+    binding: [u8; 32],
+}
+
+impl Drop for SigningNonces {
+    fn drop(&mut self) {
+        self.hiding.zeroize();
+        self.binding.zeroize();
+    }
+}
+
+impl fmt::Debug for SigningNonces {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "SigningNonces({}, [REDACTED])", self.identifier)
+    }
+}
+
+/// Round one public commitments
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SigningCommitments {
+    pub identifier: u16,
+    pub hiding: [u8; 32],
+    pub binding: [u8; 32],
+}
+
+/// Message and the commitments of the signers taking part
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SigningPackage {
+    message: Vec<u8>,
+    /// Sorted by identifier
+    commitments: Vec<SigningCommitments>,
+}
+
+impl SigningPackage {
+    pub fn new(message: &[u8], mut commitments: Vec<SigningCommitments>) -> CryptoResult<Self> {
+        commitments.sort_by_key(|c| c.identifier);
+        if commitments.windows(2).any(|w| w[0].identifier == w[1].identifier)
+            || commitments.iter().any(|c| c.identifier == 0)
+        {
+            return Err(CryptoError::ThresholdError("Duplicate or invalid signer".into()));
+        }
+        Ok(Self { message: message.to_vec(), commitments })
+    }
+
+    pub fn message(&self) -> &[u8] {
+        &self.message
+    }
+
+    pub fn signers(&self) -> impl Iterator<Item = u16> + '_ {
+        self.commitments.iter().map(|c| c.identifier)
+    }
+}
+
+/// Round two output of one signer
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignatureShare {
+    pub identifier: u16,
+    pub share: [u8; 32],
+}
+
+/// Round one: generate nonces and their commitments
+pub fn commit(key_share: &KeyShare) -> CryptoResult<(SigningNonces, SigningCommitments)> {
+    let hiding = generate_nonce(&key_share.secret)?;
+    let binding = generate_nonce(&key_share.secret)?;
+
+    let commitments = SigningCommitments {
+        identifier: key_share.identifier,
+        hiding: EdwardsPoint::mul_base(&hiding).compress().to_bytes(),
+        binding: EdwardsPoint::mul_base(&binding).compress().to_bytes(),
+    };
+    let nonces = SigningNonces {
+        identifier: key_share.identifier,
+        hiding: hiding.to_bytes(),
+        binding: binding.to_bytes(),
+    };
+    Ok((nonces, commitments))
+}
+
+/// Round two: produce this signer's share of the signature
+pub fn sign(package: &SigningPackage, nonces: SigningNonces, key_share: &KeyShare) -> CryptoResult<SignatureShare> {
+    let hiding = Scalar::from_bytes_mod_order(nonces.hiding);
+    let binding = Scalar::from_bytes_mod_order(nonces.binding);
+
+    let own = package
+        .commitments
+        .iter()
+        .find(|c| c.identifier == key_share.identifier)
+        .ok_or_else(|| CryptoError::ThresholdError("Signer is not in the signing package".into()))?;
+    if nonces.identifier != key_share.identifier
+        || own.hiding != EdwardsPoint::mul_base(&hiding).compress().to_bytes()
+        || own.binding != EdwardsPoint::mul_base(&binding).compress().to_bytes()
+    {
+        return Err(CryptoError::ThresholdError("Nonces do not match the signing package".into()));
+    }
+    if package.commitments.len() < key_share.threshold as usize {
+        return Err(CryptoError::ThresholdError("Not enough signers".into()));
+    }
+
+    let session = Session::new(package, &key_share.group_public_key)?;
+    let rho = session.binding_factors[&key_share.identifier];
+    let lambda = session.lagrange(key_share.identifier);
+
+    let share = hiding + binding * rho + lambda * key_share.scalar() * session.challenge;
+    Ok(SignatureShare { identifier: key_share.identifier, share: share.to_bytes() })
+}
+
+/// Check every share and combine them into an Ed25519 signature
+///
+/// A bad share is reported with its signer's identifier so the coordinator
+/// can exclude that signer and retry.
+pub fn aggregate(
+    package: &SigningPackage,
+    shares: &[SignatureShare],
+    public: &PublicKeyPackage,
+) -> CryptoResult<Signature> {
+    let signers: BTreeSet<u16> = package.signers().collect();
+    let shared: BTreeSet<u16> = shares.iter().map(|s| s.identifier).collect();
+    if signers != shared || shares.len() != signers.len() {
+        return Err(CryptoError::ThresholdError("Shares do not match the signing package".into()));
+    }
+    if signers.len() < public.threshold as usize {
+        return Err(CryptoError::ThresholdError("Not enough signers".into()));
+    }
+
+    let session = Session::new(package, &public.group_public_key)?;
+    let mut z = Scalar::ZERO;
+
+    for share in shares {
+        let identifier = share.identifier;
+        let invalid = || CryptoError::InvalidSignatureShare { identifier };
+
+        let z_i = Option::<Scalar>::from(Scalar::from_canonical_bytes(share.share)).ok_or_else(invalid)?;
+        let verifying_share = public.verifying_shares.get(&identifier).ok_or_else(invalid)?;
+        let commitments = package.commitments.iter().find(|c| c.identifier == identifier).ok_or_else(invalid)?;
+
+        let commitment_share = decode_point(&commitments.hiding)?
+            + decode_point(&commitments.binding)? * session.binding_factors[&identifier];
+        let expected = commitment_share
+            + decode_point(verifying_share)? * (session.challenge * session.lagrange(identifier));
+        if EdwardsPoint::mul_base(&z_i) != expected {
+            return Err(invalid());
+        }
+        z += z_i;
+    }
+
+    let mut bytes = Vec::with_capacity(64);
+    bytes.extend_from_slice(session.group_commitment.compress().as_bytes());
+    bytes.extend_from_slice(z.as_bytes());
+    Ok(Signature::new(SignatureScheme::Ed25519, bytes))
+}
+
+/// Values every participant derives from the signing package
+struct Session {
+    binding_factors: BTreeMap<u16, Scalar>,
+    group_commitment: EdwardsPoint,
+    challenge: Scalar,
+    identifiers: Vec<Scalar>,
+}
+
+impl Session {
+    fn new(package: &SigningPackage, group_public_key: &PublicKey) -> CryptoResult<Self> {
+        let group_key = group_public_key.as_bytes();
+
+        let mut encoded_commitments = Vec::with_capacity(package.commitments.len() * 96);
+        for c in &package.commitments {
+            encoded_commitments.extend_from_slice(&identifier_bytes(c.identifier));
+            encoded_commitments.extend_from_slice(&c.hiding);
+            encoded_commitments.extend_from_slice(&c.binding);
+        }
+
+        let mut prefix = group_key.to_vec();
+        prefix.extend_from_slice(&h(b"msg", &[&package.message]));
+        prefix.extend_from_slice(&h(b"com", &[&encoded_commitments]));
+
+        let mut binding_factors = BTreeMap::new();
+        let mut group_commitment = EdwardsPoint::identity();
+        for c in &package.commitments {
+            let rho = Scalar::from_bytes_mod_order_wide(&h(b"rho", &[&prefix, &identifier_bytes(c.identifier)]));
+            group_commitment += decode_point(&c.hiding)? + decode_point(&c.binding)? * rho;
+            binding_factors.insert(c.identifier, rho);
+        }
+
+        let challenge = Scalar::from_bytes_mod_order_wide(
+            &Sha512::new()
+                .chain_update(group_commitment.compress().as_bytes())
+                .chain_update(group_key)
+                .chain_update(&package.message)
+                .finalize()
+                .into(),
+        );
+
+        Ok(Self {
+            binding_factors,
+            group_commitment,
+            challenge,
+            identifiers: package.signers().map(|id| Scalar::from(u64::from(id))).collect(),
+        })
+    }
+
+    /// Lagrange coefficient of `identifier` at zero over the signer set
+    fn lagrange(&self, identifier: u16) -> Scalar {
+        let x_i = Scalar::from(u64::from(identifier));
+        let (numerator, denominator) = self
+            .identifiers
+            .iter()
+            .filter(|x_j| **x_j != x_i)
+            .fold((Scalar::ONE, Scalar::ONE), |(num, den), x_j| (num * x_j, den * (x_j - x_i)));
+        numerator * denominator.invert()
+    }
+}
+
+fn generate_nonce(secret: &[u8; 32]) -> CryptoResult<Scalar> {
+    let mut random = [0u8; 32];
+    rand::rngs::OsRng
+        .try_fill_bytes(&mut random)
+        .map_err(|_| CryptoError::RngError)?;
+    let nonce = Scalar::from_bytes_mod_order_wide(&h(b"nonce", &[&random, secret]));
+    random.zeroize();
+    Ok(nonce)
+}
+
+fn random_scalar() -> CryptoResult<Scalar> {
+    let mut wide = [0u8; 64];
+    rand::rngs::OsRng
+        .try_fill_bytes(&mut wide)
+        .map_err(|_| CryptoError::RngError)?;
+    let scalar = Scalar::from_bytes_mod_order_wide(&wide);
+    wide.zeroize();
+    Ok(scalar)
+}
+
+/// Domain-separated SHA-512 (`H1`, `H3`, `H4` and `H5` in RFC 9591)
+fn h(tag: &[u8], parts: &[&[u8]]) -> [u8; 64] {
+    let mut hasher = Sha512::new().chain_update(CONTEXT).chain_update(tag);
+    for part in parts {
+        hasher.update(part);
+    }
+    hasher.finalize().into()
+}
+
+fn identifier_bytes(identifier: u16) -> [u8; 32] {
+    Scalar::from(u64::from(identifier)).to_bytes()
+}
+
+fn decode_point(bytes: &[u8; 32]) -> CryptoResult<EdwardsPoint> {
+    CompressedEdwardsY(*bytes)
+        .decompress()
+        .filter(|point| !point.is_small_order())
+        .ok_or_else(|| CryptoError::ThresholdError("Invalid group element".into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(shares: &[&KeyShare], public: &PublicKeyPackage, message: &[u8]) -> CryptoResult<Signature> {
+        let (nonces, commitments): (Vec<_>, Vec<_>) = shares.iter().map(|s| commit(s).unwrap()).unzip();
+        let package = SigningPackage::new(message, commitments)?;
+        let signature_shares = nonces
+            .into_iter()
+            .zip(shares)
+            .map(|(nonces, share)| sign(&package, nonces, share))
+            .collect::<CryptoResult<Vec<_>>>()?;
+        aggregate(&package, &signature_shares, public)
+    }
+
+    #[test]
+    fn test_threshold_signature_verifies_as_ed25519() {
+        let keypair = KeyPair::generate(SignatureScheme::Ed25519).unwrap();
+        let (shares, public) = split_key(&keypair, 3, 5).unwrap();
+
+        for share in &shares {
+            share.verify(&public).unwrap();
+        }
+
+        for subset in [[0, 1, 2], [4, 2, 0], [1, 3, 4]] {
+            let signers: Vec<&KeyShare> = subset.iter().map(|&i| &shares[i]).collect();
+            let signature = run(&signers, &public, b"block 42").unwrap();
+            assert!(keypair.public_key().verify(b"block 42", &signature).unwrap());
+            assert!(!keypair.public_key().verify(b"block 43", &signature).unwrap());
+        }
+
+        // More than the threshold also works
+        let all: Vec<&KeyShare> = shares.iter().collect();
+        let signature = run(&all, &public, b"all signers").unwrap();
+        assert!(keypair.public_key().verify(b"all signers", &signature).unwrap());
+    }
+
+    #[test]
+    fn test_below_threshold_fails() {
+        let keypair = KeyPair::generate(SignatureScheme::Ed25519).unwrap();
+        let (shares, public) = split_key(&keypair, 3, 5).unwrap();
+        assert!(run(&[&shares[0], &shares[1]], &public, b"msg").is_err());
+    }
+
+    #[test]
+    fn test_bad_share_is_identified() {
+        let keypair = KeyPair::generate(SignatureScheme::Ed25519).unwrap();
+        let (shares, public) = split_key(&keypair, 2, 3).unwrap();
+
+        let (n1, c1) = commit(&shares[0]).unwrap();
+        let (n2, c2) = commit(&shares[1]).unwrap();
+        let package = SigningPackage::new(b"msg", vec![c1, c2]).unwrap();
+        let s1 = sign(&package, n1, &shares[0]).unwrap();
+        let mut s2 = sign(&package, n2, &shares[1]).unwrap();
+        s2.share[0] ^= 1;
+
+        match aggregate(&package, &[s1, s2], &public) {
+            Err(CryptoError::InvalidSignatureShare { identifier }) => assert_eq!(identifier, 2),
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_tampered_key_share_is_rejected() {
+        let keypair = KeyPair::generate(SignatureScheme::Ed25519).unwrap();
+        let (mut shares, public) = split_key(&keypair, 2, 3).unwrap();
+        shares[1].secret[0] ^= 1;
+        assert!(shares[0].verify(&public).is_ok());
+        assert!(shares[1].verify(&public).is_err());
+    }
+
+    #[test]
+    fn test_nonces_must_match_package() {
+        let keypair = KeyPair::generate(SignatureScheme::Ed25519).unwrap();
+        let (shares, _) = split_key(&keypair, 2, 3).unwrap();
+
+        let (n1, _) = commit(&shares[0]).unwrap();
+        let (_, c1) = commit(&shares[0]).unwrap();
+        let (_, c2) = commit(&shares[1]).unwrap();
+        let package = SigningPackage::new(b"msg", vec![c1, c2]).unwrap();
+        assert!(sign(&package, n1, &shares[0]).is_err());
+    }
+}
@@ -2,8 +2,9 @@
 
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
-use sha3::Sha3_256;
+use sha3::{Keccak256, Sha3_256};
 use std::fmt;
+use std::io;
 
 /// Hash output size in bytes
 pub const HASH_SIZE: usize = 32;
@@ -14,10 +15,25 @@ pub enum HashAlgorithm {
     Sha256,
     Sha3_256,
     Blake3,
+    /// Original Keccak padding, as used by Ethereum
+    Keccak256,
+}
+
+impl HashAlgorithm {
+    /// Start an incremental hash with this algorithm
+    pub fn hasher(self) -> StreamingHasher {
+        let inner = match self {
+            HashAlgorithm::Sha256 => Inner::Sha256(Sha256::new()),
+            HashAlgorithm::Sha3_256 => Inner::Sha3_256(Sha3_256::new()),
+            HashAlgorithm::Blake3 => Inner::Blake3(Box::new(blake3::Hasher::new())),
+            HashAlgorithm::Keccak256 => Inner::Keccak256(Keccak256::new()),
+        };
+        StreamingHasher { inner }
+    }
 }
 
 /// A 32-byte hash value
-#[derive(Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub struct Hash([u8; HASH_SIZE]);
 
 impl Hash {
@@ -96,22 +112,9 @@ impl Hashable for [u8] {
     }
 
     fn hash_with(&self, algorithm: HashAlgorithm) -> Hash {
-        match algorithm {
-            HashAlgorithm::Sha256 => {
-                let mut hasher = Sha256::new();
-                hasher.update(self);
-                Hash::new(hasher.finalize().into())
-            }
-            HashAlgorithm::Sha3_256 => {
-                let mut hasher = Sha3_256::new();
-                hasher.update(self);
-                Hash::new(hasher.finalize().into())
-            }
-            HashAlgorithm::Blake3 => {
-                let hash = blake3::hash(self);
-                Hash::new(*hash.as_bytes())
-            }
-        }
+        let mut hasher = algorithm.hasher();
+        hasher.update(self);
+        hasher.finalize()
     }
 }
 
@@ -135,12 +138,136 @@ impl Hashable for &str {
     }
 }
 
+/// Incremental hashing: feed data in any number of pieces, then take the
+/// digest, which equals the one-shot hash of the concatenated input
+pub trait Hasher {
+    fn update(&mut self, data: &[u8]);
+    fn finalize(self) -> Hash;
+}
+
+impl Hasher for Sha256 {
+    fn update(&mut self, data: &[u8]) {
+        Digest::update(self, data);
+    }
+
+    fn finalize(self) -> Hash {
+        Hash::new(Digest::finalize(self).into())
+    }
+}
+
+impl Hasher for Sha3_256 {
+    fn update(&mut self, data: &[u8]) {
+        Digest::update(self, data);
+    }
+
+    fn finalize(self) -> Hash {
+        Hash::new(Digest::finalize(self).into())
+    }
+}
+
+impl Hasher for Keccak256 {
+    fn update(&mut self, data: &[u8]) {
+        Digest::update(self, data);
+    }
+
+    fn finalize(self) -> Hash {
+        Hash::new(Digest::finalize(self).into())
+    }
+}
+
+impl Hasher for blake3::Hasher {
+    fn update(&mut self, data: &[u8]) {
+        blake3::Hasher::update(self, data);
+    }
+
+    fn finalize(self) -> Hash {
+        Hash::new(*blake3::Hasher::finalize(&self).as_bytes())
+    }
+}
+
+/// Incremental hasher for an algorithm chosen at runtime
+///
+/// Also implements `io::Write`, so serializers can write a structure straight
+/// into the hash without building the encoded bytes first.
+#[derive(Clone)]
+pub struct StreamingHasher {
+    inner: Inner,
+}
+
+#[derive(Clone)]
+enum Inner {
+    Sha256(Sha256),
+    Sha3_256(Sha3_256),
+    Blake3(Box<blake3::Hasher>),
+    Keccak256(Keccak256),
+}
+
+impl StreamingHasher {
+    pub fn algorithm(&self) -> HashAlgorithm {
+        match self.inner {
+            Inner::Sha256(_) => HashAlgorithm::Sha256,
+            Inner::Sha3_256(_) => HashAlgorithm::Sha3_256,
+            Inner::Blake3(_) => HashAlgorithm::Blake3,
+            Inner::Keccak256(_) => HashAlgorithm::Keccak256,
+        }
+    }
+}
+
+impl Hasher for StreamingHasher {
+    fn update(&mut self, data: &[u8]) {
+        match &mut self.inner {
+            Inner::Sha256(hasher) => Hasher::update(hasher, data),
+            Inner::Sha3_256(hasher) => Hasher::update(hasher, data),
+            Inner::Blake3(hasher) => Hasher::update(hasher.as_mut(), data),
+            Inner::Keccak256(hasher) => Hasher::update(hasher, data),
+        }
+    }
+
+    fn finalize(self) -> Hash {
+        match self.inner {
+            Inner::Sha256(hasher) => Hasher::finalize(hasher),
+            Inner::Sha3_256(hasher) => Hasher::finalize(hasher),
+            Inner::Blake3(hasher) => Hasher::finalize(*hasher),
+            Inner::Keccak256(hasher) => Hasher::finalize(hasher),
+        }
+    }
+}
+
+impl io::Write for StreamingHasher {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        Hasher::update(self, buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl fmt::Debug for StreamingHasher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "StreamingHasher({:?})", self.algorithm())
+    }
+}
+
 /// Double hash (hash of hash) - commonly used for additional security
 pub fn double_hash(data: &[u8]) -> Hash {
     let first = data.hash();
     first.as_bytes().hash()
 }
 
+/// Keccak-256 hash
+pub fn keccak256(data: &[u8]) -> Hash {
+    data.hash_with(HashAlgorithm::Keccak256)
+}
+
+/// EIP-191 personal message hash, as signed by `personal_sign`/`eth_sign`
+pub fn eth_message_hash(message: &[u8]) -> Hash {
+    let mut data = format!("\x19Ethereum Signed Message:\n{}", message.len()).into_bytes();
+    data.extend_from_slice(message);
+    keccak256(&data)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -165,6 +292,15 @@ mod tests {
         assert_ne!(sha3, blake3);
     }
 
+    #[test]
+    fn test_keccak256() {
+        assert_eq!(
+            keccak256(b"").to_hex(),
+            "c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"
+        );
+        assert_ne!(keccak256(b"abc"), b"abc".hash_with(HashAlgorithm::Sha3_256));
+    }
+
     #[test]
     fn test_hash_hex() {
         let data = b"test";
@@ -174,6 +310,40 @@ mod tests {
         assert_eq!(hash, parsed);
     }
 
+    #[test]
+    fn test_streaming_matches_one_shot() {
+        let data = b"incremental hashing across several updates";
+        for algorithm in [
+            HashAlgorithm::Sha256,
+            HashAlgorithm::Sha3_256,
+            HashAlgorithm::Blake3,
+            HashAlgorithm::Keccak256,
+        ] {
+            let mut hasher = algorithm.hasher();
+            assert_eq!(hasher.algorithm(), algorithm);
+            for chunk in data.chunks(5) {
+                hasher.update(chunk);
+            }
+            assert_eq!(hasher.finalize(), data.hash_with(algorithm));
+
+            let mut writer = algorithm.hasher();
+            io::Write::write_all(&mut writer, data).unwrap();
+            assert_eq!(writer.finalize(), data.hash_with(algorithm));
+        }
+    }
+
+    #[test]
+    fn test_concrete_hashers() {
+        let mut sha = Sha256::new();
+        Hasher::update(&mut sha, b"ab");
+        Hasher::update(&mut sha, b"c");
+        assert_eq!(Hasher::finalize(sha), b"abc".hash());
+
+        let mut blake = blake3::Hasher::new();
+        Hasher::update(&mut blake, b"abc");
+        assert_eq!(Hasher::finalize(blake), b"abc".hash_with(HashAlgorithm::Blake3));
+    }
+
     #[test]
     fn test_double_hash() {
         let data = b"double hash test";
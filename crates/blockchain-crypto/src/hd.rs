@@ -0,0 +1,613 @@
+// blockchain-crypto/src/hd.rs
+
+//! Hierarchical deterministic keys
+//!
+//! BIP39 mnemonics turn into a seed, and the seed into a tree of keys:
+//! BIP32 for SECP256k1 and SLIP-10 for Ed25519. Ed25519 only supports
+//! hardened derivation.
+
+use crate::{CryptoError, CryptoResult, KeyPair, SignatureScheme};
+use hmac::{Hmac, Mac};
+use sha2::Sha512;
+use std::fmt;
+use std::str::FromStr;
+use zeroize::Zeroize;
+
+type HmacSha512 = Hmac<Sha512>;
+
+/// Offset marking a hardened child index
+pub const HARDENED_OFFSET: u32 = 0x8000_0000;
+
+/// SLIP-44 coin type used by [`DerivationPath::bip44`]
+///
+/// Matches Ethereum so SECP256k1 accounts line up with existing wallets.
+pub const COIN_TYPE: u32 = 60;
+
+/// BIP39 mnemonic phrase (English word list)
+#[derive(Clone, PartialEq, Eq)]
+pub struct Mnemonic(bip39::Mnemonic);
+
+impl Mnemonic {
+    /// Generate a new random mnemonic with 12, 15, 18, 21 or 24 words
+    pub fn generate(word_count: usize) -> CryptoResult<Self> {
+        use rand::RngCore;
+
+        if !matches!(word_count, 12 | 15 | 18 | 21 | 24) {
+            return Err(CryptoError::InvalidMnemonic(format!(
+                "Unsupported word count: {}",
+                word_count
+            )));
+        }
+
+        let mut entropy = [0u8; 32];
+        let len = word_count / 3 * 4;
+        rand::rngs::OsRng
+            .try_fill_bytes(&mut entropy[..len])
+            .map_err(|_| CryptoError::RngError)?;
+
+        let mnemonic = Self::from_entropy(&entropy[..len]);
+        entropy.zeroize();
+        mnemonic
+    }
+
+    /// Build a mnemonic from 16 to 32 bytes of entropy
+    pub fn from_entropy(entropy: &[u8]) -> CryptoResult<Self> {
+        bip39::Mnemonic::from_entropy(entropy)
+            .map(Self)
+            .map_err(|e| CryptoError::InvalidMnemonic(e.to_string()))
+    }
+
+    /// Parse a phrase, checking words and checksum
+    pub fn from_phrase(phrase: &str) -> CryptoResult<Self> {
+        bip39::Mnemonic::parse_in(bip39::Language::English, phrase)
+            .map(Self)
+            .map_err(|e| CryptoError::InvalidMnemonic(e.to_string()))
+    }
+
+    /// Space separated phrase
+    pub fn phrase(&self) -> String {
+        self.0.to_string()
+    }
+
+    pub fn word_count(&self) -> usize {
+        self.0.word_count()
+    }
+
+    /// Derive the 64-byte seed, with an optional passphrase ("" for none)
+    pub fn to_seed(&self, passphrase: &str) -> Seed {
+        Seed(self.0.to_seed(passphrase))
+    }
+}
+
+impl FromStr for Mnemonic {
+    type Err = CryptoError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_phrase(s)
+    }
+}
+
+impl fmt::Debug for Mnemonic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Mnemonic({} words, [REDACTED])", self.word_count())
+    }
+}
+
+/// BIP39 seed
+pub struct Seed([u8; 64]);
+
+impl Seed {
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl Drop for Seed {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl fmt::Debug for Seed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Seed([REDACTED])")
+    }
+}
+
+/// Single step in a derivation path
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ChildNumber(u32);
+
+impl ChildNumber {
+    /// Normal (non-hardened) child
+    pub fn normal(index: u32) -> CryptoResult<Self> {
+        if index >= HARDENED_OFFSET {
+            return Err(CryptoError::InvalidDerivationPath(format!(
+                "Child index out of range: {}",
+                index
+            )));
+        }
+        Ok(Self(index))
+    }
+
+    /// Hardened child
+    pub fn hardened(index: u32) -> CryptoResult<Self> {
+        Self::normal(index).map(|c| Self(c.0 | HARDENED_OFFSET))
+    }
+
+    pub fn is_hardened(&self) -> bool {
+        self.0 >= HARDENED_OFFSET
+    }
+
+    /// Index without the hardened bit
+    pub fn index(&self) -> u32 {
+        self.0 & !HARDENED_OFFSET
+    }
+
+    /// Raw value as used in derivation (`ser32(i)`)
+    pub fn to_u32(&self) -> u32 {
+        self.0
+    }
+}
+
+impl fmt::Display for ChildNumber {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_hardened() {
+            write!(f, "{}'", self.index())
+        } else {
+            write!(f, "{}", self.index())
+        }
+    }
+}
+
+impl FromStr for ChildNumber {
+    type Err = CryptoError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (digits, hardened) = match s.strip_suffix(['\'', 'h', 'H']) {
+            Some(digits) => (digits, true),
+            None => (s, false),
+        };
+
+        let index: u32 = digits.parse().map_err(|_| {
+            CryptoError::InvalidDerivationPath(format!("Invalid child index: {}", s))
+        })?;
+
+        if hardened {
+            Self::hardened(index)
+        } else {
+            Self::normal(index)
+        }
+    }
+}
+
+/// Derivation path such as `m/44'/60'/0'/0/0`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct DerivationPath(Vec<ChildNumber>);
+
+impl DerivationPath {
+    /// Path to the master key (`m`)
+    pub fn master() -> Self {
+        Self(Vec::new())
+    }
+
+    /// BIP44 account path for a scheme
+    ///
+    /// `m/44'/60'/account'/0/index` for SECP256k1, ECDSA or Schnorr. SLIP-10
+    /// Ed25519 cannot derive normal children, so there every level is hardened.
+    pub fn bip44(scheme: SignatureScheme, account: u32, index: u32) -> CryptoResult<Self> {
+        let tail = match scheme {
+            SignatureScheme::Secp256k1 | SignatureScheme::SchnorrSecp256k1 => {
+                [ChildNumber::normal(0)?, ChildNumber::normal(index)?]
+            }
+            SignatureScheme::Ed25519 | SignatureScheme::Bls12381 => {
+                [ChildNumber::hardened(0)?, ChildNumber::hardened(index)?]
+            }
+        };
+
+        Ok(Self(vec![
+            ChildNumber::hardened(44)?,
+            ChildNumber::hardened(COIN_TYPE)?,
+            ChildNumber::hardened(account)?,
+            tail[0],
+            tail[1],
+        ]))
+    }
+
+    /// Extend the path by one child
+    pub fn child(&self, child: ChildNumber) -> Self {
+        let mut path = self.0.clone();
+        path.push(child);
+        Self(path)
+    }
+
+    pub fn as_slice(&self) -> &[ChildNumber] {
+        &self.0
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl fmt::Display for DerivationPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "m")?;
+        for child in &self.0 {
+            write!(f, "/{}", child)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for DerivationPath {
+    type Err = CryptoError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.split('/');
+        if parts.next() != Some("m") {
+            return Err(CryptoError::InvalidDerivationPath(format!(
+                "Path must start with 'm': {}",
+                s
+            )));
+        }
+
+        parts
+            .map(ChildNumber::from_str)
+            .collect::<CryptoResult<Vec<_>>>()
+            .map(Self)
+    }
+}
+
+/// Extended private key: secret key plus chain code
+pub struct ExtendedPrivateKey {
+    scheme: SignatureScheme,
+    depth: u8,
+    child_number: Option<ChildNumber>,
+    chain_code: [u8; 32],
+    secret: [u8; 32],
+}
+
+impl ExtendedPrivateKey {
+    /// Master key from a seed (BIP32 / SLIP-10)
+    pub fn new_master(scheme: SignatureScheme, seed: &[u8]) -> CryptoResult<Self> {
+        if !(16..=64).contains(&seed.len()) {
+            return Err(CryptoError::KeyDerivationError(format!(
+                "Seed must be 16 to 64 bytes, got {}",
+                seed.len()
+            )));
+        }
+
+        let key: &[u8] = match scheme {
+            SignatureScheme::Secp256k1 | SignatureScheme::SchnorrSecp256k1 => b"Bitcoin seed",
+            SignatureScheme::Ed25519 => b"ed25519 seed",
+            SignatureScheme::Bls12381 => {
+                return Err(CryptoError::KeyDerivationError(
+                    "HD derivation is not supported for BLS12-381".into(),
+                ))
+            }
+        };
+        let (secret, chain_code) = hmac_sha512(key, &[seed]);
+
+        if scheme != SignatureScheme::Ed25519 {
+            secp256k1::SecretKey::from_slice(&secret).map_err(|_| {
+                CryptoError::KeyDerivationError("Seed yields an invalid master key".into())
+            })?;
+        }
+
+        Ok(Self {
+            scheme,
+            depth: 0,
+            child_number: None,
+            chain_code,
+            secret,
+        })
+    }
+
+    /// Derive the key at `path` straight from a seed
+    pub fn from_seed(
+        scheme: SignatureScheme,
+        seed: &[u8],
+        path: &DerivationPath,
+    ) -> CryptoResult<Self> {
+        Self::new_master(scheme, seed)?.derive_path(path)
+    }
+
+    /// Derive a single child key
+    pub fn derive_child(&self, child: ChildNumber) -> CryptoResult<Self> {
+        if self.depth == u8::MAX {
+            return Err(CryptoError::KeyDerivationError("Maximum depth reached".into()));
+        }
+
+        let index = child.to_u32().to_be_bytes();
+        let (secret, chain_code) = match self.scheme {
+            SignatureScheme::Ed25519 => {
+                if !child.is_hardened() {
+                    return Err(CryptoError::KeyDerivationError(
+                        "Ed25519 only supports hardened derivation".into(),
+                    ));
+                }
+                hmac_sha512(&self.chain_code, &[&[0], &self.secret, &index])
+            }
+            SignatureScheme::Secp256k1 | SignatureScheme::SchnorrSecp256k1 => {
+                self.derive_secp256k1(child, &index)?
+            }
+            SignatureScheme::Bls12381 => {
+                return Err(CryptoError::KeyDerivationError(
+                    "HD derivation is not supported for BLS12-381".into(),
+                ))
+            }
+        };
+
+        Ok(Self {
+            scheme: self.scheme,
+            depth: self.depth + 1,
+            child_number: Some(child),
+            chain_code,
+            secret,
+        })
+    }
+
+    fn derive_secp256k1(
+        &self,
+        child: ChildNumber,
+        index: &[u8; 4],
+    ) -> CryptoResult<([u8; 32], [u8; 32])> {
+        use secp256k1::{PublicKey as Secp256k1Pk, Scalar, SecretKey as Secp256k1Sk, Secp256k1};
+
+        let parent = Secp256k1Sk::from_slice(&self.secret)
+            .map_err(|_| CryptoError::InvalidSecretKey)?;
+
+        let (tweak, chain_code) = if child.is_hardened() {
+            hmac_sha512(&self.chain_code, &[&[0], &self.secret, index])
+        } else {
+            let secp = Secp256k1::signing_only();
+            let public = Secp256k1Pk::from_secret_key(&secp, &parent).serialize();
+            hmac_sha512(&self.chain_code, &[&public, index])
+        };
+
+        // k_i = IL + k_par (mod n); IL >= n or k_i = 0 make the index unusable
+        let invalid = || CryptoError::KeyDerivationError(format!("Invalid child {}", child));
+        let tweak = Scalar::from_be_bytes(tweak).map_err(|_| invalid())?;
+        let secret = parent.add_tweak(&tweak).map_err(|_| invalid())?;
+
+        Ok((secret.secret_bytes(), chain_code))
+    }
+
+    /// Derive along a path relative to this key
+    pub fn derive_path(&self, path: &DerivationPath) -> CryptoResult<Self> {
+        let mut key = self.clone_key();
+        for child in path.as_slice() {
+            key = key.derive_child(*child)?;
+        }
+        Ok(key)
+    }
+
+    fn clone_key(&self) -> Self {
+        Self {
+            scheme: self.scheme,
+            depth: self.depth,
+            child_number: self.child_number,
+            chain_code: self.chain_code,
+            secret: self.secret,
+        }
+    }
+
+    pub fn scheme(&self) -> SignatureScheme {
+        self.scheme
+    }
+
+    pub fn depth(&self) -> u8 {
+        self.depth
+    }
+
+    /// Child number this key was derived with (`None` for the master key)
+    pub fn child_number(&self) -> Option<ChildNumber> {
+        self.child_number
+    }
+
+    pub fn chain_code(&self) -> &[u8; 32] {
+        &self.chain_code
+    }
+
+    pub fn secret_bytes(&self) -> &[u8; 32] {
+        &self.secret
+    }
+
+    /// Signing keypair for this node
+    pub fn to_keypair(&self) -> CryptoResult<KeyPair> {
+        KeyPair::from_secret_bytes(self.scheme, &self.secret)
+    }
+}
+
+impl Drop for ExtendedPrivateKey {
+    fn drop(&mut self) {
+        self.secret.zeroize();
+        self.chain_code.zeroize();
+    }
+}
+
+impl fmt::Debug for ExtendedPrivateKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ExtendedPrivateKey")
+            .field("scheme", &self.scheme)
+            .field("depth", &self.depth)
+            .field("child_number", &self.child_number)
+            .field("secret", &"[REDACTED]")
+            .finish()
+    }
+}
+
+/// Split HMAC-SHA512 output into (IL, IR)
+fn hmac_sha512(key: &[u8], data: &[&[u8]]) -> ([u8; 32], [u8; 32]) {
+    let mut mac = HmacSha512::new_from_slice(key).expect("HMAC accepts keys of any length");
+    for part in data {
+        mac.update(part);
+    }
+    let output = mac.finalize().into_bytes();
+
+    let mut left = [0u8; 32];
+    let mut right = [0u8; 32];
+    left.copy_from_slice(&output[..32]);
+    right.copy_from_slice(&output[32..]);
+    (left, right)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SEED_1: &str = "000102030405060708090a0b0c0d0e0f";
+
+    fn derive(scheme: SignatureScheme, seed: &str, path: &str) -> ExtendedPrivateKey {
+        let seed = hex::decode(seed).unwrap();
+        ExtendedPrivateKey::from_seed(scheme, &seed, &path.parse().unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_bip39_vector() {
+        let phrase = "abandon abandon abandon abandon abandon abandon \
+                      abandon abandon abandon abandon abandon about";
+        let mnemonic = Mnemonic::from_phrase(phrase).unwrap();
+        assert_eq!(mnemonic, Mnemonic::from_entropy(&[0u8; 16]).unwrap());
+
+        assert_eq!(
+            hex::encode(mnemonic.to_seed("TREZOR").as_bytes()),
+            "c55257c360c07c72029aebc1b53c05ed0362ada38ead3e3e9efa3708e53495531f09a6987599d18264c1e1c92f2cf141630c7a3c4ab7c81b2f001698e7463b04"
+        );
+    }
+
+    #[test]
+    fn test_mnemonic_generate_and_validate() {
+        for words in [12, 15, 18, 21, 24] {
+            let mnemonic = Mnemonic::generate(words).unwrap();
+            assert_eq!(mnemonic.word_count(), words);
+            assert_eq!(Mnemonic::from_phrase(&mnemonic.phrase()).unwrap(), mnemonic);
+        }
+        assert!(Mnemonic::generate(13).is_err());
+
+        // Bad checksum
+        let phrase = "abandon abandon abandon abandon abandon abandon \
+                      abandon abandon abandon abandon abandon abandon";
+        assert!(Mnemonic::from_phrase(phrase).is_err());
+        // Unknown word
+        assert!(Mnemonic::from_phrase("blockchain ".repeat(12).trim()).is_err());
+    }
+
+    #[test]
+    fn test_bip32_vector_1() {
+        let vectors = [
+            (
+                "m",
+                "873dff81c02f525623fd1fe5167eac3a55a049de3d314bb42ee227ffed37d508",
+                "e8f32e723decf4051aefac8e2c93c9c5b214313817cdb01a1494b917c8436b35",
+            ),
+            (
+                "m/0'",
+                "47fdacbd0f1097043b78c63c20c34ef4ed9a111d980047ad16282c7ae6236141",
+                "edb2e14f9ee77d26dd93b4ecede8d16ed408ce149b6cd80b0715a2d911a0afea",
+            ),
+            (
+                "m/0'/1",
+                "2a7857631386ba23dacac34180dd1983734e444fdbf774041578e9b6adb37c19",
+                "3c6cb8d0f6a264c91ea8b5030fadaa8e538b020f0a387421a12de9319dc93368",
+            ),
+            (
+                "m/0'/1/2'",
+                "04466b9cc8e161e966409ca52986c584f07e9dc81f735db683c3ff6ec7b1503f",
+                "cbce0d719ecf7431d88e6a89fa1483e02e35092af60c042b1df2ff59fa424dca",
+            ),
+        ];
+
+        for (path, chain_code, secret) in vectors {
+            let key = derive(SignatureScheme::Secp256k1, SEED_1, path);
+            assert_eq!(hex::encode(key.chain_code()), chain_code, "{}", path);
+            assert_eq!(hex::encode(key.secret_bytes()), secret, "{}", path);
+        }
+
+        let master = derive(SignatureScheme::Secp256k1, SEED_1, "m");
+        assert_eq!(
+            master.to_keypair().unwrap().public_key().to_hex(),
+            "0339a36013301597daef41fbe593a02cc513d0b55527ec2df1050e2e8ff49c85c2"
+        );
+    }
+
+    #[test]
+    fn test_slip10_ed25519_vector_1() {
+        let vectors = [
+            (
+                "m",
+                "90046a93de5380a72b5e45010748567d5ea02bbf6522f979e05c0d8d8ca9fffb",
+                "2b4be7f19ee27bbf30c667b642d5f4aa69fd169872f8fc3059c08ebae2eb19e7",
+                "a4b2856bfec510abab89753fac1ac0e1112364e7d250545963f135f2a33188ed",
+            ),
+            (
+                "m/0'",
+                "8b59aa11380b624e81507a27fedda59fea6d0b779a778918a2fd3590e16e9c69",
+                "68e0fe46dfb67e368c75379acec591dad19df3cde26e63b93a8e704f1dade7a3",
+                "8c8a13df77a28f3445213a0f432fde644acaa215fc72dcdf300d5efaa85d350c",
+            ),
+            (
+                "m/0'/1'",
+                "a320425f77d1b5c2505a6b1b27382b37368ee640e3557c315416801243552f14",
+                "b1d0bad404bf35da785a64ca1ac54b2617211d2777696fbffaf208f746ae84f2",
+                "1932a5270f335bed617d5b935c80aedb1a35bd9fc1e31acafd5372c30f5c1187",
+            ),
+        ];
+
+        for (path, chain_code, secret, public) in vectors {
+            let key = derive(SignatureScheme::Ed25519, SEED_1, path);
+            assert_eq!(hex::encode(key.chain_code()), chain_code, "{}", path);
+            assert_eq!(hex::encode(key.secret_bytes()), secret, "{}", path);
+            assert_eq!(key.to_keypair().unwrap().public_key().to_hex(), public, "{}", path);
+        }
+    }
+
+    #[test]
+    fn test_ed25519_rejects_normal_child() {
+        let master = derive(SignatureScheme::Ed25519, SEED_1, "m");
+        assert!(master.derive_child(ChildNumber::normal(0).unwrap()).is_err());
+    }
+
+    #[test]
+    fn test_derivation_path_parsing() {
+        let path: DerivationPath = "m/44'/60'/0'/0/7".parse().unwrap();
+        assert_eq!(path.len(), 5);
+        assert!(path.as_slice()[2].is_hardened());
+        assert!(!path.as_slice()[4].is_hardened());
+        assert_eq!(path.to_string(), "m/44'/60'/0'/0/7");
+        assert_eq!(path, DerivationPath::bip44(SignatureScheme::Secp256k1, 0, 7).unwrap());
+
+        assert_eq!("m/1h/2H".parse::<DerivationPath>().unwrap().to_string(), "m/1'/2'");
+        assert!("m".parse::<DerivationPath>().unwrap().is_empty());
+        assert!("44'/0".parse::<DerivationPath>().is_err());
+        assert!("m/x".parse::<DerivationPath>().is_err());
+        assert!("m/2147483648".parse::<DerivationPath>().is_err());
+    }
+
+    #[test]
+    fn test_keypair_from_mnemonic() {
+        let mnemonic = Mnemonic::generate(24).unwrap();
+        let seed = mnemonic.to_seed("");
+
+        for scheme in [SignatureScheme::Ed25519, SignatureScheme::Secp256k1] {
+            let path = DerivationPath::bip44(scheme, 0, 0).unwrap();
+            let keypair = ExtendedPrivateKey::from_seed(scheme, seed.as_bytes(), &path)
+                .unwrap()
+                .to_keypair()
+                .unwrap();
+            let again = ExtendedPrivateKey::from_seed(scheme, seed.as_bytes(), &path)
+                .unwrap()
+                .to_keypair()
+                .unwrap();
+            assert_eq!(keypair.public_key(), again.public_key());
+
+            let signature = keypair.sign(b"hd").unwrap();
+            assert!(keypair.public_key().verify(b"hd", &signature).unwrap());
+        }
+    }
+}
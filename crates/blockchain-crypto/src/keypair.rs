@@ -3,6 +3,8 @@
 use crate::{CryptoError, CryptoResult, Signature, SignatureScheme};
 use serde::{Deserialize, Serialize};
 use std::fmt;
+use subtle::ConstantTimeEq;
+use zeroize::{ZeroizeOnDrop, Zeroizing};
 
 /// Public key wrapper
 #[derive(Clone, Serialize, Deserialize)]
@@ -69,26 +71,37 @@ impl PartialEq for PublicKey {
 impl Eq for PublicKey {}
 
 /// Secret/Private key wrapper (kept private)
+///
+/// The key material is wiped on drop. `SecretKey` is deliberately neither
+/// `Clone` nor serializable; taking the bytes out goes through
+/// [`export_bytes`](Self::export_bytes) or [`export_hex`](Self::export_hex),
+/// whose results are wiped on drop as well.
 pub struct SecretKey {
     scheme: SignatureScheme,
-    bytes: Vec<u8>,
+    bytes: Zeroizing<Vec<u8>>,
 }
 
 impl SecretKey {
     pub fn new(scheme: SignatureScheme, bytes: Vec<u8>) -> Self {
-        Self { scheme, bytes }
+        Self { scheme, bytes: Zeroizing::new(bytes) }
     }
 
     pub fn scheme(&self) -> SignatureScheme {
         self.scheme
     }
 
-    pub fn as_bytes(&self) -> &[u8] {
+    pub(crate) fn as_bytes(&self) -> &[u8] {
         &self.bytes
     }
 
-    pub fn to_hex(&self) -> String {
-        hex::encode(&self.bytes)
+    /// Copy the raw key material out
+    pub fn export_bytes(&self) -> Zeroizing<Vec<u8>> {
+        self.bytes.clone()
+    }
+
+    /// Hex-encode the key material
+    pub fn export_hex(&self) -> Zeroizing<String> {
+        Zeroizing::new(hex::encode(&*self.bytes))
     }
 
     pub fn from_hex(scheme: SignatureScheme, s: &str) -> CryptoResult<Self> {
@@ -98,13 +111,22 @@ impl SecretKey {
     }
 }
 
-impl Drop for SecretKey {
-    fn drop(&mut self) {
-        // Zero out the key material on drop for security
-        self.bytes.iter_mut().for_each(|b| *b = 0);
+impl ConstantTimeEq for SecretKey {
+    fn ct_eq(&self, other: &Self) -> subtle::Choice {
+        subtle::Choice::from(u8::from(self.scheme == other.scheme)) & self.bytes.as_slice().ct_eq(&other.bytes)
+    }
+}
+
+impl PartialEq for SecretKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.ct_eq(other).into()
     }
 }
 
+impl Eq for SecretKey {}
+
+impl ZeroizeOnDrop for SecretKey {}
+
 impl fmt::Debug for SecretKey {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "SecretKey({:?}, [REDACTED])", self.scheme)
@@ -116,6 +138,8 @@ pub struct KeyPair {
     scheme: SignatureScheme,
     public_key: PublicKey,
     secret_key: SecretKey,
+    /// Parsed Ed25519 signing key, so signing does not re-derive it
+    ed25519: Option<ed25519_dalek::SigningKey>,
 }
 
 impl KeyPair {
@@ -124,64 +148,96 @@ impl KeyPair {
         match scheme {
             SignatureScheme::Ed25519 => Self::generate_ed25519(),
             SignatureScheme::Secp256k1 => Self::generate_secp256k1(),
+            SignatureScheme::Bls12381 => Self::generate_bls(),
+            SignatureScheme::SchnorrSecp256k1 => Self::generate_schnorr(),
         }
     }
 
     fn generate_ed25519() -> CryptoResult<Self> {
-        use ed25519_dalek::{SigningKey, VerifyingKey};
+        use ed25519_dalek::SigningKey;
         use rand::rngs::OsRng;
 
-        let mut csprng = OsRng;
-        let signing_key = SigningKey::generate(&mut csprng);
-        let verifying_key: VerifyingKey = (&signing_key).into();
-
-        Ok(Self {
-            scheme: SignatureScheme::Ed25519,
-            public_key: PublicKey::new(
-                SignatureScheme::Ed25519,
-                verifying_key.to_bytes().to_vec(),
-            ),
-            secret_key: SecretKey::new(
-                SignatureScheme::Ed25519,
-                signing_key.to_bytes().to_vec(),
-            ),
-        })
+        let signing_key = SigningKey::generate(&mut OsRng);
+        Self::from_secret_bytes(SignatureScheme::Ed25519, signing_key.as_bytes())
     }
 
     fn generate_secp256k1() -> CryptoResult<Self> {
         use rand::rngs::OsRng;
-        use secp256k1::{PublicKey as Secp256k1Pk, SecretKey as Secp256k1Sk, Secp256k1};
 
-        let secp = Secp256k1::new();
-        let mut rng = OsRng;
-        
-        let secret_key = Secp256k1Sk::new(&mut rng);
-        let public_key = Secp256k1Pk::from_secret_key(&secp, &secret_key);
+        let secret_key = secp256k1::SecretKey::new(&mut OsRng);
+        Self::from_secret_bytes(SignatureScheme::Secp256k1, &Zeroizing::new(secret_key.secret_bytes())[..])
+    }
+
+    fn generate_schnorr() -> CryptoResult<Self> {
+        use rand::rngs::OsRng;
+
+        let secret_key = secp256k1::SecretKey::new(&mut OsRng);
+        Self::from_secret_bytes(SignatureScheme::SchnorrSecp256k1, &Zeroizing::new(secret_key.secret_bytes())[..])
+    }
+
+    fn generate_bls() -> CryptoResult<Self> {
+        use rand::RngCore;
+
+        let mut ikm = Zeroizing::new([0u8; 32]);
+        rand::rngs::OsRng
+            .try_fill_bytes(&mut ikm[..])
+            .map_err(|_| CryptoError::RngError)?;
+        let secret_key = blst::min_pk::SecretKey::key_gen(&ikm[..], &[])
+            .map_err(|_| CryptoError::InvalidSecretKey)?;
+
+        Self::from_secret_bytes(SignatureScheme::Bls12381, &Zeroizing::new(secret_key.to_bytes())[..])
+    }
+
+    /// Rebuild a keypair from raw secret key bytes
+    pub fn from_secret_bytes(scheme: SignatureScheme, bytes: &[u8]) -> CryptoResult<Self> {
+        let mut ed25519 = None;
+        let public_bytes = match scheme {
+            SignatureScheme::Ed25519 => {
+                use ed25519_dalek::SigningKey;
+
+                let signing_key = SigningKey::from_bytes(
+                    bytes.try_into().map_err(|_| CryptoError::InvalidSecretKey)?
+                );
+                let public_bytes = signing_key.verifying_key().to_bytes().to_vec();
+                ed25519 = Some(signing_key);
+                public_bytes
+            }
+            SignatureScheme::Secp256k1 => {
+                use secp256k1::{PublicKey as Secp256k1Pk, SecretKey as Secp256k1Sk, Secp256k1};
+
+                let secp = Secp256k1::signing_only();
+                let secret_key = Secp256k1Sk::from_slice(bytes)
+                    .map_err(|_| CryptoError::InvalidSecretKey)?;
+                Secp256k1Pk::from_secret_key(&secp, &secret_key).serialize().to_vec()
+            }
+            SignatureScheme::Bls12381 => blst::min_pk::SecretKey::from_bytes(bytes)
+                .map_err(|_| CryptoError::InvalidSecretKey)?
+                .sk_to_pk()
+                .to_bytes()
+                .to_vec(),
+            SignatureScheme::SchnorrSecp256k1 => crate::schnorr::public_key_bytes(bytes)?,
+        };
 
         Ok(Self {
-            scheme: SignatureScheme::Secp256k1,
-            public_key: PublicKey::new(
-                SignatureScheme::Secp256k1,
-                public_key.serialize().to_vec(),
-            ),
-            secret_key: SecretKey::new(
-                SignatureScheme::Secp256k1,
-                secret_key.secret_bytes().to_vec(),
-            ),
+            scheme,
+            public_key: PublicKey::new(scheme, public_bytes),
+            secret_key: SecretKey::new(scheme, bytes.to_vec()),
+            ed25519,
         })
     }
 
-    /// Create keypair from existing keys
+    /// Rebuild a keypair from a secret key
+    pub fn from_secret_key(secret_key: SecretKey) -> CryptoResult<Self> {
+        Self::from_secret_bytes(secret_key.scheme(), secret_key.as_bytes())
+    }
+
+    /// Create keypair from existing keys, checking that they belong together
     pub fn from_keys(public_key: PublicKey, secret_key: SecretKey) -> CryptoResult<Self> {
-        if public_key.scheme() != secret_key.scheme() {
+        let keypair = Self::from_secret_key(secret_key)?;
+        if keypair.public_key != public_key {
             return Err(CryptoError::InvalidSecretKey);
         }
-        
-        Ok(Self {
-            scheme: public_key.scheme(),
-            public_key,
-            secret_key,
-        })
+        Ok(keypair)
     }
 
     pub fn scheme(&self) -> SignatureScheme {
@@ -201,24 +257,51 @@ impl KeyPair {
         match self.scheme {
             SignatureScheme::Ed25519 => self.sign_ed25519(message),
             SignatureScheme::Secp256k1 => self.sign_secp256k1(message),
+            SignatureScheme::Bls12381 => crate::bls::sign(&self.secret_key, message),
+            SignatureScheme::SchnorrSecp256k1 => crate::schnorr::sign(&self.secret_key, message),
         }
     }
 
     fn sign_ed25519(&self, message: &[u8]) -> CryptoResult<Signature> {
-        use ed25519_dalek::{Signature as Ed25519Sig, Signer, SigningKey};
-
-        let signing_key = SigningKey::from_bytes(
-            self.secret_key.as_bytes().try_into()
-                .map_err(|_| CryptoError::InvalidSecretKey)?
-        );
+        use ed25519_dalek::Signer;
 
-        let signature: Ed25519Sig = signing_key.sign(message);
+        let signing_key = self.ed25519.as_ref().ok_or(CryptoError::InvalidSecretKey)?;
         Ok(Signature::new(
             SignatureScheme::Ed25519,
-            signature.to_bytes().to_vec(),
+            signing_key.sign(message).to_bytes().to_vec(),
         ))
     }
 
+    /// Sign with a recoverable SECP256k1 signature (`r || s || v`, `v` = 27 or 28)
+    ///
+    /// The message is hashed the same way as `sign`, so `verify` accepts the result.
+    pub fn sign_recoverable(&self, message: &[u8]) -> CryptoResult<Signature> {
+        let digest = crate::hash::Hashable::hash(message);
+        self.sign_digest_recoverable(&digest.to_bytes())
+    }
+
+    /// Recoverable signature over a precomputed 32-byte digest, as Ethereum
+    /// tooling and Solidity `ecrecover` expect
+    pub fn sign_digest_recoverable(&self, digest: &[u8; 32]) -> CryptoResult<Signature> {
+        use secp256k1::{Message, SecretKey as Secp256k1Sk, Secp256k1};
+
+        if self.scheme != SignatureScheme::Secp256k1 {
+            return Err(CryptoError::InvalidSecretKey);
+        }
+
+        let secp = Secp256k1::signing_only();
+        let secret_key = Secp256k1Sk::from_slice(self.secret_key.as_bytes())
+            .map_err(|_| CryptoError::InvalidSecretKey)?;
+        let msg = Message::from_digest(*digest);
+
+        let (recovery_id, compact) = secp
+            .sign_ecdsa_recoverable(&msg, &secret_key)
+            .serialize_compact();
+        let mut bytes = compact.to_vec();
+        bytes.push(27 + recovery_id.to_i32() as u8);
+        Ok(Signature::new(SignatureScheme::Secp256k1, bytes))
+    }
+
     fn sign_secp256k1(&self, message: &[u8]) -> CryptoResult<Signature> {
         use secp256k1::{ecdsa::Signature as Secp256k1Sig, Message, SecretKey as Secp256k1Sk, Secp256k1};
 
@@ -256,28 +339,62 @@ pub struct Address([u8; 20]);
 
 impl Address {
     /// Create address from bytes
-    pub fn new(bytes: [u8; 20]) -> Self {
+    pub const fn new(bytes: [u8; 20]) -> Self {
         Self(bytes)
     }
 
-    /// Derive address from public key (similar to Ethereum)
+    /// Derive address from public key
+    ///
+    /// SECP256k1 keys use the Ethereum rule (last 20 bytes of the Keccak-256
+    /// of the uncompressed key), so addresses match wallets and `ecrecover`.
+    /// Other schemes take the last 20 bytes of the SHA-256 of the key bytes.
     pub fn from_public_key(public_key: &PublicKey) -> Self {
         use crate::hash::Hashable;
-        
+
+        if public_key.scheme() == SignatureScheme::Secp256k1 {
+            if let Some(address) = Self::from_secp256k1_key(public_key.as_bytes()) {
+                return address;
+            }
+        }
+
         let hash = public_key.as_bytes().hash();
         let mut address = [0u8; 20];
         address.copy_from_slice(&hash.as_bytes()[12..32]);
         Self(address)
     }
 
+    fn from_secp256k1_key(bytes: &[u8]) -> Option<Self> {
+        let key = secp256k1::PublicKey::from_slice(bytes).ok()?;
+        let hash = crate::hash::keccak256(&key.serialize_uncompressed()[1..]);
+        let mut address = [0u8; 20];
+        address.copy_from_slice(&hash.as_bytes()[12..32]);
+        Some(Self(address))
+    }
+
     pub fn as_bytes(&self) -> &[u8; 20] {
         &self.0
     }
 
+    /// EIP-55 checksummed hex with `0x` prefix
     pub fn to_hex(&self) -> String {
-        format!("0x{}", hex::encode(self.0))
-    }
-
+        let lower = hex::encode(self.0);
+        let hash = crate::hash::keccak256(lower.as_bytes());
+
+        let checksummed: String = lower
+            .chars()
+            .enumerate()
+            .map(|(i, c)| {
+                let nibble = (hash.as_bytes()[i / 2] >> if i % 2 == 0 { 4 } else { 0 }) & 0x0f;
+                if nibble >= 8 { c.to_ascii_uppercase() } else { c }
+            })
+            .collect();
+        format!("0x{}", checksummed)
+    }
+
+    /// Parse hex, with or without `0x`
+    ///
+    /// All-lowercase and all-uppercase input is accepted as is; mixed case
+    /// must carry a valid EIP-55 checksum.
     pub fn from_hex(s: &str) -> CryptoResult<Self> {
         let s = s.strip_prefix("0x").unwrap_or(s);
         let bytes = hex::decode(s)
@@ -287,7 +404,14 @@ impl Address {
         }
         let mut arr = [0u8; 20];
         arr.copy_from_slice(&bytes);
-        Ok(Self(arr))
+        let address = Self(arr);
+
+        let mixed_case = s.chars().any(|c| c.is_ascii_lowercase())
+            && s.chars().any(|c| c.is_ascii_uppercase());
+        if mixed_case && address.to_hex()[2..] != *s {
+            return Err(CryptoError::DeserializationError("Invalid EIP-55 checksum".into()));
+        }
+        Ok(address)
     }
 
     pub fn zero() -> Self {
@@ -342,4 +466,57 @@ mod tests {
         let parsed = Address::from_hex(&hex).unwrap();
         assert_eq!(address, parsed);
     }
+
+    #[test]
+    fn test_eip55_checksum() {
+        for expected in [
+            "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+            "0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359",
+            "0xdbF03B407c01E7cD3CBea99509d93f8DDDC8C6FB",
+            "0xD1220A0cf47c7B9Be7A2E6BA89F429762e7b9aDb",
+        ] {
+            let address = Address::from_hex(expected).unwrap();
+            assert_eq!(address.to_hex(), expected);
+            assert_eq!(Address::from_hex(&expected.to_lowercase()).unwrap(), address);
+        }
+
+        // Flipping the case of one letter breaks the checksum
+        assert!(Address::from_hex("0x5aaeb6053F3E94C9b9A09f33669435E7Ef1BeAed").is_err());
+    }
+
+    #[test]
+    fn test_secret_key_export_and_compare() {
+        let keypair = KeyPair::generate(SignatureScheme::Ed25519).unwrap();
+        let exported = keypair.secret_key().export_hex();
+
+        let imported = SecretKey::from_hex(SignatureScheme::Ed25519, &exported).unwrap();
+        assert!(&imported == keypair.secret_key());
+        assert!(SecretKey::new(SignatureScheme::Secp256k1, keypair.secret_key().export_bytes().to_vec())
+            != imported);
+        assert!(!format!("{:?}", imported).contains(exported.as_str()));
+
+        let rebuilt = KeyPair::from_secret_key(imported).unwrap();
+        assert_eq!(rebuilt.public_key(), keypair.public_key());
+    }
+
+    #[test]
+    fn test_from_keys_rejects_mismatched_pair() {
+        let a = KeyPair::generate(SignatureScheme::Ed25519).unwrap();
+        let b = KeyPair::generate(SignatureScheme::Ed25519).unwrap();
+        let secret = SecretKey::new(SignatureScheme::Ed25519, a.secret_key().export_bytes().to_vec());
+
+        assert!(KeyPair::from_keys(b.public_key().clone(), secret).is_err());
+        let secret = SecretKey::new(SignatureScheme::Ed25519, a.secret_key().export_bytes().to_vec());
+        assert!(KeyPair::from_keys(a.public_key().clone(), secret).is_ok());
+    }
+
+    #[test]
+    fn test_ethereum_address_derivation() {
+        let secret = hex::decode("4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318").unwrap();
+        let keypair = KeyPair::from_secret_bytes(SignatureScheme::Secp256k1, &secret).unwrap();
+        assert_eq!(
+            keypair.public_key().to_address().to_hex(),
+            "0x2c7536E3605D9C16a7a3D7b1898e529396a65c23"
+        );
+    }
 }
\ No newline at end of file
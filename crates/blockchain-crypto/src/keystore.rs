@@ -0,0 +1,353 @@
+// blockchain-crypto/src/keystore.rs
+
+//! Password-encrypted key files
+//!
+//! Follows the Web3 Secret Storage (Ethereum V3 keystore) layout: a scrypt or
+//! PBKDF2 derived key, AES-128-CTR over the 32-byte secret and a Keccak-256
+//! MAC. Files carry an extra `scheme` field so Ed25519 keys fit the same
+//! format; files without it are SECP256k1, as written by Ethereum wallets.
+
+use crate::{CryptoError, CryptoResult, KeyPair, SignatureScheme};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Keccak256};
+use subtle::ConstantTimeEq;
+use zeroize::Zeroize;
+
+type Aes128Ctr = ctr::Ctr128BE<aes::Aes128>;
+
+/// Keystore format version
+pub const KEYSTORE_VERSION: u32 = 3;
+
+const CIPHER: &str = "aes-128-ctr";
+const PBKDF2_PRF: &str = "hmac-sha256";
+const DKLEN: u32 = 32;
+
+/// Key derivation function used to encrypt a keystore
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kdf {
+    /// scrypt with `n = 2^log_n`
+    Scrypt { log_n: u8, r: u32, p: u32 },
+    /// PBKDF2-HMAC-SHA256 with `c` iterations
+    Pbkdf2 { c: u32 },
+}
+
+impl Kdf {
+    /// Cheaper scrypt parameters (`n = 4096, p = 6`) for tests and light clients
+    pub fn scrypt_light() -> Self {
+        Kdf::Scrypt { log_n: 12, r: 8, p: 6 }
+    }
+}
+
+impl Default for Kdf {
+    /// Standard scrypt parameters (`n = 262144, r = 8, p = 1`)
+    fn default() -> Self {
+        Kdf::Scrypt { log_n: 18, r: 8, p: 1 }
+    }
+}
+
+/// Encrypted key file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Keystore {
+    pub version: u32,
+    pub id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub address: Option<String>,
+    #[serde(default = "default_scheme")]
+    pub scheme: SignatureScheme,
+    #[serde(alias = "Crypto")]
+    pub crypto: KeystoreCrypto,
+}
+
+/// Cipher, KDF and MAC section of a keystore
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KeystoreCrypto {
+    pub cipher: String,
+    pub cipherparams: CipherParams,
+    pub ciphertext: String,
+    pub kdf: String,
+    pub kdfparams: KdfParams,
+    pub mac: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CipherParams {
+    pub iv: String,
+}
+
+/// KDF parameters as stored on disk
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum KdfParams {
+    Scrypt {
+        dklen: u32,
+        n: u64,
+        r: u32,
+        p: u32,
+        salt: String,
+    },
+    Pbkdf2 {
+        c: u32,
+        dklen: u32,
+        prf: String,
+        salt: String,
+    },
+}
+
+fn default_scheme() -> SignatureScheme {
+    SignatureScheme::Secp256k1
+}
+
+impl Keystore {
+    /// Encrypt a keypair's secret key with a password
+    pub fn encrypt(keypair: &KeyPair, password: &str, kdf: Kdf) -> CryptoResult<Self> {
+        let mut salt = [0u8; 32];
+        let mut iv = [0u8; 16];
+        let mut id = [0u8; 16];
+        let mut rng = rand::rngs::OsRng;
+        rng.try_fill_bytes(&mut salt).map_err(|_| CryptoError::RngError)?;
+        rng.try_fill_bytes(&mut iv).map_err(|_| CryptoError::RngError)?;
+        rng.try_fill_bytes(&mut id).map_err(|_| CryptoError::RngError)?;
+
+        let (kdf_name, kdfparams) = match kdf {
+            Kdf::Scrypt { log_n, r, p } => (
+                "scrypt",
+                KdfParams::Scrypt {
+                    dklen: DKLEN,
+                    n: 1u64.checked_shl(log_n as u32).ok_or_else(|| {
+                        CryptoError::KeystoreError(format!("scrypt log_n too large: {}", log_n))
+                    })?,
+                    r,
+                    p,
+                    salt: hex::encode(salt),
+                },
+            ),
+            Kdf::Pbkdf2 { c } => (
+                "pbkdf2",
+                KdfParams::Pbkdf2 {
+                    c,
+                    dklen: DKLEN,
+                    prf: PBKDF2_PRF.into(),
+                    salt: hex::encode(salt),
+                },
+            ),
+        };
+
+        let mut derived = derive_key(password, &kdfparams)?;
+        let mut ciphertext = keypair.secret_key().as_bytes().to_vec();
+        apply_cipher(&derived, &iv, &mut ciphertext);
+        let mac = compute_mac(&derived, &ciphertext);
+        derived.zeroize();
+
+        Ok(Self {
+            version: KEYSTORE_VERSION,
+            id: format_uuid(id),
+            address: Some(hex::encode(keypair.public_key().to_address().as_bytes())),
+            scheme: keypair.scheme(),
+            crypto: KeystoreCrypto {
+                cipher: CIPHER.into(),
+                cipherparams: CipherParams { iv: hex::encode(iv) },
+                ciphertext: hex::encode(ciphertext),
+                kdf: kdf_name.into(),
+                kdfparams,
+                mac: hex::encode(mac),
+            },
+        })
+    }
+
+    /// Decrypt with a password, checking the MAC first
+    pub fn decrypt(&self, password: &str) -> CryptoResult<KeyPair> {
+        if self.version != KEYSTORE_VERSION {
+            return Err(CryptoError::KeystoreError(format!(
+                "Unsupported version: {}",
+                self.version
+            )));
+        }
+        if self.crypto.cipher != CIPHER {
+            return Err(CryptoError::KeystoreError(format!(
+                "Unsupported cipher: {}",
+                self.crypto.cipher
+            )));
+        }
+        let kdf_matches = match &self.crypto.kdfparams {
+            KdfParams::Scrypt { .. } => self.crypto.kdf == "scrypt",
+            KdfParams::Pbkdf2 { .. } => self.crypto.kdf == "pbkdf2",
+        };
+        if !kdf_matches {
+            return Err(CryptoError::KeystoreError(format!(
+                "Parameters do not match kdf {}",
+                self.crypto.kdf
+            )));
+        }
+
+        let iv = decode_hex(&self.crypto.cipherparams.iv, "iv")?;
+        if iv.len() != 16 {
+            return Err(CryptoError::KeystoreError("iv must be 16 bytes".into()));
+        }
+        let mut plaintext = decode_hex(&self.crypto.ciphertext, "ciphertext")?;
+        let expected_mac = decode_hex(&self.crypto.mac, "mac")?;
+
+        let mut derived = derive_key(password, &self.crypto.kdfparams)?;
+        let mac = compute_mac(&derived, &plaintext);
+        if !bool::from(mac.as_slice().ct_eq(&expected_mac)) {
+            derived.zeroize();
+            return Err(CryptoError::InvalidPassword);
+        }
+
+        apply_cipher(&derived, &iv, &mut plaintext);
+        derived.zeroize();
+
+        let keypair = KeyPair::from_secret_bytes(self.scheme, &plaintext);
+        plaintext.zeroize();
+        keypair
+    }
+
+    pub fn to_json(&self) -> CryptoResult<String> {
+        serde_json::to_string_pretty(self)
+            .map_err(|e| CryptoError::SerializationError(e.to_string()))
+    }
+
+    pub fn from_json(s: &str) -> CryptoResult<Self> {
+        serde_json::from_str(s).map_err(|e| CryptoError::DeserializationError(e.to_string()))
+    }
+}
+
+fn derive_key(password: &str, params: &KdfParams) -> CryptoResult<[u8; 32]> {
+    let mut derived = [0u8; 32];
+
+    match params {
+        KdfParams::Scrypt { dklen, n, r, p, salt } => {
+            check_dklen(*dklen)?;
+            if !n.is_power_of_two() || *n < 2 {
+                return Err(CryptoError::KeystoreError(format!(
+                    "scrypt n must be a power of two: {}",
+                    n
+                )));
+            }
+            let params = scrypt::Params::new(n.trailing_zeros() as u8, *r, *p, derived.len())
+                .map_err(|e| CryptoError::KeystoreError(e.to_string()))?;
+            let salt = decode_hex(salt, "salt")?;
+            scrypt::scrypt(password.as_bytes(), &salt, &params, &mut derived)
+                .map_err(|e| CryptoError::KeystoreError(e.to_string()))?;
+        }
+        KdfParams::Pbkdf2 { c, dklen, prf, salt } => {
+            check_dklen(*dklen)?;
+            if prf != PBKDF2_PRF {
+                return Err(CryptoError::KeystoreError(format!("Unsupported prf: {}", prf)));
+            }
+            let salt = decode_hex(salt, "salt")?;
+            pbkdf2::pbkdf2_hmac::<sha2::Sha256>(password.as_bytes(), &salt, *c, &mut derived);
+        }
+    }
+
+    Ok(derived)
+}
+
+fn check_dklen(dklen: u32) -> CryptoResult<()> {
+    if dklen != DKLEN {
+        return Err(CryptoError::KeystoreError(format!("Unsupported dklen: {}", dklen)));
+    }
+    Ok(())
+}
+
+/// AES-128-CTR keyed with the first half of the derived key
+fn apply_cipher(derived: &[u8; 32], iv: &[u8], data: &mut [u8]) {
+    use ctr::cipher::{KeyIvInit, StreamCipher};
+
+    let mut cipher = Aes128Ctr::new(derived[..16].into(), iv.into());
+    cipher.apply_keystream(data);
+}
+
+/// Keccak-256 over the second half of the derived key and the ciphertext
+fn compute_mac(derived: &[u8; 32], ciphertext: &[u8]) -> [u8; 32] {
+    let mut hasher = Keccak256::new();
+    hasher.update(&derived[16..]);
+    hasher.update(ciphertext);
+    hasher.finalize().into()
+}
+
+fn decode_hex(s: &str, field: &str) -> CryptoResult<Vec<u8>> {
+    hex::decode(s.strip_prefix("0x").unwrap_or(s))
+        .map_err(|e| CryptoError::KeystoreError(format!("Invalid {}: {}", field, e)))
+}
+
+/// Random (version 4) UUID string
+fn format_uuid(mut bytes: [u8; 16]) -> String {
+    bytes[6] = (bytes[6] & 0x0f) | 0x40;
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+    let hex = hex::encode(bytes);
+    format!(
+        "{}-{}-{}-{}-{}",
+        &hex[..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..]
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_SECRET: &str = "7a28b5ba57c53603b0b07b56bba752f7784bf506fa95edc395f5cf6c7514fe9d";
+
+    #[test]
+    fn test_web3_pbkdf2_vector() {
+        let json = r#"{
+            "crypto": {
+                "cipher": "aes-128-ctr",
+                "cipherparams": { "iv": "6087dab2f9fdbbfaddc31a909735c1e6" },
+                "ciphertext": "5318b4d5bcd28de64ee5559e671353e16f075ecae9f99c7a79a38af5f869aa46",
+                "kdf": "pbkdf2",
+                "kdfparams": {
+                    "c": 262144,
+                    "dklen": 32,
+                    "prf": "hmac-sha256",
+                    "salt": "ae3cd4e7013836a3df6bd7241b12db061dbe2c6785853cce422d148a624ce0bd"
+                },
+                "mac": "517ead924a9d0dc3124507e3393d175ce3ff7c1e96529c6c555ce9e51205e9b2"
+            },
+            "id": "3198bc9c-6672-5ab3-d995-4942343ae5b6",
+            "version": 3
+        }"#;
+
+        let keystore = Keystore::from_json(json).unwrap();
+        assert_eq!(keystore.scheme, SignatureScheme::Secp256k1);
+
+        let keypair = keystore.decrypt("testpassword").unwrap();
+        assert_eq!(keypair.secret_key().export_hex().as_str(), TEST_SECRET);
+        assert!(matches!(keystore.decrypt("wrong"), Err(CryptoError::InvalidPassword)));
+    }
+
+    #[test]
+    fn test_roundtrip_both_schemes() {
+        for scheme in [SignatureScheme::Ed25519, SignatureScheme::Secp256k1] {
+            let keypair = KeyPair::generate(scheme).unwrap();
+            for kdf in [Kdf::scrypt_light(), Kdf::Pbkdf2 { c: 1024 }] {
+                let json = Keystore::encrypt(&keypair, "hunter2", kdf)
+                    .unwrap()
+                    .to_json()
+                    .unwrap();
+                let keystore = Keystore::from_json(&json).unwrap();
+                assert_eq!(keystore.scheme, scheme);
+                assert_eq!(keystore.id.len(), 36);
+
+                let decrypted = keystore.decrypt("hunter2").unwrap();
+                assert_eq!(decrypted.public_key(), keypair.public_key());
+                assert!(matches!(keystore.decrypt("hunter3"), Err(CryptoError::InvalidPassword)));
+            }
+        }
+    }
+
+    #[test]
+    fn test_tampered_ciphertext_rejected() {
+        let keypair = KeyPair::generate(SignatureScheme::Ed25519).unwrap();
+        let mut keystore = Keystore::encrypt(&keypair, "pw", Kdf::Pbkdf2 { c: 16 }).unwrap();
+        let mut ciphertext = hex::decode(&keystore.crypto.ciphertext).unwrap();
+        ciphertext[0] ^= 1;
+        keystore.crypto.ciphertext = hex::encode(ciphertext);
+
+        assert!(matches!(keystore.decrypt("pw"), Err(CryptoError::InvalidPassword)));
+    }
+}
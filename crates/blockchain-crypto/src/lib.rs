@@ -3,20 +3,43 @@
 //! Cryptographic primitives for the utility-backed blockchain protocol
 //!
 //! This crate provides:
-//! - Hashing functions (SHA256, SHA3, Blake3)
-//! - Digital signatures (Ed25519, SECP256k1)
+//! - Hashing functions (SHA256, SHA3, Blake3, Keccak-256)
+//! - Digital signatures (Ed25519, SECP256k1 ECDSA and BIP-340 Schnorr,
+//!   BLS12-381 with aggregation)
 //! - Key pair generation and management
 //! - Merkle tree implementation
+//! - Sparse Merkle tree with non-membership proofs
+//! - HD key derivation (BIP39, BIP32, SLIP-10)
+//! - Encrypted keystore files (Web3 Secret Storage)
+//! - Verifiable random function (ECVRF, RFC 9381)
+//! - Shamir secret sharing and FROST threshold Ed25519 signing
 
 pub mod hash;
 pub mod signature;
 pub mod keypair;
 pub mod merkle;
+pub mod smt;
+pub mod bls;
+pub mod schnorr;
+pub mod hd;
+pub mod keystore;
+pub mod vrf;
+pub mod shamir;
+pub mod frost;
 
-pub use hash::{Hash, HashAlgorithm, Hashable};
-pub use signature::{Signature, SignatureScheme};
+pub use hash::{Hash, HashAlgorithm, Hashable, Hasher, StreamingHasher};
+pub use signature::{
+    recover_public_key, recover_public_key_from_digest, verify_batch, BatchItem, Signature,
+    SignatureScheme,
+};
 pub use keypair::{KeyPair, PublicKey, SecretKey, Address};
-pub use merkle::MerkleTree;
+pub use merkle::{IncrementalMerkleTree, MerkleMultiProof, MerkleProof, MerkleTree};
+pub use smt::{MemoryStore, SmtNode, SmtStore, SparseMerkleProof, SparseMerkleTree};
+pub use bls::{AggregateSignature, SignerBitfield};
+pub use hd::{ChildNumber, DerivationPath, ExtendedPrivateKey, Mnemonic, Seed};
+pub use keystore::{Kdf, Keystore};
+pub use vrf::{VrfOutput, VrfProof};
+pub use shamir::SecretShare;
 
 /// Result type for cryptographic operations
 pub type CryptoResult<T> = Result<T, CryptoError>;
@@ -47,6 +70,33 @@ pub enum CryptoError {
     
     #[error("Merkle tree error: {0}")]
     MerkleError(String),
+
+    #[error("Invalid mnemonic: {0}")]
+    InvalidMnemonic(String),
+
+    #[error("Invalid derivation path: {0}")]
+    InvalidDerivationPath(String),
+
+    #[error("Key derivation error: {0}")]
+    KeyDerivationError(String),
+
+    #[error("Keystore error: {0}")]
+    KeystoreError(String),
+
+    #[error("Invalid password")]
+    InvalidPassword,
+
+    #[error("Invalid VRF proof")]
+    InvalidVrfProof,
+
+    #[error("Threshold error: {0}")]
+    ThresholdError(String),
+
+    #[error("Invalid signature share from signer {identifier}")]
+    InvalidSignatureShare { identifier: u16 },
+
+    #[error("Batch verification failed at index {index}")]
+    BatchVerificationFailed { index: usize },
 }
 
 #[cfg(test)]
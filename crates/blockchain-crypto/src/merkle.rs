@@ -1,15 +1,45 @@
 // blockchain-crypto/src/merkle.rs
 
+//! Merkle trees with leaf/node domain separation
+//!
+//! Leaves are hashed as `H(0x00 || data)` and inner nodes as
+//! `H(0x01 || left || right)`, so an inner node can never pass for a leaf.
+//! A node without a sibling is carried up unchanged, which gives the same
+//! shape as RFC 6962 and lets [`IncrementalMerkleTree`] reach the same root
+//! as [`MerkleTree`] one leaf at a time.
+
 use crate::{hash::Hashable, CryptoError, CryptoResult, Hash};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+
+/// Prefix for leaf hashes
+pub const LEAF_PREFIX: u8 = 0x00;
+
+/// Prefix for inner node hashes
+pub const NODE_PREFIX: u8 = 0x01;
+
+/// Hash leaf data
+pub fn hash_leaf(data: &[u8]) -> Hash {
+    let mut bytes = Vec::with_capacity(1 + data.len());
+    bytes.push(LEAF_PREFIX);
+    bytes.extend_from_slice(data);
+    bytes.hash()
+}
+
+/// Hash two child nodes
+pub fn hash_node(left: Hash, right: Hash) -> Hash {
+    let mut bytes = Vec::with_capacity(65);
+    bytes.push(NODE_PREFIX);
+    bytes.extend_from_slice(left.as_bytes());
+    bytes.extend_from_slice(right.as_bytes());
+    bytes.hash()
+}
 
 /// Merkle tree for efficient verification of large datasets
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MerkleTree {
-    /// All nodes in the tree (stored as a flat array)
-    nodes: Vec<Hash>,
-    /// Number of leaf nodes
-    leaf_count: usize,
+    /// Nodes per level, leaves first and the root last
+    levels: Vec<Vec<Hash>>,
 }
 
 impl MerkleTree {
@@ -19,130 +49,113 @@ impl MerkleTree {
             return Err(CryptoError::MerkleError("Cannot create empty tree".into()));
         }
 
-        let leaf_count = leaves.len();
-        let total_nodes = Self::total_nodes(leaf_count);
-        let mut nodes = vec![Hash::zero(); total_nodes];
-
-        // Hash all leaves
-        let leaf_start = total_nodes - leaf_count;
-        for (i, leaf) in leaves.iter().enumerate() {
-            nodes[leaf_start + i] = leaf.as_ref().hash();
+        let mut levels = vec![leaves.iter().map(|leaf| hash_leaf(leaf.as_ref())).collect::<Vec<_>>()];
+
+        while levels.last().map_or(0, Vec::len) > 1 {
+            let level = levels.last().unwrap();
+            let parent = level
+                .chunks(2)
+                .map(|pair| match pair {
+                    [left, right] => hash_node(*left, *right),
+                    [single] => *single,
+                    _ => unreachable!(),
+                })
+                .collect();
+            levels.push(parent);
         }
 
-        // Build tree from bottom up
-        Self::build_tree(&mut nodes, leaf_start);
-
-        Ok(Self { nodes, leaf_count })
+        Ok(Self { levels })
     }
 
     /// Get the root hash of the tree
     pub fn root(&self) -> Hash {
-        self.nodes[0]
+        self.levels.last().unwrap()[0]
     }
 
     /// Get the number of leaves
     pub fn leaf_count(&self) -> usize {
-        self.leaf_count
+        self.levels[0].len()
     }
 
     /// Generate a Merkle proof for a specific leaf
     pub fn proof(&self, index: usize) -> CryptoResult<MerkleProof> {
-        if index >= self.leaf_count {
+        if index >= self.leaf_count() {
             return Err(CryptoError::MerkleError("Index out of bounds".into()));
         }
 
         let mut proof_hashes = Vec::new();
-        let mut current_index = self.leaf_start() + index;
+        let mut current_index = index;
 
-        while current_index > 0 {
-            let sibling_index = Self::sibling_index(current_index);
-            proof_hashes.push(self.nodes[sibling_index]);
-            current_index = Self::parent_index(current_index);
+        for level in &self.levels[..self.levels.len() - 1] {
+            let sibling_index = current_index ^ 1;
+            if sibling_index < level.len() {
+                proof_hashes.push(level[sibling_index]);
+            }
+            current_index /= 2;
         }
 
         Ok(MerkleProof {
             leaf_index: index,
-            leaf_hash: self.nodes[self.leaf_start() + index],
+            leaf_count: self.leaf_count(),
+            leaf_hash: self.levels[0][index],
             proof_hashes,
         })
     }
 
+    /// Generate one compact proof for several leaves
+    ///
+    /// Siblings that can be computed from other proven leaves are left out.
+    pub fn multiproof(&self, indices: &[usize]) -> CryptoResult<MerkleMultiProof> {
+        let indices = sorted_unique(indices, self.leaf_count())?;
+
+        let mut hashes = Vec::new();
+        let mut known: BTreeSet<usize> = indices.iter().copied().collect();
+
+        for level in &self.levels[..self.levels.len() - 1] {
+            for &index in &known {
+                let sibling = index ^ 1;
+                if sibling < level.len() && !known.contains(&sibling) {
+                    hashes.push(level[sibling]);
+                }
+            }
+            known = known.iter().map(|i| i / 2).collect();
+        }
+
+        Ok(MerkleMultiProof {
+            leaf_count: self.leaf_count(),
+            indices,
+            hashes,
+        })
+    }
+
     /// Verify a Merkle proof
     pub fn verify_proof(root: Hash, proof: &MerkleProof, leaf_data: &[u8]) -> bool {
-        let leaf_hash = leaf_data.hash();
-        if leaf_hash != proof.leaf_hash {
+        let leaf_hash = hash_leaf(leaf_data);
+        if leaf_hash != proof.leaf_hash || proof.leaf_index >= proof.leaf_count {
             return false;
         }
 
         let mut current_hash = leaf_hash;
         let mut index = proof.leaf_index;
+        let mut level_len = proof.leaf_count;
+        let mut proof_hashes = proof.proof_hashes.iter();
 
-        for proof_hash in &proof.proof_hashes {
-            current_hash = if index % 2 == 0 {
-                Self::combine_hashes(current_hash, *proof_hash)
-            } else {
-                Self::combine_hashes(*proof_hash, current_hash)
-            };
-            index /= 2;
-        }
-
-        current_hash == root
-    }
-
-    // Helper functions
-
-    fn total_nodes(leaf_count: usize) -> usize {
-        // For a binary tree: 2*n - 1 nodes for n leaves
-        // But we pad to next power of 2 for simplicity
-        let padded_leaves = leaf_count.next_power_of_two();
-        2 * padded_leaves - 1
-    }
-
-    fn leaf_start(&self) -> usize {
-        self.nodes.len() - self.leaf_count.next_power_of_two()
-    }
-
-    fn parent_index(index: usize) -> usize {
-        (index - 1) / 2
-    }
-
-    fn sibling_index(index: usize) -> usize {
-        if index % 2 == 0 {
-            index - 1
-        } else {
-            index + 1
-        }
-    }
-
-    fn build_tree(nodes: &mut [Hash], leaf_start: usize) {
-        let mut level_start = leaf_start;
-        
-        while level_start > 0 {
-            let parent_start = Self::parent_index(level_start);
-            let level_size = level_start - parent_start;
-            
-            for i in 0..level_size {
-                let left_index = level_start + i * 2;
-                let right_index = left_index + 1;
-                
-                let combined = if right_index < nodes.len() {
-                    Self::combine_hashes(nodes[left_index], nodes[right_index])
+        while level_len > 1 {
+            if index ^ 1 < level_len {
+                let Some(sibling) = proof_hashes.next() else {
+                    return false;
+                };
+                current_hash = if index.is_multiple_of(2) {
+                    hash_node(current_hash, *sibling)
                 } else {
-                    nodes[left_index]
+                    hash_node(*sibling, current_hash)
                 };
-                
-                nodes[parent_start + i] = combined;
             }
-            
-            level_start = parent_start;
+            index /= 2;
+            level_len = level_len.div_ceil(2);
         }
-    }
 
-    fn combine_hashes(left: Hash, right: Hash) -> Hash {
-        let mut combined = Vec::with_capacity(64);
-        combined.extend_from_slice(left.as_bytes());
-        combined.extend_from_slice(right.as_bytes());
-        combined.hash()
+        proof_hashes.next().is_none() && current_hash == root
     }
 }
 
@@ -150,6 +163,7 @@ impl MerkleTree {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MerkleProof {
     leaf_index: usize,
+    leaf_count: usize,
     leaf_hash: Hash,
     proof_hashes: Vec<Hash>,
 }
@@ -159,6 +173,10 @@ impl MerkleProof {
         self.leaf_index
     }
 
+    pub fn leaf_count(&self) -> usize {
+        self.leaf_count
+    }
+
     pub fn leaf_hash(&self) -> Hash {
         self.leaf_hash
     }
@@ -172,15 +190,162 @@ impl MerkleProof {
     }
 }
 
+/// Proof that several leaves are in the tree
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MerkleMultiProof {
+    leaf_count: usize,
+    /// Proven leaf indices, ascending
+    indices: Vec<usize>,
+    /// Sibling hashes, level by level and left to right
+    hashes: Vec<Hash>,
+}
+
+impl MerkleMultiProof {
+    pub fn leaf_count(&self) -> usize {
+        self.leaf_count
+    }
+
+    pub fn indices(&self) -> &[usize] {
+        &self.indices
+    }
+
+    pub fn hashes(&self) -> &[Hash] {
+        &self.hashes
+    }
+
+    /// Verify with the leaf data in the order of `indices()`
+    pub fn verify<T: AsRef<[u8]>>(&self, root: Hash, leaves: &[T]) -> bool {
+        if leaves.len() != self.indices.len()
+            || !sorted_unique(&self.indices, self.leaf_count).is_ok_and(|sorted| sorted == self.indices)
+        {
+            return false;
+        }
+
+        let mut known: Vec<(usize, Hash)> = self
+            .indices
+            .iter()
+            .zip(leaves)
+            .map(|(&i, leaf)| (i, hash_leaf(leaf.as_ref())))
+            .collect();
+        let mut hashes = self.hashes.iter();
+        let mut level_len = self.leaf_count;
+
+        while level_len > 1 {
+            let mut parents: Vec<(usize, Hash)> = Vec::with_capacity(known.len());
+            let mut i = 0;
+            while i < known.len() {
+                let (index, hash) = known[i];
+                let sibling = index ^ 1;
+
+                let parent = if sibling >= level_len {
+                    hash
+                } else if known.get(i + 1).is_some_and(|(next, _)| *next == sibling) {
+                    i += 1;
+                    hash_node(hash, known[i].1)
+                } else {
+                    let Some(sibling_hash) = hashes.next() else {
+                        return false;
+                    };
+                    if index.is_multiple_of(2) {
+                        hash_node(hash, *sibling_hash)
+                    } else {
+                        hash_node(*sibling_hash, hash)
+                    }
+                };
+                parents.push((index / 2, parent));
+                i += 1;
+            }
+            known = parents;
+            level_len = level_len.div_ceil(2);
+        }
+
+        hashes.next().is_none() && known.len() == 1 && known[0].1 == root
+    }
+}
+
+/// Append-only Merkle tree keeping only its frontier
+///
+/// Stores one hash per level (the roots of the perfect subtrees that make up
+/// the tree), so append and root are O(log n) in time and space. The root
+/// matches [`MerkleTree::new`] over the same leaves; an empty tree has a zero
+/// root. Suited to transaction/receipt roots and chain-history accumulators.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IncrementalMerkleTree {
+    /// `frontier[k]` holds the root of a perfect subtree of 2^k leaves
+    frontier: Vec<Option<Hash>>,
+    leaf_count: u64,
+}
+
+impl IncrementalMerkleTree {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append leaf data
+    pub fn append(&mut self, data: &[u8]) {
+        self.append_leaf_hash(hash_leaf(data));
+    }
+
+    /// Append an already hashed leaf (see [`hash_leaf`])
+    pub fn append_leaf_hash(&mut self, leaf_hash: Hash) {
+        let mut carry = leaf_hash;
+        for slot in self.frontier.iter_mut() {
+            match slot.take() {
+                Some(left) => carry = hash_node(left, carry),
+                None => {
+                    *slot = Some(carry);
+                    self.leaf_count += 1;
+                    return;
+                }
+            }
+        }
+        self.frontier.push(Some(carry));
+        self.leaf_count += 1;
+    }
+
+    /// Current root
+    pub fn root(&self) -> Hash {
+        self.frontier
+            .iter()
+            .flatten()
+            .copied()
+            .reduce(|right, left| hash_node(left, right))
+            .unwrap_or_else(Hash::zero)
+    }
+
+    pub fn leaf_count(&self) -> u64 {
+        self.leaf_count
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.leaf_count == 0
+    }
+}
+
+fn sorted_unique(indices: &[usize], leaf_count: usize) -> CryptoResult<Vec<usize>> {
+    if indices.is_empty() {
+        return Err(CryptoError::MerkleError("No indices to prove".into()));
+    }
+    let set: BTreeSet<usize> = indices.iter().copied().collect();
+    if set.iter().next_back().is_some_and(|&max| max >= leaf_count) {
+        return Err(CryptoError::MerkleError("Index out of bounds".into()));
+    }
+    Ok(set.into_iter().collect())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn leaves(n: usize) -> Vec<Vec<u8>> {
+        (0..n).map(|i| format!("leaf{}", i).into_bytes()).collect()
+    }
+
     #[test]
     fn test_merkle_tree_basic() {
         let leaves = vec![b"leaf1", b"leaf2", b"leaf3", b"leaf4"];
         let tree = MerkleTree::new(&leaves).unwrap();
-        
+
         assert_eq!(tree.leaf_count(), 4);
         assert_ne!(tree.root(), Hash::zero());
     }
@@ -189,7 +354,7 @@ mod tests {
     fn test_merkle_proof() {
         let leaves = vec![b"apple", b"banan", b"chery", b"dates"];
         let tree = MerkleTree::new(&leaves).unwrap();
-        
+
         for i in 0..leaves.len() {
             let proof = tree.proof(i).unwrap();
             assert!(proof.verify(tree.root(), leaves[i]));
@@ -200,7 +365,7 @@ mod tests {
     fn test_merkle_proof_invalid() {
         let leaves = vec![b"apple", b"banan", b"chery"];
         let tree = MerkleTree::new(&leaves).unwrap();
-        
+
         let proof = tree.proof(0).unwrap();
         assert!(!proof.verify(tree.root(), b"invalid"));
     }
@@ -212,4 +377,66 @@ mod tests {
         let proof = tree.proof(0).unwrap();
         assert!(proof.verify(tree.root(), b"single"));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_proofs_for_unbalanced_trees() {
+        for n in 1..=17 {
+            let data = leaves(n);
+            let tree = MerkleTree::new(&data).unwrap();
+            for (i, leaf) in data.iter().enumerate() {
+                assert!(tree.proof(i).unwrap().verify(tree.root(), leaf), "n={} i={}", n, i);
+            }
+        }
+    }
+
+    #[test]
+    fn test_inner_node_cannot_pose_as_leaf() {
+        let data = leaves(2);
+        let tree = MerkleTree::new(&data).unwrap();
+
+        // Presenting the children of the root as a single leaf must not verify
+        let mut inner = Vec::new();
+        inner.extend_from_slice(hash_leaf(&data[0]).as_bytes());
+        inner.extend_from_slice(hash_leaf(&data[1]).as_bytes());
+        assert_ne!(hash_leaf(&inner), tree.root());
+        assert_ne!(MerkleTree::new(&[inner]).unwrap().root(), tree.root());
+    }
+
+    #[test]
+    fn test_multiproof() {
+        let data = leaves(13);
+        let tree = MerkleTree::new(&data).unwrap();
+
+        for indices in [vec![0], vec![12], vec![0, 1], vec![2, 5, 6, 12], (0..13).collect()] {
+            let proof = tree.multiproof(&indices).unwrap();
+            let proven: Vec<&Vec<u8>> = proof.indices().iter().map(|&i| &data[i]).collect();
+            assert!(proof.verify(tree.root(), &proven), "{:?}", indices);
+
+            // Smaller than the individual proofs combined
+            let individual: usize = indices.iter().map(|&i| tree.proof(i).unwrap().proof_hashes().len()).sum();
+            assert!(proof.hashes().len() <= individual);
+        }
+
+        // Proving every leaf needs no extra hashes
+        assert!(tree.multiproof(&(0..13).collect::<Vec<_>>()).unwrap().hashes().is_empty());
+
+        let proof = tree.multiproof(&[3, 7]).unwrap();
+        assert!(!proof.verify(tree.root(), &[&data[3], &data[8]]));
+        assert!(!proof.verify(tree.root(), &[&data[3]]));
+        assert!(tree.multiproof(&[13]).is_err());
+        assert!(tree.multiproof(&[]).is_err());
+    }
+
+    #[test]
+    fn test_incremental_matches_full_tree() {
+        let mut incremental = IncrementalMerkleTree::new();
+        assert_eq!(incremental.root(), Hash::zero());
+
+        let data = leaves(40);
+        for (i, leaf) in data.iter().enumerate() {
+            incremental.append(leaf);
+            assert_eq!(incremental.leaf_count(), i as u64 + 1);
+            assert_eq!(incremental.root(), MerkleTree::new(&data[..=i]).unwrap().root(), "n={}", i + 1);
+        }
+    }
+}
@@ -0,0 +1,188 @@
+// blockchain-crypto/src/schnorr.rs
+
+//! BIP-340 Schnorr signatures over SECP256k1
+//!
+//! Public keys are 32-byte x-only keys (the curve point with even Y) and
+//! signatures are 64 bytes, as in Bitcoin Taproot. `KeyPair::sign` hashes the
+//! message with SHA-256 like the ECDSA scheme; bridges that already hold a
+//! 32-byte digest (e.g. a Bitcoin sighash) use [`sign_digest`] and
+//! [`verify_digest`] directly.
+
+use crate::{CryptoError, CryptoResult, KeyPair, PublicKey, SecretKey, Signature, SignatureScheme};
+use secp256k1::{schnorr, Keypair, Message, Secp256k1, XOnlyPublicKey};
+
+/// X-only public key size
+pub const PUBLIC_KEY_SIZE: usize = 32;
+
+/// Signature size
+pub const SIGNATURE_SIZE: usize = 64;
+
+pub(crate) fn sign(secret_key: &SecretKey, message: &[u8]) -> CryptoResult<Signature> {
+    use rand::RngCore;
+
+    let mut aux_rand = [0u8; 32];
+    rand::rngs::OsRng
+        .try_fill_bytes(&mut aux_rand)
+        .map_err(|_| CryptoError::RngError)?;
+    sign_with_aux(secret_key, &crate::hash::Hashable::hash(message).to_bytes(), &aux_rand)
+}
+
+pub(crate) fn verify(message: &[u8], public_key: &PublicKey, signature: &Signature) -> CryptoResult<bool> {
+    verify_digest(&crate::hash::Hashable::hash(message).to_bytes(), public_key, signature)
+}
+
+/// Sign a 32-byte digest with explicit auxiliary randomness
+pub fn sign_digest(keypair: &KeyPair, digest: &[u8; 32], aux_rand: &[u8; 32]) -> CryptoResult<Signature> {
+    if keypair.scheme() != SignatureScheme::SchnorrSecp256k1 {
+        return Err(CryptoError::InvalidSecretKey);
+    }
+    sign_with_aux(keypair.secret_key(), digest, aux_rand)
+}
+
+/// Verify a signature over a 32-byte digest
+pub fn verify_digest(digest: &[u8; 32], public_key: &PublicKey, signature: &Signature) -> CryptoResult<bool> {
+    if public_key.scheme() != SignatureScheme::SchnorrSecp256k1
+        || signature.scheme() != SignatureScheme::SchnorrSecp256k1
+    {
+        return Ok(false);
+    }
+
+    let key = XOnlyPublicKey::from_slice(public_key.as_bytes()).map_err(|_| CryptoError::InvalidPublicKey)?;
+    let sig = schnorr::Signature::from_slice(signature.as_bytes()).map_err(|_| CryptoError::InvalidSignature)?;
+
+    Ok(Secp256k1::verification_only()
+        .verify_schnorr(&sig, &Message::from_digest(*digest), &key)
+        .is_ok())
+}
+
+/// X-only public key for a secret key
+pub(crate) fn public_key_bytes(secret: &[u8]) -> CryptoResult<Vec<u8>> {
+    let (key, _parity) = to_keypair(secret)?.x_only_public_key();
+    Ok(key.serialize().to_vec())
+}
+
+/// Convert a compressed ECDSA public key to its x-only form
+pub fn x_only_from_secp256k1(public_key: &PublicKey) -> CryptoResult<PublicKey> {
+    if public_key.scheme() != SignatureScheme::Secp256k1 {
+        return Err(CryptoError::InvalidPublicKey);
+    }
+    let key = secp256k1::PublicKey::from_slice(public_key.as_bytes()).map_err(|_| CryptoError::InvalidPublicKey)?;
+    let (x_only, _parity) = key.x_only_public_key();
+    Ok(PublicKey::new(SignatureScheme::SchnorrSecp256k1, x_only.serialize().to_vec()))
+}
+
+fn sign_with_aux(secret_key: &SecretKey, digest: &[u8; 32], aux_rand: &[u8; 32]) -> CryptoResult<Signature> {
+    let keypair = to_keypair(secret_key.as_bytes())?;
+    let sig = Secp256k1::signing_only().sign_schnorr_with_aux_rand(&Message::from_digest(*digest), &keypair, aux_rand);
+    Ok(Signature::new(SignatureScheme::SchnorrSecp256k1, sig.as_ref().to_vec()))
+}
+
+fn to_keypair(secret: &[u8]) -> CryptoResult<Keypair> {
+    Keypair::from_seckey_slice(&Secp256k1::signing_only(), secret).map_err(|_| CryptoError::InvalidSecretKey)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bytes32(s: &str) -> [u8; 32] {
+        hex::decode(s).unwrap().try_into().unwrap()
+    }
+
+    // BIP-340 test vectors 0 to 3
+    #[test]
+    fn test_bip340_signing_vectors() {
+        let vectors = [
+            (
+                "0000000000000000000000000000000000000000000000000000000000000003",
+                "F9308A019258C31049344F85F89D5229B531C845836F99B08601F113BCE036F9",
+                "0000000000000000000000000000000000000000000000000000000000000000",
+                "0000000000000000000000000000000000000000000000000000000000000000",
+                "E907831F80848D1069A5371B402410364BDF1C5F8307B0084C55F1CE2DCA821525F66A4A85EA8B71E482A74F382D2CE5EBEEE8FDB2172F477DF4900D310536C0",
+            ),
+            (
+                "B7E151628AED2A6ABF7158809CF4F3C762E7160F38B4DA56A784D9045190CFEF",
+                "DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA659",
+                "0000000000000000000000000000000000000000000000000000000000000001",
+                "243F6A8885A308D313198A2E03707344A4093822299F31D0082EFA98EC4E6C89",
+                "6896BD60EEAE296DB48A229FF71DFE071BDE413E6D43F917DC8DCF8C78DE33418906D11AC976ABCCB20B091292BFF4EA897EFCB639EA871CFA95F6DE339E4B0A",
+            ),
+            (
+                "C90FDAA22168C234C4C6628B80DC1CD129024E088A67CC74020BBEA63B14E5C9",
+                "DD308AFEC5777E13121FA72B9CC1B7CC0139715309B086C960E18FD969774EB8",
+                "C87AA53824B4D7AE2EB035A2B5BBBCCC080E76CDC6D1692C4B0B62D798E6D906",
+                "7E2D58D8B3BCDF1ABADEC7829054F90DDA9805AAB56C77333024B9D0A508B75C",
+                "5831AAEED7B44BB74E5EAB94BA9D4294C49BCF2A60728D8B4C200F50DD313C1BAB745879A5AD954A72C45A91C3A51D3C7ADEA98D82F8481E0E1E03674A6F3FB7",
+            ),
+            (
+                "0B432B2677937381AEF05BB02A66ECD012773062CF3FA2549E44F58ED2401710",
+                "25D1DFF95105F5253C4022F628A996AD3A0D95FBF21D468A1B33F8C160D8F517",
+                "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF",
+                "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF",
+                "7EB0509757E246F19449885651611CB965ECC1A187DD51B64FDA1EDC9637D5EC97582B9CB13DB3933705B32BA982AF5AF25FD78881EBB32771FC5922EFC66EA3",
+            ),
+        ];
+
+        for (secret, public, aux, message, signature) in vectors {
+            let keypair =
+                KeyPair::from_secret_bytes(SignatureScheme::SchnorrSecp256k1, &hex::decode(secret).unwrap()).unwrap();
+            assert_eq!(keypair.public_key().to_hex(), public.to_lowercase());
+
+            let sig = sign_digest(&keypair, &bytes32(message), &bytes32(aux)).unwrap();
+            assert_eq!(sig.to_hex(), signature.to_lowercase());
+            assert!(verify_digest(&bytes32(message), keypair.public_key(), &sig).unwrap());
+        }
+    }
+
+    // BIP-340 test vectors 4 to 6
+    #[test]
+    fn test_bip340_verification_vectors() {
+        let verify = |public: &str, message: &str, signature: &str| {
+            let key = PublicKey::from_hex(SignatureScheme::SchnorrSecp256k1, public).unwrap();
+            let sig = Signature::from_hex(SignatureScheme::SchnorrSecp256k1, signature).unwrap();
+            verify_digest(&bytes32(message), &key, &sig)
+        };
+
+        assert!(verify(
+            "D69C3509BB99E412E68B0FE8544E72837DFA30746D8BE2AA65975F29D22DC7B9",
+            "4DF3C3F68FCC83B27E9D42C90431A72499F17875C81A599B566C9889B9696703",
+            "00000000000000000000003B78CE563F89A0ED9414F5AA28AD0D96D6795F9C6376AFB1548AF603B3EB45C9F8207DEE1060CB71C04E80F593060B07D28308D7F4",
+        )
+        .unwrap());
+
+        // Public key not on the curve
+        assert!(verify(
+            "EEFDEA4CDB677750A420FEE807EACF21EB9898AE79B9768766E4FAA04A2D4A34",
+            "243F6A8885A308D313198A2E03707344A4093822299F31D0082EFA98EC4E6C89",
+            "6CFF5C3BA86C69EA4B7376F31A9BCB4F74C1976089B2D9963DA2E5543E17776969E89B4C5564D00349106B8497785DD7D1D713A8AE82B32FA79D5F7FC407D39B",
+        )
+        .is_err());
+
+        // R has odd Y
+        assert!(!verify(
+            "DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA659",
+            "243F6A8885A308D313198A2E03707344A4093822299F31D0082EFA98EC4E6C89",
+            "FFF97BD5755EEEA420453A14355235D382F6472F8568A18B2F057A14602975563CC27944640AC607CD107AE10923D9EF7A73C643E166BE5EBEAFA34B1AC553E2",
+        )
+        .unwrap());
+    }
+
+    #[test]
+    fn test_keypair_roundtrip() {
+        let keypair = KeyPair::generate(SignatureScheme::SchnorrSecp256k1).unwrap();
+        assert_eq!(keypair.public_key().as_bytes().len(), PUBLIC_KEY_SIZE);
+
+        let sig = keypair.sign(b"bridge transfer").unwrap();
+        assert_eq!(sig.as_bytes().len(), SIGNATURE_SIZE);
+        assert!(keypair.public_key().verify(b"bridge transfer", &sig).unwrap());
+        assert!(!keypair.public_key().verify(b"other", &sig).unwrap());
+    }
+
+    #[test]
+    fn test_x_only_matches_ecdsa_key() {
+        let secret = [7u8; 32];
+        let ecdsa = KeyPair::from_secret_bytes(SignatureScheme::Secp256k1, &secret).unwrap();
+        let schnorr = KeyPair::from_secret_bytes(SignatureScheme::SchnorrSecp256k1, &secret).unwrap();
+        assert_eq!(&x_only_from_secp256k1(ecdsa.public_key()).unwrap(), schnorr.public_key());
+    }
+}
@@ -0,0 +1,241 @@
+// blockchain-crypto/src/shamir.rs
+
+//! Shamir secret sharing of secret keys
+//!
+//! Each byte of the key is shared with its own random polynomial over
+//! GF(2^8), so any `threshold` shares recover the key and fewer reveal
+//! nothing about it. Shares carry the key's public key; recovery rebuilds the
+//! key pair and rejects the result if it does not match, which catches
+//! corrupted, mixed-up or insufficient shares.
+
+use crate::{CryptoError, CryptoResult, KeyPair, PublicKey, SignatureScheme};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::fmt;
+use zeroize::Zeroize;
+
+/// One share of a split secret key
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SecretShare {
+    scheme: SignatureScheme,
+    threshold: u8,
+    /// Evaluation point, 1..=255
+    index: u8,
+    public_key: PublicKey,
+    value: Vec<u8>,
+}
+
+impl SecretShare {
+    pub fn scheme(&self) -> SignatureScheme {
+        self.scheme
+    }
+
+    pub fn threshold(&self) -> u8 {
+        self.threshold
+    }
+
+    pub fn index(&self) -> u8 {
+        self.index
+    }
+
+    /// Public key of the shared secret key
+    pub fn public_key(&self) -> &PublicKey {
+        &self.public_key
+    }
+}
+
+impl Drop for SecretShare {
+    fn drop(&mut self) {
+        self.value.zeroize();
+    }
+}
+
+impl fmt::Debug for SecretShare {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "SecretShare({:?}, {}/{}, [REDACTED])", self.scheme, self.index, self.threshold)
+    }
+}
+
+/// Split a key pair's secret key into `shares` shares, any `threshold` of
+/// which recover it
+pub fn split(keypair: &KeyPair, threshold: u8, shares: u8) -> CryptoResult<Vec<SecretShare>> {
+    if threshold == 0 || threshold > shares {
+        return Err(CryptoError::ThresholdError(format!(
+            "Invalid {}-of-{} split",
+            threshold, shares
+        )));
+    }
+
+    let secret = keypair.secret_key().as_bytes();
+    let mut values = vec![Vec::with_capacity(secret.len()); shares as usize];
+    let mut coefficients = vec![0u8; threshold as usize];
+
+    for &byte in secret {
+        coefficients[0] = byte;
+        rand::rngs::OsRng
+            .try_fill_bytes(&mut coefficients[1..])
+            .map_err(|_| CryptoError::RngError)?;
+        for (i, value) in values.iter_mut().enumerate() {
+            value.push(evaluate(&coefficients, i as u8 + 1));
+        }
+    }
+    coefficients.zeroize();
+
+    Ok(values
+        .into_iter()
+        .enumerate()
+        .map(|(i, value)| SecretShare {
+            scheme: keypair.scheme(),
+            threshold,
+            index: i as u8 + 1,
+            public_key: keypair.public_key().clone(),
+            value,
+        })
+        .collect())
+}
+
+/// Recover the key pair from at least `threshold` shares
+pub fn recover(shares: &[SecretShare]) -> CryptoResult<KeyPair> {
+    let first = shares
+        .first()
+        .ok_or_else(|| CryptoError::ThresholdError("No shares".into()))?;
+
+    let indices: BTreeSet<u8> = shares.iter().map(|share| share.index).collect();
+    if indices.len() != shares.len() || indices.contains(&0) {
+        return Err(CryptoError::ThresholdError("Duplicate or invalid share index".into()));
+    }
+    if shares.iter().any(|share| {
+        share.scheme != first.scheme
+            || share.threshold != first.threshold
+            || share.public_key != first.public_key
+            || share.value.len() != first.value.len()
+    }) {
+        return Err(CryptoError::ThresholdError("Shares belong to different keys".into()));
+    }
+    if shares.len() < first.threshold as usize {
+        return Err(CryptoError::ThresholdError(format!(
+            "Need {} shares, got {}",
+            first.threshold,
+            shares.len()
+        )));
+    }
+
+    let weights: Vec<u8> = shares
+        .iter()
+        .map(|share| {
+            shares
+                .iter()
+                .filter(|other| other.index != share.index)
+                .fold(1, |acc, other| {
+                    gf_mul(acc, gf_div(other.index, other.index ^ share.index))
+                })
+        })
+        .collect();
+
+    let mut secret: Vec<u8> = (0..first.value.len())
+        .map(|byte| {
+            shares
+                .iter()
+                .zip(&weights)
+                .fold(0, |acc, (share, &weight)| acc ^ gf_mul(share.value[byte], weight))
+        })
+        .collect();
+
+    let keypair = KeyPair::from_secret_bytes(first.scheme, &secret);
+    secret.zeroize();
+
+    match keypair {
+        Ok(keypair) if keypair.public_key() == &first.public_key => Ok(keypair),
+        _ => Err(CryptoError::ThresholdError(
+            "Recovered key does not match the shared public key".into(),
+        )),
+    }
+}
+
+fn evaluate(coefficients: &[u8], x: u8) -> u8 {
+    coefficients.iter().rev().fold(0, |acc, &c| gf_mul(acc, x) ^ c)
+}
+
+/// Multiplication in GF(2^8) with the AES polynomial
+fn gf_mul(mut a: u8, mut b: u8) -> u8 {
+    let mut product = 0;
+    for _ in 0..8 {
+        product ^= a & 0u8.wrapping_sub(b & 1);
+        let carry = 0u8.wrapping_sub(a >> 7);
+        a = (a << 1) ^ (0x1b & carry);
+        b >>= 1;
+    }
+    product
+}
+
+fn gf_div(a: u8, b: u8) -> u8 {
+    // b^254 = b^-1 for b != 0
+    let mut inverse = 1;
+    let mut power = b;
+    for bit in 0..8 {
+        if (254 >> bit) & 1 == 1 {
+            inverse = gf_mul(inverse, power);
+        }
+        power = gf_mul(power, power);
+    }
+    gf_mul(a, inverse)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_field_inverse() {
+        for a in 1..=255u8 {
+            assert_eq!(gf_mul(a, gf_div(1, a)), 1);
+        }
+    }
+
+    #[test]
+    fn test_split_and_recover_any_subset() {
+        for scheme in [SignatureScheme::Ed25519, SignatureScheme::Secp256k1] {
+            let keypair = KeyPair::generate(scheme).unwrap();
+            let shares = split(&keypair, 3, 5).unwrap();
+
+            for subset in [[0, 1, 2], [0, 2, 4], [4, 3, 1]] {
+                let chosen: Vec<SecretShare> = subset.iter().map(|&i| shares[i].clone()).collect();
+                let recovered = recover(&chosen).unwrap();
+                assert_eq!(recovered.public_key(), keypair.public_key());
+                assert_eq!(recovered.secret_key().as_bytes(), keypair.secret_key().as_bytes());
+            }
+
+            assert!(recover(&shares).is_ok());
+        }
+    }
+
+    #[test]
+    fn test_recover_rejects_bad_share_sets() {
+        let keypair = KeyPair::generate(SignatureScheme::Ed25519).unwrap();
+        let shares = split(&keypair, 3, 5).unwrap();
+
+        // Too few
+        assert!(recover(&shares[..2]).is_err());
+
+        // Duplicate index
+        assert!(recover(&[shares[0].clone(), shares[0].clone(), shares[1].clone()]).is_err());
+
+        // Corrupted share
+        let mut corrupted = shares[..3].to_vec();
+        corrupted[1].value[5] ^= 0x40;
+        assert!(recover(&corrupted).is_err());
+
+        // Shares of another key
+        let other = split(&KeyPair::generate(SignatureScheme::Ed25519).unwrap(), 3, 5).unwrap();
+        assert!(recover(&[shares[0].clone(), shares[1].clone(), other[2].clone()]).is_err());
+    }
+
+    #[test]
+    fn test_invalid_parameters() {
+        let keypair = KeyPair::generate(SignatureScheme::Ed25519).unwrap();
+        assert!(split(&keypair, 0, 3).is_err());
+        assert!(split(&keypair, 4, 3).is_err());
+        assert_eq!(split(&keypair, 1, 1).unwrap().len(), 1);
+    }
+}
@@ -10,6 +10,10 @@ pub enum SignatureScheme {
     Ed25519,
     /// SECP256k1 (Ethereum-compatible)
     Secp256k1,
+    /// BLS12-381 (aggregatable, public keys in G1)
+    Bls12381,
+    /// BIP-340 Schnorr over SECP256k1 (x-only public keys)
+    SchnorrSecp256k1,
 }
 
 /// Digital signature wrapper
@@ -65,6 +69,8 @@ impl Signature {
         match self.scheme {
             SignatureScheme::Ed25519 => self.verify_ed25519(message, public_key),
             SignatureScheme::Secp256k1 => self.verify_secp256k1(message, public_key),
+            SignatureScheme::Bls12381 => crate::bls::verify(message, public_key, self),
+            SignatureScheme::SchnorrSecp256k1 => crate::schnorr::verify(message, public_key, self),
         }
     }
 
@@ -73,17 +79,18 @@ impl Signature {
         message: &[u8],
         public_key: &crate::PublicKey,
     ) -> Result<bool, crate::CryptoError> {
-        use ed25519_dalek::{Signature as Ed25519Sig, Verifier, VerifyingKey};
+        use curve25519_dalek::edwards::CompressedEdwardsY;
 
-        let sig = Ed25519Sig::from_slice(&self.bytes)
-            .map_err(|_| crate::CryptoError::InvalidSignature)?;
-
-        let pk = VerifyingKey::from_bytes(
-            public_key.as_bytes().try_into()
-                .map_err(|_| crate::CryptoError::InvalidPublicKey)?
-        ).map_err(|_| crate::CryptoError::InvalidPublicKey)?;
+        if self.bytes.len() != 64 {
+            return Err(crate::CryptoError::InvalidSignature);
+        }
+        let key: [u8; 32] = public_key.as_bytes().try_into()
+            .map_err(|_| crate::CryptoError::InvalidPublicKey)?;
+        if CompressedEdwardsY(key).decompress().is_none() {
+            return Err(crate::CryptoError::InvalidPublicKey);
+        }
 
-        Ok(pk.verify(message, &sig).is_ok())
+        Ok(Ed25519Check::new(message, &key, &self.bytes).is_some_and(|check| check.holds()))
     }
 
     fn verify_secp256k1(
@@ -95,7 +102,12 @@ impl Signature {
 
         let secp = Secp256k1::verification_only();
         
-        let sig = Secp256k1Sig::from_compact(&self.bytes)
+        // Recoverable signatures carry a trailing `v` byte
+        let compact = match self.bytes.len() {
+            65 => &self.bytes[..64],
+            _ => &self.bytes[..],
+        };
+        let sig = Secp256k1Sig::from_compact(compact)
             .map_err(|_| crate::CryptoError::InvalidSignature)?;
 
         let pk = Secp256k1Pk::from_slice(public_key.as_bytes())
@@ -110,6 +122,184 @@ impl Signature {
     }
 }
 
+/// Recover the SECP256k1 public key from a recoverable signature
+///
+/// `message` is hashed as in `KeyPair::sign_recoverable`.
+pub fn recover_public_key(
+    message: &[u8],
+    signature: &Signature,
+) -> Result<crate::PublicKey, crate::CryptoError> {
+    let digest = crate::hash::Hashable::hash(message);
+    recover_public_key_from_digest(&digest.to_bytes(), signature)
+}
+
+/// Recover the SECP256k1 public key that signed a 32-byte digest (`ecrecover`)
+///
+/// Accepts `v` as 0/1 or 27/28.
+pub fn recover_public_key_from_digest(
+    digest: &[u8; 32],
+    signature: &Signature,
+) -> Result<crate::PublicKey, crate::CryptoError> {
+    use secp256k1::ecdsa::{RecoverableSignature, RecoveryId};
+    use secp256k1::{Message, Secp256k1};
+
+    if signature.scheme != SignatureScheme::Secp256k1 || signature.bytes.len() != 65 {
+        return Err(crate::CryptoError::InvalidSignature);
+    }
+
+    let v = signature.bytes[64];
+    let recovery_id = RecoveryId::from_i32(i32::from(if v >= 27 { v - 27 } else { v }))
+        .map_err(|_| crate::CryptoError::InvalidSignature)?;
+    let sig = RecoverableSignature::from_compact(&signature.bytes[..64], recovery_id)
+        .map_err(|_| crate::CryptoError::InvalidSignature)?;
+
+    let key = Secp256k1::verification_only()
+        .recover_ecdsa(&Message::from_digest(*digest), &sig)
+        .map_err(|_| crate::CryptoError::InvalidSignature)?;
+    Ok(crate::PublicKey::new(SignatureScheme::Secp256k1, key.serialize().to_vec()))
+}
+
+/// One signature check in a batch
+#[derive(Debug, Clone, Copy)]
+pub struct BatchItem<'a> {
+    pub message: &'a [u8],
+    pub public_key: &'a crate::PublicKey,
+    pub signature: &'a Signature,
+}
+
+impl<'a> BatchItem<'a> {
+    pub fn new(message: &'a [u8], public_key: &'a crate::PublicKey, signature: &'a Signature) -> Self {
+        Self { message, public_key, signature }
+    }
+
+    fn is_ed25519(&self) -> bool {
+        self.signature.scheme == SignatureScheme::Ed25519
+            && self.public_key.scheme() == SignatureScheme::Ed25519
+    }
+
+    /// Decoded Ed25519 check, or `None` for other schemes and malformed encodings
+    fn ed25519_check(&self) -> Option<Ed25519Check> {
+        if !self.is_ed25519() {
+            return None;
+        }
+        Ed25519Check::new(self.message, self.public_key.as_bytes(), self.signature.as_bytes())
+    }
+
+    fn verify(&self) -> bool {
+        matches!(self.signature.verify(self.message, self.public_key), Ok(true))
+    }
+}
+
+/// Ed25519 verification inputs decoded from their wire encoding
+///
+/// Single and batch verification both use the cofactored equation
+/// `[8]([s]B - [k]A - R) = 0`, so a signature is valid or invalid no matter
+/// which path checks it. Non-canonical encodings and small-order keys and
+/// nonces are rejected up front.
+struct Ed25519Check {
+    /// Nonce commitment R
+    r: curve25519_dalek::EdwardsPoint,
+    /// Public key A
+    a: curve25519_dalek::EdwardsPoint,
+    /// Response s
+    s: curve25519_dalek::Scalar,
+    /// Challenge k = H(R || A || M)
+    k: curve25519_dalek::Scalar,
+}
+
+impl Ed25519Check {
+    /// Decode a signature, or `None` if its encoding can never verify
+    fn new(message: &[u8], public_key: &[u8], signature: &[u8]) -> Option<Self> {
+        use curve25519_dalek::Scalar;
+        use sha2::{Digest, Sha512};
+
+        if signature.len() != 64 {
+            return None;
+        }
+        let (r_bytes, s_bytes) = signature.split_at(32);
+        let r = decode_ed25519_point(r_bytes)?;
+        let a = decode_ed25519_point(public_key)?;
+        let s = Option::from(Scalar::from_canonical_bytes(s_bytes.try_into().ok()?))?;
+
+        let hash: [u8; 64] = Sha512::new()
+            .chain_update(r_bytes)
+            .chain_update(public_key)
+            .chain_update(message)
+            .finalize()
+            .into();
+        Some(Self { r, a, s, k: Scalar::from_bytes_mod_order_wide(&hash) })
+    }
+
+    fn holds(&self) -> bool {
+        use curve25519_dalek::traits::IsIdentity;
+        use curve25519_dalek::EdwardsPoint;
+
+        (EdwardsPoint::vartime_double_scalar_mul_basepoint(&self.k, &-self.a, &self.s) - self.r)
+            .mul_by_cofactor()
+            .is_identity()
+    }
+}
+
+/// Decompress a canonically encoded point that is not of small order
+fn decode_ed25519_point(bytes: &[u8]) -> Option<curve25519_dalek::EdwardsPoint> {
+    use curve25519_dalek::edwards::CompressedEdwardsY;
+
+    let point = CompressedEdwardsY::from_slice(bytes).ok()?.decompress()?;
+    (point.compress().as_bytes() == bytes && !point.is_small_order()).then_some(point)
+}
+
+/// Verify many signatures at once
+///
+/// Ed25519 items are decoded in parallel and checked together with a single
+/// batched equation; the rest are verified in parallel on the rayon thread
+/// pool. Only if the batch fails are its items verified one by one to find
+/// the culprit. On failure the error carries the lowest failing index.
+pub fn verify_batch(items: &[BatchItem<'_>]) -> Result<(), crate::CryptoError> {
+    use rayon::prelude::*;
+
+    let checks: Vec<Option<Ed25519Check>> = items.par_iter().map(BatchItem::ed25519_check).collect();
+    let batch: Vec<&Ed25519Check> = checks.iter().flatten().collect();
+    let batch_ok = !batch.is_empty() && verify_ed25519_batch(&batch);
+
+    let failed = items
+        .par_iter()
+        .zip(checks.par_iter())
+        .enumerate()
+        .find_first(|(_, (item, check))| match check {
+            Some(check) => !batch_ok && !check.holds(),
+            None => !item.verify(),
+        })
+        .map(|(index, _)| index);
+
+    match failed {
+        Some(index) => Err(crate::CryptoError::BatchVerificationFailed { index }),
+        None => Ok(()),
+    }
+}
+
+/// Check `[8](sum z_i ([s_i]B - [k_i]A_i - R_i)) = 0` for random 128-bit `z_i`
+fn verify_ed25519_batch(checks: &[&Ed25519Check]) -> bool {
+    use curve25519_dalek::constants::ED25519_BASEPOINT_POINT;
+    use curve25519_dalek::traits::{IsIdentity, VartimeMultiscalarMul};
+    use curve25519_dalek::{EdwardsPoint, Scalar};
+    use rand::Rng;
+
+    let mut rng = rand::thread_rng();
+    let z: Vec<Scalar> = checks.iter().map(|_| Scalar::from(rng.gen::<u128>())).collect();
+
+    let basepoint_scalar: Scalar = checks.iter().zip(&z).map(|(check, z)| z * check.s).sum();
+    let scalars = std::iter::once(basepoint_scalar)
+        .chain(z.iter().map(|z| -z))
+        .chain(checks.iter().zip(&z).map(|(check, z)| -(z * check.k)));
+    let points = std::iter::once(ED25519_BASEPOINT_POINT)
+        .chain(checks.iter().map(|check| check.r))
+        .chain(checks.iter().map(|check| check.a));
+
+    EdwardsPoint::vartime_multiscalar_mul(scalars, points)
+        .mul_by_cofactor()
+        .is_identity()
+}
+
 impl fmt::Debug for Signature {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
@@ -155,6 +345,168 @@ mod tests {
         assert!(signature.verify(message, keypair.public_key()).unwrap());
     }
 
+    #[test]
+    fn test_signature_bls() {
+        let keypair = KeyPair::generate(SignatureScheme::Bls12381).unwrap();
+        let message = b"Test message";
+
+        let signature = keypair.sign(message).unwrap();
+        assert!(signature.verify(message, keypair.public_key()).unwrap());
+        assert!(!signature.verify(b"Wrong message", keypair.public_key()).unwrap());
+    }
+
+    #[test]
+    fn test_verify_batch() {
+        let keypairs: Vec<KeyPair> = [
+            SignatureScheme::Ed25519,
+            SignatureScheme::Secp256k1,
+            SignatureScheme::Ed25519,
+            SignatureScheme::Bls12381,
+            SignatureScheme::Secp256k1,
+            SignatureScheme::Ed25519,
+        ]
+        .into_iter()
+        .map(|scheme| KeyPair::generate(scheme).unwrap())
+        .collect();
+        let messages: Vec<Vec<u8>> = (0..keypairs.len() as u8).map(|i| vec![i; 32]).collect();
+        let mut signatures: Vec<Signature> = keypairs
+            .iter()
+            .zip(&messages)
+            .map(|(kp, msg)| kp.sign(msg).unwrap())
+            .collect();
+
+        let batch: Vec<BatchItem<'_>> = (0..keypairs.len())
+            .map(|i| BatchItem::new(&messages[i], keypairs[i].public_key(), &signatures[i]))
+            .collect();
+        assert!(verify_batch(&batch).is_ok());
+        assert!(verify_batch(&[]).is_ok());
+
+        // Corrupt one Ed25519 and one SECP256k1 signature; the lowest index is reported
+        signatures[4] = keypairs[4].sign(b"other").unwrap();
+        signatures[2] = keypairs[2].sign(b"other").unwrap();
+        let batch: Vec<BatchItem<'_>> = (0..keypairs.len())
+            .map(|i| BatchItem::new(&messages[i], keypairs[i].public_key(), &signatures[i]))
+            .collect();
+        assert!(matches!(
+            verify_batch(&batch),
+            Err(crate::CryptoError::BatchVerificationFailed { index: 2 })
+        ));
+
+        // Mismatched key for an otherwise valid signature
+        let batch = [BatchItem::new(&messages[0], keypairs[5].public_key(), &signatures[0])];
+        assert!(matches!(
+            verify_batch(&batch),
+            Err(crate::CryptoError::BatchVerificationFailed { index: 0 })
+        ));
+    }
+
+    #[test]
+    fn test_batch_agrees_with_single_on_torsion_keys() {
+        use curve25519_dalek::constants::{ED25519_BASEPOINT_POINT, EIGHT_TORSION};
+        use curve25519_dalek::scalar::Scalar;
+        use sha2::{Digest, Sha512};
+
+        // A key with a small-order component, signed so that the component
+        // survives in [s]B - [k]A - R: only the cofactored equation ignores it
+        let secret = Scalar::from_bytes_mod_order([7; 32]);
+        let nonce = Scalar::from_bytes_mod_order([9; 32]);
+        let key = (ED25519_BASEPOINT_POINT * secret + EIGHT_TORSION[1]).compress();
+        let r = (ED25519_BASEPOINT_POINT * nonce).compress();
+        let (message, challenge) = (0u8..)
+            .map(|i| {
+                let hash: [u8; 64] = Sha512::new()
+                    .chain_update(r.as_bytes())
+                    .chain_update(key.as_bytes())
+                    .chain_update([i])
+                    .finalize()
+                    .into();
+                ([i], Scalar::from_bytes_mod_order_wide(&hash))
+            })
+            .find(|(_, k)| k.as_bytes()[0] % 8 != 0)
+            .unwrap();
+        let s = nonce + challenge * secret;
+        let public_key = crate::PublicKey::new(SignatureScheme::Ed25519, key.as_bytes().to_vec());
+        let signature = Signature::new(
+            SignatureScheme::Ed25519,
+            [r.as_bytes().as_slice(), s.as_bytes()].concat(),
+        );
+
+        let honest = KeyPair::generate(SignatureScheme::Ed25519).unwrap();
+        let honest_signature = honest.sign(&message).unwrap();
+
+        // Both paths use the cofactored equation, so they agree on it
+        assert!(signature.verify(&message, &public_key).unwrap());
+        let batch = [
+            BatchItem::new(&message, honest.public_key(), &honest_signature),
+            BatchItem::new(&message, &public_key, &signature),
+        ];
+        assert!(verify_batch(&batch).is_ok());
+
+        // And on rejecting it for another message
+        assert!(!signature.verify(b"other", &public_key).unwrap());
+        let batch = [
+            BatchItem::new(&message, honest.public_key(), &honest_signature),
+            BatchItem::new(b"other", &public_key, &signature),
+        ];
+        assert!(matches!(
+            verify_batch(&batch),
+            Err(crate::CryptoError::BatchVerificationFailed { index: 1 })
+        ));
+
+        // Small-order keys are rejected by both
+        let weak_key = crate::PublicKey::new(SignatureScheme::Ed25519, EIGHT_TORSION[1].compress().as_bytes().to_vec());
+        let weak_signature = Signature::new(
+            SignatureScheme::Ed25519,
+            [EIGHT_TORSION[2].compress().as_bytes().as_slice(), Scalar::ZERO.as_bytes()].concat(),
+        );
+        assert!(!weak_signature.verify(&message, &weak_key).unwrap());
+        assert!(matches!(
+            verify_batch(&[BatchItem::new(&message, &weak_key, &weak_signature)]),
+            Err(crate::CryptoError::BatchVerificationFailed { index: 0 })
+        ));
+    }
+
+    #[test]
+    fn test_recoverable_signature() {
+        let keypair = KeyPair::generate(SignatureScheme::Secp256k1).unwrap();
+        let message = b"recover me";
+
+        let signature = keypair.sign_recoverable(message).unwrap();
+        assert_eq!(signature.as_bytes().len(), 65);
+        assert!(signature.verify(message, keypair.public_key()).unwrap());
+        assert_eq!(&recover_public_key(message, &signature).unwrap(), keypair.public_key());
+        assert_ne!(&recover_public_key(b"other", &signature).unwrap(), keypair.public_key());
+
+        // Compact signatures carry no recovery id
+        let compact = keypair.sign(message).unwrap();
+        assert!(recover_public_key(message, &compact).is_err());
+    }
+
+    #[test]
+    fn test_ethereum_signature_vector() {
+        // web3.js `accounts.sign("Some data", key)`
+        let secret = hex::decode("4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318").unwrap();
+        let keypair = KeyPair::from_secret_bytes(SignatureScheme::Secp256k1, &secret).unwrap();
+        let digest = crate::hash::eth_message_hash(b"Some data");
+        assert_eq!(
+            digest.to_hex(),
+            "1da44b586eb0729ff70a73c326926f6ed5a25f5b056e7f47fbc6e58d86871655"
+        );
+
+        let signature = keypair.sign_digest_recoverable(&digest.to_bytes()).unwrap();
+        assert_eq!(
+            signature.to_hex(),
+            "b91467e570a6466aa9e9876cbcd013baba02900b8979d43fe208a4a4f339f5fd\
+             6007e74cd82e037b800186422fc2da167c747ef045e5d18a5f5d4300f8e1a0291c"
+        );
+
+        let recovered = recover_public_key_from_digest(&digest.to_bytes(), &signature).unwrap();
+        assert_eq!(
+            recovered.to_address().to_hex(),
+            "0x2c7536E3605D9C16a7a3D7b1898e529396a65c23"
+        );
+    }
+
     #[test]
     fn test_signature_hex() {
         let keypair = KeyPair::generate(SignatureScheme::Ed25519).unwrap();
@@ -0,0 +1,447 @@
+// blockchain-crypto/src/smt.rs
+
+//! Sparse Merkle tree over 256-bit keys
+//!
+//! Every key has a fixed position among 2^256 leaves, so a proof can show
+//! that a key is absent as well as present. Empty subtrees hash to
+//! [`Hash::zero`] and are never stored, and a subtree holding a single leaf
+//! collapses into that leaf, so the tree only materializes the branches that
+//! distinguish its keys. The shape depends only on the key set, not on the
+//! order of updates.
+//!
+//! Values are committed by hash; the values themselves live wherever the
+//! caller keeps them. Nodes are content-addressed and kept in a [`SmtStore`],
+//! so old roots stay readable until the store is pruned.
+
+use crate::{
+    hash::Hashable,
+    merkle::{LEAF_PREFIX, NODE_PREFIX},
+    CryptoError, CryptoResult, Hash, HashAlgorithm,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+/// Depth of the tree (bits in a key)
+pub const SMT_DEPTH: usize = 256;
+
+/// A stored tree node
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SmtNode {
+    Leaf { key: Hash, value_hash: Hash },
+    Internal { left: Hash, right: Hash },
+}
+
+/// Backing store for tree nodes, keyed by node hash
+pub trait SmtStore {
+    fn get_node(&self, hash: &Hash) -> CryptoResult<Option<SmtNode>>;
+
+    /// Write the nodes created by one update
+    fn put_nodes(&mut self, nodes: Vec<(Hash, SmtNode)>) -> CryptoResult<()>;
+}
+
+/// In-memory node store
+#[derive(Debug, Clone, Default)]
+pub struct MemoryStore {
+    nodes: HashMap<Hash, SmtNode>,
+}
+
+impl MemoryStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+}
+
+impl SmtStore for MemoryStore {
+    fn get_node(&self, hash: &Hash) -> CryptoResult<Option<SmtNode>> {
+        Ok(self.nodes.get(hash).cloned())
+    }
+
+    fn put_nodes(&mut self, nodes: Vec<(Hash, SmtNode)>) -> CryptoResult<()> {
+        self.nodes.extend(nodes);
+        Ok(())
+    }
+}
+
+/// Sparse Merkle tree
+pub struct SparseMerkleTree<S: SmtStore> {
+    store: S,
+    root: Hash,
+    algorithm: HashAlgorithm,
+}
+
+impl<S: SmtStore> SparseMerkleTree<S> {
+    /// Create an empty tree
+    pub fn new(store: S, algorithm: HashAlgorithm) -> Self {
+        Self::with_root(store, Hash::zero(), algorithm)
+    }
+
+    /// Open an existing tree whose nodes are already in `store`
+    pub fn with_root(store: S, root: Hash, algorithm: HashAlgorithm) -> Self {
+        Self { store, root, algorithm }
+    }
+
+    pub fn root(&self) -> Hash {
+        self.root
+    }
+
+    pub fn algorithm(&self) -> HashAlgorithm {
+        self.algorithm
+    }
+
+    pub fn store(&self) -> &S {
+        &self.store
+    }
+
+    pub fn into_store(self) -> S {
+        self.store
+    }
+
+    /// Get the hash of the value stored under `key`
+    pub fn get(&self, key: &Hash) -> CryptoResult<Option<Hash>> {
+        let mut current = self.root;
+        for depth in 0..SMT_DEPTH {
+            match self.load(&current, &HashMap::new())? {
+                None => return Ok(None),
+                Some(SmtNode::Leaf { key: leaf_key, value_hash }) => {
+                    return Ok((leaf_key == *key).then_some(value_hash));
+                }
+                Some(SmtNode::Internal { left, right }) => {
+                    current = if bit(key, depth) { right } else { left };
+                }
+            }
+        }
+        Err(CryptoError::MerkleError("Tree deeper than key size".into()))
+    }
+
+    /// Set (`Some`) or remove (`None`) the value under `key`
+    pub fn update(&mut self, key: Hash, value: Option<&[u8]>) -> CryptoResult<Hash> {
+        self.update_batch(&[(key, value)])
+    }
+
+    /// Apply several updates at once, later entries winning for repeated keys
+    ///
+    /// Each touched branch is rebuilt once and all new nodes are written to
+    /// the store together.
+    pub fn update_batch<T: AsRef<[u8]>>(&mut self, updates: &[(Hash, Option<T>)]) -> CryptoResult<Hash> {
+        let updates: Vec<(Hash, Option<Hash>)> = updates
+            .iter()
+            .map(|(key, value)| (*key, value.as_ref().map(|v| v.as_ref().hash_with(self.algorithm))))
+            .collect::<BTreeMap<_, _>>()
+            .into_iter()
+            .collect();
+
+        let mut pending = HashMap::new();
+        let root = self.update_subtree(self.root, 0, &updates, &mut pending)?;
+        self.store.put_nodes(pending.into_iter().collect())?;
+        self.root = root;
+        Ok(root)
+    }
+
+    /// Build an inclusion or exclusion proof for `key`
+    pub fn prove(&self, key: &Hash) -> CryptoResult<SparseMerkleProof> {
+        let mut siblings = Vec::new();
+        let mut current = self.root;
+        for depth in 0..SMT_DEPTH {
+            match self.load(&current, &HashMap::new())? {
+                None => return Ok(SparseMerkleProof { leaf: None, siblings }),
+                Some(SmtNode::Leaf { key: leaf_key, value_hash }) => {
+                    return Ok(SparseMerkleProof { leaf: Some((leaf_key, value_hash)), siblings });
+                }
+                Some(SmtNode::Internal { left, right }) => {
+                    let (next, sibling) = if bit(key, depth) { (right, left) } else { (left, right) };
+                    siblings.push(sibling);
+                    current = next;
+                }
+            }
+        }
+        Err(CryptoError::MerkleError("Tree deeper than key size".into()))
+    }
+
+    fn update_subtree(
+        &self,
+        node: Hash,
+        depth: usize,
+        updates: &[(Hash, Option<Hash>)],
+        pending: &mut HashMap<Hash, SmtNode>,
+    ) -> CryptoResult<Hash> {
+        if updates.is_empty() {
+            return Ok(node);
+        }
+
+        match self.load(&node, pending)? {
+            Some(SmtNode::Internal { left, right }) => {
+                let split = updates.partition_point(|(key, _)| !bit(key, depth));
+                let left = self.update_subtree(left, depth + 1, &updates[..split], pending)?;
+                let right = self.update_subtree(right, depth + 1, &updates[split..], pending)?;
+                self.join(left, right, pending)
+            }
+            existing => {
+                let mut leaves: Vec<(Hash, Hash)> =
+                    updates.iter().filter_map(|(key, value)| value.map(|v| (*key, v))).collect();
+                if let Some(SmtNode::Leaf { key, value_hash }) = existing {
+                    if updates.binary_search_by(|(k, _)| k.cmp(&key)).is_err() {
+                        let at = leaves.partition_point(|(k, _)| *k < key);
+                        leaves.insert(at, (key, value_hash));
+                    }
+                }
+                self.build_subtree(depth, &leaves, pending)
+            }
+        }
+    }
+
+    /// Build a fresh subtree over sorted, distinct leaves
+    fn build_subtree(
+        &self,
+        depth: usize,
+        leaves: &[(Hash, Hash)],
+        pending: &mut HashMap<Hash, SmtNode>,
+    ) -> CryptoResult<Hash> {
+        match leaves {
+            [] => Ok(Hash::zero()),
+            [(key, value_hash)] => {
+                let hash = leaf_hash(self.algorithm, key, value_hash);
+                pending.insert(hash, SmtNode::Leaf { key: *key, value_hash: *value_hash });
+                Ok(hash)
+            }
+            _ if depth == SMT_DEPTH => Err(CryptoError::MerkleError("Duplicate key".into())),
+            _ => {
+                let split = leaves.partition_point(|(key, _)| !bit(key, depth));
+                let left = self.build_subtree(depth + 1, &leaves[..split], pending)?;
+                let right = self.build_subtree(depth + 1, &leaves[split..], pending)?;
+                self.join(left, right, pending)
+            }
+        }
+    }
+
+    /// Combine two children, collapsing empty and single-leaf subtrees
+    fn join(&self, left: Hash, right: Hash, pending: &mut HashMap<Hash, SmtNode>) -> CryptoResult<Hash> {
+        let zero = Hash::zero();
+        if left == zero && right == zero {
+            return Ok(zero);
+        }
+        if left == zero || right == zero {
+            let child = if left == zero { right } else { left };
+            if matches!(self.load(&child, pending)?, Some(SmtNode::Leaf { .. })) {
+                return Ok(child);
+            }
+        }
+
+        let hash = node_hash(self.algorithm, &left, &right);
+        pending.insert(hash, SmtNode::Internal { left, right });
+        Ok(hash)
+    }
+
+    fn load(&self, hash: &Hash, pending: &HashMap<Hash, SmtNode>) -> CryptoResult<Option<SmtNode>> {
+        if *hash == Hash::zero() {
+            return Ok(None);
+        }
+        if let Some(node) = pending.get(hash) {
+            return Ok(Some(node.clone()));
+        }
+        match self.store.get_node(hash)? {
+            Some(node) => Ok(Some(node)),
+            None => Err(CryptoError::MerkleError(format!("Missing node {}", hash))),
+        }
+    }
+}
+
+/// Proof that a key is or is not in a sparse Merkle tree
+///
+/// Holds the siblings from the root down to where the key's path ends, at
+/// either an empty subtree or a leaf. For an exclusion proof that leaf
+/// belongs to a different key sharing the path.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SparseMerkleProof {
+    /// Leaf at the end of the path as `(key, value_hash)`
+    pub leaf: Option<(Hash, Hash)>,
+    /// Sibling hashes, root first
+    pub siblings: Vec<Hash>,
+}
+
+impl SparseMerkleProof {
+    /// Check that `key` maps to `value` under `root`
+    pub fn verify_inclusion(&self, root: Hash, key: &Hash, value: &[u8], algorithm: HashAlgorithm) -> bool {
+        match self.leaf {
+            Some((leaf_key, value_hash)) => {
+                leaf_key == *key && value_hash == value.hash_with(algorithm) && self.fold(root, key, algorithm)
+            }
+            None => false,
+        }
+    }
+
+    /// Check that `key` has no value under `root`
+    pub fn verify_exclusion(&self, root: Hash, key: &Hash, algorithm: HashAlgorithm) -> bool {
+        match self.leaf {
+            Some((leaf_key, _)) => {
+                leaf_key != *key
+                    && (0..self.siblings.len()).all(|depth| bit(&leaf_key, depth) == bit(key, depth))
+                    && self.fold(root, key, algorithm)
+            }
+            None => self.fold(root, key, algorithm),
+        }
+    }
+
+    fn fold(&self, root: Hash, key: &Hash, algorithm: HashAlgorithm) -> bool {
+        if self.siblings.len() > SMT_DEPTH {
+            return false;
+        }
+
+        let mut current = match self.leaf {
+            Some((leaf_key, value_hash)) => leaf_hash(algorithm, &leaf_key, &value_hash),
+            None => Hash::zero(),
+        };
+        for (depth, sibling) in self.siblings.iter().enumerate().rev() {
+            current = if bit(key, depth) {
+                node_hash(algorithm, sibling, &current)
+            } else {
+                node_hash(algorithm, &current, sibling)
+            };
+        }
+        current == root
+    }
+}
+
+fn bit(key: &Hash, depth: usize) -> bool {
+    key.as_bytes()[depth / 8] & (0x80 >> (depth % 8)) != 0
+}
+
+fn leaf_hash(algorithm: HashAlgorithm, key: &Hash, value_hash: &Hash) -> Hash {
+    let mut bytes = Vec::with_capacity(65);
+    bytes.push(LEAF_PREFIX);
+    bytes.extend_from_slice(key.as_bytes());
+    bytes.extend_from_slice(value_hash.as_bytes());
+    bytes.hash_with(algorithm)
+}
+
+fn node_hash(algorithm: HashAlgorithm, left: &Hash, right: &Hash) -> Hash {
+    let mut bytes = Vec::with_capacity(65);
+    bytes.push(NODE_PREFIX);
+    bytes.extend_from_slice(left.as_bytes());
+    bytes.extend_from_slice(right.as_bytes());
+    bytes.hash_with(algorithm)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(n: u32) -> Hash {
+        n.to_be_bytes().hash()
+    }
+
+    fn tree() -> SparseMerkleTree<MemoryStore> {
+        SparseMerkleTree::new(MemoryStore::new(), HashAlgorithm::Blake3)
+    }
+
+    #[test]
+    fn test_empty_tree() {
+        let tree = tree();
+        assert_eq!(tree.root(), Hash::zero());
+        assert_eq!(tree.get(&key(1)).unwrap(), None);
+
+        let proof = tree.prove(&key(1)).unwrap();
+        assert!(proof.verify_exclusion(tree.root(), &key(1), HashAlgorithm::Blake3));
+    }
+
+    #[test]
+    fn test_inclusion_and_exclusion() {
+        let mut tree = tree();
+        for n in 0..50u32 {
+            tree.update(key(n), Some(&n.to_le_bytes())).unwrap();
+        }
+        let root = tree.root();
+
+        for n in 0..50u32 {
+            let value = n.to_le_bytes();
+            assert_eq!(tree.get(&key(n)).unwrap(), Some(value.hash_with(HashAlgorithm::Blake3)));
+
+            let proof = tree.prove(&key(n)).unwrap();
+            assert!(proof.verify_inclusion(root, &key(n), &value, HashAlgorithm::Blake3));
+            assert!(!proof.verify_inclusion(root, &key(n), b"wrong", HashAlgorithm::Blake3));
+            assert!(!proof.verify_exclusion(root, &key(n), HashAlgorithm::Blake3));
+        }
+
+        for n in 50..100u32 {
+            let proof = tree.prove(&key(n)).unwrap();
+            assert!(proof.verify_exclusion(root, &key(n), HashAlgorithm::Blake3));
+            assert!(!proof.verify_inclusion(root, &key(n), &n.to_le_bytes(), HashAlgorithm::Blake3));
+            assert!(!proof.verify_exclusion(Hash::zero(), &key(n), HashAlgorithm::Blake3));
+        }
+    }
+
+    #[test]
+    fn test_root_is_order_independent() {
+        let mut forward = tree();
+        let mut backward = tree();
+        for n in 0..30u32 {
+            forward.update(key(n), Some(b"v")).unwrap();
+            backward.update(key(29 - n), Some(b"v")).unwrap();
+        }
+        assert_eq!(forward.root(), backward.root());
+
+        let updates: Vec<(Hash, Option<&[u8]>)> = (0..30u32).map(|n| (key(n), Some(&b"v"[..]))).collect();
+        let mut batched = tree();
+        batched.update_batch(&updates).unwrap();
+        assert_eq!(batched.root(), forward.root());
+    }
+
+    #[test]
+    fn test_delete_restores_previous_root() {
+        let mut tree = tree();
+        for n in 0..10u32 {
+            tree.update(key(n), Some(b"v")).unwrap();
+        }
+        let before = tree.root();
+
+        tree.update(key(99), Some(b"extra")).unwrap();
+        assert_ne!(tree.root(), before);
+        tree.update(key(99), None).unwrap();
+        assert_eq!(tree.root(), before);
+
+        for n in 0..10u32 {
+            tree.update(key(n), None).unwrap();
+        }
+        assert_eq!(tree.root(), Hash::zero());
+    }
+
+    #[test]
+    fn test_batch_last_write_wins() {
+        let mut tree = tree();
+        tree.update_batch(&[(key(1), Some(b"a")), (key(2), Some(b"b")), (key(1), None)])
+            .unwrap();
+        assert_eq!(tree.get(&key(1)).unwrap(), None);
+        assert!(tree.get(&key(2)).unwrap().is_some());
+    }
+
+    #[test]
+    fn test_reopen_from_store() {
+        let mut tree = tree();
+        tree.update(key(7), Some(b"seven")).unwrap();
+        tree.update(key(8), Some(b"eight")).unwrap();
+        let root = tree.root();
+
+        let reopened = SparseMerkleTree::with_root(tree.into_store(), root, HashAlgorithm::Blake3);
+        assert_eq!(reopened.get(&key(7)).unwrap(), Some(b"seven".to_vec().hash_with(HashAlgorithm::Blake3)));
+    }
+
+    #[test]
+    fn test_hash_algorithm_changes_root() {
+        let mut blake = tree();
+        let mut sha = SparseMerkleTree::new(MemoryStore::new(), HashAlgorithm::Sha256);
+        blake.update(key(1), Some(b"v")).unwrap();
+        sha.update(key(1), Some(b"v")).unwrap();
+        assert_ne!(blake.root(), sha.root());
+
+        let proof = sha.prove(&key(1)).unwrap();
+        assert!(proof.verify_inclusion(sha.root(), &key(1), b"v", HashAlgorithm::Sha256));
+        assert!(!proof.verify_inclusion(sha.root(), &key(1), b"v", HashAlgorithm::Blake3));
+    }
+}
@@ -0,0 +1,269 @@
+// blockchain-crypto/src/vrf.rs
+
+//! Verifiable random function (RFC 9381 ECVRF-EDWARDS25519-SHA512-TAI)
+//!
+//! The holder of an Ed25519 key computes a pseudorandom output for any input
+//! together with a proof; anyone with the public key can check the proof and
+//! recompute the output, but nobody can predict it without the secret key or
+//! choose among several outputs for the same input.
+
+use crate::{CryptoError, CryptoResult, Hash, KeyPair, PublicKey, SignatureScheme};
+use curve25519_dalek::edwards::{CompressedEdwardsY, EdwardsPoint};
+use curve25519_dalek::scalar::{clamp_integer, Scalar};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha512};
+use std::fmt;
+
+/// ECVRF-EDWARDS25519-SHA512-TAI suite identifier
+const SUITE: u8 = 0x03;
+
+/// Proof size: Gamma (32) || c (16) || s (32)
+pub const PROOF_SIZE: usize = 80;
+
+/// Output size (SHA-512)
+pub const OUTPUT_SIZE: usize = 64;
+
+const CHALLENGE_SIZE: usize = 16;
+
+/// A VRF proof
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VrfProof(Vec<u8>);
+
+impl VrfProof {
+    pub fn from_bytes(bytes: &[u8]) -> CryptoResult<Self> {
+        if bytes.len() != PROOF_SIZE {
+            return Err(CryptoError::InvalidVrfProof);
+        }
+        Ok(Self(bytes.to_vec()))
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    /// Output committed to by this proof, without checking the proof
+    ///
+    /// Only meaningful once the proof has been verified; prefer [`verify`].
+    pub fn to_output(&self) -> CryptoResult<VrfOutput> {
+        let (gamma, _, _) = decode_proof(self)?;
+        Ok(proof_to_hash(&gamma))
+    }
+}
+
+impl fmt::Debug for VrfProof {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "VrfProof({})", hex::encode(&self.0))
+    }
+}
+
+/// A VRF output (`beta` in RFC 9381)
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct VrfOutput([u8; OUTPUT_SIZE]);
+
+impl VrfOutput {
+    pub fn as_bytes(&self) -> &[u8; OUTPUT_SIZE] {
+        &self.0
+    }
+
+    /// First 32 bytes as a hash, for use as a random seed
+    pub fn to_hash(&self) -> Hash {
+        Hash::from_slice(&self.0[..32]).expect("32 bytes")
+    }
+}
+
+impl fmt::Debug for VrfOutput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "VrfOutput({})", hex::encode(self.0))
+    }
+}
+
+/// Evaluate the VRF on `alpha`, returning the output and its proof
+pub fn prove(keypair: &KeyPair, alpha: &[u8]) -> CryptoResult<(VrfOutput, VrfProof)> {
+    if keypair.scheme() != SignatureScheme::Ed25519 {
+        return Err(CryptoError::InvalidSecretKey);
+    }
+    let secret: [u8; 32] = keypair
+        .secret_key()
+        .as_bytes()
+        .try_into()
+        .map_err(|_| CryptoError::InvalidSecretKey)?;
+
+    let expanded: [u8; 64] = Sha512::digest(secret).into();
+    let mut scalar_bytes = [0u8; 32];
+    scalar_bytes.copy_from_slice(&expanded[..32]);
+    let x = Scalar::from_bytes_mod_order(clamp_integer(scalar_bytes));
+
+    let public_key = keypair.public_key().as_bytes();
+    let h = encode_to_curve(public_key, alpha)?;
+    let h_string = h.compress().to_bytes();
+    let gamma = x * h;
+
+    let nonce: [u8; 64] = Sha512::new()
+        .chain_update(&expanded[32..])
+        .chain_update(h_string)
+        .finalize()
+        .into();
+    let k = Scalar::from_bytes_mod_order_wide(&nonce);
+
+    let c = challenge(public_key, &h, &gamma, &EdwardsPoint::mul_base(&k), &(k * h));
+    let s = k + challenge_scalar(&c) * x;
+
+    let mut proof = Vec::with_capacity(PROOF_SIZE);
+    proof.extend_from_slice(gamma.compress().as_bytes());
+    proof.extend_from_slice(&c);
+    proof.extend_from_slice(s.as_bytes());
+
+    Ok((proof_to_hash(&gamma), VrfProof(proof)))
+}
+
+/// Verify a proof for `alpha` and return the output it commits to
+pub fn verify(public_key: &PublicKey, alpha: &[u8], proof: &VrfProof) -> CryptoResult<VrfOutput> {
+    if public_key.scheme() != SignatureScheme::Ed25519 {
+        return Err(CryptoError::InvalidPublicKey);
+    }
+    let y = decode_point(public_key.as_bytes()).ok_or(CryptoError::InvalidPublicKey)?;
+    if y.is_small_order() {
+        return Err(CryptoError::InvalidPublicKey);
+    }
+
+    let (gamma, c, s) = decode_proof(proof)?;
+    let h = encode_to_curve(public_key.as_bytes(), alpha)?;
+
+    let c_scalar = challenge_scalar(&c);
+    let u = EdwardsPoint::vartime_double_scalar_mul_basepoint(&-c_scalar, &y, &s);
+    let v = s * h - c_scalar * gamma;
+
+    if challenge(public_key.as_bytes(), &h, &gamma, &u, &v) != c {
+        return Err(CryptoError::InvalidVrfProof);
+    }
+    Ok(proof_to_hash(&gamma))
+}
+
+/// Try-and-increment hash to curve (RFC 9381 section 5.4.1.1)
+fn encode_to_curve(public_key: &[u8], alpha: &[u8]) -> CryptoResult<EdwardsPoint> {
+    for ctr in 0..=u8::MAX {
+        let hash = Sha512::new()
+            .chain_update([SUITE, 0x01])
+            .chain_update(public_key)
+            .chain_update(alpha)
+            .chain_update([ctr, 0x00])
+            .finalize();
+        if let Some(point) = decode_point(&hash[..32]) {
+            return Ok(point.mul_by_cofactor());
+        }
+    }
+    Err(CryptoError::InvalidVrfProof)
+}
+
+fn challenge(
+    public_key: &[u8],
+    h: &EdwardsPoint,
+    gamma: &EdwardsPoint,
+    u: &EdwardsPoint,
+    v: &EdwardsPoint,
+) -> [u8; CHALLENGE_SIZE] {
+    let mut hasher = Sha512::new().chain_update([SUITE, 0x02]).chain_update(public_key);
+    for point in [h, gamma, u, v] {
+        hasher.update(point.compress().as_bytes());
+    }
+    let hash = hasher.chain_update([0x00]).finalize();
+
+    let mut c = [0u8; CHALLENGE_SIZE];
+    c.copy_from_slice(&hash[..CHALLENGE_SIZE]);
+    c
+}
+
+fn challenge_scalar(c: &[u8; CHALLENGE_SIZE]) -> Scalar {
+    let mut bytes = [0u8; 32];
+    bytes[..CHALLENGE_SIZE].copy_from_slice(c);
+    Scalar::from_bytes_mod_order(bytes)
+}
+
+fn proof_to_hash(gamma: &EdwardsPoint) -> VrfOutput {
+    let hash = Sha512::new()
+        .chain_update([SUITE, 0x03])
+        .chain_update(gamma.mul_by_cofactor().compress().as_bytes())
+        .chain_update([0x00])
+        .finalize();
+    VrfOutput(hash.into())
+}
+
+fn decode_proof(proof: &VrfProof) -> CryptoResult<(EdwardsPoint, [u8; CHALLENGE_SIZE], Scalar)> {
+    let bytes = proof.as_bytes();
+    if bytes.len() != PROOF_SIZE {
+        return Err(CryptoError::InvalidVrfProof);
+    }
+
+    let gamma = decode_point(&bytes[..32]).ok_or(CryptoError::InvalidVrfProof)?;
+    let mut c = [0u8; CHALLENGE_SIZE];
+    c.copy_from_slice(&bytes[32..48]);
+    let s_bytes: [u8; 32] = bytes[48..].try_into().expect("32 bytes");
+    let s = Option::<Scalar>::from(Scalar::from_canonical_bytes(s_bytes)).ok_or(CryptoError::InvalidVrfProof)?;
+
+    Ok((gamma, c, s))
+}
+
+fn decode_point(bytes: &[u8]) -> Option<EdwardsPoint> {
+    CompressedEdwardsY::from_slice(bytes).ok()?.decompress()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vector(secret: &str, alpha: &str, proof: &str, output: &str) {
+        let keypair = KeyPair::from_secret_bytes(SignatureScheme::Ed25519, &hex::decode(secret).unwrap()).unwrap();
+        let alpha = hex::decode(alpha).unwrap();
+
+        let (beta, pi) = prove(&keypair, &alpha).unwrap();
+        assert_eq!(hex::encode(pi.as_bytes()), proof);
+        assert_eq!(hex::encode(beta.as_bytes()), output);
+        assert_eq!(verify(keypair.public_key(), &alpha, &pi).unwrap(), beta);
+        assert_eq!(pi.to_output().unwrap(), beta);
+    }
+
+    // RFC 9381 appendix B.3, example 16
+    #[test]
+    fn test_rfc9381_vector() {
+        vector(
+            "9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60",
+            "",
+            "8657106690b5526245a92b003bb079ccd1a92130477671f6fc01ad16f26f723f26f8a57ccaed74ee1b190bed1f479d9727d2d0f9b005a6e456a35d4fb0daab1268a1b0db10836d9826a528ca76567805",
+            "90cf1df3b703cce59e2a35b925d411164068269d7b2d29f3301c03dd757876ff66b71dda49d2de59d03450451af026798e8f81cd2e333de5cdf4f3e140fdd8ae",
+        );
+    }
+
+    #[test]
+    fn test_rejects_wrong_input_key_or_proof() {
+        let keypair = KeyPair::generate(SignatureScheme::Ed25519).unwrap();
+        let other = KeyPair::generate(SignatureScheme::Ed25519).unwrap();
+        let (_, proof) = prove(&keypair, b"slot 42").unwrap();
+
+        assert!(verify(keypair.public_key(), b"slot 42", &proof).is_ok());
+        assert!(verify(keypair.public_key(), b"slot 43", &proof).is_err());
+        assert!(verify(other.public_key(), b"slot 42", &proof).is_err());
+
+        let mut tampered = proof.as_bytes().to_vec();
+        tampered[40] ^= 1;
+        let tampered = VrfProof::from_bytes(&tampered).unwrap();
+        assert!(verify(keypair.public_key(), b"slot 42", &tampered).is_err());
+
+        assert!(VrfProof::from_bytes(&[0u8; 79]).is_err());
+    }
+
+    #[test]
+    fn test_deterministic() {
+        let keypair = KeyPair::generate(SignatureScheme::Ed25519).unwrap();
+        let (first, _) = prove(&keypair, b"epoch 7").unwrap();
+        let (second, _) = prove(&keypair, b"epoch 7").unwrap();
+        let (other, _) = prove(&keypair, b"epoch 8").unwrap();
+        assert_eq!(first, second);
+        assert_ne!(first, other);
+    }
+
+    #[test]
+    fn test_requires_ed25519() {
+        let keypair = KeyPair::generate(SignatureScheme::Secp256k1).unwrap();
+        assert!(prove(&keypair, b"x").is_err());
+    }
+}
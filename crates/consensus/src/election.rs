@@ -0,0 +1,410 @@
+// consensus/src/election.rs
+
+//! Stake-weighted private leader election
+//!
+//! Every validator evaluates a VRF over the epoch randomness and the slot.
+//! A validator is eligible to propose when its output falls below a
+//! threshold proportional to its selection weight, so only the validator
+//! itself knows it leads a slot until it publishes the proof in the block
+//! header. Verified outputs are mixed into the randomness of the next epoch
+//! (RANDAO-style), so the schedule cannot be computed ahead of time.
+//!
+//! Slots for which the elected proposer stays silent, or nobody is elected,
+//! fall to a public ranking of fallback proposers drawn from the same
+//! randomness by `ValidatorSelector::select_committee`.
+
+use crate::{
+    selection::ValidatorSelector,
+    validator::ValidatorInfo,
+    ConsensusError, ConsensusResult,
+};
+use blockchain_core::{block::ProposerElection, config, Block};
+use blockchain_crypto::{vrf, Address, Hash, Hasher, KeyPair, VrfOutput};
+use num_bigint::BigUint;
+use serde::{Deserialize, Serialize};
+
+/// Domain separator for election VRF inputs
+const ELECTION_DOMAIN: &[u8] = b"POAS_LEADER_ELECTION_V1";
+
+/// Domain separator for randomness mixing
+const RANDOMNESS_DOMAIN: &[u8] = b"POAS_EPOCH_RANDOMNESS_V1";
+
+/// Domain separator for fallback proposer seeds
+const FALLBACK_DOMAIN: &[u8] = b"POAS_FALLBACK_PROPOSERS_V1";
+
+/// VRF input for a slot: domain, epoch randomness and slot
+pub fn election_input(randomness: &Hash, slot: u64) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(ELECTION_DOMAIN.len() + 32 + 8);
+    bytes.extend_from_slice(ELECTION_DOMAIN);
+    bytes.extend_from_slice(randomness.as_bytes());
+    bytes.extend_from_slice(&slot.to_le_bytes());
+    bytes
+}
+
+/// Whether a VRF output wins a slot for the given share of the total weight
+///
+/// The first eight bytes of the output are read as a fraction of 2^64 and
+/// must be below `weight / total_weight`, so the expected number of eligible
+/// proposers per slot is one. Both sides of the comparison can exceed 128
+/// bits for large stakes, so it is done in arbitrary precision.
+pub fn is_eligible(output: &VrfOutput, weight: u128, total_weight: u128) -> bool {
+    if weight == 0 || total_weight == 0 {
+        return false;
+    }
+    let mut draw = [0u8; 8];
+    draw.copy_from_slice(&output.as_bytes()[..8]);
+    let draw = BigUint::from(u64::from_be_bytes(draw));
+
+    draw * BigUint::from(total_weight) < BigUint::from(weight) << 64u32
+}
+
+/// Randomness of the current epoch and the accumulator for the next one
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EpochRandomness {
+    epoch: u64,
+    current: Hash,
+    next: Hash,
+}
+
+impl EpochRandomness {
+    /// Start at epoch 0 from a genesis seed
+    pub fn new(seed: Hash) -> Self {
+        Self {
+            epoch: 0,
+            current: seed,
+            next: seed,
+        }
+    }
+
+    /// Epoch whose randomness is current
+    pub fn epoch(&self) -> u64 {
+        self.epoch
+    }
+
+    /// Randomness used for elections in the current epoch
+    pub fn current(&self) -> &Hash {
+        &self.current
+    }
+
+    /// Mix a verified VRF output into the next epoch's randomness
+    pub fn mix(&mut self, output: &VrfOutput) {
+        let mut hasher = config::hash_algorithm().hasher();
+        hasher.update(RANDOMNESS_DOMAIN);
+        hasher.update(self.next.as_bytes());
+        hasher.update(output.as_bytes());
+        self.next = hasher.finalize();
+    }
+
+    /// Move to the next epoch, making the accumulated randomness current
+    pub fn advance(&mut self) {
+        self.epoch += 1;
+        self.current = self.next;
+    }
+}
+
+/// Leader election over the active validator set
+#[derive(Debug, Clone)]
+pub struct LeaderElection {
+    randomness: EpochRandomness,
+}
+
+impl LeaderElection {
+    /// Create an election seeded with the genesis randomness
+    pub fn new(seed: Hash) -> Self {
+        Self {
+            randomness: EpochRandomness::new(seed),
+        }
+    }
+
+    /// Current epoch randomness
+    pub fn randomness(&self) -> &EpochRandomness {
+        &self.randomness
+    }
+
+    /// Evaluate the VRF for a slot, returning the proof if the key is eligible
+    pub fn claim(
+        &self,
+        keypair: &KeyPair,
+        slot: u64,
+        validators: &[&ValidatorInfo],
+    ) -> ConsensusResult<Option<ProposerElection>> {
+        let address = keypair.public_key().to_address();
+        let (weight, total_weight) = Self::weights(&address, validators)?;
+
+        let input = election_input(self.randomness.current(), slot);
+        let (output, proof) = vrf::prove(keypair, &input)
+            .map_err(|e| ConsensusError::SelectionError(e.to_string()))?;
+
+        if !is_eligible(&output, weight, total_weight) {
+            return Ok(None);
+        }
+        Ok(Some(ProposerElection {
+            public_key: keypair.public_key().clone(),
+            proof,
+        }))
+    }
+
+    /// Verify that a block's proposer was elected for `slot`, returning its VRF output
+    pub fn verify(
+        &self,
+        block: &Block,
+        slot: u64,
+        validators: &[&ValidatorInfo],
+    ) -> ConsensusResult<VrfOutput> {
+        let proposer = block.header.proposer;
+        let election = block.header.election.as_ref().ok_or_else(|| {
+            ConsensusError::ValidationError("Block carries no election proof".into())
+        })?;
+        if election.public_key.to_address() != proposer {
+            return Err(ConsensusError::ValidationError(
+                "Election key does not match proposer".into()
+            ));
+        }
+        let (weight, total_weight) = Self::weights(&proposer, validators)?;
+
+        let input = election_input(self.randomness.current(), slot);
+        let output = vrf::verify(&election.public_key, &input, &election.proof)
+            .map_err(|e| ConsensusError::ValidationError(e.to_string()))?;
+
+        if !is_eligible(&output, weight, total_weight) {
+            return Err(ConsensusError::ValidationError(format!(
+                "Proposer {} not elected for slot {}",
+                proposer.to_hex(),
+                slot
+            )));
+        }
+        Ok(output)
+    }
+
+    /// Fallback proposers for a slot, highest precedence first
+    ///
+    /// Unlike the VRF election the ranking is public, so every validator
+    /// knows who may take over a slot once the proposer timeouts expire.
+    pub fn fallbacks(
+        &self,
+        slot: u64,
+        validators: &[&ValidatorInfo],
+        count: usize,
+    ) -> ConsensusResult<Vec<Address>> {
+        let mut hasher = config::hash_algorithm().hasher();
+        hasher.update(FALLBACK_DOMAIN);
+        hasher.update(self.randomness.current().as_bytes());
+        hasher.update(&slot.to_le_bytes());
+        let mut seed = [0u8; 8];
+        seed.copy_from_slice(&hasher.finalize().as_bytes()[..8]);
+
+        ValidatorSelector::new(u64::from_le_bytes(seed)).select_committee(validators, count)
+    }
+
+    /// Mix a verified proposer output into the next epoch's randomness
+    pub fn record(&mut self, output: &VrfOutput) {
+        self.randomness.mix(output);
+    }
+
+    /// Switch to the randomness accumulated over the finished epoch
+    pub fn advance_epoch(&mut self) {
+        self.randomness.advance();
+    }
+
+    /// Selection weight of `address` and the total over `validators`
+    fn weights(address: &Address, validators: &[&ValidatorInfo]) -> ConsensusResult<(u128, u128)> {
+        let weights = ValidatorSelector::weights(validators);
+        let weight = weights.iter()
+            .find(|w| w.address == *address)
+            .map(|w| w.weight)
+            .ok_or_else(|| ConsensusError::ValidatorNotFound(address.to_hex()))?;
+        let total_weight = weights.iter().fold(0u128, |total, w| total.saturating_add(w.weight));
+        Ok((weight, total_weight))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use blockchain_core::StakeAmount;
+    use blockchain_crypto::SignatureScheme;
+
+    fn validator(keypair: &KeyPair, stake: u64) -> ValidatorInfo {
+        ValidatorInfo::new(keypair.public_key().clone(), StakeAmount::from_u64(stake), 100)
+    }
+
+    fn elected_block(election: ProposerElection) -> Block {
+        let mut block = Block::new(
+            1,
+            Hash::zero(),
+            Hash::zero(),
+            election.public_key.to_address(),
+            vec![],
+            10_000_000,
+        ).unwrap();
+        block.header.election = Some(election);
+        block
+    }
+
+    #[test]
+    fn test_eligibility_threshold() {
+        let keypair = KeyPair::generate(SignatureScheme::Ed25519).unwrap();
+        let (output, _) = vrf::prove(&keypair, b"input").unwrap();
+
+        assert!(is_eligible(&output, 10, 10));
+        assert!(!is_eligible(&output, 0, 10));
+        assert!(!is_eligible(&output, 10, 0));
+
+        // Weights beyond u64 neither overflow nor make everyone eligible
+        assert!(is_eligible(&output, u128::MAX, u128::MAX));
+        assert!(!is_eligible(&output, 1, u128::MAX));
+    }
+
+    #[test]
+    fn test_sole_validator_always_elected() {
+        let keypair = KeyPair::generate(SignatureScheme::Ed25519).unwrap();
+        let info = validator(&keypair, 100_000);
+        let election = LeaderElection::new(Hash::zero());
+
+        for slot in 0..5 {
+            let claim = election.claim(&keypair, slot, &[&info]).unwrap().unwrap();
+            let block = elected_block(claim);
+            assert!(election.verify(&block, slot, &[&info]).is_ok());
+            // The proof is bound to its slot
+            assert!(election.verify(&block, slot + 1, &[&info]).is_err());
+        }
+    }
+
+    #[test]
+    fn test_election_follows_weight() {
+        let keys: Vec<KeyPair> = (0..4)
+            .map(|_| KeyPair::generate(SignatureScheme::Ed25519).unwrap())
+            .collect();
+        let infos = [
+            validator(&keys[0], 700_000),
+            validator(&keys[1], 100_000),
+            validator(&keys[2], 100_000),
+            validator(&keys[3], 100_000),
+        ];
+        let refs: Vec<&ValidatorInfo> = infos.iter().collect();
+        let election = LeaderElection::new(Hash::new([7u8; 32]));
+
+        let mut wins = [0u32; 4];
+        for slot in 0..400 {
+            for (i, key) in keys.iter().enumerate() {
+                if let Some(claim) = election.claim(key, slot, &refs).unwrap() {
+                    assert!(election.verify(&elected_block(claim), slot, &refs).is_ok());
+                    wins[i] += 1;
+                }
+            }
+        }
+
+        // Expected 280 wins against 40 for each smaller validator
+        assert!(wins[0] > 200 && wins[0] < 360, "{:?}", wins);
+        for small in &wins[1..] {
+            assert!(*small < 100, "{:?}", wins);
+        }
+    }
+
+    #[test]
+    fn test_election_follows_token_stakes() {
+        let keys: Vec<KeyPair> = (0..4)
+            .map(|_| KeyPair::generate(SignatureScheme::Ed25519).unwrap())
+            .collect();
+        let stake = StakeAmount::from_tokens;
+        let infos = [
+            ValidatorInfo::new(keys[0].public_key().clone(), stake(70_000), 100),
+            ValidatorInfo::new(keys[1].public_key().clone(), stake(10_000), 100),
+            ValidatorInfo::new(keys[2].public_key().clone(), stake(10_000), 100),
+            ValidatorInfo::new(keys[3].public_key().clone(), stake(10_000), 100),
+        ];
+        let refs: Vec<&ValidatorInfo> = infos.iter().collect();
+        let election = LeaderElection::new(Hash::new([5u8; 32]));
+
+        let mut wins = [0u32; 4];
+        for slot in 0..400 {
+            for (i, key) in keys.iter().enumerate() {
+                if election.claim(key, slot, &refs).unwrap().is_some() {
+                    wins[i] += 1;
+                }
+            }
+        }
+
+        // Stakes of 10^22 base units still elect about one proposer per slot, by weight
+        let total: u32 = wins.iter().sum();
+        assert!(total > 300 && total < 500, "{:?}", wins);
+        assert!(wins[0] > 200 && wins[0] < 360, "{:?}", wins);
+        for small in &wins[1..] {
+            assert!(*small < 100, "{:?}", wins);
+        }
+    }
+
+    #[test]
+    fn test_forged_claims_rejected() {
+        let keys: Vec<KeyPair> = (0..2)
+            .map(|_| KeyPair::generate(SignatureScheme::Ed25519).unwrap())
+            .collect();
+        let infos = [validator(&keys[0], 100_000), validator(&keys[1], 100_000)];
+        let refs: Vec<&ValidatorInfo> = infos.iter().collect();
+        let election = LeaderElection::new(Hash::zero());
+
+        let slot = (0..).find(|slot| election.claim(&keys[0], *slot, &refs).unwrap().is_some()).unwrap();
+        let claim = election.claim(&keys[0], slot, &refs).unwrap().unwrap();
+
+        // Another validator cannot reuse the proof as proposer
+        let mut stolen = elected_block(claim.clone());
+        stolen.header.proposer = keys[1].public_key().to_address();
+        assert!(election.verify(&stolen, slot, &refs).is_err());
+
+        // Nor can a proof made with different randomness be replayed
+        let mut other = LeaderElection::new(Hash::zero());
+        other.record(&vrf::prove(&keys[1], b"mix").unwrap().0);
+        other.advance_epoch();
+        let block = elected_block(claim);
+        assert!(election.verify(&block, slot, &refs).is_ok());
+        assert!(other.verify(&block, slot, &refs).is_err());
+
+        // Blocks without a proof are rejected
+        let mut bare = block.clone();
+        bare.header.election = None;
+        assert!(election.verify(&bare, slot, &refs).is_err());
+    }
+
+    #[test]
+    fn test_fallback_ranking() {
+        let keys: Vec<KeyPair> = (0..5)
+            .map(|_| KeyPair::generate(SignatureScheme::Ed25519).unwrap())
+            .collect();
+        let infos: Vec<ValidatorInfo> = keys.iter().map(|key| validator(key, 100_000)).collect();
+        let refs: Vec<&ValidatorInfo> = infos.iter().collect();
+        let election = LeaderElection::new(Hash::new([3u8; 32]));
+
+        // Every node derives the same distinct ranking for a slot
+        let ranking = election.fallbacks(9, &refs, 3).unwrap();
+        assert_eq!(ranking.len(), 3);
+        assert_eq!(ranking, election.fallbacks(9, &refs, 3).unwrap());
+        assert!(ranking.iter().all(|a| ranking.iter().filter(|b| *b == a).count() == 1));
+
+        // The ranking changes from slot to slot
+        assert!((10..20).any(|slot| election.fallbacks(slot, &refs, 3).unwrap() != ranking));
+
+        // Never more fallbacks than validators
+        assert_eq!(election.fallbacks(9, &refs, 10).unwrap().len(), 5);
+    }
+
+    #[test]
+    fn test_randomness_mixing() {
+        let keypair = KeyPair::generate(SignatureScheme::Ed25519).unwrap();
+        let (output, _) = vrf::prove(&keypair, b"input").unwrap();
+
+        let mut randomness = EpochRandomness::new(Hash::zero());
+        randomness.mix(&output);
+        // Mixing only affects the next epoch
+        assert_eq!(randomness.current(), &Hash::zero());
+
+        randomness.advance();
+        assert_eq!(randomness.epoch(), 1);
+        assert_ne!(randomness.current(), &Hash::zero());
+
+        // Deterministic for the same outputs
+        let mut replay = EpochRandomness::new(Hash::zero());
+        replay.mix(&output);
+        replay.advance();
+        assert_eq!(replay, randomness);
+    }
+}
@@ -0,0 +1,222 @@
+// consensus/src/evidence.rs
+
+//! Pool of double-sign evidence awaiting inclusion
+//!
+//! Evidence arrives from the local finality gadget, from reorgs or from peers.
+//! It is verified and de-duplicated by offense id before it is held, queued
+//! for gossip, and dropped once a block includes a `SubmitEvidence`
+//! transaction for the same offense.
+//!
+//! Consensus and the network service share one pool through
+//! [`SharedEvidencePool`].
+
+use crate::ConsensusResult;
+use blockchain_core::{transaction::TransactionType, BlockNumber, DoubleSignEvidence, Transaction};
+use blockchain_crypto::{Hash, KeyPair};
+use std::collections::{BTreeMap, HashSet};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+/// Evidence pool shared between consensus and the network service
+pub type SharedEvidencePool = Arc<Mutex<EvidencePool>>;
+
+/// Lock a shared evidence pool; the pool stays consistent even if a holder panicked
+pub fn lock_pool(pool: &SharedEvidencePool) -> MutexGuard<'_, EvidencePool> {
+    pool.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Build a signed transaction reporting an offense, paying `gas_price` per unit of gas
+pub fn submit_evidence_transaction(
+    evidence: &DoubleSignEvidence,
+    reporter: &KeyPair,
+    nonce: u64,
+    gas_price: u64,
+) -> ConsensusResult<Transaction> {
+    let mut tx = Transaction::new(
+        reporter.public_key().to_address(),
+        nonce,
+        TransactionType::SubmitEvidence { evidence: Box::new(evidence.clone()) },
+        gas_price,
+        0,
+    );
+    tx.gas_limit = tx.intrinsic_gas();
+    tx.sign(reporter)?;
+    Ok(tx)
+}
+
+/// Verified evidence not yet included on chain
+#[derive(Debug, Clone, Default)]
+pub struct EvidencePool {
+    /// Pending evidence by (height, id), oldest offense first
+    pending: BTreeMap<(BlockNumber, Hash), DoubleSignEvidence>,
+    /// Ids of offenses pending or already included
+    known: HashSet<Hash>,
+    /// Keys of pending evidence not yet gossiped to peers
+    unannounced: Vec<(BlockNumber, Hash)>,
+}
+
+impl EvidencePool {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Verify and add evidence, returning whether it was new
+    ///
+    /// New evidence is queued for gossip until taken by [`Self::take_unannounced`]
+    /// or marked with [`Self::mark_announced`].
+    pub fn add(&mut self, evidence: DoubleSignEvidence) -> ConsensusResult<bool> {
+        let id = evidence.id();
+        if self.known.contains(&id) {
+            return Ok(false);
+        }
+        evidence.verify()?;
+
+        tracing::warn!(
+            "Double-sign evidence against {} at height {}",
+            evidence.offender().to_hex(),
+            evidence.height()
+        );
+        self.known.insert(id);
+        self.unannounced.push((evidence.height(), id));
+        self.pending.insert((evidence.height(), id), evidence);
+        Ok(true)
+    }
+
+    /// Take pending evidence that has not been gossiped yet
+    pub fn take_unannounced(&mut self) -> Vec<DoubleSignEvidence> {
+        let keys = std::mem::take(&mut self.unannounced);
+        keys.iter().filter_map(|key| self.pending.get(key).cloned()).collect()
+    }
+
+    /// Record that evidence was gossiped, e.g. forwarded on receipt from a peer
+    pub fn mark_announced(&mut self, id: &Hash) {
+        self.unannounced.retain(|(_, pending)| pending != id);
+    }
+
+    /// Whether an offense is pending or already included
+    pub fn contains(&self, id: &Hash) -> bool {
+        self.known.contains(id)
+    }
+
+    /// Up to `max` pending pieces of evidence, oldest offense first
+    pub fn pending(&self, max: usize) -> Vec<DoubleSignEvidence> {
+        self.pending.values().take(max).cloned().collect()
+    }
+
+    /// Number of pending pieces of evidence
+    pub fn len(&self) -> usize {
+        self.pending.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+
+    /// Drop evidence for offenses submitted by the given transactions
+    pub fn remove_included(&mut self, txs: &[Transaction]) {
+        for tx in txs {
+            if let TransactionType::SubmitEvidence { evidence } = &tx.tx_type {
+                let id = evidence.id();
+                self.known.insert(id);
+                self.pending.remove(&(evidence.height(), id));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::finality::{Vote, VoteType};
+    use blockchain_crypto::{KeyPair, SignatureScheme};
+
+    fn evidence(keypair: &KeyPair, height: BlockNumber) -> DoubleSignEvidence {
+        let vote = |hash| Box::new(Vote::sign(keypair, VoteType::Precommit, height, Hash::new(hash)).unwrap().to_signed());
+        DoubleSignEvidence::ConflictingVotes {
+            first: vote([1u8; 32]),
+            second: vote([2u8; 32]),
+        }
+    }
+
+    #[test]
+    fn test_add_and_deduplicate() {
+        let keypair = KeyPair::generate(SignatureScheme::Ed25519).unwrap();
+        let mut pool = EvidencePool::new();
+
+        assert!(pool.add(evidence(&keypair, 7)).unwrap());
+        assert!(!pool.add(evidence(&keypair, 7)).unwrap());
+        assert!(pool.add(evidence(&keypair, 3)).unwrap());
+        assert_eq!(pool.len(), 2);
+
+        // Oldest offense first
+        let pending = pool.pending(10);
+        assert_eq!(pending[0].height(), 3);
+        assert_eq!(pending[1].height(), 7);
+
+        // Forged evidence is rejected
+        let mut forged = evidence(&keypair, 9);
+        if let DoubleSignEvidence::ConflictingVotes { second, .. } = &mut forged {
+            second.block_hash = Hash::new([3u8; 32]);
+        }
+        assert!(pool.add(forged).is_err());
+        assert_eq!(pool.len(), 2);
+    }
+
+    #[test]
+    fn test_remove_included() {
+        let keypair = KeyPair::generate(SignatureScheme::Ed25519).unwrap();
+        let reporter = KeyPair::generate(SignatureScheme::Ed25519).unwrap();
+        let mut pool = EvidencePool::new();
+        let included = evidence(&keypair, 4);
+        pool.add(included.clone()).unwrap();
+
+        let mut tx = Transaction::new(
+            reporter.public_key().to_address(),
+            0,
+            TransactionType::SubmitEvidence { evidence: Box::new(included.clone()) },
+            1,
+            100_000,
+        );
+        tx.sign(&reporter).unwrap();
+        pool.remove_included(&[tx]);
+
+        assert!(pool.is_empty());
+        assert!(pool.contains(&included.id()));
+        // Evidence for an included offense is not pooled again
+        assert!(!pool.add(included).unwrap());
+        assert!(pool.take_unannounced().is_empty());
+    }
+
+    #[test]
+    fn test_unannounced_evidence_gossiped_once() {
+        let keypair = KeyPair::generate(SignatureScheme::Ed25519).unwrap();
+        let mut pool = EvidencePool::new();
+        let local = evidence(&keypair, 5);
+        let forwarded = evidence(&keypair, 6);
+        pool.add(local.clone()).unwrap();
+        pool.add(forwarded.clone()).unwrap();
+        pool.mark_announced(&forwarded.id());
+
+        let announced = pool.take_unannounced();
+        assert_eq!(announced.len(), 1);
+        assert_eq!(announced[0].id(), local.id());
+        assert!(pool.take_unannounced().is_empty());
+        assert_eq!(pool.len(), 2);
+    }
+
+    #[test]
+    fn test_submit_evidence_transaction() {
+        let keypair = KeyPair::generate(SignatureScheme::Ed25519).unwrap();
+        let reporter = KeyPair::generate(SignatureScheme::Ed25519).unwrap();
+        let offense = evidence(&keypair, 4);
+
+        let tx = submit_evidence_transaction(&offense, &reporter, 3, 2).unwrap();
+        assert!(tx.validate_basic().is_ok());
+        assert_eq!(tx.nonce, 3);
+        assert_eq!(tx.gas_limit, tx.intrinsic_gas());
+
+        let mut pool = EvidencePool::new();
+        pool.add(offense.clone()).unwrap();
+        pool.remove_included(&[tx]);
+        assert!(pool.is_empty());
+    }
+}
@@ -0,0 +1,562 @@
+// consensus/src/finality.rs
+
+//! BFT finality gadget
+//!
+//! Validators sign prevotes and precommits for block hashes. Votes carrying
+//! more than two thirds of the active stake form a quorum certificate: a
+//! prevote certificate justifies the block, a precommit certificate
+//! finalizes it. Each block carries the precommit certificate of its parent
+//! in `Block::validator_signatures`, so finality travels with the chain and
+//! any node can re-check it against the validator set.
+
+use crate::{validator::ValidatorSet, ConsensusError, ConsensusResult};
+use blockchain_core::{
+    block::ValidatorSignature,
+    clock::{Clock, SystemClock},
+    evidence::{DoubleSignEvidence, SignedVote},
+    Block, BlockNumber, StakeAmount, Timestamp,
+};
+use blockchain_crypto::{Address, Hash, KeyPair, PublicKey, Signature};
+use serde::{Deserialize, Serialize};
+use std::collections::{hash_map::Entry, BTreeMap, HashMap, HashSet};
+
+pub use blockchain_core::block::{vote_signing_bytes as signing_bytes, VoteType};
+
+/// A validator's signed vote for a block
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Vote {
+    pub vote_type: VoteType,
+    pub height: BlockNumber,
+    pub block_hash: Hash,
+    pub validator: Address,
+    /// Signer's public key, which must hash to `validator`
+    pub public_key: PublicKey,
+    pub signature: Signature,
+    /// When the vote was cast (not signed)
+    pub timestamp: Timestamp,
+}
+
+impl Vote {
+    /// Sign a vote
+    pub fn sign(
+        keypair: &KeyPair,
+        vote_type: VoteType,
+        height: BlockNumber,
+        block_hash: Hash,
+    ) -> ConsensusResult<Self> {
+        Self::sign_with_clock(keypair, vote_type, height, block_hash, &SystemClock)
+    }
+
+    /// Sign a vote timestamped by the given clock
+    pub fn sign_with_clock(
+        keypair: &KeyPair,
+        vote_type: VoteType,
+        height: BlockNumber,
+        block_hash: Hash,
+        clock: &dyn Clock,
+    ) -> ConsensusResult<Self> {
+        let signature = keypair.sign(&signing_bytes(vote_type, height, &block_hash))?;
+        Ok(Self {
+            vote_type,
+            height,
+            block_hash,
+            validator: keypair.public_key().to_address(),
+            public_key: keypair.public_key().clone(),
+            signature,
+            timestamp: clock.now(),
+        })
+    }
+
+    /// Rebuild a vote from a signature carried in a certificate
+    pub fn from_validator_signature(
+        vote_type: VoteType,
+        height: BlockNumber,
+        block_hash: Hash,
+        signature: &ValidatorSignature,
+    ) -> Self {
+        Self {
+            vote_type,
+            height,
+            block_hash,
+            validator: signature.validator,
+            public_key: signature.public_key.clone(),
+            signature: signature.signature.clone(),
+            timestamp: signature.timestamp,
+        }
+    }
+
+    /// Signature in the form carried by blocks
+    pub fn to_validator_signature(&self) -> ValidatorSignature {
+        ValidatorSignature {
+            validator: self.validator,
+            public_key: self.public_key.clone(),
+            signature: self.signature.clone(),
+            timestamp: self.timestamp,
+        }
+    }
+
+    /// The vote in the form carried by double-sign evidence
+    pub fn to_signed(&self) -> SignedVote {
+        SignedVote {
+            vote_type: self.vote_type,
+            height: self.height,
+            block_hash: self.block_hash,
+            public_key: self.public_key.clone(),
+            signature: self.signature.clone(),
+        }
+    }
+
+    /// Check the signature and that the signer is an active validator,
+    /// returning its voting power
+    pub fn verify(&self, validators: &ValidatorSet) -> ConsensusResult<StakeAmount> {
+        if self.public_key.to_address() != self.validator {
+            return Err(ConsensusError::FinalityError(format!(
+                "Public key does not match validator {}",
+                self.validator.to_hex()
+            )));
+        }
+
+        let validator = validators
+            .get(&self.validator)
+            .filter(|v| v.is_active())
+            .ok_or_else(|| ConsensusError::FinalityError(format!(
+                "Vote from inactive or unknown validator {}",
+                self.validator.to_hex()
+            )))?;
+
+        let message = signing_bytes(self.vote_type, self.height, &self.block_hash);
+        if !self.public_key.verify(&message, &self.signature)? {
+            return Err(ConsensusError::FinalityError(format!(
+                "Invalid vote signature from {}",
+                self.validator.to_hex()
+            )));
+        }
+
+        Ok(validator.stake.clone())
+    }
+}
+
+/// Whether `signed` is more than two thirds of `total`
+pub fn has_quorum(signed: &StakeAmount, total: &StakeAmount) -> bool {
+    !total.is_zero() && signed.inner() * 3u32 > total.inner() * 2u32
+}
+
+/// Votes of the same type for the same block from validators holding more
+/// than two thirds of the active stake
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuorumCertificate {
+    pub vote_type: VoteType,
+    pub height: BlockNumber,
+    pub block_hash: Hash,
+    pub signatures: Vec<ValidatorSignature>,
+}
+
+impl QuorumCertificate {
+    /// Precommit certificate for the parent carried by a block, if any
+    pub fn for_parent(block: &Block) -> Option<Self> {
+        if block.is_genesis() || block.validator_signatures.is_empty() {
+            return None;
+        }
+        Some(Self {
+            vote_type: VoteType::Precommit,
+            height: block.number() - 1,
+            block_hash: block.header.parent_hash,
+            signatures: block.validator_signatures.clone(),
+        })
+    }
+
+    /// Votes making up the certificate
+    pub fn votes(&self) -> impl Iterator<Item = Vote> + '_ {
+        self.signatures.iter().map(|signature| {
+            Vote::from_validator_signature(self.vote_type, self.height, self.block_hash, signature)
+        })
+    }
+
+    /// Verify every signature and the quorum, returning the signed stake
+    pub fn verify(&self, validators: &ValidatorSet) -> ConsensusResult<StakeAmount> {
+        let mut signers = HashSet::new();
+        let mut signed = StakeAmount::zero();
+
+        for vote in self.votes() {
+            if !signers.insert(vote.validator) {
+                return Err(ConsensusError::FinalityError(format!(
+                    "Duplicate signature from {}",
+                    vote.validator.to_hex()
+                )));
+            }
+            let stake = vote.verify(validators)?;
+            signed = signed.checked_add(&stake).unwrap_or(signed);
+        }
+
+        if !has_quorum(&signed, &validators.active_stake()) {
+            return Err(ConsensusError::FinalityError(format!(
+                "Certificate for block {} lacks a two-thirds quorum",
+                self.height
+            )));
+        }
+
+        Ok(signed)
+    }
+}
+
+/// A justified or finalized block
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Checkpoint {
+    pub height: BlockNumber,
+    pub block_hash: Hash,
+}
+
+/// Collects votes into certificates and tracks justified and finalized
+/// checkpoints
+///
+/// Checkpoints only ever move forward and only on the strength of a verified
+/// certificate.
+#[derive(Debug, Clone, Default)]
+pub struct FinalityGadget {
+    /// Pending votes by (type, height, block), keyed by validator
+    votes: HashMap<(VoteType, BlockNumber, Hash), BTreeMap<Address, Vote>>,
+    /// Vote each validator cast at each (type, height)
+    cast: HashMap<(VoteType, BlockNumber, Address), Vote>,
+    /// Certificates by (type, block)
+    certificates: HashMap<(VoteType, Hash), QuorumCertificate>,
+    justified: Option<Checkpoint>,
+    finalized: Option<Checkpoint>,
+}
+
+impl FinalityGadget {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Highest justified checkpoint
+    pub fn justified(&self) -> Option<Checkpoint> {
+        self.justified
+    }
+
+    /// Highest finalized checkpoint
+    pub fn finalized(&self) -> Option<Checkpoint> {
+        self.finalized
+    }
+
+    /// Whether the block at `height` on the finalized chain is final
+    pub fn is_finalized(&self, height: BlockNumber) -> bool {
+        self.finalized.is_some_and(|f| height <= f.height)
+    }
+
+    /// Certificate collected or received for a block
+    pub fn certificate(&self, vote_type: VoteType, block_hash: &Hash) -> Option<&QuorumCertificate> {
+        self.certificates.get(&(vote_type, *block_hash))
+    }
+
+    /// Add a vote, returning the certificate if it completes a quorum
+    ///
+    /// Votes below the finalized height are ignored. A vote arriving after
+    /// its block's certificate formed is added to that certificate, so late
+    /// signers stay on record. Precommits only form a certificate once the
+    /// block has a prevote certificate. A validator voting for two different
+    /// blocks of the same type and height is rejected with
+    /// `ConsensusError::DoubleSign` carrying both votes.
+    pub fn add_vote(&mut self, vote: Vote, validators: &ValidatorSet) -> ConsensusResult<Option<QuorumCertificate>> {
+        if self.finalized.is_some_and(|f| vote.height < f.height) {
+            return Ok(None);
+        }
+        vote.verify(validators)?;
+
+        match self.cast.get(&(vote.vote_type, vote.height, vote.validator)) {
+            Some(cast) if cast.block_hash == vote.block_hash => return Ok(None),
+            Some(cast) => {
+                return Err(ConsensusError::DoubleSign(Box::new(DoubleSignEvidence::ConflictingVotes {
+                    first: Box::new(cast.to_signed()),
+                    second: Box::new(vote.to_signed()),
+                })))
+            }
+            None => {}
+        }
+        self.cast.insert((vote.vote_type, vote.height, vote.validator), vote.clone());
+
+        let (vote_type, height, block_hash) = (vote.vote_type, vote.height, vote.block_hash);
+        if let Some(certificate) = self.certificates.get_mut(&(vote_type, block_hash)) {
+            if !certificate.signatures.iter().any(|s| s.validator == vote.validator) {
+                certificate.signatures.push(vote.to_validator_signature());
+            }
+            return Ok(None);
+        }
+        self.votes
+            .entry((vote_type, height, block_hash))
+            .or_default()
+            .insert(vote.validator, vote);
+
+        let certificate = self.try_certify(vote_type, height, block_hash, validators)?;
+        if vote_type == VoteType::Prevote && certificate.is_some() {
+            // Precommits may have reached quorum while waiting for the prevotes
+            if let Some(precommits) = self.try_certify(VoteType::Precommit, height, block_hash, validators)? {
+                return Ok(Some(precommits));
+            }
+        }
+        Ok(certificate)
+    }
+
+    /// Form a certificate from the pending votes for a block if they reach quorum
+    fn try_certify(
+        &mut self,
+        vote_type: VoteType,
+        height: BlockNumber,
+        block_hash: Hash,
+        validators: &ValidatorSet,
+    ) -> ConsensusResult<Option<QuorumCertificate>> {
+        if vote_type == VoteType::Precommit && !self.certificates.contains_key(&(VoteType::Prevote, block_hash)) {
+            return Ok(None);
+        }
+        let key = (vote_type, height, block_hash);
+        let Some(votes) = self.votes.get(&key) else {
+            return Ok(None);
+        };
+
+        let signed = votes
+            .keys()
+            .filter_map(|address| validators.get(address))
+            .fold(StakeAmount::zero(), |acc, v| acc.checked_add(&v.stake).unwrap_or(acc));
+        if !has_quorum(&signed, &validators.active_stake()) {
+            return Ok(None);
+        }
+
+        let certificate = QuorumCertificate {
+            vote_type,
+            height,
+            block_hash,
+            signatures: votes.values().map(Vote::to_validator_signature).collect(),
+        };
+        self.votes.remove(&key);
+        self.apply(certificate.clone())?;
+        Ok(Some(certificate))
+    }
+
+    /// Verify a certificate and advance the checkpoints it supports
+    ///
+    /// A precommit certificate is accepted without the matching prevote
+    /// certificate: honest validators only precommit after seeing one, so a
+    /// precommit quorum vouches for it.
+    pub fn process_certificate(&mut self, certificate: &QuorumCertificate, validators: &ValidatorSet) -> ConsensusResult<()> {
+        certificate.verify(validators)?;
+        self.apply(certificate.clone())
+    }
+
+    fn apply(&mut self, certificate: QuorumCertificate) -> ConsensusResult<()> {
+        let checkpoint = Checkpoint {
+            height: certificate.height,
+            block_hash: certificate.block_hash,
+        };
+
+        if certificate.vote_type == VoteType::Precommit {
+            if let Some(finalized) = self.finalized {
+                if finalized.height == checkpoint.height && finalized.block_hash != checkpoint.block_hash {
+                    return Err(ConsensusError::FinalityError(format!(
+                        "Conflicting finalized blocks at height {}",
+                        checkpoint.height
+                    )));
+                }
+            }
+            if self.finalized.is_none_or(|f| checkpoint.height > f.height) {
+                self.finalized = Some(checkpoint);
+                self.prune(checkpoint.height);
+            }
+        }
+
+        // A finalized block is also justified
+        if self.justified.is_none_or(|j| checkpoint.height > j.height) {
+            self.justified = Some(checkpoint);
+        }
+
+        // Merge with signatures already collected for the same block
+        match self.certificates.entry((certificate.vote_type, certificate.block_hash)) {
+            Entry::Occupied(mut known) => {
+                let known = known.get_mut();
+                for signature in certificate.signatures {
+                    if !known.signatures.iter().any(|s| s.validator == signature.validator) {
+                        known.signatures.push(signature);
+                    }
+                }
+            }
+            Entry::Vacant(slot) => {
+                slot.insert(certificate);
+            }
+        }
+        Ok(())
+    }
+
+    /// Drop votes and certificates below the finalized height
+    ///
+    /// Votes at the finalized height are kept so late votes are still
+    /// recognized as duplicates or equivocations.
+    fn prune(&mut self, finalized: BlockNumber) {
+        self.votes.retain(|(_, height, _), _| *height >= finalized);
+        self.cast.retain(|(_, height, _), _| *height >= finalized);
+        self.certificates.retain(|_, certificate| certificate.height >= finalized);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use blockchain_crypto::SignatureScheme;
+
+    fn setup(count: usize) -> (ValidatorSet, Vec<KeyPair>) {
+        let mut validators = ValidatorSet::new(StakeAmount::from_u64(1000), 3600);
+        let keys: Vec<KeyPair> = (0..count)
+            .map(|_| KeyPair::generate(SignatureScheme::Ed25519).unwrap())
+            .collect();
+        for key in &keys {
+            validators
+                .register(key.public_key().clone(), StakeAmount::from_u64(10_000), 100)
+                .unwrap();
+        }
+        (validators, keys)
+    }
+
+    #[test]
+    fn test_quorum_threshold() {
+        let total = StakeAmount::from_u64(300);
+        assert!(!has_quorum(&StakeAmount::from_u64(200), &total));
+        assert!(has_quorum(&StakeAmount::from_u64(201), &total));
+        assert!(!has_quorum(&StakeAmount::zero(), &StakeAmount::zero()));
+    }
+
+    #[test]
+    fn test_votes_form_certificates() {
+        let (validators, keys) = setup(4);
+        let mut gadget = FinalityGadget::new();
+        let block = Hash::new([7u8; 32]);
+
+        for key in &keys[..2] {
+            let vote = Vote::sign(key, VoteType::Prevote, 5, block).unwrap();
+            assert!(gadget.add_vote(vote, &validators).unwrap().is_none());
+        }
+        let vote = Vote::sign(&keys[2], VoteType::Prevote, 5, block).unwrap();
+        let prevotes = gadget.add_vote(vote, &validators).unwrap().unwrap();
+        assert_eq!(prevotes.signatures.len(), 3);
+        assert_eq!(gadget.justified(), Some(Checkpoint { height: 5, block_hash: block }));
+        assert!(!gadget.is_finalized(5));
+
+        let mut precommits = None;
+        for key in &keys[1..] {
+            let vote = Vote::sign(key, VoteType::Precommit, 5, block).unwrap();
+            precommits = gadget.add_vote(vote, &validators).unwrap();
+        }
+        let precommits = precommits.unwrap();
+        assert!(gadget.is_finalized(5));
+        assert!(precommits.verify(&validators).is_ok());
+
+        // A late precommit joins the certificate instead of being dropped
+        let late = Vote::sign(&keys[0], VoteType::Precommit, 5, block).unwrap();
+        assert!(gadget.add_vote(late.clone(), &validators).unwrap().is_none());
+        let certificate = gadget.certificate(VoteType::Precommit, &block).unwrap();
+        assert_eq!(certificate.signatures.len(), 4);
+        assert!(certificate.verify(&validators).is_ok());
+
+        // Repeating it changes nothing
+        assert!(gadget.add_vote(late, &validators).unwrap().is_none());
+        assert_eq!(gadget.certificate(VoteType::Precommit, &block).unwrap().signatures.len(), 4);
+    }
+
+    #[test]
+    fn test_precommits_wait_for_prevote_certificate() {
+        let (validators, keys) = setup(4);
+        let mut gadget = FinalityGadget::new();
+        let block = Hash::new([7u8; 32]);
+
+        for key in &keys[..3] {
+            let vote = Vote::sign(key, VoteType::Precommit, 5, block).unwrap();
+            assert!(gadget.add_vote(vote, &validators).unwrap().is_none());
+        }
+        assert!(!gadget.is_finalized(5));
+
+        for key in &keys[..2] {
+            let vote = Vote::sign(key, VoteType::Prevote, 5, block).unwrap();
+            assert!(gadget.add_vote(vote, &validators).unwrap().is_none());
+        }
+        // The prevote quorum releases the precommits already collected
+        let vote = Vote::sign(&keys[3], VoteType::Prevote, 5, block).unwrap();
+        let certificate = gadget.add_vote(vote, &validators).unwrap().unwrap();
+        assert_eq!(certificate.vote_type, VoteType::Precommit);
+        assert_eq!(certificate.signatures.len(), 3);
+        assert!(gadget.certificate(VoteType::Prevote, &block).is_some());
+        assert_eq!(gadget.finalized(), Some(Checkpoint { height: 5, block_hash: block }));
+    }
+
+    #[test]
+    fn test_rejects_invalid_votes() {
+        let (validators, keys) = setup(4);
+        let mut gadget = FinalityGadget::new();
+
+        // Equivocation
+        let vote = Vote::sign(&keys[0], VoteType::Prevote, 1, Hash::new([1u8; 32])).unwrap();
+        gadget.add_vote(vote, &validators).unwrap();
+        let conflicting = Vote::sign(&keys[0], VoteType::Prevote, 1, Hash::new([2u8; 32])).unwrap();
+        match gadget.add_vote(conflicting, &validators) {
+            Err(ConsensusError::DoubleSign(evidence)) => {
+                assert!(evidence.verify().is_ok());
+                assert_eq!(evidence.offender(), keys[0].public_key().to_address());
+            }
+            other => panic!("expected double-sign evidence, got {:?}", other),
+        }
+
+        // Not a validator
+        let outsider = KeyPair::generate(SignatureScheme::Ed25519).unwrap();
+        let vote = Vote::sign(&outsider, VoteType::Prevote, 1, Hash::new([1u8; 32])).unwrap();
+        assert!(gadget.add_vote(vote, &validators).is_err());
+
+        // Signature over a different block
+        let mut forged = Vote::sign(&keys[1], VoteType::Prevote, 1, Hash::new([1u8; 32])).unwrap();
+        forged.block_hash = Hash::new([3u8; 32]);
+        assert!(gadget.add_vote(forged, &validators).is_err());
+    }
+
+    #[test]
+    fn test_certificate_verification() {
+        let (validators, keys) = setup(4);
+        let block = Hash::new([9u8; 32]);
+        let signatures: Vec<ValidatorSignature> = keys
+            .iter()
+            .map(|key| Vote::sign(key, VoteType::Precommit, 3, block).unwrap().to_validator_signature())
+            .collect();
+
+        let certificate = QuorumCertificate {
+            vote_type: VoteType::Precommit,
+            height: 3,
+            block_hash: block,
+            signatures: signatures[..3].to_vec(),
+        };
+        assert!(certificate.verify(&validators).is_ok());
+
+        // Two of four is not a quorum
+        let weak = QuorumCertificate { signatures: signatures[..2].to_vec(), ..certificate.clone() };
+        assert!(weak.verify(&validators).is_err());
+
+        // Padding with a duplicate does not help
+        let padded = QuorumCertificate {
+            signatures: vec![signatures[0].clone(), signatures[1].clone(), signatures[1].clone()],
+            ..certificate.clone()
+        };
+        assert!(padded.verify(&validators).is_err());
+
+        // Signatures are bound to the vote type
+        let prevote = QuorumCertificate { vote_type: VoteType::Prevote, ..certificate.clone() };
+        assert!(prevote.verify(&validators).is_err());
+
+        let mut gadget = FinalityGadget::new();
+        gadget.process_certificate(&certificate, &validators).unwrap();
+        assert_eq!(gadget.finalized(), Some(Checkpoint { height: 3, block_hash: block }));
+
+        // A different block finalized at the same height is a safety violation
+        let other = Hash::new([8u8; 32]);
+        let conflicting = QuorumCertificate {
+            block_hash: other,
+            signatures: keys[..3]
+                .iter()
+                .map(|key| Vote::sign(key, VoteType::Precommit, 3, other).unwrap().to_validator_signature())
+                .collect(),
+            ..certificate
+        };
+        assert!(gadget.process_certificate(&conflicting, &validators).is_err());
+    }
+}
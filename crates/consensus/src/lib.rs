@@ -6,19 +6,35 @@
 //! - Validators stake tokens to participate
 //! - Staked tokens are actively deployed as liquidity
 //! - Selection weight based on: Stake × Utility_Score × Uptime
+//! - Proposers are elected privately per slot by a stake-weighted VRF
+//! - Ranked fallback proposers take over silent slots after timeouts
+//! - Blocks are signed with the proposer's registered consensus key
+//! - Double-signing is proven by evidence and slashed on chain
+//! - Validators that miss too many slots are jailed until they unjail
+//! - Validator-set changes take effect at epoch boundaries, committed in headers
 //! - Validators earn both protocol rewards and DeFi yields
 
 pub mod poas;
 pub mod validator;
 pub mod selection;
 pub mod slashing;
+pub mod finality;
+pub mod election;
+pub mod evidence;
+pub mod liveness;
+pub mod slot;
 
 pub use poas::{PoASConsensus, ConsensusConfig};
-pub use validator::{Validator, ValidatorSet, ValidatorInfo, ValidatorStatus};
+pub use validator::{Validator, ValidatorChange, ValidatorSet, ValidatorInfo, ValidatorStatus};
 pub use selection::{ValidatorSelector, SelectionWeight};
 pub use slashing::{SlashingManager, SlashingCondition, SlashingPenalty};
+pub use finality::{Checkpoint, FinalityGadget, QuorumCertificate, Vote, VoteType};
+pub use election::{EpochRandomness, LeaderElection};
+pub use evidence::{EvidencePool, SharedEvidencePool};
+pub use liveness::LivenessTracker;
+pub use slot::{ProposerRole, SlotClock};
 
-use blockchain_core::BlockchainError;
+use blockchain_core::{BlockchainError, DoubleSignEvidence, StakeAmount};
 
 /// Result type for consensus operations
 pub type ConsensusResult<T> = Result<T, ConsensusError>;
@@ -30,7 +46,7 @@ pub enum ConsensusError {
     ValidatorError(String),
     
     #[error("Insufficient stake: required {required}, provided {provided}")]
-    InsufficientStake { required: u64, provided: u64 },
+    InsufficientStake { required: StakeAmount, provided: StakeAmount },
     
     #[error("Validator not found: {0}")]
     ValidatorNotFound(String),
@@ -52,6 +68,12 @@ pub enum ConsensusError {
     
     #[error("Selection error: {0}")]
     SelectionError(String),
+
+    #[error("Finality error: {0}")]
+    FinalityError(String),
+
+    #[error("Double signing by {}", .0.offender().to_hex())]
+    DoubleSign(Box<DoubleSignEvidence>),
     
     #[error("Blockchain error: {0}")]
     BlockchainError(#[from] BlockchainError),
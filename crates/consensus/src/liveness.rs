@@ -0,0 +1,104 @@
+// consensus/src/liveness.rs
+
+//! Validator liveness over a sliding window of slots
+//!
+//! Each slot has expected proposers: the validator elected by the VRF and
+//! the ranked fallbacks behind it. The elected proposer stays private until
+//! it proposes, so a slot that stays empty is charged to its fallbacks, whose
+//! windows all opened without a block. Vote participation is not counted:
+//! certificates close at the first quorum, so an honest validator whose
+//! vote arrives late would look absent.
+
+use blockchain_crypto::Address;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+
+/// Whether each validator produced in the most recent slots it was expected in
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LivenessTracker {
+    /// Number of slots in the window
+    window: usize,
+    /// Whether the validator produced in each recorded slot, oldest first
+    history: HashMap<Address, VecDeque<bool>>,
+}
+
+impl LivenessTracker {
+    /// Track participation over the last `window` slots
+    pub fn new(window: u64) -> Self {
+        Self {
+            window: window.max(1) as usize,
+            history: HashMap::new(),
+        }
+    }
+
+    /// Record whether a validator produced in a slot it was expected in
+    pub fn record(&mut self, address: &Address, signed: bool) {
+        let slots = self.history.entry(*address).or_default();
+        slots.push_back(signed);
+        if slots.len() > self.window {
+            slots.pop_front();
+        }
+    }
+
+    /// Slots recorded for a validator, at most the window size
+    pub fn observed(&self, address: &Address) -> u64 {
+        self.history.get(address).map_or(0, |slots| slots.len() as u64)
+    }
+
+    /// Slots missed by a validator within the window
+    pub fn missed(&self, address: &Address) -> u64 {
+        self.history.get(address)
+            .map_or(0, |slots| slots.iter().filter(|signed| !**signed).count() as u64)
+    }
+
+    /// Share of recorded slots filled, in basis points; 100% when nothing is recorded
+    pub fn signed_bps(&self, address: &Address) -> u16 {
+        let observed = self.observed(address);
+        if observed == 0 {
+            return 10000;
+        }
+        ((observed - self.missed(address)) * 10000 / observed) as u16
+    }
+
+    /// Whether a full window has been recorded for a validator
+    pub fn is_full(&self, address: &Address) -> bool {
+        self.observed(address) >= self.window as u64
+    }
+
+    /// Forget a validator's history, e.g. after it is jailed
+    pub fn reset(&mut self, address: &Address) {
+        self.history.remove(address);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sliding_window() {
+        let mut tracker = LivenessTracker::new(4);
+        let address = Address::zero();
+        assert_eq!(tracker.signed_bps(&address), 10000);
+
+        tracker.record(&address, true);
+        tracker.record(&address, false);
+        tracker.record(&address, false);
+        assert!(!tracker.is_full(&address));
+        assert_eq!(tracker.missed(&address), 2);
+        assert_eq!(tracker.signed_bps(&address), 3333);
+
+        tracker.record(&address, true);
+        assert!(tracker.is_full(&address));
+        assert_eq!(tracker.signed_bps(&address), 5000);
+
+        // The oldest slots drop out of the window
+        tracker.record(&address, true);
+        tracker.record(&address, true);
+        assert_eq!(tracker.observed(&address), 4);
+        assert_eq!(tracker.missed(&address), 1);
+
+        tracker.reset(&address);
+        assert_eq!(tracker.observed(&address), 0);
+    }
+}
@@ -1,32 +1,45 @@
 // consensus/src/poas.rs
 
 use crate::{
-    selection::ValidatorSelector,
+    election::LeaderElection,
+    evidence::{lock_pool, EvidencePool, SharedEvidencePool},
+    liveness::LivenessTracker,
+    slot::{ProposerRole, SlotClock},
+    finality::{Checkpoint, FinalityGadget, QuorumCertificate, Vote, VoteType},
     slashing::SlashingManager,
-    validator::{ValidatorInfo, ValidatorSet},
+    validator::{ValidatorChange, ValidatorInfo, ValidatorSet, ValidatorStatus},
     ConsensusError, ConsensusResult,
 };
-use blockchain_core::{Block, BlockNumber, StakeAmount, Timestamp, fork::{ForkChoice, ForkResolver, ForkInfo, ReorgPath}, mempool::TransactionPool, Gas};
-use blockchain_crypto::Hash;
+use blockchain_core::{Block, BlockNumber, DoubleSignEvidence, evidence::DEFAULT_UNBONDING_PERIOD, ProposerElection, StakeAmount, Timestamp, clock::{system_clock, SharedClock}, fork::{ForkChoice, ForkResolver, ForkInfo, ReorgPath}, mempool::TransactionPool, transaction::TransactionType};
+use blockchain_crypto::{Hash, KeyPair, PublicKey};
 use blockchain_crypto::Address;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::sync::MutexGuard;
+
+/// Seconds a block timestamp may run ahead of local time
+const MAX_CLOCK_DRIFT: u64 = 2;
 
 /// Configuration for PoAS consensus
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ConsensusConfig {
-    /// Block time target in seconds
+    /// Block time target in seconds, the length of a slot
     pub block_time: u64,
+    /// Start of slot 0, the genesis block's timestamp
+    pub genesis_time: Timestamp,
+    /// Seconds each fallback rank waits for the proposers ahead of it
+    pub proposer_timeout: u64,
+    /// Fallback proposers ranked for each slot
+    pub max_fallback_proposers: usize,
     /// Minimum stake required to be a validator (e.g., 10,000 tokens)
     pub min_stake: StakeAmount,
     /// Unbonding period in seconds (e.g., 14 days)
     pub unbonding_period: u64,
-    /// Required uptime percentage (basis points, 9500 = 95%)
+    /// Required share of signed slots in the liveness window (basis points, 9500 = 95%)
     pub required_uptime: u16,
-    /// Blocks before downtime slashing
-    pub max_downtime_blocks: u64,
-    /// Number of blocks for finality
-    pub finality_blocks: u64,
+    /// Expected proposer slots per validator in the liveness window
+    pub liveness_window: u64,
+    /// Blocks a jailed validator must wait before it can unjail
+    pub jail_duration: u64,
     /// Target validator count
     pub target_validator_count: usize,
     /// Maximum validator count
@@ -37,25 +50,31 @@ pub struct ConsensusConfig {
     pub max_reorg_depth: u64,
     /// Enable fork detection and resolution
     pub enable_fork_detection: bool,
-    /// Slash validators for producing blocks on wrong fork
+    /// Pool double-sign evidence for conflicting headers found during a reorg
     pub slash_for_wrong_fork: bool,
+    /// Seed for the first epoch's leader election randomness
+    pub genesis_randomness: Hash,
 }
 
 impl Default for ConsensusConfig {
     fn default() -> Self {
         Self {
             block_time: 3,                          // 3 seconds
+            genesis_time: 0,
+            proposer_timeout: 1,                    // fallbacks at 1s and 2s into a slot
+            max_fallback_proposers: 2,
             min_stake: StakeAmount::from_u64(10000), // 10,000 tokens
-            unbonding_period: 14 * 24 * 3600,       // 14 days
+            unbonding_period: DEFAULT_UNBONDING_PERIOD, // 14 days
             required_uptime: 9500,                  // 95%
-            max_downtime_blocks: 28800,             // ~1 day at 3s blocks
-            finality_blocks: 2,                     // 6 seconds
+            liveness_window: 100,                   // slots each validator was expected to fill
+            jail_duration: 1200,                    // ~1 hour at 3s blocks
             target_validator_count: 100,
             max_validator_count: 1000,
             fork_choice: ForkChoice::LatestJustified,
             max_reorg_depth: 100,
             enable_fork_detection: true,
             slash_for_wrong_fork: true,
+            genesis_randomness: Hash::zero(),
         }
     }
 }
@@ -66,8 +85,14 @@ pub struct PoASConsensus {
     config: ConsensusConfig,
     /// Validator set
     validator_set: ValidatorSet,
-    /// Validator selector
-    selector: ValidatorSelector,
+    /// VRF leader election and epoch randomness
+    election: LeaderElection,
+    /// Slot timing and proposer windows
+    slots: SlotClock,
+    /// Slot of the last finalized block
+    last_slot: u64,
+    /// Slots skipped without a block
+    empty_slots: u64,
     /// Slashing manager
     slashing: SlashingManager,
     /// Current epoch number
@@ -76,50 +101,83 @@ pub struct PoASConsensus {
     blocks_per_epoch: u64,
     /// Fork resolver for chain reorganizations
     fork_resolver: ForkResolver,
-    /// Finality checkpoints: block_number -> is_justified
-    finality_checkpoints: HashMap<BlockNumber, bool>,
+    /// Vote aggregation and justified/finalized checkpoints
+    finality: FinalityGadget,
+    /// Double-sign evidence awaiting inclusion, shared with the network service
+    evidence: SharedEvidencePool,
+    /// Proposer slots filled or missed over the liveness window
+    liveness: LivenessTracker,
     /// Fork events for metrics
     fork_events: Vec<(BlockNumber, String)>,
-    /// Highest justified checkpoint (block number)
-    highest_justified: Option<BlockNumber>,
     /// Metrics: total forks observed
     fork_frequency: u64,
     /// Metrics: total reorg depth observed
     total_reorg_depth: u64,
     /// Metrics: maximum reorg depth observed
     max_reorg_depth_observed: u64,
-    /// Finality time records: block_number -> timestamp when justified
+    /// Finality time records: block_number -> timestamp when finalized
     finality_times: Vec<(BlockNumber, u64)>,
+    /// Time source for block timestamps, unbonding and finality records
+    clock: SharedClock,
 }
 
 impl PoASConsensus {
     /// Create a new PoAS consensus engine
     pub fn new(config: ConsensusConfig) -> Self {
-        let validator_set = ValidatorSet::new(
+        let mut validator_set = ValidatorSet::new(
             config.min_stake.clone(),
             config.unbonding_period,
         );
+        validator_set.set_max_active(config.target_validator_count);
         
         let fork_resolver = ForkResolver::new(config.fork_choice, config.max_reorg_depth);
+        let election = LeaderElection::new(config.genesis_randomness);
+        let slots = SlotClock::new(config.genesis_time, config.block_time, config.proposer_timeout);
+        let liveness = LivenessTracker::new(config.liveness_window);
         
         Self {
             config,
             validator_set,
-            selector: ValidatorSelector::new_random(),
+            election,
+            slots,
+            last_slot: 0,
+            empty_slots: 0,
             slashing: SlashingManager::new(),
             current_epoch: 0,
             blocks_per_epoch: 28800, // ~1 day at 3s blocks
             fork_resolver,
-            finality_checkpoints: HashMap::new(),
+            finality: FinalityGadget::new(),
+            evidence: SharedEvidencePool::default(),
+            liveness,
             fork_events: Vec::new(),
-            highest_justified: None,
             fork_frequency: 0,
             total_reorg_depth: 0,
             max_reorg_depth_observed: 0,
             finality_times: Vec::new(),
+            clock: system_clock(),
         }
     }
 
+    /// Replace the time source used by the engine, validator set and slashing manager
+    pub fn set_clock(&mut self, clock: SharedClock) {
+        self.validator_set.set_clock(clock.clone());
+        self.slashing.set_clock(clock.clone());
+        self.clock = clock;
+    }
+
+    /// Anchor slot timing at the genesis block: slot 0 starts at its timestamp
+    /// and no slot before the genesis slot counts as missed
+    pub fn set_genesis(&mut self, genesis: &Block) {
+        self.config.genesis_time = genesis.header.timestamp;
+        self.slots = SlotClock::new(genesis.header.timestamp, self.config.block_time, self.config.proposer_timeout);
+        self.last_slot = genesis.header.slot;
+    }
+
+    /// Get the time source
+    pub fn clock(&self) -> &SharedClock {
+        &self.clock
+    }
+
     /// Get consensus configuration
     pub fn config(&self) -> &ConsensusConfig {
         &self.config
@@ -145,8 +203,83 @@ impl PoASConsensus {
         &mut self.slashing
     }
 
-    /// Select the next block proposer
-    pub fn select_proposer(&mut self, slot: u64) -> ConsensusResult<Address> {
+    /// Get the leader election
+    pub fn election(&self) -> &LeaderElection {
+        &self.election
+    }
+
+    /// Get the slot clock
+    pub fn slot_clock(&self) -> &SlotClock {
+        &self.slots
+    }
+
+    /// Current slot, or `None` before genesis
+    pub fn current_slot(&self) -> Option<u64> {
+        self.slots.current_slot(&*self.clock)
+    }
+
+    /// Slot a block was proposed in, from its timestamp
+    ///
+    /// `validate_block` checks the header's `slot` agrees with it.
+    pub fn block_slot(&self, block: &Block) -> ConsensusResult<u64> {
+        if block.is_genesis() {
+            return Ok(0);
+        }
+        self.slots.slot_at(block.header.timestamp).ok_or_else(|| {
+            ConsensusError::ValidationError(format!(
+                "Block {} timestamp {} is before genesis",
+                block.header.number,
+                block.header.timestamp
+            ))
+        })
+    }
+
+    /// Number of slots skipped without a block
+    pub fn empty_slots(&self) -> u64 {
+        self.empty_slots
+    }
+
+    /// Epoch a block belongs to; the epoch ends with its last block finalized
+    pub fn epoch_of_block(&self, number: BlockNumber) -> u64 {
+        number.saturating_sub(1) / self.blocks_per_epoch
+    }
+
+    /// Whether a block is the last of its epoch
+    pub fn is_epoch_end(&self, number: BlockNumber) -> bool {
+        number > 0 && number.is_multiple_of(self.blocks_per_epoch)
+    }
+
+    /// Validator-set hash to commit in an epoch's last block with this timestamp
+    pub fn next_validators_hash(&self, timestamp: Timestamp) -> Hash {
+        self.validator_set.next_set_hash(timestamp)
+    }
+
+    /// Check whether `keypair` is elected to propose block `number` at `slot`, returning its proof if so
+    pub fn claim_slot(&self, keypair: &KeyPair, number: BlockNumber, slot: u64) -> ConsensusResult<Option<ProposerElection>> {
+        let active_validators = self.active_for_election(number)?;
+        self.election.claim(keypair, slot, &active_validators)
+    }
+
+    /// Fallback proposers for block `number` at `slot`, highest precedence first
+    pub fn fallback_proposers(&self, number: BlockNumber, slot: u64) -> ConsensusResult<Vec<Address>> {
+        let active_validators = self.active_for_election(number)?;
+        self.election.fallbacks(slot, &active_validators, self.config.max_fallback_proposers)
+    }
+
+    /// How `keypair` may propose block `number` at `slot`, if at all
+    pub fn proposer_role(&self, keypair: &KeyPair, number: BlockNumber, slot: u64) -> ConsensusResult<Option<ProposerRole>> {
+        if let Some(election) = self.claim_slot(keypair, number, slot)? {
+            return Ok(Some(ProposerRole::Elected(election)));
+        }
+        let address = keypair.public_key().to_address();
+        Ok(self.fallback_proposers(number, slot)?
+            .iter()
+            .position(|a| *a == address)
+            .map(|i| ProposerRole::Fallback(i + 1)))
+    }
+
+    /// Active validators, once the election randomness is that of the block's epoch
+    fn active_for_election(&self, number: BlockNumber) -> ConsensusResult<Vec<&ValidatorInfo>> {
         let active_validators = self.validator_set.active_validators();
         
         if active_validators.is_empty() {
@@ -155,8 +288,21 @@ impl PoASConsensus {
             ));
         }
 
-        // Use deterministic selection for predictability
-        self.selector.select_for_slot(&active_validators, slot)
+        self.check_epoch(number)?;
+        Ok(active_validators)
+    }
+
+    /// Election randomness must be that of the block's epoch
+    fn check_epoch(&self, number: BlockNumber) -> ConsensusResult<()> {
+        let epoch = self.epoch_of_block(number);
+        let randomness_epoch = self.election.randomness().epoch();
+        if epoch != randomness_epoch {
+            return Err(ConsensusError::SelectionError(format!(
+                "Block {} is in epoch {}, election randomness is for epoch {}",
+                number, epoch, randomness_epoch
+            )));
+        }
+        Ok(())
     }
 
     /// Validate a proposed block
@@ -177,21 +323,79 @@ impl PoASConsensus {
             ));
         }
 
-        // Verify block time
-        let expected_time = parent.header.timestamp + self.config.block_time;
-        let time_diff = if block.header.timestamp > expected_time {
-            block.header.timestamp - expected_time
-        } else {
-            expected_time - block.header.timestamp
+        // Verify the proposer signed the block with its registered consensus key
+        block.verify_proposer_signature(&validator.consensus_key)
+            .map_err(|e| ConsensusError::ValidationError(e.to_string()))?;
+
+        // The block's slot must match its timestamp, follow its parent's and
+        // not lie in the future
+        let slot = self.block_slot(block)?;
+        if block.header.slot != slot {
+            return Err(ConsensusError::ValidationError(format!(
+                "Block slot {} does not match timestamp slot {}",
+                block.header.slot, slot
+            )));
+        }
+        let parent_slot = self.block_slot(parent)?;
+        if slot <= parent_slot {
+            return Err(ConsensusError::ValidationError(format!(
+                "Block slot {} does not follow parent slot {}",
+                slot, parent_slot
+            )));
+        }
+        if block.header.timestamp > self.clock.now() + MAX_CLOCK_DRIFT {
+            return Err(ConsensusError::ValidationError(format!(
+                "Block timestamp {} is ahead of local time",
+                block.header.timestamp
+            )));
+        }
+
+        // Verify the proposer was elected for this slot, or is a fallback whose turn has come
+        let rank = match block.header.election {
+            Some(_) => {
+                let active_validators = self.active_for_election(block.header.number)?;
+                self.election.verify(block, slot, &active_validators)?;
+                0
+            }
+            None => self.fallback_proposers(block.header.number, slot)?
+                .iter()
+                .position(|a| *a == proposer)
+                .map(|i| i + 1)
+                .ok_or_else(|| ConsensusError::ValidationError(format!(
+                    "Proposer {} is neither elected nor a fallback for slot {}",
+                    proposer.to_hex(),
+                    slot
+                )))?,
         };
+        let window_start = self.slots.window_start(slot, rank);
+        if block.header.timestamp < window_start {
+            return Err(ConsensusError::ValidationError(format!(
+                "Fallback proposer of rank {} may not propose in slot {} before {}",
+                rank, slot, window_start
+            )));
+        }
+
+        // Verify the parent's precommit certificate, if carried
+        if let Some(certificate) = QuorumCertificate::for_parent(block) {
+            certificate.verify(&self.validator_set)?;
+        }
 
-        // Allow some tolerance (±2 seconds)
-        if time_diff > 2 {
+        // The last block of an epoch commits to the next validator set
+        let expected = self.is_epoch_end(block.header.number)
+            .then(|| self.next_validators_hash(block.header.timestamp));
+        if block.header.next_validators_hash != expected {
             return Err(ConsensusError::ValidationError(
-                format!("Block time outside acceptable range: {}s difference", time_diff)
+                "Next validator set hash missing, unexpected or incorrect".into()
             ));
         }
 
+        // Unjail transactions are only valid once the jail time is served
+        for tx in &block.transactions {
+            if let TransactionType::Unjail = tx.tx_type {
+                self.check_unjail(&tx.from, block.header.number)?;
+            }
+        }
+
         // Fork detection
         if self.config.enable_fork_detection {
             if let Some(fork_info) = self.fork_resolver.detect_fork(parent, block) {
@@ -213,39 +417,176 @@ impl PoASConsensus {
 
     /// Process a finalized block
     pub fn finalize_block(&mut self, block: &Block) -> ConsensusResult<()> {
-        let proposer = block.header.proposer;
+        // The block's certificate finalizes its parent
+        let certificate = QuorumCertificate::for_parent(block);
+        if let Some(certificate) = &certificate {
+            self.process_certificate(certificate)?;
+        }
         
-        // Update validator statistics
-        if let Some(validator) = self.validator_set.get_mut(&proposer) {
-            validator.update_uptime(true);
-            
-            // Check for downtime slashing
-            self.slashing.check_downtime_slashing(
-                validator,
-                self.config.max_downtime_blocks,
-            )?;
+        // Slots skipped since the previous block stayed empty. Who was
+        // expected to fill them is worked out with this epoch's randomness,
+        // before the epoch transition below replaces it.
+        let slot = self.block_slot(block)?;
+        let outcomes = self.slot_outcomes(block, slot)?;
+        self.empty_slots += slot.saturating_sub(self.last_slot + 1);
+        self.last_slot = slot;
+
+        // Mix the proposer's VRF output, verified in `validate_block`, into the next epoch's randomness
+        if let Some(election) = &block.header.election {
+            let output = election.proof.to_output()
+                .map_err(|e| ConsensusError::ValidationError(e.to_string()))?;
+            self.election.record(&output);
+        }
+
+        // Switch to the next epoch's validator set before this block's
+        // slashes and jailings, matching the set its header commits to
+        if self.is_epoch_end(block.header.number) {
+            self.process_epoch_transition(block)?;
+        }
+        
+        // Mirror the on-chain double-sign slashes in the validator set
+        for tx in &block.transactions {
+            match &tx.tx_type {
+                TransactionType::SubmitEvidence { evidence } => {
+                    if let Some(validator) = self.validator_set.get_mut(&evidence.offender()) {
+                        self.slashing.mirror_double_sign(validator, evidence.id())?;
+                    }
+                }
+                TransactionType::Unjail => self.unjail(&tx.from, block.header.number)?,
+                _ => {}
+            }
+        }
+        lock_pool(&self.evidence).remove_included(&block.transactions);
+
+        self.record_liveness(&outcomes, block.header.number)?;
+
+        Ok(())
+    }
+
+    /// Expected proposers of the slots up to and including `block`'s, and
+    /// whether each of them produced
+    ///
+    /// The elected proposer of a slot is only known once it reveals its
+    /// proof, so a slot without a block is charged to its fallback
+    /// proposers, whose windows all opened. A slot filled by a fallback is
+    /// charged to the fallbacks ranked ahead of it. The block's proposer is
+    /// credited. Only the last `liveness_window` slots are considered.
+    fn slot_outcomes(&self, block: &Block, slot: u64) -> ConsensusResult<Vec<(Address, bool)>> {
+        let proposer = block.header.proposer;
+        let mut outcomes = vec![(proposer, true)];
+        if self.validator_set.active_count() == 0 {
+            return Ok(outcomes);
         }
 
-        // Update epoch if needed
-        if block.header.number % self.blocks_per_epoch == 0 {
-            self.process_epoch_transition(block.header.number)?;
+        let number = block.header.number;
+        let first_empty = (self.last_slot + 1).max(slot.saturating_sub(self.config.liveness_window));
+        for empty in first_empty..slot {
+            outcomes.extend(self.fallback_proposers(number, empty)?.into_iter().map(|a| (a, false)));
         }
+        if block.header.election.is_none() {
+            let fallbacks = self.fallback_proposers(number, slot)?;
+            if let Some(rank) = fallbacks.iter().position(|a| *a == proposer) {
+                outcomes.extend(fallbacks[..rank].iter().map(|a| (*a, false)));
+            }
+        }
+        Ok(outcomes)
+    }
+
+    /// Record whether active validators produced in the slots they were
+    /// expected in, jailing and slashing those below the required share of
+    /// a full window
+    ///
+    /// Uptime also weighs selection, so it is only updated once a full window
+    /// is recorded; a few early misses would otherwise stop a validator from
+    /// ever being expected again.
+    fn record_liveness(&mut self, outcomes: &[(Address, bool)], height: BlockNumber) -> ConsensusResult<()> {
+        let now = self.clock.now();
+        for &(address, produced) in outcomes {
+            let Some(validator) = self.validator_set.get_mut(&address) else { continue };
+            if !validator.is_active() {
+                continue;
+            }
+            self.liveness.record(&address, produced);
+            if produced {
+                validator.blocks_produced += 1;
+                validator.last_active = now;
+            } else {
+                validator.blocks_missed += 1;
+            }
+            if !self.liveness.is_full(&address) {
+                continue;
+            }
+            validator.uptime = self.liveness.signed_bps(&address);
+
+            if validator.uptime < self.config.required_uptime {
+                let missed = self.liveness.missed(&address);
+                validator.status = ValidatorStatus::Jailed {
+                    release_height: height + self.config.jail_duration,
+                };
+                self.slashing.slash_downtime(validator, missed)?;
+                self.liveness.reset(&address);
+                tracing::warn!(
+                    "Jailed validator {} at height {} after missing {} slots",
+                    address.to_hex(),
+                    height,
+                    missed
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// Check a validator may unjail at `height`
+    pub fn check_unjail(&self, address: &Address, height: BlockNumber) -> ConsensusResult<()> {
+        let validator = self.validator_set.get(address)
+            .ok_or_else(|| ConsensusError::ValidatorNotFound(address.to_hex()))?;
+        match validator.status {
+            ValidatorStatus::Jailed { release_height } if height >= release_height => Ok(()),
+            ValidatorStatus::Jailed { release_height } => Err(ConsensusError::InvalidValidatorStatus(format!(
+                "Validator {} is jailed until height {}",
+                address.to_hex(),
+                release_height
+            ))),
+            _ => Err(ConsensusError::InvalidValidatorStatus(format!(
+                "Validator {} is not jailed",
+                address.to_hex()
+            ))),
+        }
+    }
 
+    /// Return a jailed validator to the active set
+    pub fn unjail(&mut self, address: &Address, height: BlockNumber) -> ConsensusResult<()> {
+        self.check_unjail(address, height)?;
+        self.validator_set.update_status(address, ValidatorStatus::Active)?;
+        // Its liveness history was cleared on jailing, so it starts over
+        if let Some(validator) = self.validator_set.get_mut(address) {
+            validator.uptime = 10000;
+        }
+        tracing::info!("Unjailed validator {} at height {}", address.to_hex(), height);
         Ok(())
     }
 
     /// Process epoch transition
-    fn process_epoch_transition(&mut self, block_number: BlockNumber) -> ConsensusResult<()> {
+    fn process_epoch_transition(&mut self, block: &Block) -> ConsensusResult<()> {
         self.current_epoch += 1;
-
-        // Process unbonding completions
-        let current_time = current_timestamp();
-        let completed = self.validator_set.process_unbonding(current_time);
+        self.election.advance_epoch();
+
+        // Apply queued changes and unbonding completions at the block's time
+        let completed = self.validator_set.apply_epoch_changes(block.header.timestamp);
+        if let Some(committed) = block.header.next_validators_hash {
+            if committed != self.validator_set.active_set_hash() {
+                return Err(ConsensusError::ValidationError(format!(
+                    "Block {} committed to a different validator set",
+                    block.header.number
+                )));
+            }
+        }
 
         tracing::info!(
-            "Epoch {} transition at block {}, {} validators completed unbonding",
+            "Epoch {} transition at block {}: {} active validators, {} completed unbonding",
             self.current_epoch,
-            block_number,
+            block.header.number,
+            self.validator_set.active_count(),
             completed.len()
         );
 
@@ -263,25 +604,73 @@ impl PoASConsensus {
         Ok(())
     }
 
-    /// Check if a block is finalized
-    pub fn is_finalized(&self, block_number: BlockNumber, head: BlockNumber) -> bool {
-        // If we have a justified checkpoint, enforce it as final
-        if let Some(j) = self.highest_justified {
-            return block_number <= j;
-        }
+    /// Check if a block is finalized by a precommit certificate
+    pub fn is_finalized(&self, block_number: BlockNumber) -> bool {
+        self.finality.is_finalized(block_number)
+    }
+
+    /// Get the finality gadget
+    pub fn finality(&self) -> &FinalityGadget {
+        &self.finality
+    }
+
+    /// Highest justified checkpoint
+    pub fn justified_checkpoint(&self) -> Option<Checkpoint> {
+        self.finality.justified()
+    }
+
+    /// Highest finalized checkpoint
+    pub fn finalized_checkpoint(&self) -> Option<Checkpoint> {
+        self.finality.finalized()
+    }
+
+    /// Add a validator's vote, returning the certificate if it completes a quorum
+    ///
+    /// An equivocating vote is rejected and the resulting evidence is added
+    /// to the evidence pool.
+    pub fn submit_vote(&mut self, vote: Vote) -> ConsensusResult<Option<QuorumCertificate>> {
+        let finalized = self.finality.finalized();
+        let certificate = match self.finality.add_vote(vote, &self.validator_set) {
+            Err(ConsensusError::DoubleSign(evidence)) => {
+                lock_pool(&self.evidence).add((*evidence).clone())?;
+                return Err(ConsensusError::DoubleSign(evidence));
+            }
+            result => result?,
+        };
+        self.record_finality(finalized);
+        Ok(certificate)
+    }
 
-        head >= block_number + self.config.finality_blocks
+    /// Add double-sign evidence received from a peer, returning whether it was new
+    pub fn submit_evidence(&mut self, evidence: DoubleSignEvidence) -> ConsensusResult<bool> {
+        lock_pool(&self.evidence).add(evidence)
     }
 
-    /// Mark a block as justified (justified checkpoint)
-    pub fn update_justified_checkpoint(&mut self, block_number: BlockNumber) {
-        let entry = self.finality_checkpoints.entry(block_number).or_insert(true);
-        *entry = true;
+    /// Evidence awaiting inclusion
+    pub fn evidence_pool(&self) -> MutexGuard<'_, EvidencePool> {
+        lock_pool(&self.evidence)
+    }
 
-        self.highest_justified = Some(std::cmp::max(self.highest_justified.unwrap_or(0), block_number));
-        // Record finality timestamp
-        self.finality_times.push((block_number, current_timestamp()));
-        tracing::info!("Justified checkpoint updated: {}", block_number);
+    /// Handle to the evidence pool, for sharing it with the network service
+    pub fn shared_evidence_pool(&self) -> SharedEvidencePool {
+        self.evidence.clone()
+    }
+
+    /// Verify a quorum certificate and advance the justified/finalized checkpoints
+    pub fn process_certificate(&mut self, certificate: &QuorumCertificate) -> ConsensusResult<()> {
+        let finalized = self.finality.finalized();
+        self.finality.process_certificate(certificate, &self.validator_set)?;
+        self.record_finality(finalized);
+        Ok(())
+    }
+
+    fn record_finality(&mut self, previous: Option<Checkpoint>) {
+        if let Some(finalized) = self.finality.finalized() {
+            if previous != Some(finalized) {
+                self.finality_times.push((finalized.height, self.clock.now()));
+                tracing::info!("Finalized checkpoint updated: {}", finalized.height);
+            }
+        }
     }
 
     /// Verify before block production that head is canonical and safe to build on
@@ -290,12 +679,6 @@ impl PoASConsensus {
             return Err(ConsensusError::BlockProductionError("Head is not canonical according to fork history".into()));
         }
 
-        // Do not produce if head would violate a justified checkpoint
-        if let Some(j) = self.highest_justified {
-            // Here we would check head block number against justified checkpoint; simplified: ensure not empty
-            // In a full node this would translate head hash to block number via storage and compare.
-        }
-
         Ok(())
     }
 
@@ -309,12 +692,34 @@ impl PoASConsensus {
         &self.finality_times
     }
 
-    /// Produce a new block: select transactions from mempool, validate head canonicality,
-    /// and return the constructed block. Removes included transactions from the pool.
-    pub fn produce_block(&mut self, parent: &Block, proposer: Address, pool: &mut TransactionPool) -> ConsensusResult<Block> {
+    /// Produce a new block in the current slot: check the proposer's election or fallback
+    /// timeout, select transactions from mempool, validate head canonicality, and return the
+    /// constructed block. Removes included transactions from the pool.
+    pub fn produce_block(&mut self, parent: &Block, keypair: &KeyPair, pool: &mut TransactionPool) -> ConsensusResult<Block> {
         // Pre-production checks
         self.verify_before_produce(&parent.hash())?;
 
+        let number = parent.number() + 1;
+        let proposer = keypair.public_key().to_address();
+        let now = self.clock.now();
+        let slot = self.slots.slot_at(now).ok_or_else(|| {
+            ConsensusError::BlockProductionError("Genesis time not reached".into())
+        })?;
+        if slot <= self.block_slot(parent)? {
+            return Err(ConsensusError::BlockProductionError(format!("Slot {} already has a block", slot)));
+        }
+
+        let role = self.proposer_role(keypair, number, slot)?.ok_or_else(|| {
+            ConsensusError::BlockProductionError(format!("Neither elected nor a fallback for slot {}", slot))
+        })?;
+        if now < self.slots.window_start(slot, role.rank()) {
+            return Err(ConsensusError::BlockProductionError(format!(
+                "Proposer timeout for rank {} in slot {} has not expired",
+                role.rank(),
+                slot
+            )));
+        }
+
         // Choose transactions by gas price up to gas limit
         let max_gas = parent.header.gas_limit;
         let txs = pool.get_pending(max_gas, 1000);
@@ -328,20 +733,28 @@ impl PoASConsensus {
         }
 
         // Create block
-        let number = parent.number() + 1;
         let state_root = Hash::zero(); // state root computed during execution in full node
-        let block = Block::new(number, parent.hash(), state_root, proposer, valid_txs.clone(), max_gas)
+        let mut block = Block::new_with_clock(number, parent.hash(), state_root, proposer, valid_txs.clone(), max_gas, &*self.clock)
             .map_err(|e| ConsensusError::BlockchainError(e))?;
+        block.header.slot = slot;
+        if let ProposerRole::Elected(election) = role {
+            block.header.election = Some(election);
+        }
+        if self.is_epoch_end(number) {
+            block.header.next_validators_hash = Some(self.next_validators_hash(block.header.timestamp));
+        }
+
+        // Carry the parent's precommit certificate so others can finalize it
+        if let Some(certificate) = self.finality.certificate(VoteType::Precommit, &parent.hash()) {
+            block.validator_signatures = certificate.signatures.clone();
+        }
+
+        // Sign the finished header with the proposer's consensus key
+        block.sign(keypair)?;
 
         // Remove included transactions from pool
         pool.remove_included(&valid_txs);
 
-        // Update proposer stats
-        if let Some(v) = self.validator_set.get_mut(&proposer) {
-            v.blocks_produced += 1;
-            v.update_uptime(true);
-        }
-
         Ok(block)
     }
 
@@ -353,8 +766,9 @@ impl PoASConsensus {
         }).collect()
     }
 
-    /// Apply a reorganization path. This records fork events, enforces justified checkpoints,
-    /// and performs basic slashing for double-signing evidence.
+    /// Apply a reorganization path. This records fork events, enforces finalized checkpoints
+    /// and the certificates carried by the new blocks, and pools double-sign evidence found
+    /// across the two branches.
     ///
     /// An optional persistence callback may be provided to persist fork events. The callback
     /// receives `(&ForkInfo, reorg_depth, resolution_str)` and should return `ConsensusResult<()>`.
@@ -368,28 +782,41 @@ impl PoASConsensus {
             return Err(ConsensusError::ValidationError("Empty reorg path".into()));
         };
 
-        // Prevent reorgs that revert past justified checkpoints
-        if let Some(j) = self.highest_justified {
-            if common_ancestor_number < j {
+        // Prevent reorgs that revert finalized blocks
+        if let Some(finalized) = self.finality.finalized() {
+            if common_ancestor_number < finalized.height {
                 return Err(ConsensusError::BlockchainError(
                     blockchain_core::BlockchainError::ReorgTooDeep { depth: reorg.depth }
                 ));
             }
         }
 
-        // Detect double-signing: same proposer produced blocks at same height on both sides
-        for a in &reorg.apply_blocks {
-            for r in &reorg.revert_blocks {
-                if a.header.number == r.header.number && a.header.proposer == r.header.proposer {
-                    tracing::warn!("Double-sign detected: validator={} at height {}", a.header.proposer.to_hex(), a.header.number);
-                    if self.config.slash_for_wrong_fork {
-                        if let Some(validator) = self.validator_set.get_mut(&a.header.proposer) {
-                            let _penalty = self.slashing.slash_validator(
-                                validator,
-                                crate::slashing::SlashingCondition::DoubleSigning,
-                                Some(a.hash()),
-                            )?;
-                        }
+        // Every certificate carried by the new branch must be valid
+        let certificates = reorg.apply_blocks.iter()
+            .filter_map(QuorumCertificate::for_parent)
+            .collect::<Vec<_>>();
+        for certificate in &certificates {
+            certificate.verify(&self.validator_set)?;
+        }
+
+        // Signed headers from the same proposer on both branches may be
+        // double-sign evidence. It is only pooled here; the offender is
+        // slashed once the evidence is included and executed on chain.
+        if self.config.slash_for_wrong_fork {
+            for a in &reorg.apply_blocks {
+                for r in &reorg.revert_blocks {
+                    if a.header.number != r.header.number || a.header.proposer != r.header.proposer {
+                        continue;
+                    }
+                    let Some(validator) = self.validator_set.get(&a.header.proposer) else { continue };
+                    let key = &validator.consensus_key;
+                    let (Some(first), Some(second)) = (r.signed_header(key), a.signed_header(key)) else { continue };
+                    let evidence = DoubleSignEvidence::ConflictingHeaders {
+                        first: Box::new(first),
+                        second: Box::new(second),
+                    };
+                    if evidence.verify().is_ok() {
+                        lock_pool(&self.evidence).add(evidence)?;
                     }
                 }
             }
@@ -427,6 +854,9 @@ impl PoASConsensus {
         }
 
         if choice {
+            for certificate in &certificates {
+                self.process_certificate(certificate)?;
+            }
             tracing::info!("Reorg chosen: switch to fork chain (depth={})", reorg.depth);
         } else {
             tracing::info!("Reorg chosen: keep main chain (depth={})", reorg.depth);
@@ -448,24 +878,44 @@ impl PoASConsensus {
         true
     }
 
-    /// Register a new validator
-    pub fn register_validator(
+    /// Add a validator to the genesis set, active immediately
+    pub fn add_genesis_validator(
         &mut self,
-        address: Address,
+        consensus_key: PublicKey,
         stake: StakeAmount,
         commission_rate: u16,
     ) -> ConsensusResult<()> {
-        // Check maximum validator count
-        if self.validator_set.count() >= self.config.max_validator_count {
-            return Err(ConsensusError::ValidatorError(
-                "Maximum validator count reached".into()
-            ));
-        }
+        let address = consensus_key.to_address();
+        self.check_validator_count()?;
+        self.validator_set.register(consensus_key, stake.clone(), commission_rate)?;
 
-        self.validator_set.register(address, stake.clone(), commission_rate)?;
+        tracing::info!(
+            "Genesis validator {} added with stake {} and commission {}%",
+            address.to_hex(),
+            stake,
+            commission_rate as f64 / 100.0
+        );
+
+        Ok(())
+    }
+
+    /// Register a new validator from the next epoch, signing with `consensus_key`
+    pub fn register_validator(
+        &mut self,
+        consensus_key: PublicKey,
+        stake: StakeAmount,
+        commission_rate: u16,
+    ) -> ConsensusResult<()> {
+        let address = consensus_key.to_address();
+        self.check_validator_count()?;
+        self.validator_set.queue(ValidatorChange::Register {
+            consensus_key,
+            stake: stake.clone(),
+            commission_rate,
+        })?;
 
         tracing::info!(
-            "Validator {} registered with stake {} and commission {}%",
+            "Validator {} queued for registration with stake {} and commission {}%",
             address.to_hex(),
             stake,
             commission_rate as f64 / 100.0
@@ -474,16 +924,39 @@ impl PoASConsensus {
         Ok(())
     }
 
-    /// Unregister a validator
-    pub fn unregister_validator(&mut self, address: &Address) -> ConsensusResult<ValidatorInfo> {
-        let validator = self.validator_set.unregister(address)?;
+    /// Reject registrations beyond the maximum, counting queued ones
+    fn check_validator_count(&self) -> ConsensusResult<()> {
+        let queued = self.validator_set.pending_changes().iter()
+            .filter(|c| matches!(c, ValidatorChange::Register { .. }))
+            .count();
+        if self.validator_set.count() + queued >= self.config.max_validator_count {
+            return Err(ConsensusError::ValidatorError(
+                "Maximum validator count reached".into()
+            ));
+        }
+        Ok(())
+    }
+
+    /// Add stake to a validator from the next epoch
+    pub fn add_stake(&mut self, address: &Address, amount: StakeAmount) -> ConsensusResult<()> {
+        self.validator_set.queue(ValidatorChange::AddStake { address: *address, amount })
+    }
+
+    /// Start unbonding part of a validator's stake at the next epoch
+    pub fn begin_unbonding(&mut self, address: &Address, amount: StakeAmount) -> ConsensusResult<()> {
+        self.validator_set.queue(ValidatorChange::BeginUnbonding { address: *address, amount })
+    }
+
+    /// Remove a validator from the set at the next epoch
+    pub fn unregister_validator(&mut self, address: &Address) -> ConsensusResult<()> {
+        self.validator_set.queue(ValidatorChange::Exit { address: *address })?;
 
         tracing::info!(
-            "Validator {} unregistered",
+            "Validator {} queued to exit",
             address.to_hex()
         );
 
-        Ok(validator)
+        Ok(())
     }
 
     /// Get current epoch
@@ -556,33 +1029,40 @@ pub struct SecurityMetrics {
     pub attack_cost: StakeAmount,
 }
 
-/// Helper to get current timestamp
-fn current_timestamp() -> Timestamp {
-    std::time::SystemTime::now()
-        .duration_since(std::time::UNIX_EPOCH)
-        .unwrap()
-        .as_secs()
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
     use blockchain_crypto::{KeyPair, SignatureScheme};
 
+    fn test_key(seed: u8) -> KeyPair {
+        KeyPair::from_secret_bytes(SignatureScheme::Ed25519, &[seed; 32]).unwrap()
+    }
+
+    /// Attach the proposer's election proof for the block's slot and sign the block
+    fn elect(consensus: &PoASConsensus, keypair: &KeyPair, mut block: Block) -> Block {
+        let slot = consensus.block_slot(&block).unwrap();
+        block.header.slot = slot;
+        block.header.election = consensus.claim_slot(keypair, block.header.number, slot).unwrap();
+        block.sign(keypair).unwrap();
+        block
+    }
+
     #[test]
-    fn test_apply_reorg_and_slash() {
+    fn test_apply_reorg_pools_double_sign_evidence() {
         let config = ConsensusConfig::default();
         let mut consensus = PoASConsensus::new(config);
 
         // Create a validator and register
         let kp = KeyPair::generate(SignatureScheme::Ed25519).unwrap();
         let addr = kp.public_key().to_address();
-        consensus.register_validator(addr, StakeAmount::from_u64(100000), 100).unwrap();
+        consensus.add_genesis_validator(kp.public_key().clone(), StakeAmount::from_u64(100000), 100).unwrap();
 
         // Create two conflicting blocks at same height with same proposer to simulate double-sign
         let genesis = Block::genesis(Hash::zero());
         let mut block_main = Block::new(1, genesis.hash(), Hash::zero(), addr, vec![], 10_000_000).unwrap();
-        let mut block_fork = Block::new(1, genesis.hash(), Hash::zero(), addr, vec![], 10_000_000).unwrap();
+        let mut block_fork = Block::new(1, genesis.hash(), Hash::new([1u8; 32]), addr, vec![], 10_000_000).unwrap();
+        block_main.sign(&kp).unwrap();
+        block_fork.sign(&kp).unwrap();
 
         // Build a reorg path: revert main (one block) and apply fork (one block)
         let reorg = ReorgPath {
@@ -595,9 +1075,177 @@ mod tests {
         // Apply reorg without persistence (None)
         consensus.apply_reorg(reorg, None).unwrap();
 
-        // Validator should have been slashed for double-sign
+        // The double-sign is pooled as evidence; slashing waits for it to be executed on chain
+        assert_eq!(consensus.evidence_pool().len(), 1);
+        assert_eq!(consensus.evidence_pool().pending(1)[0].offender(), addr);
         let v = consensus.validator_set().get(&addr).unwrap();
-        assert!(v.stake.inner() < StakeAmount::from_u64(100000).inner());
+        assert_eq!(v.stake, StakeAmount::from_u64(100000));
+    }
+
+    #[test]
+    fn test_certificates_drive_finality() {
+        let clock = blockchain_core::ManualClock::new(0);
+        let mut consensus = PoASConsensus::new(ConsensusConfig::default());
+        consensus.set_clock(clock.shared());
+
+        let keys: Vec<KeyPair> = (1..=4).map(test_key).collect();
+        for key in &keys {
+            consensus.add_genesis_validator(key.public_key().clone(), StakeAmount::from_u64(100000), 100).unwrap();
+        }
+        let proposer = keys[0].public_key().to_address();
+        let leader = keys.iter()
+            .find(|key| consensus.claim_slot(key, 2, 2).unwrap().is_some())
+            .expect("some validator is elected for slot 2");
+
+        let genesis = Block::genesis(Hash::zero());
+        clock.advance(3);
+        let block1 = Block::new_with_clock(1, genesis.hash(), Hash::zero(), proposer, vec![], 10_000_000, &clock).unwrap();
+        assert!(!consensus.is_finalized(1));
+
+        // Prevotes then precommits from three of four validators finalize block 1
+        for vote_type in [VoteType::Prevote, VoteType::Precommit] {
+            for key in &keys[..3] {
+                let vote = Vote::sign(key, vote_type, 1, block1.hash()).unwrap();
+                consensus.submit_vote(vote).unwrap();
+            }
+        }
+        assert!(consensus.is_finalized(1));
+        assert_eq!(consensus.finality_records().len(), 1);
+
+        // The next block carries the certificate and passes validation
+        let mut pool = TransactionPool::new(Default::default());
+        clock.advance(3);
+        let block2 = consensus.produce_block(&block1, leader, &mut pool).unwrap();
+        assert_eq!(block2.validator_signatures.len(), 3);
+        assert!(block2.header.election.is_some());
+        assert!(consensus.validate_block(&block2, &block1).is_ok());
+
+        // A block whose certificate has been tampered with is rejected
+        let mut forged = block2.clone();
+        forged.validator_signatures.truncate(2);
+        assert!(consensus.validate_block(&forged, &block1).is_err());
+
+        // Reverting the finalized block is refused
+        let reorg = ReorgPath {
+            common_ancestor: genesis.hash(),
+            revert_blocks: vec![block1.clone(), block2.clone()],
+            apply_blocks: vec![Block::new(1, genesis.hash(), Hash::zero(), proposer, vec![], 10_000_000).unwrap()],
+            depth: 2,
+        };
+        assert!(consensus.apply_reorg(reorg, None).is_err());
+
+        // So is a branch carrying an invalid certificate
+        let reorg = ReorgPath {
+            common_ancestor: block1.hash(),
+            revert_blocks: vec![block2],
+            apply_blocks: vec![forged],
+            depth: 1,
+        };
+        assert!(consensus.apply_reorg(reorg, None).is_err());
+    }
+
+    #[test]
+    fn test_double_vote_evidence_pooled_and_slashed() {
+        let mut consensus = PoASConsensus::new(ConsensusConfig::default());
+        let keys: Vec<KeyPair> = (1..=4).map(test_key).collect();
+        for key in &keys {
+            consensus.add_genesis_validator(key.public_key().clone(), StakeAmount::from_u64(100000), 100).unwrap();
+        }
+        let offender = keys[0].public_key().to_address();
+
+        consensus.submit_vote(Vote::sign(&keys[0], VoteType::Prevote, 1, Hash::new([1u8; 32])).unwrap()).unwrap();
+        let conflicting = Vote::sign(&keys[0], VoteType::Prevote, 1, Hash::new([2u8; 32])).unwrap();
+        assert!(matches!(consensus.submit_vote(conflicting), Err(ConsensusError::DoubleSign(_))));
+        assert_eq!(consensus.evidence_pool().len(), 1);
+
+        // The network service sees the same pool and gossips the new evidence
+        let shared = consensus.shared_evidence_pool();
+        assert_eq!(lock_pool(&shared).take_unannounced().len(), 1);
+
+        // Including the evidence slashes the offender and empties the pool
+        let evidence = consensus.evidence_pool().pending(1).remove(0);
+        let tx = crate::evidence::submit_evidence_transaction(&evidence, &keys[1], 0, 1).unwrap();
+        let block = Block::new(1, Hash::zero(), Hash::zero(), keys[1].public_key().to_address(), vec![tx], 10_000_000).unwrap();
+        consensus.finalize_block(&block).unwrap();
+
+        assert!(consensus.evidence_pool().is_empty());
+        assert_eq!(consensus.validator_set().get(&offender).unwrap().stake, StakeAmount::from_u64(95000));
+        assert!(!consensus.submit_evidence(evidence).unwrap());
+    }
+
+    #[test]
+    fn test_missed_slots_jail_and_unjail() {
+        let config = ConsensusConfig {
+            liveness_window: 4,
+            jail_duration: 3,
+            required_uptime: 7500,
+            max_fallback_proposers: 1,
+            ..Default::default()
+        };
+        let clock = blockchain_core::ManualClock::new(0);
+        let mut consensus = PoASConsensus::new(config);
+        consensus.set_clock(clock.shared());
+        let keys: Vec<KeyPair> = (1..=7).map(test_key).collect();
+        for key in &keys {
+            consensus.add_genesis_validator(key.public_key().clone(), StakeAmount::from_u64(288_000_000), 100).unwrap();
+        }
+        let late = keys[5].public_key().to_address();
+        let offline = keys[6].public_key().to_address();
+        let slots = *consensus.slot_clock();
+        let block_at = |number: BlockNumber, slot: u64, parent: &Block, proposer: Address, txs| {
+            clock.set(slots.slot_start(slot));
+            Block::new_with_clock(number, parent.hash(), Hash::zero(), proposer, txs, 10_000_000, &clock).unwrap()
+        };
+
+        // Every slot's fallback proposes except the offline validator, whose
+        // slots stay empty. Certificates close with five of seven votes, so
+        // the late signer never appears in them.
+        let mut parent = Block::genesis(Hash::zero());
+        let mut slot = 0;
+        while consensus.validator_set().get(&offline).unwrap().is_active() {
+            slot += 1;
+            let number = parent.number() + 1;
+            let fallback = consensus.fallback_proposers(number, slot).unwrap()[0];
+            if fallback == offline {
+                continue;
+            }
+            let mut block = block_at(number, slot, &parent, fallback, vec![]);
+            if number > 1 {
+                block.validator_signatures = keys[..5].iter()
+                    .map(|key| Vote::sign(key, VoteType::Precommit, number - 1, parent.hash()).unwrap().to_validator_signature())
+                    .collect();
+            }
+            consensus.finalize_block(&block).unwrap();
+            parent = block;
+        }
+
+        // Four missed slots fill the window and jail the validator, slashing 0.1% per day of slots
+        let validator = consensus.validator_set().get(&offline).unwrap();
+        let release_height = parent.number() + 3;
+        assert_eq!(validator.status, ValidatorStatus::Jailed { release_height });
+        assert_eq!(validator.blocks_missed, 4);
+        // 4 slots of a day's 0.1% is 40, rounded down in fixed point
+        assert_eq!(validator.stake, StakeAmount::from_u64(288_000_000 - 39));
+        assert!(!consensus.validator_set().active_validators().iter().any(|v| v.address == offline));
+
+        // Signing late is not held against a validator
+        let validator = consensus.validator_set().get(&late).unwrap();
+        assert!(validator.is_active());
+        assert_eq!(validator.blocks_missed, 0);
+        assert_eq!(validator.uptime, 10000);
+
+        // Unjailing before the jail time is served is rejected
+        let unjail = |number: BlockNumber, slot: u64, parent: &Block| {
+            let mut tx = blockchain_core::Transaction::new(offline, 0, TransactionType::Unjail, 1, 21_000);
+            tx.sign(&keys[6]).unwrap();
+            block_at(number, slot, parent, offline, vec![tx])
+        };
+        assert!(consensus.check_unjail(&offline, release_height - 1).is_err());
+        assert!(consensus.finalize_block(&unjail(release_height - 1, slot + 1, &parent)).is_err());
+
+        consensus.finalize_block(&unjail(release_height, slot + 2, &parent)).unwrap();
+        assert!(consensus.validator_set().get(&offline).unwrap().is_active());
+        assert!(consensus.check_unjail(&offline, release_height + 1).is_err());
     }
 
     #[test]
@@ -631,6 +1279,216 @@ mod tests {
         assert_eq!(max_depth, 0);
     }
 
+    #[test]
+    fn test_blocks_signed_by_registered_key() {
+        let clock = blockchain_core::ManualClock::new(0);
+        let mut consensus = PoASConsensus::new(ConsensusConfig::default());
+        consensus.set_clock(clock.shared());
+
+        let kp = test_key(1);
+        let impostor = test_key(2);
+        let addr = kp.public_key().to_address();
+        consensus.add_genesis_validator(kp.public_key().clone(), StakeAmount::from_u64(100000), 100).unwrap();
+        assert_eq!(&consensus.validator_set().get(&addr).unwrap().consensus_key, kp.public_key());
+
+        let genesis = Block::genesis(Hash::zero());
+        clock.advance(3);
+        let mut pool = TransactionPool::new(Default::default());
+        let block = consensus.produce_block(&genesis, &kp, &mut pool).unwrap();
+        assert!(block.proposer_signature.is_some());
+        assert!(consensus.validate_block(&block, &genesis).is_ok());
+
+        // Unsigned blocks are rejected
+        let mut unsigned = block.clone();
+        unsigned.proposer_signature = None;
+        assert!(consensus.validate_block(&unsigned, &genesis).is_err());
+
+        // So are blocks signed with a key other than the registered one
+        let mut forged = block.clone();
+        forged.proposer_signature = Some(impostor.sign(&forged.header.signing_bytes()).unwrap());
+        assert!(consensus.validate_block(&forged, &genesis).is_err());
+
+        // And blocks whose header changed after signing
+        let mut tampered = block;
+        tampered.header.state_root = Hash::new([1u8; 32]);
+        assert!(consensus.validate_block(&tampered, &genesis).is_err());
+    }
+
+    #[test]
+    fn test_producer_and_peer_agree_on_liveness() {
+        let clock = blockchain_core::ManualClock::new(0);
+        let kp = test_key(1);
+        let addr = kp.public_key().to_address();
+        let node = || {
+            let mut consensus = PoASConsensus::new(ConsensusConfig::default());
+            consensus.set_clock(clock.shared());
+            consensus.add_genesis_validator(kp.public_key().clone(), StakeAmount::from_u64(100000), 100).unwrap();
+            consensus
+        };
+        let mut producer = node();
+        let mut peer = node();
+
+        let genesis = Block::genesis(Hash::zero());
+        clock.advance(3);
+        let mut pool = TransactionPool::new(Default::default());
+        let block = producer.produce_block(&genesis, &kp, &mut pool).unwrap();
+        peer.validate_block(&block, &genesis).unwrap();
+        producer.finalize_block(&block).unwrap();
+        peer.finalize_block(&block).unwrap();
+
+        // Only finalization updates liveness, so both nodes hold the same validator state
+        let ours = producer.validator_set().get(&addr).unwrap();
+        let theirs = peer.validator_set().get(&addr).unwrap();
+        assert_eq!(ours.blocks_produced, 1);
+        assert_eq!(ours.blocks_produced, theirs.blocks_produced);
+        assert_eq!(ours.blocks_missed, theirs.blocks_missed);
+        assert_eq!(ours.uptime, theirs.uptime);
+        assert_eq!(ours.last_active, theirs.last_active);
+        assert_eq!(producer.next_validators_hash(block.header.timestamp), peer.next_validators_hash(block.header.timestamp));
+    }
+
+    #[test]
+    fn test_first_block_after_genesis_jails_nobody() {
+        let genesis_time = 1_760_000_000;
+        let clock = blockchain_core::ManualClock::new(genesis_time);
+        let mut consensus = PoASConsensus::new(ConsensusConfig::default());
+        consensus.set_clock(clock.shared());
+        let mut genesis = Block::genesis(Hash::zero());
+        genesis.header.timestamp = genesis_time;
+        consensus.set_genesis(&genesis);
+
+        let keys: Vec<KeyPair> = (1..=7).map(test_key).collect();
+        for kp in &keys {
+            consensus.add_genesis_validator(kp.public_key().clone(), StakeAmount::from_u64(100000), 100).unwrap();
+        }
+
+        // Late in slot 1 every fallback window is open; whoever may propose does
+        clock.advance(5);
+        let mut pool = TransactionPool::new(Default::default());
+        let block = keys.iter()
+            .find_map(|kp| consensus.produce_block(&genesis, kp, &mut pool).ok())
+            .unwrap();
+        assert_eq!(block.header.slot, 1);
+        consensus.finalize_block(&block).unwrap();
+
+        assert_eq!(consensus.empty_slots(), 0);
+        for validator in consensus.validator_set().all_validators() {
+            assert_eq!(validator.status, ValidatorStatus::Active);
+            assert_eq!(validator.blocks_missed, 0);
+        }
+    }
+
+    #[test]
+    fn test_block_slots_in_virtual_time() {
+        let clock = blockchain_core::ManualClock::new(0);
+        let mut consensus = PoASConsensus::new(ConsensusConfig::default());
+        consensus.set_clock(clock.shared());
+
+        let kp = KeyPair::generate(SignatureScheme::Ed25519).unwrap();
+        let addr = kp.public_key().to_address();
+        consensus.add_genesis_validator(kp.public_key().clone(), StakeAmount::from_u64(100000), 100).unwrap();
+        let block = |consensus: &PoASConsensus, number: BlockNumber, parent: &Block| {
+            let block = Block::new_with_clock(number, parent.hash(), Hash::zero(), addr, vec![], 10_000_000, &clock).unwrap();
+            elect(consensus, &kp, block)
+        };
+
+        let genesis = Block::genesis(Hash::zero());
+        clock.advance(3);
+        let block1 = block(&consensus, 1, &genesis);
+        assert_eq!(consensus.block_slot(&block1).unwrap(), 1);
+        assert!(consensus.validate_block(&block1, &genesis).is_ok());
+
+        // The header's slot must match its timestamp
+        let mut mislabeled = block1.clone();
+        mislabeled.header.slot = 2;
+        mislabeled.sign(&kp).unwrap();
+        assert!(consensus.validate_block(&mislabeled, &genesis).is_err());
+        consensus.finalize_block(&block1).unwrap();
+
+        // A second block in the same slot is rejected
+        clock.advance(1);
+        assert!(consensus.validate_block(&block(&consensus, 2, &block1), &block1).is_err());
+
+        // A block timestamped beyond the allowed clock drift is rejected
+        let mut early = Block::new_with_clock(2, block1.hash(), Hash::zero(), addr, vec![], 10_000_000, &clock).unwrap();
+        early.header.timestamp = blockchain_core::Clock::now(&clock) + MAX_CLOCK_DRIFT + 1;
+        let early = elect(&consensus, &kp, early);
+        assert!(consensus.validate_block(&early, &block1).is_err());
+
+        // A proposer that is 10s late skips the empty slots in between
+        clock.advance(12);
+        let late = block(&consensus, 2, &block1);
+        assert_eq!(consensus.block_slot(&late).unwrap(), 5);
+        assert!(consensus.validate_block(&late, &block1).is_ok());
+        consensus.finalize_block(&late).unwrap();
+        assert_eq!(consensus.empty_slots(), 3);
+    }
+
+    #[test]
+    fn test_fallback_proposer_after_timeout() {
+        let clock = blockchain_core::ManualClock::new(0);
+        let mut consensus = PoASConsensus::new(ConsensusConfig::default());
+        consensus.set_clock(clock.shared());
+
+        let keys: Vec<KeyPair> = (1..=4).map(test_key).collect();
+        for key in &keys {
+            consensus.add_genesis_validator(key.public_key().clone(), StakeAmount::from_u64(100000), 100).unwrap();
+        }
+        let genesis = Block::genesis(Hash::zero());
+        let mut pool = TransactionPool::new(Default::default());
+
+        // A slot whose first fallback was not also elected
+        let (slot, fallback) = (1..).find_map(|slot| {
+            let first = consensus.fallback_proposers(1, slot).unwrap()[0];
+            let key = keys.iter().find(|key| key.public_key().to_address() == first).unwrap();
+            consensus.claim_slot(key, 1, slot).unwrap().is_none().then_some((slot, key))
+        }).unwrap();
+        assert_eq!(consensus.proposer_role(fallback, 1, slot).unwrap(), Some(ProposerRole::Fallback(1)));
+        let bystander = keys.iter().find(|key| consensus.proposer_role(key, 1, slot).unwrap().is_none());
+
+        // The fallback waits out the elected proposer's window
+        clock.set(consensus.slot_clock().slot_start(slot));
+        assert!(consensus.produce_block(&genesis, fallback, &mut pool).is_err());
+
+        clock.advance(consensus.config().proposer_timeout);
+        let block = consensus.produce_block(&genesis, fallback, &mut pool).unwrap();
+        assert!(block.header.election.is_none());
+        assert!(consensus.validate_block(&block, &genesis).is_ok());
+
+        // Backdating the block into the elected proposer's window is rejected
+        let mut early = block.clone();
+        early.header.timestamp = consensus.slot_clock().slot_start(slot);
+        early.sign(fallback).unwrap();
+        assert!(consensus.validate_block(&early, &genesis).is_err());
+
+        // Validators that are neither elected nor ranked cannot propose
+        if let Some(bystander) = bystander {
+            assert!(consensus.produce_block(&genesis, bystander, &mut pool).is_err());
+            let mut taken = block.clone();
+            taken.header.proposer = bystander.public_key().to_address();
+            taken.sign(bystander).unwrap();
+            assert!(consensus.validate_block(&taken, &genesis).is_err());
+        }
+    }
+
+    #[test]
+    fn test_unbonding_completes_at_epoch_in_virtual_time() {
+        let clock = blockchain_core::ManualClock::new(1_000);
+        let mut consensus = PoASConsensus::new(ConsensusConfig::default());
+        consensus.set_clock(clock.shared());
+
+        let kp = KeyPair::generate(SignatureScheme::Ed25519).unwrap();
+        let addr = kp.public_key().to_address();
+        consensus.add_genesis_validator(kp.public_key().clone(), StakeAmount::from_u64(100000), 100).unwrap();
+        assert_eq!(consensus.validator_set().get(&addr).unwrap().registered_at, 1_000);
+
+        consensus.validator_set_mut().begin_unbonding(&addr, &StakeAmount::from_u64(1000)).unwrap();
+        assert!(consensus.validator_set_mut().process_unbonding_now().is_empty());
+
+        clock.advance(consensus.config().unbonding_period);
+        assert_eq!(consensus.validator_set_mut().process_unbonding_now(), vec![addr]);
+    }
+
     #[test]
     fn test_consensus_creation() {
         let config = ConsensusConfig::default();
@@ -646,10 +1504,8 @@ mod tests {
         let mut consensus = PoASConsensus::new(config);
         
         let keypair = KeyPair::generate(SignatureScheme::Ed25519).unwrap();
-        let address = keypair.public_key().to_address();
-        
-        consensus.register_validator(
-            address,
+        consensus.add_genesis_validator(
+            keypair.public_key().clone(),
             StakeAmount::from_u64(20000),
             500,
         ).unwrap();
@@ -660,22 +1516,146 @@ mod tests {
 
     #[test]
     fn test_proposer_selection() {
+        let clock = blockchain_core::ManualClock::new(0);
         let config = ConsensusConfig::default();
         let mut consensus = PoASConsensus::new(config);
+        consensus.set_clock(clock.shared());
         
         // Register multiple validators
-        for i in 0..5 {
-            let keypair = KeyPair::generate(SignatureScheme::Ed25519).unwrap();
-            let address = keypair.public_key().to_address();
-            consensus.register_validator(
-                address,
-                StakeAmount::from_u64(10000 + i * 1000),
+        let keys: Vec<KeyPair> = (1..=5).map(test_key).collect();
+        for (i, keypair) in keys.iter().enumerate() {
+            consensus.add_genesis_validator(
+                keypair.public_key().clone(),
+                StakeAmount::from_u64(10000 + i as u64 * 1000),
                 100,
             ).unwrap();
         }
-        
-        let proposer = consensus.select_proposer(1).unwrap();
-        assert!(consensus.validator_set().get(&proposer).is_some());
+
+        let genesis = Block::genesis(Hash::zero());
+        clock.advance(3);
+        let block = |keypair: &KeyPair| Block::new_with_clock(
+            1, genesis.hash(), Hash::zero(), keypair.public_key().to_address(), vec![], 10_000_000, &clock,
+        ).unwrap();
+
+        for keypair in &keys {
+            let claimed = elect(&consensus, keypair, block(keypair));
+            if claimed.header.election.is_some() {
+                assert!(consensus.validate_block(&claimed, &genesis).is_ok());
+            } else {
+                // Unelected validators cannot pass off a block without a proof...
+                assert!(consensus.validate_block(&claimed, &genesis).is_err());
+            }
+        }
+
+        // ...nor borrow another validator's proof
+        let leader = keys.iter()
+            .find(|key| consensus.claim_slot(key, 1, 1).unwrap().is_some())
+            .expect("some validator is elected for slot 1");
+        let other = keys.iter().find(|key| key.public_key() != leader.public_key()).unwrap();
+        let mut stolen = block(other);
+        stolen.header.election = consensus.claim_slot(leader, 1, 1).unwrap();
+        stolen.sign(other).unwrap();
+        assert!(consensus.validate_block(&stolen, &genesis).is_err());
+
+        // Blocks outside the current epoch cannot be claimed yet
+        let next_epoch = consensus.blocks_per_epoch + 1;
+        assert!(consensus.claim_slot(leader, next_epoch, next_epoch).is_err());
+    }
+
+    #[test]
+    fn test_epoch_randomness_from_proposer_outputs() {
+        let clock = blockchain_core::ManualClock::new(0);
+        let mut consensus = PoASConsensus::new(ConsensusConfig::default());
+        consensus.set_clock(clock.shared());
+        consensus.blocks_per_epoch = 2;
+
+        let kp = test_key(1);
+        let addr = kp.public_key().to_address();
+        consensus.add_genesis_validator(kp.public_key().clone(), StakeAmount::from_u64(100000), 100).unwrap();
+        let seed = *consensus.election().randomness().current();
+
+        let mut parent = Block::genesis(Hash::zero());
+        for number in 1..=2 {
+            clock.advance(3);
+            let mut block = Block::new_with_clock(number, parent.hash(), Hash::zero(), addr, vec![], 10_000_000, &clock).unwrap();
+            if consensus.is_epoch_end(number) {
+                block.header.next_validators_hash = Some(consensus.next_validators_hash(block.header.timestamp));
+            }
+            let block = elect(&consensus, &kp, block);
+            consensus.validate_block(&block, &parent).unwrap();
+            consensus.finalize_block(&block).unwrap();
+            parent = block;
+        }
+
+        // Finalizing the epoch's last block switches to the mixed randomness
+        assert_eq!(consensus.current_epoch(), 1);
+        assert_eq!(consensus.election().randomness().epoch(), 1);
+        assert_ne!(consensus.election().randomness().current(), &seed);
+
+        // Proofs from the previous epoch no longer verify
+        clock.advance(3);
+        let stale = Block::new_with_clock(3, parent.hash(), Hash::zero(), addr, vec![], 10_000_000, &clock).unwrap();
+        let mut stale = elect(&consensus, &kp, stale);
+        assert!(consensus.validate_block(&stale, &parent).is_ok());
+        stale.header.election = parent.header.election.clone();
+        stale.sign(&kp).unwrap();
+        assert!(consensus.validate_block(&stale, &parent).is_err());
+    }
+
+    #[test]
+    fn test_validator_changes_apply_at_epoch_boundary() {
+        let config = ConsensusConfig {
+            target_validator_count: 2,
+            ..Default::default()
+        };
+        let clock = blockchain_core::ManualClock::new(0);
+        let mut consensus = PoASConsensus::new(config);
+        consensus.set_clock(clock.shared());
+        consensus.blocks_per_epoch = 2;
+        let keys: Vec<KeyPair> = (1..=3).map(test_key).collect();
+        let [a, c] = [0, 2].map(|i| keys[i].public_key().to_address());
+        consensus.add_genesis_validator(keys[0].public_key().clone(), StakeAmount::from_u64(100_000), 100).unwrap();
+        consensus.add_genesis_validator(keys[1].public_key().clone(), StakeAmount::from_u64(50_000), 100).unwrap();
+
+        // A heavier validator joins, but only from the next epoch
+        consensus.register_validator(keys[2].public_key().clone(), StakeAmount::from_u64(200_000), 100).unwrap();
+        assert!(consensus.register_validator(keys[2].public_key().clone(), StakeAmount::from_u64(200_000), 100).is_err());
+        assert!(consensus.validator_set().get(&c).is_none());
+
+        let block = |number: BlockNumber, parent: &Block, proposer: Address| {
+            clock.advance(3);
+            Block::new_with_clock(number, parent.hash(), Hash::zero(), proposer, vec![], 10_000_000, &clock).unwrap()
+        };
+
+        let genesis = Block::genesis(Hash::zero());
+        let first = elect(&consensus, &keys[0], block(1, &genesis, a));
+        consensus.validate_block(&first, &genesis).unwrap();
+        consensus.finalize_block(&first).unwrap();
+        assert!(consensus.validator_set().get(&c).is_none());
+
+        // The epoch's last block must commit to the next set
+        let mut last = block(2, &first, a);
+        let last_unset = elect(&consensus, &keys[0], last.clone());
+        assert!(consensus.validate_block(&last_unset, &first).is_err());
+        last.header.next_validators_hash = Some(Hash::zero());
+        assert!(consensus.validate_block(&elect(&consensus, &keys[0], last.clone()), &first).is_err());
+        last.header.next_validators_hash = Some(consensus.next_validators_hash(last.header.timestamp));
+        let last = elect(&consensus, &keys[0], last);
+        consensus.validate_block(&last, &first).unwrap();
+        consensus.finalize_block(&last).unwrap();
+
+        // The new set holds the two heaviest validators and matches the commitment
+        let mut active: Vec<Address> = consensus.validator_set().active_validators().iter().map(|v| v.address).collect();
+        active.sort();
+        let mut expected = vec![a, c];
+        expected.sort();
+        assert_eq!(active, expected);
+        assert_eq!(last.header.next_validators_hash, Some(consensus.validator_set().active_set_hash()));
+
+        // Other blocks must not carry a commitment
+        let mut next = block(3, &last, c);
+        next.header.next_validators_hash = Some(consensus.validator_set().active_set_hash());
+        assert!(consensus.validate_block(&elect(&consensus, &keys[2], next), &last).is_err());
     }
 
     #[test]
@@ -686,9 +1666,8 @@ mod tests {
         // Register validators
         for i in 0..10 {
             let keypair = KeyPair::generate(SignatureScheme::Ed25519).unwrap();
-            let address = keypair.public_key().to_address();
-            consensus.register_validator(
-                address,
+            consensus.add_genesis_validator(
+                keypair.public_key().clone(),
                 StakeAmount::from_u64(10000 + i * 5000),
                 100,
             ).unwrap();
@@ -1,6 +1,7 @@
 // consensus/src/selection.rs
 
 use crate::{validator::ValidatorInfo, ConsensusError, ConsensusResult};
+use blockchain_core::Fixed;
 use blockchain_crypto::Address;
 use rand::{Rng, SeedableRng};
 use rand::rngs::StdRng;
@@ -10,7 +11,8 @@ use serde::{Deserialize, Serialize};
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SelectionWeight {
     pub address: Address,
-    pub weight: u64,
+    /// Weight in stake base units, wide enough for any realistic stake
+    pub weight: u128,
 }
 
 /// Validator selector implementing the PoAS selection algorithm
@@ -27,51 +29,46 @@ impl ValidatorSelector {
         }
     }
 
-    /// Create with a random seed
-    pub fn new_random() -> Self {
-        Self {
-            rng: StdRng::from_entropy(),
-        }
-    }
-
     /// Calculate selection weight for a validator
     /// Weight = Staked_Amount × Utility_Score × Reliability_Factor
-    pub fn calculate_weight(&self, validator: &ValidatorInfo) -> u64 {
+    pub fn calculate_weight(&self, validator: &ValidatorInfo) -> u128 {
+        Self::weight(validator)
+    }
+
+    /// Selection weight of a validator; zero if it cannot produce blocks
+    pub fn weight(validator: &ValidatorInfo) -> u128 {
         if !validator.can_produce_blocks() {
             return 0;
         }
 
-        // Get stake amount (as u64 for simplicity, in production would handle BigUint properly)
-        let stake = validator.stake.inner()
-            .to_u64_digits()
-            .first()
-            .copied()
-            .unwrap_or(0);
+        // Utility score (0-10000) as a fraction, so utility/10 on the 0-10 scale
+        let utility = Fixed::from_bps(validator.utility_score.value());
 
-        // Get utility score (scaled 0-10000)
-        let utility = validator.utility_score.value() as f64 / 1000.0; // Convert to 0-10 range
-
-        // Get reliability factor (0.0-1.0)
+        // Get reliability factor (0-1)
         let reliability = validator.reliability_factor();
 
-        // Get efficiency score (0.0-1.0)
+        // Get efficiency score (0-1)
         let efficiency = validator.efficiency_score();
 
         // Calculate weight with formula: stake × (1 + utility/10) × reliability × (1 + efficiency)
-        let weight = (stake as f64) 
-            * (1.0 + utility / 10.0) 
-            * reliability 
-            * (1.0 + efficiency);
+        let multiplier = (Fixed::ONE + utility)
+            * reliability
+            * (Fixed::ONE + efficiency);
 
-        weight as u64
+        validator.stake.mul_fixed(multiplier).saturating_to_u128()
     }
 
     /// Calculate weights for all validators
     pub fn calculate_weights(&self, validators: &[&ValidatorInfo]) -> Vec<SelectionWeight> {
+        Self::weights(validators)
+    }
+
+    /// Non-zero selection weights of `validators`
+    pub fn weights(validators: &[&ValidatorInfo]) -> Vec<SelectionWeight> {
         validators.iter()
             .map(|v| SelectionWeight {
                 address: v.address,
-                weight: self.calculate_weight(v),
+                weight: Self::weight(v),
             })
             .filter(|w| w.weight > 0)
             .collect()
@@ -89,7 +86,7 @@ impl ValidatorSelector {
         }
 
         // Calculate total weight
-        let total_weight: u64 = weights.iter().map(|w| w.weight).sum();
+        let total_weight = weights.iter().fold(0u128, |total, w| total.saturating_add(w.weight));
         if total_weight == 0 {
             return Err(ConsensusError::SelectionError("Total weight is zero".into()));
         }
@@ -135,42 +132,6 @@ impl ValidatorSelector {
 
         Ok(selected)
     }
-
-    /// Deterministically select validator for a specific slot
-    /// Used for round-robin or time-based selection
-    pub fn select_for_slot(
-        &self,
-        validators: &[&ValidatorInfo],
-        slot: u64,
-    ) -> ConsensusResult<Address> {
-        if validators.is_empty() {
-            return Err(ConsensusError::SelectionError("No validators available".into()));
-        }
-
-        let weights = self.calculate_weights(validators);
-        if weights.is_empty() {
-            return Err(ConsensusError::SelectionError("No eligible validators".into()));
-        }
-
-        // Weighted deterministic selection based on slot
-        let total_weight: u64 = weights.iter().map(|w| w.weight).sum();
-        if total_weight == 0 {
-            return Err(ConsensusError::SelectionError("Total weight is zero".into()));
-        }
-
-        let selection = slot % total_weight;
-        let mut accumulated = 0u64;
-
-        for weight in &weights {
-            accumulated += weight.weight;
-            if selection < accumulated {
-                return Ok(weight.address);
-            }
-        }
-
-        // Fallback
-        Ok(weights[0].address)
-    }
 }
 
 /// Probability calculator for validator selection
@@ -178,7 +139,7 @@ pub struct SelectionProbability;
 
 impl SelectionProbability {
     /// Calculate probability of being selected for a single block
-    pub fn calculate_probability(validator: &ValidatorInfo, total_weight: u64) -> f64 {
+    pub fn calculate_probability(validator: &ValidatorInfo, total_weight: u128) -> f64 {
         let selector = ValidatorSelector::new(0);
         let validator_weight = selector.calculate_weight(validator);
         
@@ -192,7 +153,7 @@ impl SelectionProbability {
     /// Calculate expected blocks per epoch
     pub fn expected_blocks(
         validator: &ValidatorInfo,
-        total_weight: u64,
+        total_weight: u128,
         epoch_blocks: u64,
     ) -> f64 {
         let prob = Self::calculate_probability(validator, total_weight);
@@ -209,14 +170,14 @@ impl SelectionProbability {
         // Sort descending
         weights.sort_by(|a, b| b.cmp(a));
 
-        let total_weight: u64 = weights.iter().sum();
+        let total_weight = weights.iter().fold(0u128, |total, w| total.saturating_add(*w));
         let threshold = total_weight / 3;
 
-        let mut accumulated = 0u64;
+        let mut accumulated = 0u128;
         let mut count = 0;
 
         for weight in weights {
-            accumulated += weight;
+            accumulated = accumulated.saturating_add(weight);
             count += 1;
             if accumulated >= threshold {
                 break;
@@ -259,16 +220,17 @@ impl SelectionProbability {
 mod tests {
     use super::*;
     use blockchain_core::{StakeAmount, UtilityScore};
+    use blockchain_crypto::{PublicKey, SignatureScheme};
 
     fn create_test_validator(stake: u64, utility: u64, uptime: u16) -> ValidatorInfo {
-        // Create a deterministic, unique address per stake for tests
-        let mut bytes = [0u8; 20];
+        // Create a deterministic, unique key per stake for tests
+        let mut bytes = [0u8; 32];
         let s_bytes = stake.to_be_bytes();
-        bytes[12..20].copy_from_slice(&s_bytes);
-        let addr = Address::new(bytes);
+        bytes[24..32].copy_from_slice(&s_bytes);
+        let key = PublicKey::new(SignatureScheme::Ed25519, bytes.to_vec());
 
         let mut validator = ValidatorInfo::new(
-            addr,
+            key,
             StakeAmount::from_u64(stake),
             100,
         );
@@ -282,8 +244,38 @@ mod tests {
         let selector = ValidatorSelector::new(42);
         let validator = create_test_validator(10000, 5000, 9500);
 
+        // 10000 × (1 + 0.5) × 0.95 × (1 + 0)
         let weight = selector.calculate_weight(&validator);
-        assert!(weight > 0);
+        assert_eq!(weight, 14250);
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn prop_weight_matches_integer_reference(
+            stake in 0u64..1_000_000_000_000,
+            utility in 0u64..=10000,
+            uptime in 0u16..=10000,
+        ) {
+            let selector = ValidatorSelector::new(42);
+            let validator = create_test_validator(stake, utility, uptime);
+
+            let reference = stake as u128 * (10000 + utility as u128) * uptime as u128 / 100_000_000;
+            proptest::prop_assert_eq!(selector.calculate_weight(&validator), reference);
+        }
+    }
+
+    #[test]
+    fn test_weights_above_u64_stay_proportional() {
+        let key = |seed: u8| PublicKey::new(SignatureScheme::Ed25519, vec![seed; 32]);
+        let small = ValidatorInfo::new(key(1), StakeAmount::from_tokens(100), 100);
+        let large = ValidatorInfo::new(key(2), StakeAmount::from_tokens(300), 100);
+
+        // Both stakes exceed u64::MAX base units
+        let small_weight = ValidatorSelector::weight(&small);
+        let large_weight = ValidatorSelector::weight(&large);
+        assert!(small_weight > u64::MAX as u128);
+        assert_eq!(small_weight, 100 * 10u128.pow(18));
+        assert_eq!(large_weight, 3 * small_weight);
     }
 
     #[test]
@@ -317,21 +309,6 @@ mod tests {
         assert_eq!(unique.len(), 5);
     }
 
-    #[test]
-    fn test_deterministic_selection() {
-        let selector = ValidatorSelector::new(42);
-        
-        let validators: Vec<_> = (0..5)
-            .map(|i| create_test_validator(10000, 5000 + i * 100, 9500))
-            .collect();
-        let validator_refs: Vec<_> = validators.iter().collect();
-
-        let selected1 = selector.select_for_slot(&validator_refs, 100).unwrap();
-        let selected2 = selector.select_for_slot(&validator_refs, 100).unwrap();
-
-        assert_eq!(selected1, selected2);
-    }
-
     #[test]
     fn test_nakamoto_coefficient() {
         let validators: Vec<_> = vec![
@@ -1,18 +1,23 @@
 // consensus/src/slashing.rs
 
 use crate::{validator::ValidatorInfo, ConsensusError, ConsensusResult};
-use blockchain_core::{StakeAmount, Timestamp};
+use blockchain_core::{clock::{system_clock, SharedClock}, evidence::DOUBLE_SIGN_SLASH_BPS, Fixed, StakeAmount, Timestamp};
 use blockchain_crypto::Address;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+pub use blockchain_core::evidence::SlashingDistribution;
+
+/// Slots per day at 3s slots, the unit of downtime penalties
+pub const SLOTS_PER_DAY: u64 = 28800;
+
 /// Slashing conditions as defined in the whitepaper
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum SlashingCondition {
     /// Double-signing blocks: 5% stake slash
     DoubleSigning,
-    /// Extended downtime: 0.1% slash per day
-    ExtendedDowntime { days: u64 },
+    /// Extended downtime: 0.1% slash per day of missed slots
+    ExtendedDowntime { missed_slots: u64 },
     /// Liquidity mismanagement: 10% slash
     LiquidityMismanagement,
     /// Governance attack attempts: 100% slash
@@ -20,23 +25,37 @@ pub enum SlashingCondition {
 }
 
 impl SlashingCondition {
-    /// Get the base penalty rate (in basis points, 0-10000)
+    /// Get the base penalty rate (in basis points, 0-10000, rounded down)
     pub fn base_penalty_rate(&self) -> u16 {
         match self {
-            SlashingCondition::DoubleSigning => 500,        // 5%
-            SlashingCondition::ExtendedDowntime { days } => {
+            SlashingCondition::DoubleSigning => DOUBLE_SIGN_SLASH_BPS as u16, // 5%, as executed on chain
+            SlashingCondition::ExtendedDowntime { missed_slots } => {
                 // 0.1% per day, max 100%
-                (10 * days).min(10000) as u16
+                (missed_slots.saturating_mul(10) / SLOTS_PER_DAY).min(10000) as u16
             }
             SlashingCondition::LiquidityMismanagement => 1000, // 10%
             SlashingCondition::GovernanceAttack => 10000,   // 100%
         }
     }
 
+    /// Get the exact base penalty as a fraction of stake
+    ///
+    /// Downtime is charged per missed slot rather than per whole day.
+    pub fn penalty_rate(&self) -> Fixed {
+        match self {
+            SlashingCondition::ExtendedDowntime { missed_slots } => {
+                Fixed::from_ratio(missed_slots.saturating_mul(10), SLOTS_PER_DAY * 10000)
+                    .unwrap_or(Fixed::ONE)
+                    .min(Fixed::ONE)
+            }
+            _ => Fixed::from_bps(self.base_penalty_rate() as u64),
+        }
+    }
+
     /// Get severity multiplier based on previous offenses
-    pub fn severity_multiplier(&self, previous_offenses: u32) -> f64 {
-        let base = 1.0 + (previous_offenses as f64 * 0.5);
-        base.min(3.0) // Max 3x multiplier
+    pub fn severity_multiplier(&self, previous_offenses: u32) -> Fixed {
+        let escalation = Fixed::from_ratio(previous_offenses as u64, 2).unwrap_or(Fixed::MAX);
+        (Fixed::ONE + escalation).min(Fixed::from_int(3)) // Max 3x multiplier
     }
 
     /// Check if this is a capital offense (requires immediate exit)
@@ -62,35 +81,6 @@ pub struct SlashingPenalty {
     pub evidence_hash: Option<blockchain_crypto::Hash>,
 }
 
-/// Fund distribution for slashed tokens
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct SlashingDistribution {
-    /// Amount to burn (50%)
-    pub burn: StakeAmount,
-    /// Amount to insurance fund (30%)
-    pub insurance: StakeAmount,
-    /// Amount to whistleblower reward (20%)
-    pub whistleblower: StakeAmount,
-}
-
-impl SlashingDistribution {
-    /// Calculate distribution from slashed amount
-    pub fn from_slashed_amount(amount: &StakeAmount) -> Self {
-        let total = amount.inner();
-        
-        // Calculate percentages
-        let burn_amount = (total * 50u64) / 100u64;
-        let insurance_amount = (total * 30u64) / 100u64;
-        let whistleblower_amount = (total * 20u64) / 100u64;
-
-        Self {
-            burn: StakeAmount::new(burn_amount),
-            insurance: StakeAmount::new(insurance_amount),
-            whistleblower: StakeAmount::new(whistleblower_amount),
-        }
-    }
-}
-
 /// Slashing manager
 pub struct SlashingManager {
     /// Slashing events history
@@ -103,6 +93,8 @@ pub struct SlashingManager {
     total_slashed: StakeAmount,
     /// Total burned
     total_burned: StakeAmount,
+    /// Time source for penalty records
+    clock: SharedClock,
 }
 
 impl SlashingManager {
@@ -114,31 +106,30 @@ impl SlashingManager {
             insurance_fund: StakeAmount::zero(),
             total_slashed: StakeAmount::zero(),
             total_burned: StakeAmount::zero(),
+            clock: system_clock(),
         }
     }
 
+    /// Replace the time source
+    pub fn set_clock(&mut self, clock: SharedClock) {
+        self.clock = clock;
+    }
+
     /// Calculate slash amount for a validator
     pub fn calculate_slash_amount(
         &self,
         validator: &ValidatorInfo,
         condition: SlashingCondition,
     ) -> StakeAmount {
-        let base_rate = condition.base_penalty_rate();
         let previous_offenses = self.get_offense_count(&validator.address);
         let multiplier = condition.severity_multiplier(previous_offenses);
 
-        // Calculate: stake × (base_rate / 10000) × multiplier
-        let stake_u64 = validator.stake.inner()
-            .to_u64_digits()
-            .first()
-            .copied()
-            .unwrap_or(0);
-
-        let base_slash = (stake_u64 as f64 * base_rate as f64 / 10000.0) as u64;
-        let final_slash = (base_slash as f64 * multiplier) as u64;
+        // Calculate: stake × penalty_rate × multiplier
+        let base_slash = validator.stake.mul_fixed(condition.penalty_rate());
+        let final_slash = base_slash.mul_fixed(multiplier);
 
         // Cap at total stake
-        StakeAmount::from_u64(final_slash.min(stake_u64))
+        final_slash.min(validator.stake.clone())
     }
 
     /// Execute slashing on a validator
@@ -148,9 +139,32 @@ impl SlashingManager {
         condition: SlashingCondition,
         evidence_hash: Option<blockchain_crypto::Hash>,
     ) -> ConsensusResult<SlashingPenalty> {
-        // Calculate slash amount
         let slash_amount = self.calculate_slash_amount(validator, condition);
+        self.apply_slash(validator, condition, slash_amount, evidence_hash)
+    }
 
+    /// Mirror a double-sign slash executed on chain
+    ///
+    /// The chain is the source of truth for double-signing: executing the
+    /// evidence takes a flat `DOUBLE_SIGN_SLASH_BPS` of the offender's stake.
+    /// The same share is taken here, without escalation for repeat offenses,
+    /// so the validator set keeps matching the chain.
+    pub fn mirror_double_sign(
+        &mut self,
+        validator: &mut ValidatorInfo,
+        evidence_hash: blockchain_crypto::Hash,
+    ) -> ConsensusResult<SlashingPenalty> {
+        let slash_amount = validator.stake.mul_bps(DOUBLE_SIGN_SLASH_BPS);
+        self.apply_slash(validator, SlashingCondition::DoubleSigning, slash_amount, Some(evidence_hash))
+    }
+
+    fn apply_slash(
+        &mut self,
+        validator: &mut ValidatorInfo,
+        condition: SlashingCondition,
+        slash_amount: StakeAmount,
+        evidence_hash: Option<blockchain_crypto::Hash>,
+    ) -> ConsensusResult<SlashingPenalty> {
         // Verify sufficient stake
         if validator.stake.inner() < slash_amount.inner() {
             return Err(ConsensusError::SlashingError(
@@ -166,7 +180,7 @@ impl SlashingManager {
             validator: validator.address,
             condition,
             amount: slash_amount.clone(),
-            timestamp: current_timestamp(),
+            timestamp: self.clock.now(),
             previous_offenses,
             evidence_hash,
         };
@@ -233,30 +247,19 @@ impl SlashingManager {
         &self.slashing_history
     }
 
-    /// Check if validator should be auto-slashed for downtime
-    pub fn check_downtime_slashing(
+    /// Slash a validator for the slots it missed in its liveness window
+    pub fn slash_downtime(
         &mut self,
         validator: &mut ValidatorInfo,
-        max_downtime_blocks: u64,
+        missed_slots: u64,
     ) -> ConsensusResult<Option<SlashingPenalty>> {
-        // Calculate consecutive misses
-        let total_blocks = validator.blocks_produced + validator.blocks_missed;
-        if total_blocks == 0 {
+        if missed_slots == 0 {
             return Ok(None);
         }
 
-        // Check if downtime threshold exceeded
-        if validator.blocks_missed >= max_downtime_blocks {
-            let days_down = validator.blocks_missed / (28800); // Assuming 3s blocks, 28800 blocks/day
-            
-            if days_down > 0 {
-                let condition = SlashingCondition::ExtendedDowntime { days: days_down };
-                let penalty = self.slash_validator(validator, condition, None)?;
-                return Ok(Some(penalty));
-            }
-        }
-
-        Ok(None)
+        let condition = SlashingCondition::ExtendedDowntime { missed_slots };
+        let penalty = self.slash_validator(validator, condition, None)?;
+        Ok(Some(penalty))
     }
 
     /// Withdraw from insurance fund (governance action)
@@ -278,21 +281,15 @@ impl Default for SlashingManager {
     }
 }
 
-/// Helper to get current timestamp
-fn current_timestamp() -> Timestamp {
-    std::time::SystemTime::now()
-        .duration_since(std::time::UNIX_EPOCH)
-        .unwrap()
-        .as_secs()
-}
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use blockchain_crypto::{PublicKey, SignatureScheme};
 
     fn create_test_validator(stake: u64) -> ValidatorInfo {
         ValidatorInfo::new(
-            Address::zero(),
+            PublicKey::new(SignatureScheme::Ed25519, vec![0u8; 32]),
             StakeAmount::from_u64(stake),
             100,
         )
@@ -308,10 +305,11 @@ mod tests {
     #[test]
     fn test_severity_multiplier() {
         let condition = SlashingCondition::DoubleSigning;
-        assert_eq!(condition.severity_multiplier(0), 1.0);
-        assert_eq!(condition.severity_multiplier(1), 1.5);
-        assert_eq!(condition.severity_multiplier(2), 2.0);
-        assert_eq!(condition.severity_multiplier(10), 3.0); // Capped at 3.0
+        assert_eq!(condition.severity_multiplier(0), Fixed::ONE);
+        assert_eq!(condition.severity_multiplier(1), Fixed::from_ratio(3, 2).unwrap());
+        assert_eq!(condition.severity_multiplier(2), Fixed::from_int(2));
+        assert_eq!(condition.severity_multiplier(10), Fixed::from_int(3)); // Capped at 3.0
+        assert_eq!(condition.severity_multiplier(u32::MAX), Fixed::from_int(3));
     }
 
     #[test]
@@ -340,6 +338,20 @@ mod tests {
         assert_eq!(manager.get_offense_count(&validator.address), 1);
     }
 
+    #[test]
+    fn test_downtime_proportional_to_missed_slots() {
+        let mut manager = SlashingManager::new();
+        let mut validator = create_test_validator(10_000_000);
+
+        // A day of missed slots costs 0.1%, half a day 0.05%
+        let day = SlashingCondition::ExtendedDowntime { missed_slots: SLOTS_PER_DAY };
+        assert_eq!(manager.calculate_slash_amount(&validator, day), StakeAmount::from_u64(10_000));
+        let half = manager.slash_downtime(&mut validator, SLOTS_PER_DAY / 2).unwrap().unwrap();
+        assert_eq!(half.amount, StakeAmount::from_u64(5_000));
+
+        assert!(manager.slash_downtime(&mut validator, 0).unwrap().is_none());
+    }
+
     #[test]
     fn test_distribution() {
         let amount = StakeAmount::from_u64(10000);
@@ -366,6 +378,20 @@ mod tests {
         assert!(validator.stake.inner() < stake_after_first.inner());
     }
 
+    #[test]
+    fn test_mirrored_double_sign_is_flat() {
+        let mut manager = SlashingManager::new();
+        let mut validator = create_test_validator(100000);
+
+        // Repeat offenses are not escalated, matching the chain's flat slash
+        manager.mirror_double_sign(&mut validator, blockchain_crypto::Hash::zero()).unwrap();
+        assert_eq!(validator.stake, StakeAmount::from_u64(95000));
+        let penalty = manager.mirror_double_sign(&mut validator, blockchain_crypto::Hash::zero()).unwrap();
+        assert_eq!(penalty.amount, StakeAmount::from_u64(4750));
+        assert_eq!(penalty.condition, SlashingCondition::DoubleSigning);
+        assert_eq!(manager.get_offense_count(&validator.address), 2);
+    }
+
     #[test]
     fn test_capital_offense() {
         let mut manager = SlashingManager::new();
@@ -0,0 +1,108 @@
+// consensus/src/slot.rs
+
+//! Wall-clock slots and proposer windows
+//!
+//! Time since genesis is divided into slots of `block_time` seconds, with at
+//! most one block per slot. The proposer elected by the VRF may propose from
+//! the start of its slot. If it stays silent, fallback proposers take over in
+//! order of rank, each one proposer timeout after the previous. A slot in
+//! which nobody proposes is left empty and the next block skips over it.
+
+use blockchain_core::{Clock, ProposerElection, Timestamp};
+
+/// Maps timestamps to slots and opens proposer windows within them
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SlotClock {
+    genesis_time: Timestamp,
+    slot_duration: u64,
+    proposer_timeout: u64,
+}
+
+impl SlotClock {
+    /// Slots of `slot_duration` seconds starting at `genesis_time`
+    pub fn new(genesis_time: Timestamp, slot_duration: u64, proposer_timeout: u64) -> Self {
+        Self {
+            genesis_time,
+            slot_duration: slot_duration.max(1),
+            proposer_timeout,
+        }
+    }
+
+    /// Start of slot 0, the genesis block's slot
+    pub fn genesis_time(&self) -> Timestamp {
+        self.genesis_time
+    }
+
+    /// Length of a slot in seconds
+    pub fn slot_duration(&self) -> u64 {
+        self.slot_duration
+    }
+
+    /// Slot containing `timestamp`, or `None` before genesis
+    pub fn slot_at(&self, timestamp: Timestamp) -> Option<u64> {
+        timestamp.checked_sub(self.genesis_time).map(|elapsed| elapsed / self.slot_duration)
+    }
+
+    /// Current slot according to `clock`
+    pub fn current_slot(&self, clock: &dyn Clock) -> Option<u64> {
+        self.slot_at(clock.now())
+    }
+
+    /// Time at which `slot` begins
+    pub fn slot_start(&self, slot: u64) -> Timestamp {
+        self.genesis_time.saturating_add(slot.saturating_mul(self.slot_duration))
+    }
+
+    /// Earliest time a proposer of `rank` may propose in `slot`
+    ///
+    /// Rank 0 is the elected proposer; fallback ranks start at 1.
+    pub fn window_start(&self, slot: u64, rank: usize) -> Timestamp {
+        self.slot_start(slot).saturating_add(self.proposer_timeout.saturating_mul(rank as u64))
+    }
+}
+
+/// A validator's right to propose in a slot
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProposerRole {
+    /// Elected by the VRF, with the proof to put in the header
+    Elected(ProposerElection),
+    /// Fallback of the given rank, starting at 1
+    Fallback(usize),
+}
+
+impl ProposerRole {
+    /// Rank of the role; the elected proposer has rank 0
+    pub fn rank(&self) -> usize {
+        match self {
+            ProposerRole::Elected(_) => 0,
+            ProposerRole::Fallback(rank) => *rank,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use blockchain_core::ManualClock;
+
+    #[test]
+    fn test_slots_from_genesis() {
+        let slots = SlotClock::new(1_000, 3, 1);
+        assert_eq!(slots.slot_at(999), None);
+        assert_eq!(slots.slot_at(1_000), Some(0));
+        assert_eq!(slots.slot_at(1_002), Some(0));
+        assert_eq!(slots.slot_at(1_003), Some(1));
+        assert_eq!(slots.slot_start(5), 1_015);
+        assert_eq!(slots.current_slot(&ManualClock::new(1_016)), Some(5));
+    }
+
+    #[test]
+    fn test_proposer_windows() {
+        let slots = SlotClock::new(0, 3, 1);
+        assert_eq!(slots.window_start(4, 0), 12);
+        assert_eq!(slots.window_start(4, 1), 13);
+        assert_eq!(slots.window_start(4, 2), 14);
+        // Rank 3 would open with the next slot, so it never gets a window
+        assert_eq!(slots.window_start(4, 3), slots.slot_start(5));
+    }
+}
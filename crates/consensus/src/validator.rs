@@ -1,10 +1,13 @@
 // consensus/src/validator.rs
 
-use crate::{ConsensusError, ConsensusResult};
-use blockchain_core::{Amount, StakeAmount, Timestamp, UtilityScore};
-use blockchain_crypto::Address;
+use crate::{selection::ValidatorSelector, ConsensusError, ConsensusResult};
+use blockchain_core::{
+    clock::{system_clock, Clock, ManualClock, SharedClock, SystemClock},
+    config, Amount, BlockNumber, Fixed, StakeAmount, Timestamp, UtilityScore,
+};
+use blockchain_crypto::{Address, Hash, PublicKey};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
 
 /// Validator status
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -15,6 +18,8 @@ pub enum ValidatorStatus {
     Inactive,
     /// Unbonding period (after unstaking request)
     Unbonding { unlock_time: Timestamp },
+    /// Jailed for missing slots; may unjail from `release_height`
+    Jailed { release_height: BlockNumber },
     /// Slashed due to misbehavior
     Slashed,
     /// Exited from validator set
@@ -24,8 +29,10 @@ pub enum ValidatorStatus {
 /// Complete validator information
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ValidatorInfo {
-    /// Validator address
+    /// Validator address, derived from the consensus key
     pub address: Address,
+    /// Key the validator signs blocks and votes with
+    pub consensus_key: PublicKey,
     /// Staked amount
     pub stake: StakeAmount,
     /// Liquidity deployed to utility pools
@@ -50,10 +57,21 @@ pub struct ValidatorInfo {
 
 impl ValidatorInfo {
     /// Create a new validator
-    pub fn new(address: Address, stake: StakeAmount, commission_rate: u16) -> Self {
-        let now = current_timestamp();
+    pub fn new(consensus_key: PublicKey, stake: StakeAmount, commission_rate: u16) -> Self {
+        Self::new_with_clock(consensus_key, stake, commission_rate, &SystemClock)
+    }
+
+    /// Create a new validator registered at the given clock's time
+    pub fn new_with_clock(
+        consensus_key: PublicKey,
+        stake: StakeAmount,
+        commission_rate: u16,
+        clock: &dyn Clock,
+    ) -> Self {
+        let now = clock.now();
         Self {
-            address,
+            address: consensus_key.to_address(),
+            consensus_key,
             stake,
             liquidity_deployed: Amount::zero(),
             utility_score: UtilityScore::zero(),
@@ -79,48 +97,42 @@ impl ValidatorInfo {
 
     /// Update uptime based on block production
     pub fn update_uptime(&mut self, produced: bool) {
+        self.update_uptime_with_clock(produced, &SystemClock)
+    }
+
+    /// Update uptime, recording activity at the given clock's time
+    pub fn update_uptime_with_clock(&mut self, produced: bool, clock: &dyn Clock) {
         if produced {
             self.blocks_produced += 1;
         } else {
             self.blocks_missed += 1;
         }
 
-        // Calculate new uptime (exponential moving average)
-        let total = self.blocks_produced + self.blocks_missed;
+        // Uptime in basis points, rounded down
+        let total = self.blocks_produced as u128 + self.blocks_missed as u128;
         if total > 0 {
-            self.uptime = ((self.blocks_produced as f64 / total as f64) * 10000.0) as u16;
+            self.uptime = (self.blocks_produced as u128 * 10000 / total) as u16;
         }
 
-        self.last_active = current_timestamp();
+        self.last_active = clock.now();
     }
 
-    /// Calculate reliability factor (0.0 to 1.0)
-    pub fn reliability_factor(&self) -> f64 {
-        self.uptime as f64 / 10000.0
+    /// Calculate reliability factor (0 to 1)
+    pub fn reliability_factor(&self) -> Fixed {
+        Fixed::from_bps(self.uptime as u64)
     }
 
     /// Calculate efficiency score (yield generated per risk)
-    pub fn efficiency_score(&self) -> f64 {
+    pub fn efficiency_score(&self) -> Fixed {
         if self.stake.is_zero() {
-            return 0.0;
+            return Fixed::ZERO;
         }
         
-        // Simplified: based on liquidity deployment ratio
-        let deployment_ratio = if !self.stake.is_zero() {
-            self.liquidity_deployed.inner().clone().min(self.stake.inner().clone());
-            let deployed = self.liquidity_deployed.inner();
-            let staked = self.stake.inner();
-            
-            if staked.bits() > 0 {
-                (deployed.clone() * 10000u64) / staked.clone()
-            } else {
-                num_bigint::BigUint::from(0u64)
-            }
-        } else {
-            num_bigint::BigUint::from(0u64)
-        };
+        // Simplified: based on liquidity deployment ratio, capped at 100%
+        let deployed = self.liquidity_deployed.clone().min(self.stake.clone());
+        let deployment_ratio = deployed.ratio_bps(&self.stake).unwrap_or(0);
 
-        deployment_ratio.to_u64_digits().first().copied().unwrap_or(0) as f64 / 10000.0
+        Fixed::from_bps(deployment_ratio)
     }
 
     /// Add stake
@@ -132,10 +144,20 @@ impl ValidatorInfo {
 
     /// Remove stake (initiate unbonding)
     pub fn remove_stake(&mut self, amount: &StakeAmount, unbonding_period: u64) -> ConsensusResult<()> {
+        self.remove_stake_with_clock(amount, unbonding_period, &SystemClock)
+    }
+
+    /// Remove stake, starting the unbonding period at the given clock's time
+    pub fn remove_stake_with_clock(
+        &mut self,
+        amount: &StakeAmount,
+        unbonding_period: u64,
+        clock: &dyn Clock,
+    ) -> ConsensusResult<()> {
         if self.stake.inner() < amount.inner() {
             return Err(ConsensusError::InsufficientStake {
-                required: amount.inner().to_u64_digits().first().copied().unwrap_or(0),
-                provided: self.stake.inner().to_u64_digits().first().copied().unwrap_or(0),
+                required: amount.clone(),
+                provided: self.stake.clone(),
             });
         }
 
@@ -143,7 +165,7 @@ impl ValidatorInfo {
             .ok_or_else(|| ConsensusError::ValidatorError("Stake underflow".into()))?;
 
         // Set unbonding status
-        let unlock_time = current_timestamp() + unbonding_period;
+        let unlock_time = clock.now() + unbonding_period;
         self.status = ValidatorStatus::Unbonding { unlock_time };
 
         Ok(())
@@ -172,15 +194,55 @@ impl ValidatorInfo {
     }
 }
 
+/// A validator-set change queued until the next epoch boundary
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ValidatorChange {
+    /// Join with an initial stake, signing with `consensus_key`
+    Register { consensus_key: PublicKey, stake: StakeAmount, commission_rate: u16 },
+    /// Increase a validator's stake
+    AddStake { address: Address, amount: StakeAmount },
+    /// Start unbonding part of a validator's stake
+    BeginUnbonding { address: Address, amount: StakeAmount },
+    /// Leave the validator set
+    Exit { address: Address },
+}
+
+impl ValidatorChange {
+    /// Validator the change applies to
+    pub fn address(&self) -> Address {
+        match self {
+            ValidatorChange::Register { consensus_key, .. } => consensus_key.to_address(),
+            ValidatorChange::AddStake { address, .. }
+            | ValidatorChange::BeginUnbonding { address, .. }
+            | ValidatorChange::Exit { address } => *address,
+        }
+    }
+}
+
 /// Manages the validator set
+///
+/// Changes are queued with `queue` and applied together at epoch
+/// boundaries by `apply_epoch_changes`, which also re-elects the active set:
+/// the highest-weight validators able to produce blocks, up to `max_active`.
+/// Only `register` and `unregister` take effect immediately, for building
+/// the genesis set.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ValidatorSet {
     /// All validators indexed by address
     validators: HashMap<Address, ValidatorInfo>,
+    /// Validators elected for the current epoch
+    active: BTreeSet<Address>,
+    /// Changes waiting for the next epoch boundary
+    pending: Vec<ValidatorChange>,
+    /// Maximum size of the active set
+    max_active: usize,
     /// Minimum stake required to be a validator
     min_stake: StakeAmount,
     /// Unbonding period in seconds (default: 14 days)
     unbonding_period: u64,
+    /// Time source for registration and unbonding
+    #[serde(skip, default = "system_clock")]
+    clock: SharedClock,
 }
 
 impl ValidatorSet {
@@ -188,16 +250,36 @@ impl ValidatorSet {
     pub fn new(min_stake: StakeAmount, unbonding_period: u64) -> Self {
         Self {
             validators: HashMap::new(),
+            active: BTreeSet::new(),
+            pending: Vec::new(),
+            max_active: usize::MAX,
             min_stake,
             unbonding_period,
+            clock: system_clock(),
         }
     }
 
+    /// Replace the time source
+    pub fn set_clock(&mut self, clock: SharedClock) {
+        self.clock = clock;
+    }
+
+    /// Get the time source
+    pub fn clock(&self) -> &SharedClock {
+        &self.clock
+    }
+
     /// Get minimum stake requirement
     pub fn min_stake(&self) -> &StakeAmount {
         &self.min_stake
     }
 
+    /// Cap the active set, re-electing it immediately
+    pub fn set_max_active(&mut self, max_active: usize) {
+        self.max_active = max_active;
+        self.elect_active();
+    }
+
     /// Get a validator by address
     pub fn get(&self, address: &Address) -> Option<&ValidatorInfo> {
         self.validators.get(address)
@@ -208,13 +290,15 @@ impl ValidatorSet {
         self.validators.get_mut(address)
     }
 
-    /// Register a new validator
+    /// Register a new validator immediately, for the genesis set
     pub fn register(
         &mut self,
-        address: Address,
+        consensus_key: PublicKey,
         stake: StakeAmount,
         commission_rate: u16,
     ) -> ConsensusResult<()> {
+        let address = consensus_key.to_address();
+
         // Check if already exists
         if self.validators.contains_key(&address) {
             return Err(ConsensusError::ValidatorAlreadyExists(address.to_hex()));
@@ -223,30 +307,129 @@ impl ValidatorSet {
         // Check minimum stake
         if stake.inner() < self.min_stake.inner() {
             return Err(ConsensusError::InsufficientStake {
-                required: self.min_stake.inner().to_u64_digits().first().copied().unwrap_or(0),
-                provided: stake.inner().to_u64_digits().first().copied().unwrap_or(0),
+                required: self.min_stake.clone(),
+                provided: stake.clone(),
             });
         }
 
         // Create and add validator
-        let validator = ValidatorInfo::new(address, stake, commission_rate);
+        let validator = ValidatorInfo::new_with_clock(consensus_key, stake, commission_rate, &*self.clock);
         self.validators.insert(address, validator);
+        self.elect_active();
 
         Ok(())
     }
 
-    /// Unregister a validator (exit)
+    /// Unregister a validator (exit) immediately
     pub fn unregister(&mut self, address: &Address) -> ConsensusResult<ValidatorInfo> {
         let mut validator = self.validators.remove(address)
             .ok_or_else(|| ConsensusError::ValidatorNotFound(address.to_hex()))?;
+        self.active.remove(address);
 
         validator.status = ValidatorStatus::Exited;
         Ok(validator)
     }
 
-    /// Get all active validators
+    /// Queue a change for the next epoch boundary
+    pub fn queue(&mut self, change: ValidatorChange) -> ConsensusResult<()> {
+        let address = change.address();
+        match &change {
+            ValidatorChange::Register { stake, .. } => {
+                let queued = self.pending.iter()
+                    .any(|c| matches!(c, ValidatorChange::Register { .. }) && c.address() == address);
+                if queued || self.validators.contains_key(&address) {
+                    return Err(ConsensusError::ValidatorAlreadyExists(address.to_hex()));
+                }
+                if stake.inner() < self.min_stake.inner() {
+                    return Err(ConsensusError::InsufficientStake {
+                        required: self.min_stake.clone(),
+                        provided: stake.clone(),
+                    });
+                }
+            }
+            _ => {
+                if !self.validators.contains_key(&address) {
+                    return Err(ConsensusError::ValidatorNotFound(address.to_hex()));
+                }
+            }
+        }
+
+        self.pending.push(change);
+        Ok(())
+    }
+
+    /// Changes waiting for the next epoch boundary, in application order
+    pub fn pending_changes(&self) -> &[ValidatorChange] {
+        &self.pending
+    }
+
+    /// Apply queued changes and completed unbondings at `now`, then
+    /// re-elect the active set, returning validators whose unbonding completed
+    ///
+    /// `now` is the timestamp of the block ending the epoch, so every node
+    /// applies the same changes.
+    pub fn apply_epoch_changes(&mut self, now: Timestamp) -> Vec<Address> {
+        let at = ManualClock::new(now);
+        for change in std::mem::take(&mut self.pending) {
+            let address = change.address();
+            let result = match change {
+                ValidatorChange::Register { consensus_key, stake, commission_rate } => {
+                    let validator = ValidatorInfo::new_with_clock(consensus_key, stake, commission_rate, &at);
+                    self.validators.insert(address, validator);
+                    Ok(())
+                }
+                ValidatorChange::AddStake { amount, .. } => self.validators.get_mut(&address)
+                    .ok_or_else(|| ConsensusError::ValidatorNotFound(address.to_hex()))
+                    .and_then(|v| v.add_stake(&amount)),
+                ValidatorChange::BeginUnbonding { amount, .. } => self.validators.get_mut(&address)
+                    .ok_or_else(|| ConsensusError::ValidatorNotFound(address.to_hex()))
+                    .and_then(|v| v.remove_stake_with_clock(&amount, self.unbonding_period, &at)),
+                ValidatorChange::Exit { .. } => self.unregister(&address).map(|_| ()),
+            };
+            if let Err(e) = result {
+                tracing::warn!("Dropped validator change for {}: {}", address.to_hex(), e);
+            }
+        }
+
+        let completed = self.process_unbonding(now);
+        self.elect_active();
+        completed
+    }
+
+    /// Hash of the set `apply_epoch_changes(now)` would elect
+    pub fn next_set_hash(&self, now: Timestamp) -> Hash {
+        let mut next = self.clone();
+        next.apply_epoch_changes(now);
+        next.active_set_hash()
+    }
+
+    /// Hash of the active set's addresses and stakes, in address order
+    pub fn active_set_hash(&self) -> Hash {
+        let members: Vec<(Address, &StakeAmount)> = self.active.iter()
+            .filter_map(|address| self.validators.get(address).map(|v| (*address, &v.stake)))
+            .collect();
+        config::hash_encoded(&members, config::hash_algorithm())
+    }
+
+    /// Elect the highest-weight validators able to produce blocks
+    fn elect_active(&mut self) {
+        let mut candidates: Vec<(u128, Address)> = self.validators.values()
+            .filter(|v| v.can_produce_blocks())
+            .map(|v| (ValidatorSelector::weight(v), v.address))
+            .collect();
+        candidates.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+        self.active = candidates.into_iter()
+            .take(self.max_active)
+            .map(|(_, address)| address)
+            .collect();
+    }
+
+    /// Get the validators elected for the epoch that are still active
+    ///
+    /// Jailed and slashed validators drop out mid-epoch.
     pub fn active_validators(&self) -> Vec<&ValidatorInfo> {
-        self.validators.values()
+        self.active.iter()
+            .filter_map(|address| self.validators.get(address))
             .filter(|v| v.is_active())
             .collect()
     }
@@ -264,6 +447,14 @@ impl ValidatorSet {
             })
     }
 
+    /// Get total stake of active validators, i.e. the total voting power
+    pub fn active_stake(&self) -> StakeAmount {
+        self.active_validators().into_iter()
+            .fold(StakeAmount::zero(), |acc, v| {
+                acc.checked_add(&v.stake).unwrap_or(acc)
+            })
+    }
+
     /// Get total liquidity deployed
     pub fn total_liquidity_deployed(&self) -> Amount {
         self.validators.values()
@@ -281,6 +472,20 @@ impl ValidatorSet {
         Ok(())
     }
 
+    /// Start unbonding part of a validator's stake
+    pub fn begin_unbonding(&mut self, address: &Address, amount: &StakeAmount) -> ConsensusResult<()> {
+        let validator = self.validators.get_mut(address)
+            .ok_or_else(|| ConsensusError::ValidatorNotFound(address.to_hex()))?;
+
+        validator.remove_stake_with_clock(amount, self.unbonding_period, &*self.clock)
+    }
+
+    /// Process unbonding completions at the current clock time
+    pub fn process_unbonding_now(&mut self) -> Vec<Address> {
+        let now = self.clock.now();
+        self.process_unbonding(now)
+    }
+
     /// Process unbonding completions
     pub fn process_unbonding(&mut self, current_time: Timestamp) -> Vec<Address> {
         let mut completed = Vec::new();
@@ -346,25 +551,23 @@ impl Validator {
     }
 }
 
-/// Helper function to get current timestamp
-fn current_timestamp() -> Timestamp {
-    std::time::SystemTime::now()
-        .duration_since(std::time::UNIX_EPOCH)
-        .unwrap()
-        .as_secs()
-}
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use blockchain_crypto::{KeyPair, SignatureScheme};
 
+    fn test_key(seed: u8) -> PublicKey {
+        KeyPair::from_secret_bytes(SignatureScheme::Ed25519, &[seed; 32]).unwrap().public_key().clone()
+    }
+
     #[test]
     fn test_validator_creation() {
-        let address = Address::zero();
+        let key = test_key(1);
         let stake = StakeAmount::from_u64(10000);
-        let validator = ValidatorInfo::new(address, stake, 500);
+        let validator = ValidatorInfo::new(key.clone(), stake, 500);
 
+        assert_eq!(validator.address, key.to_address());
         assert!(validator.is_active());
         assert!(validator.can_produce_blocks());
         assert_eq!(validator.commission_rate, 500);
@@ -374,26 +577,59 @@ mod tests {
     fn test_validator_set() {
         let mut set = ValidatorSet::new(StakeAmount::from_u64(1000), 14 * 24 * 3600);
         let keypair = KeyPair::generate(SignatureScheme::Ed25519).unwrap();
-        let address = keypair.public_key().to_address();
 
-        set.register(address, StakeAmount::from_u64(5000), 100).unwrap();
+        set.register(keypair.public_key().clone(), StakeAmount::from_u64(5000), 100).unwrap();
         assert_eq!(set.count(), 1);
         assert_eq!(set.active_count(), 1);
     }
 
+    #[test]
+    fn test_queued_changes_apply_at_epoch() {
+        let mut set = ValidatorSet::new(StakeAmount::from_u64(1000), 100);
+        set.set_max_active(2);
+        let keys = [test_key(1), test_key(2), test_key(3)];
+        let [a, b, c] = [0, 1, 2].map(|i| keys[i].to_address());
+        set.register(keys[0].clone(), StakeAmount::from_u64(5000), 100).unwrap();
+        set.register(keys[1].clone(), StakeAmount::from_u64(4000), 100).unwrap();
+
+        let join = ValidatorChange::Register { consensus_key: keys[2].clone(), stake: StakeAmount::from_u64(6000), commission_rate: 100 };
+        set.queue(join.clone()).unwrap();
+        set.queue(ValidatorChange::BeginUnbonding { address: b, amount: StakeAmount::from_u64(500) }).unwrap();
+        // Duplicate registrations and changes for unknown validators are rejected
+        assert!(set.queue(join).is_err());
+        assert!(set.queue(ValidatorChange::AddStake { address: Address::zero(), amount: StakeAmount::from_u64(1) }).is_err());
+
+        // Nothing changes until the epoch boundary
+        assert_eq!(set.count(), 2);
+        assert_eq!(set.get(&b).unwrap().stake, StakeAmount::from_u64(4000));
+        let committed = set.next_set_hash(10);
+        assert_ne!(committed, set.active_set_hash());
+
+        set.apply_epoch_changes(10);
+        assert!(set.pending_changes().is_empty());
+        assert_eq!(set.active_set_hash(), committed);
+        // The two heaviest validators are active
+        let mut active: Vec<Address> = set.active_validators().iter().map(|v| v.address).collect();
+        active.sort();
+        let mut expected = vec![a, c];
+        expected.sort();
+        assert_eq!(active, expected);
+        assert_eq!(set.get(&b).unwrap().stake, StakeAmount::from_u64(3500));
+
+        // The unbonded stake is released once the period has passed
+        assert_eq!(set.apply_epoch_changes(110), vec![b]);
+    }
+
     #[test]
     fn test_insufficient_stake() {
         let mut set = ValidatorSet::new(StakeAmount::from_u64(10000), 14 * 24 * 3600);
-        let address = Address::zero();
-
-        let result = set.register(address, StakeAmount::from_u64(5000), 100);
+        let result = set.register(test_key(1), StakeAmount::from_u64(5000), 100);
         assert!(result.is_err());
     }
 
     #[test]
     fn test_liquidity_deployment() {
-        let address = Address::zero();
-        let mut validator = ValidatorInfo::new(address, StakeAmount::from_u64(10000), 100);
+        let mut validator = ValidatorInfo::new(test_key(1), StakeAmount::from_u64(10000), 100);
 
         validator.deploy_liquidity(&Amount::from_u64(5000)).unwrap();
         assert_eq!(validator.liquidity_deployed, Amount::from_u64(5000));
@@ -1,7 +1,7 @@
 // liquidity/src/amm.rs
 
 use crate::{pool::LiquidityPool, LiquidityError, LiquidityResult};
-use blockchain_core::Amount;
+use blockchain_core::{clock::SharedClock, Amount, Rounding, BPS_DENOMINATOR};
 use blockchain_crypto::Address;
 use serde::{Deserialize, Serialize};
 
@@ -58,10 +58,15 @@ impl AMMPool {
             reserve_a: Amount::zero(),
             reserve_b: Amount::zero(),
             total_shares: Amount::zero(),
-            fee_rate: fee_rate.min(10000),
+            fee_rate: fee_rate.min(BPS_DENOMINATOR as u16),
         }
     }
 
+    /// Replace the time source used for withdrawal delays
+    pub fn set_clock(&mut self, clock: SharedClock) {
+        self.base.set_clock(clock);
+    }
+
     /// Add liquidity to the pool
     pub fn add_liquidity(
         &mut self,
@@ -77,19 +82,14 @@ impl AMMPool {
 
         let shares = if self.total_shares.is_zero() {
             // Initial liquidity: shares = sqrt(amount_a * amount_b)
-            let a_val = amount_a.inner().to_u64_digits().first().copied().unwrap_or(0) as f64;
-            let b_val = amount_b.inner().to_u64_digits().first().copied().unwrap_or(0) as f64;
-            let shares_val = (a_val * b_val).sqrt();
-            Amount::from_u64(shares_val as u64)
+            Amount::new((amount_a.inner() * amount_b.inner()).sqrt())
         } else {
             // Proportional shares: shares = min(amount_a / reserve_a, amount_b / reserve_b) * total_shares
-            let a_ratio = self.calculate_ratio(&amount_a, &self.reserve_a);
-            let b_ratio = self.calculate_ratio(&amount_b, &self.reserve_b);
-            let ratio = a_ratio.min(b_ratio);
-            
-            let total_val = self.total_shares.inner().to_u64_digits().first().copied().unwrap_or(0) as f64;
-            let shares_val = total_val * ratio;
-            Amount::from_u64(shares_val as u64)
+            let a_shares = self.total_shares.mul_div(&amount_a, &self.reserve_a, Rounding::Down);
+            let b_shares = self.total_shares.mul_div(&amount_b, &self.reserve_b, Rounding::Down);
+            a_shares.zip(b_shares)
+                .map(|(a, b)| a.min(b))
+                .ok_or_else(|| LiquidityError::CalculationError("Pool reserves are empty".into()))?
         };
 
         // Update reserves
@@ -130,13 +130,10 @@ impl AMMPool {
         }
 
         // Calculate amounts: amount = (shares / total_shares) * reserve
-        let share_ratio = self.calculate_ratio(&shares, &self.total_shares);
-        
-        let reserve_a_val = self.reserve_a.inner().to_u64_digits().first().copied().unwrap_or(0) as f64;
-        let reserve_b_val = self.reserve_b.inner().to_u64_digits().first().copied().unwrap_or(0) as f64;
-        
-        let amount_a = Amount::from_u64((reserve_a_val * share_ratio) as u64);
-        let amount_b = Amount::from_u64((reserve_b_val * share_ratio) as u64);
+        let amount_a = self.reserve_a.mul_div(&shares, &self.total_shares, Rounding::Down)
+            .ok_or_else(|| LiquidityError::CalculationError("Total shares is zero".into()))?;
+        let amount_b = self.reserve_b.mul_div(&shares, &self.total_shares, Rounding::Down)
+            .ok_or_else(|| LiquidityError::CalculationError("Total shares is zero".into()))?;
 
         // Update reserves
         self.reserve_a = self.reserve_a.checked_sub(&amount_a)
@@ -152,7 +149,7 @@ impl AMMPool {
         let total_withdrawn = amount_a.checked_add(&amount_b)
             .ok_or_else(|| LiquidityError::CalculationError("Total withdrawal overflow".into()))?;
         
-        let unlock_time = current_timestamp() + 7 * 24 * 3600; // 7 days
+        let unlock_time = self.base.clock().now() + 7 * 24 * 3600; // 7 days
         self.base.request_withdrawal(provider, total_withdrawn, unlock_time)?;
 
         Ok((amount_a, amount_b))
@@ -179,17 +176,23 @@ impl AMMPool {
         // Calculate output using constant product formula: (x + dx) * (y - dy) = x * y
         // dy = (y * dx * (1 - fee)) / (x + dx * (1 - fee))
         
-        let amount_in_val = amount_in.inner().to_u64_digits().first().copied().unwrap_or(0) as f64;
-        let reserve_in_val = reserve_in.inner().to_u64_digits().first().copied().unwrap_or(1) as f64;
-        let reserve_out_val = reserve_out.inner().to_u64_digits().first().copied().unwrap_or(0) as f64;
-
-        // Apply fee
-        let fee_multiplier = 1.0 - (self.fee_rate as f64 / 10000.0);
-        let amount_in_with_fee = amount_in_val * fee_multiplier;
-
-        // Calculate output
-        let amount_out_val = (reserve_out_val * amount_in_with_fee) / (reserve_in_val + amount_in_with_fee);
-        let amount_out = Amount::from_u64(amount_out_val as u64);
+        // Computed in basis points to stay in exact integer math
+        let fee_complement = BPS_DENOMINATOR
+            .checked_sub(self.fee_rate as u64)
+            .ok_or_else(|| LiquidityError::CalculationError("Fee rate above 100%".into()))?;
+        let amount_in_with_fee = amount_in.inner() * fee_complement;
+        let denominator = Amount::new(reserve_in.inner() * BPS_DENOMINATOR + &amount_in_with_fee);
+        let amount_out = reserve_out
+            .mul_div(&Amount::new(amount_in_with_fee), &denominator, Rounding::Down)
+            .ok_or_else(|| LiquidityError::InsufficientLiquidity {
+                required: amount_in.clone(),
+                available: reserve_in.clone(),
+            })?;
+
+        let amount_in_val = amount_in.to_f64_lossy();
+        let reserve_in_val = reserve_in.to_f64_lossy();
+        let reserve_out_val = reserve_out.to_f64_lossy();
+        let amount_out_val = amount_out.to_f64_lossy();
 
         // Calculate price impact
         let price_before = reserve_out_val / reserve_in_val;
@@ -206,7 +209,7 @@ impl AMMPool {
             amount_out,
             price_impact,
             effective_price,
-            fee_amount: Amount::from_u64((amount_in_val * (self.fee_rate as f64 / 10000.0)) as u64),
+            fee_amount: amount_in.mul_bps(self.fee_rate as u64),
         })
     }
 
@@ -221,9 +224,9 @@ impl AMMPool {
 
         // Check slippage
         if quote.amount_out.inner() < min_amount_out.inner() {
-            let slippage = ((min_amount_out.inner().to_u64_digits().first().copied().unwrap_or(0) as f64
-                - quote.amount_out.inner().to_u64_digits().first().copied().unwrap_or(0) as f64)
-                / min_amount_out.inner().to_u64_digits().first().copied().unwrap_or(1) as f64) * 100.0;
+            let slippage = ((min_amount_out.to_f64_lossy()
+                - quote.amount_out.to_f64_lossy())
+                / min_amount_out.to_f64_lossy()) * 100.0;
             return Err(LiquidityError::SlippageTooHigh(slippage));
         }
 
@@ -248,17 +251,6 @@ impl AMMPool {
     pub fn pair(&self) -> &TradingPair {
         &self.pair
     }
-
-    /// Calculate ratio between two amounts
-    fn calculate_ratio(&self, numerator: &Amount, denominator: &Amount) -> f64 {
-        if denominator.is_zero() {
-            return 0.0;
-        }
-
-        let num = numerator.inner().to_u64_digits().first().copied().unwrap_or(0) as f64;
-        let den = denominator.inner().to_u64_digits().first().copied().unwrap_or(1) as f64;
-        num / den
-    }
 }
 
 /// Swap quote information
@@ -276,14 +268,6 @@ pub struct SwapQuote {
     pub fee_amount: Amount,
 }
 
-/// Helper to get current timestamp
-fn current_timestamp() -> blockchain_core::Timestamp {
-    std::time::SystemTime::now()
-        .duration_since(std::time::UNIX_EPOCH)
-        .unwrap()
-        .as_secs()
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -334,6 +318,21 @@ mod tests {
         assert!(quote.price_impact < 5.0); // Small trade should have low impact
     }
 
+    #[test]
+    fn test_swap_quote_fee_rate_bounds() {
+        let base = create_test_amm().base;
+        let pair = TradingPair::new(Address::zero(), Address::zero());
+        assert_eq!(AMMPool::new(base, pair, u16::MAX).fee_rate, BPS_DENOMINATOR as u16);
+
+        let mut amm = create_test_amm();
+        amm.add_liquidity(Address::zero(), Amount::from_u64(10000), Amount::from_u64(10000)).unwrap();
+        amm.fee_rate = 20_000;
+        assert!(matches!(
+            amm.get_swap_quote(amm.pair.token_a, Amount::from_u64(100)),
+            Err(LiquidityError::CalculationError(_))
+        ));
+    }
+
     #[test]
     fn test_swap() {
         let mut amm = create_test_amm();
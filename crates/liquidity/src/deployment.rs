@@ -5,7 +5,7 @@ use crate::{
     risk::RiskCalculator,
     LiquidityError, LiquidityResult,
 };
-use blockchain_core::{Amount, StakeAmount};
+use blockchain_core::{clock::SharedClock, Amount, StakeAmount, BPS_DENOMINATOR};
 use blockchain_crypto::Address;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -30,25 +30,25 @@ impl DeploymentStrategy {
             DeploymentStrategy::Conservative => vec![
                 DeploymentAllocation {
                     pool_type: PoolType::Treasury,
-                    percentage: 40.0,
+                    share: 4000,
                     min_amount: None,
                     max_amount: None,
                 },
                 DeploymentAllocation {
                     pool_type: PoolType::StabilityReserve,
-                    percentage: 35.0,
+                    share: 3500,
                     min_amount: None,
                     max_amount: None,
                 },
                 DeploymentAllocation {
                     pool_type: PoolType::Lending,
-                    percentage: 20.0,
+                    share: 2000,
                     min_amount: None,
                     max_amount: None,
                 },
                 DeploymentAllocation {
                     pool_type: PoolType::AMM,
-                    percentage: 5.0,
+                    share: 500,
                     min_amount: None,
                     max_amount: None,
                 },
@@ -56,25 +56,25 @@ impl DeploymentStrategy {
             DeploymentStrategy::Balanced => vec![
                 DeploymentAllocation {
                     pool_type: PoolType::Lending,
-                    percentage: 35.0,
+                    share: 3500,
                     min_amount: None,
                     max_amount: None,
                 },
                 DeploymentAllocation {
                     pool_type: PoolType::AMM,
-                    percentage: 30.0,
+                    share: 3000,
                     min_amount: None,
                     max_amount: None,
                 },
                 DeploymentAllocation {
                     pool_type: PoolType::StabilityReserve,
-                    percentage: 20.0,
+                    share: 2000,
                     min_amount: None,
                     max_amount: None,
                 },
                 DeploymentAllocation {
                     pool_type: PoolType::Treasury,
-                    percentage: 15.0,
+                    share: 1500,
                     min_amount: None,
                     max_amount: None,
                 },
@@ -82,19 +82,19 @@ impl DeploymentStrategy {
             DeploymentStrategy::Aggressive => vec![
                 DeploymentAllocation {
                     pool_type: PoolType::AMM,
-                    percentage: 50.0,
+                    share: 5000,
                     min_amount: None,
                     max_amount: None,
                 },
                 DeploymentAllocation {
                     pool_type: PoolType::Lending,
-                    percentage: 40.0,
+                    share: 4000,
                     min_amount: None,
                     max_amount: None,
                 },
                 DeploymentAllocation {
                     pool_type: PoolType::StabilityReserve,
-                    percentage: 10.0,
+                    share: 1000,
                     min_amount: None,
                     max_amount: None,
                 },
@@ -103,14 +103,14 @@ impl DeploymentStrategy {
         }
     }
 
-    /// Validate that allocations sum to ~100%
+    /// Validate that allocations sum to 100%
     pub fn validate(&self) -> LiquidityResult<()> {
         let allocations = self.default_allocations();
-        let total: f64 = allocations.iter().map(|a| a.percentage).sum();
+        let total: u64 = allocations.iter().map(|a| a.share as u64).sum();
         
-        if (total - 100.0).abs() > 0.1 {
+        if total != BPS_DENOMINATOR {
             return Err(LiquidityError::InvalidAllocation(
-                format!("Allocations sum to {}%, must be 100%", total)
+                format!("Allocations sum to {} bps, must be {}", total, BPS_DENOMINATOR)
             ));
         }
 
@@ -123,8 +123,8 @@ impl DeploymentStrategy {
 pub struct DeploymentAllocation {
     /// Pool type to allocate to
     pub pool_type: PoolType,
-    /// Percentage of total to allocate (0-100)
-    pub percentage: f64,
+    /// Share of total to allocate (basis points, 0-10000)
+    pub share: u16,
     /// Minimum amount to allocate
     pub min_amount: Option<Amount>,
     /// Maximum amount to allocate
@@ -154,6 +154,14 @@ impl DeploymentManager {
         }
     }
 
+    /// Replace the time source of the risk calculator and all registered pools
+    pub fn set_clock(&mut self, clock: SharedClock) {
+        for pool in self.pools.values_mut() {
+            pool.set_clock(clock.clone());
+        }
+        self.risk_calculator.set_clock(clock);
+    }
+
     /// Register a new pool
     pub fn register_pool(&mut self, pool: LiquidityPool) -> LiquidityResult<()> {
         let id = pool.id();
@@ -252,32 +260,24 @@ impl DeploymentManager {
         Ok(report)
     }
 
-    /// Calculate allocation amount based on percentage
+    /// Calculate allocation amount based on its share
     fn calculate_allocation_amount(
         &self,
         total: &StakeAmount,
         allocation: &DeploymentAllocation,
     ) -> LiquidityResult<Amount> {
-        let total_val = total.inner()
-            .to_u64_digits()
-            .first()
-            .copied()
-            .unwrap_or(0) as f64;
-
-        let mut amount_val = (total_val * allocation.percentage) / 100.0;
+        let mut amount = total.mul_bps(allocation.share as u64);
 
         // Apply min/max constraints
         if let Some(min) = &allocation.min_amount {
-            let min_val = min.inner().to_u64_digits().first().copied().unwrap_or(0) as f64;
-            amount_val = amount_val.max(min_val);
+            amount = amount.max(min.clone());
         }
 
         if let Some(max) = &allocation.max_amount {
-            let max_val = max.inner().to_u64_digits().first().copied().unwrap_or(u64::MAX) as f64;
-            amount_val = amount_val.min(max_val);
+            amount = amount.min(max.clone());
         }
 
-        Ok(Amount::from_u64(amount_val as u64))
+        Ok(amount)
     }
 
     /// Find best pool for a given type based on risk-adjusted return
@@ -448,7 +448,7 @@ mod tests {
         let custom = DeploymentStrategy::Custom(vec![
             DeploymentAllocation {
                 pool_type: PoolType::AMM,
-                percentage: 60.0,
+                share: 6000,
                 min_amount: None,
                 max_amount: None,
             },
@@ -456,6 +456,23 @@ mod tests {
         assert!(custom.validate().is_err()); // Doesn't sum to 100%
     }
 
+    #[test]
+    fn test_allocation_amounts_are_exact() {
+        let manager = DeploymentManager::new(Amount::from_u64(100));
+        let total = Amount::from_tokens(1_000_000) + Amount::from_u64(1);
+
+        let amounts: Vec<Amount> = DeploymentStrategy::Balanced.default_allocations().iter()
+            .map(|allocation| manager.calculate_allocation_amount(&total, allocation).unwrap())
+            .collect();
+        assert_eq!(amounts[0], Amount::from_tokens(350_000));
+        assert_eq!(amounts[3], Amount::from_tokens(150_000));
+
+        // Rounding down never allocates more than the total
+        let allocated = amounts.into_iter().fold(Amount::zero(), |sum, amount| sum + amount);
+        assert!(allocated <= total);
+        assert_eq!(total.saturating_sub(&allocated), Amount::from_u64(1));
+    }
+
     #[test]
     fn test_deployment_manager() {
         let mut manager = DeploymentManager::new(Amount::from_u64(100));
@@ -1,7 +1,10 @@
 // liquidity/src/lending.rs
 
 use crate::{pool::LiquidityPool, LiquidityError, LiquidityResult};
-use blockchain_core::{Amount, Timestamp};
+use blockchain_core::{
+    clock::{Clock, SharedClock, SystemClock},
+    Amount, Rounding, Timestamp,
+};
 use blockchain_crypto::{Address, Hash};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -81,7 +84,18 @@ impl LoanPosition {
         collateral: Amount,
         rate: u16,
     ) -> Self {
-        let now = current_timestamp();
+        Self::new_with_clock(borrower, principal, collateral, rate, &SystemClock)
+    }
+
+    /// Create new loan position opened at the given clock's time
+    pub fn new_with_clock(
+        borrower: Address,
+        principal: Amount,
+        collateral: Amount,
+        rate: u16,
+        clock: &dyn Clock,
+    ) -> Self {
+        let now = clock.now();
         Self {
             id: Hash::zero(), // Would generate proper hash
             borrower,
@@ -98,18 +112,12 @@ impl LoanPosition {
     /// Calculate accrued interest
     pub fn calculate_interest(&self, current_time: Timestamp) -> Amount {
         let elapsed = current_time.saturating_sub(self.last_accrual);
-        let elapsed_years = elapsed as f64 / (365.25 * 24.0 * 3600.0);
-
-        let principal_val = self.principal.inner()
-            .to_u64_digits()
-            .first()
-            .copied()
-            .unwrap_or(0) as f64;
 
-        let rate_per_year = self.rate as f64 / 10000.0;
-        let interest_val = principal_val * rate_per_year * elapsed_years;
-
-        Amount::from_u64(interest_val as u64)
+        // principal × rate × elapsed / (365.25 days)
+        self.principal
+            .mul_bps(self.rate as u64)
+            .mul_div_u64(elapsed, 31_557_600, Rounding::Down)
+            .unwrap_or_else(Amount::zero)
     }
 
     /// Update accrued interest
@@ -133,16 +141,8 @@ impl LoanPosition {
             return f64::INFINITY;
         }
 
-        let collateral_val = self.collateral.inner()
-            .to_u64_digits()
-            .first()
-            .copied()
-            .unwrap_or(0) as f64;
-        let debt_val = debt.inner()
-            .to_u64_digits()
-            .first()
-            .copied()
-            .unwrap_or(1) as f64;
+        let collateral_val = self.collateral.to_f64_lossy();
+        let debt_val = debt.to_f64_lossy();
 
         collateral_val / debt_val
     }
@@ -199,6 +199,11 @@ impl LendingPool {
         }
     }
 
+    /// Replace the time source used for loans and interest accrual
+    pub fn set_clock(&mut self, clock: SharedClock) {
+        self.base.set_clock(clock);
+    }
+
     /// Supply liquidity for lending
     pub fn supply(&mut self, supplier: Address, amount: Amount) -> LiquidityResult<()> {
         self.base.deposit(supplier, amount.clone())?;
@@ -239,7 +244,7 @@ impl LendingPool {
         let rate = self.interest_model.calculate_rate(utilization);
 
         // Create loan
-        let mut loan = LoanPosition::new(borrower, borrow_amount.clone(), collateral, rate);
+        let mut loan = LoanPosition::new_with_clock(borrower, borrow_amount.clone(), collateral, rate, &**self.base.clock());
         loan.id = self.generate_loan_id(&loan);
 
         // Update totals
@@ -266,7 +271,7 @@ impl LendingPool {
         }
 
         // Accrue interest
-        loan.accrue_interest(current_timestamp());
+        loan.accrue_interest(self.base.clock().now());
 
         let total_debt = loan.total_debt();
         let repay_amount = amount.inner().min(total_debt.inner()).clone();
@@ -343,16 +348,8 @@ impl LendingPool {
             return 0.0;
         }
 
-        let borrowed = self.total_borrowed.inner()
-            .to_u64_digits()
-            .first()
-            .copied()
-            .unwrap_or(0) as f64;
-        let supplied = self.total_supplied.inner()
-            .to_u64_digits()
-            .first()
-            .copied()
-            .unwrap_or(1) as f64;
+        let borrowed = self.total_borrowed.to_f64_lossy();
+        let supplied = self.total_supplied.to_f64_lossy();
 
         borrowed / supplied
     }
@@ -375,16 +372,7 @@ impl LendingPool {
 
     /// Calculate required collateral for borrow amount
     fn calculate_required_collateral(&self, borrow_amount: &Amount) -> Amount {
-        let amount_val = borrow_amount.inner()
-            .to_u64_digits()
-            .first()
-            .copied()
-            .unwrap_or(0) as f64;
-
-        let ratio = self.collateral_ratio as f64 / 10000.0;
-        let required = amount_val * ratio;
-
-        Amount::from_u64(required as u64)
+        borrow_amount.mul_bps_rounded(self.collateral_ratio as u64, Rounding::Up)
     }
 
     /// Get available liquidity for borrowing
@@ -412,14 +400,6 @@ impl LendingPool {
     }
 }
 
-/// Helper to get current timestamp
-fn current_timestamp() -> Timestamp {
-    std::time::SystemTime::now()
-        .duration_since(std::time::UNIX_EPOCH)
-        .unwrap()
-        .as_secs()
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -473,19 +453,20 @@ mod tests {
 
     #[test]
     fn test_interest_accrual() {
+        let clock = blockchain_core::ManualClock::new(1_000);
         let borrower = Address::zero();
-        let mut loan = LoanPosition::new(
+        let mut loan = LoanPosition::new_with_clock(
             borrower,
             Amount::from_u64(10000),
             Amount::from_u64(15000),
             1000, // 10% APY
+            &clock,
         );
 
-        // Fast forward 1 year (simplified)
-        let future_time = current_timestamp() + 365 * 24 * 3600;
-        loan.accrue_interest(future_time);
+        // Fast forward 365.25 days
+        loan.accrue_interest(clock.advance(31_557_600));
 
-        assert!(loan.interest.inner() > &Amount::zero().inner());
+        assert_eq!(loan.interest, Amount::from_u64(1000));
     }
 
     #[test]
@@ -497,8 +478,8 @@ mod tests {
             interest: Amount::zero(),
             collateral: Amount::from_u64(11000), // Only 110% collateralized
             rate: 1000,
-            borrowed_at: current_timestamp(),
-            last_accrual: current_timestamp(),
+            borrowed_at: 0,
+            last_accrual: 0,
             status: LoanStatus::Active,
         };
 
@@ -1,7 +1,10 @@
 // liquidity/src/pool.rs
 
 use crate::{LiquidityError, LiquidityResult};
-use blockchain_core::{Amount, Timestamp};
+use blockchain_core::{
+    clock::{system_clock, Clock, SharedClock, SystemClock},
+    Amount, Rounding, Timestamp,
+};
 use blockchain_crypto::Address;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -51,13 +54,26 @@ impl PoolInfo {
         owner: Address,
         apy: u16,
         risk_score: u16,
+    ) -> Self {
+        Self::new_with_clock(id, pool_type, name, owner, apy, risk_score, &SystemClock)
+    }
+
+    /// Create new pool info stamped with the given clock's time
+    pub fn new_with_clock(
+        id: u64,
+        pool_type: PoolType,
+        name: String,
+        owner: Address,
+        apy: u16,
+        risk_score: u16,
+        clock: &dyn Clock,
     ) -> Self {
         Self {
             id,
             pool_type,
             name,
             tvl: Amount::zero(),
-            created_at: current_timestamp(),
+            created_at: clock.now(),
             owner,
             active: true,
             apy,
@@ -67,14 +83,7 @@ impl PoolInfo {
 
     /// Calculate expected annual yield for an amount
     pub fn calculate_yield(&self, amount: &Amount) -> Amount {
-        let amount_val = amount.inner()
-            .to_u64_digits()
-            .first()
-            .copied()
-            .unwrap_or(0) as f64;
-
-        let yield_val = (amount_val * self.apy as f64) / 10000.0;
-        Amount::from_u64(yield_val as u64)
+        amount.mul_bps(self.apy as u64)
     }
 
     /// Calculate risk-adjusted return
@@ -109,6 +118,11 @@ pub struct PoolMetrics {
 impl PoolMetrics {
     /// Create new metrics
     pub fn new() -> Self {
+        Self::new_with_clock(&SystemClock)
+    }
+
+    /// Create new metrics stamped with the given clock's time
+    pub fn new_with_clock(clock: &dyn Clock) -> Self {
         Self {
             total_deposits: Amount::zero(),
             total_withdrawals: Amount::zero(),
@@ -116,19 +130,16 @@ impl PoolMetrics {
             depositor_count: 0,
             avg_deposit: Amount::zero(),
             utilization_rate: 0.0,
-            updated_at: current_timestamp(),
+            updated_at: clock.now(),
         }
     }
 
     /// Update average deposit
     pub fn update_avg_deposit(&mut self) {
         if self.depositor_count > 0 {
-            let total = self.total_deposits.inner()
-                .to_u64_digits()
-                .first()
-                .copied()
-                .unwrap_or(0);
-            self.avg_deposit = Amount::from_u64(total / self.depositor_count as u64);
+            self.avg_deposit = self.total_deposits
+                .checked_div_u64(self.depositor_count as u64)
+                .unwrap_or_else(Amount::zero);
         }
     }
 }
@@ -149,6 +160,8 @@ pub struct LiquidityPool {
     deposits: HashMap<Address, Amount>,
     /// Pending withdrawals
     pending_withdrawals: HashMap<Address, PendingWithdrawal>,
+    /// Time source for metrics, withdrawal requests and delays
+    clock: SharedClock,
 }
 
 impl LiquidityPool {
@@ -159,9 +172,20 @@ impl LiquidityPool {
             metrics: PoolMetrics::new(),
             deposits: HashMap::new(),
             pending_withdrawals: HashMap::new(),
+            clock: system_clock(),
         }
     }
 
+    /// Replace the time source
+    pub fn set_clock(&mut self, clock: SharedClock) {
+        self.clock = clock;
+    }
+
+    /// Get the time source
+    pub fn clock(&self) -> &SharedClock {
+        &self.clock
+    }
+
     /// Get pool ID
     pub fn id(&self) -> u64 {
         self.info.id
@@ -204,7 +228,7 @@ impl LiquidityPool {
             self.metrics.depositor_count += 1;
         }
         self.metrics.update_avg_deposit();
-        self.metrics.updated_at = current_timestamp();
+        self.metrics.updated_at = self.clock.now();
 
         Ok(())
     }
@@ -229,7 +253,7 @@ impl LiquidityPool {
         let withdrawal = PendingWithdrawal {
             validator,
             amount: amount.clone(),
-            request_time: current_timestamp(),
+            request_time: self.clock.now(),
             unlock_time,
         };
 
@@ -238,6 +262,12 @@ impl LiquidityPool {
         Ok(())
     }
 
+    /// Process withdrawals unlocked at the current clock time
+    pub fn process_withdrawals_now(&mut self) -> Vec<(Address, Amount)> {
+        let now = self.clock.now();
+        self.process_withdrawals(now)
+    }
+
     /// Process completed withdrawals
     pub fn process_withdrawals(&mut self, current_time: Timestamp) -> Vec<(Address, Amount)> {
         let mut completed = Vec::new();
@@ -294,17 +324,10 @@ impl LiquidityPool {
     /// Calculate total yield generated
     pub fn calculate_yield(&mut self, elapsed_seconds: u64) -> Amount {
         // Simplified yield calculation: (TVL * APY * time) / (365 days)
-        let tvl_val = self.info.tvl.inner()
-            .to_u64_digits()
-            .first()
-            .copied()
-            .unwrap_or(0) as f64;
-
-        let apy_rate = self.info.apy as f64 / 10000.0;
-        let time_fraction = elapsed_seconds as f64 / (365.0 * 24.0 * 3600.0);
-        
-        let yield_val = tvl_val * apy_rate * time_fraction;
-        let yield_amount = Amount::from_u64(yield_val as u64);
+        let yield_amount = self.info.tvl
+            .mul_bps(self.info.apy as u64)
+            .mul_div_u64(elapsed_seconds, 365 * 24 * 3600, Rounding::Down)
+            .unwrap_or_else(Amount::zero);
 
         self.metrics.total_yield = self.metrics.total_yield.checked_add(&yield_amount)
             .unwrap_or_else(|| self.metrics.total_yield.clone());
@@ -342,14 +365,6 @@ pub struct PendingWithdrawal {
     pub unlock_time: Timestamp,
 }
 
-/// Helper to get current timestamp
-fn current_timestamp() -> Timestamp {
-    std::time::SystemTime::now()
-        .duration_since(std::time::UNIX_EPOCH)
-        .unwrap()
-        .as_secs()
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -393,7 +408,7 @@ mod tests {
 
         pool.deposit(validator, amount.clone()).unwrap();
         
-        let unlock_time = current_timestamp() + 3600;
+        let unlock_time = pool.clock().now() + 3600;
         pool.request_withdrawal(validator, Amount::from_u64(500), unlock_time).unwrap();
 
         assert!(pool.pending_withdrawals().contains_key(&validator));
@@ -401,21 +416,24 @@ mod tests {
 
     #[test]
     fn test_process_withdrawals() {
+        let clock = blockchain_core::ManualClock::new(1_000);
         let mut pool = create_test_pool();
+        pool.set_clock(clock.shared());
         let validator = Address::zero();
         let amount = Amount::from_u64(1000);
 
         pool.deposit(validator, amount).unwrap();
         
-        let unlock_time = current_timestamp() + 10;
+        let unlock_time = clock.now() + 10;
         pool.request_withdrawal(validator, Amount::from_u64(500), unlock_time).unwrap();
 
         // Process before unlock time
-        let completed1 = pool.process_withdrawals(current_timestamp());
+        let completed1 = pool.process_withdrawals_now();
         assert_eq!(completed1.len(), 0);
 
         // Process after unlock time
-        let completed2 = pool.process_withdrawals(unlock_time + 1);
+        clock.advance(11);
+        let completed2 = pool.process_withdrawals_now();
         assert_eq!(completed2.len(), 1);
         assert_eq!(pool.get_balance(&validator), Amount::from_u64(500));
     }
@@ -1,7 +1,10 @@
 // liquidity/src/risk.rs
 
 use crate::{pool::PoolInfo, LiquidityError, LiquidityResult};
-use blockchain_core::Amount;
+use blockchain_core::{
+    clock::{system_clock, SharedClock},
+    Amount, BPS_DENOMINATOR,
+};
 use serde::{Deserialize, Serialize};
 
 /// Risk assessment for a liquidity deployment
@@ -62,14 +65,14 @@ impl RiskCategory {
         }
     }
 
-    /// Get maximum allocation percentage for this risk category
-    pub fn max_allocation_percentage(&self) -> f64 {
+    /// Get maximum allocation share for this risk category (basis points)
+    pub fn max_allocation_share(&self) -> u16 {
         match self {
-            RiskCategory::VeryLow => 100.0,
-            RiskCategory::Low => 50.0,
-            RiskCategory::Medium => 30.0,
-            RiskCategory::High => 15.0,
-            RiskCategory::VeryHigh => 5.0,
+            RiskCategory::VeryLow => 10000,
+            RiskCategory::Low => 5000,
+            RiskCategory::Medium => 3000,
+            RiskCategory::High => 1500,
+            RiskCategory::VeryHigh => 500,
         }
     }
 }
@@ -79,21 +82,21 @@ impl RiskCategory {
 pub struct RiskProfile {
     /// Maximum acceptable risk score
     pub max_risk_score: u16,
-    /// Maximum percentage in high-risk pools
-    pub max_high_risk_percentage: f64,
+    /// Maximum share in high-risk pools (basis points)
+    pub max_high_risk_share: u16,
     /// Diversification requirements (min number of pools)
     pub min_pool_diversification: usize,
     /// Maximum allocation to single pool
-    pub max_single_pool_percentage: f64,
+    pub max_single_pool_share: u16,
 }
 
 impl Default for RiskProfile {
     fn default() -> Self {
         Self {
             max_risk_score: 5000,           // Medium risk tolerance
-            max_high_risk_percentage: 20.0, // Max 20% in high-risk
+            max_high_risk_share: 2000,      // Max 20% in high-risk
             min_pool_diversification: 3,     // At least 3 pools
-            max_single_pool_percentage: 40.0, // Max 40% in one pool
+            max_single_pool_share: 4000,     // Max 40% in one pool
         }
     }
 }
@@ -103,9 +106,9 @@ impl RiskProfile {
     pub fn conservative() -> Self {
         Self {
             max_risk_score: 3000,
-            max_high_risk_percentage: 10.0,
+            max_high_risk_share: 1000,
             min_pool_diversification: 4,
-            max_single_pool_percentage: 30.0,
+            max_single_pool_share: 3000,
         }
     }
 
@@ -113,9 +116,9 @@ impl RiskProfile {
     pub fn aggressive() -> Self {
         Self {
             max_risk_score: 7000,
-            max_high_risk_percentage: 40.0,
+            max_high_risk_share: 4000,
             min_pool_diversification: 2,
-            max_single_pool_percentage: 60.0,
+            max_single_pool_share: 6000,
         }
     }
 
@@ -129,6 +132,8 @@ impl RiskProfile {
 pub struct RiskCalculator {
     /// Historical volatility data (simplified)
     volatility_cache: std::collections::HashMap<u64, f64>,
+    /// Time source for pool age
+    clock: SharedClock,
 }
 
 impl RiskCalculator {
@@ -136,9 +141,15 @@ impl RiskCalculator {
     pub fn new() -> Self {
         Self {
             volatility_cache: std::collections::HashMap::new(),
+            clock: system_clock(),
         }
     }
 
+    /// Replace the time source
+    pub fn set_clock(&mut self, clock: SharedClock) {
+        self.clock = clock;
+    }
+
     /// Calculate risk assessment for a pool
     pub fn assess_pool(&self, pool: &PoolInfo) -> RiskAssessment {
         let volatility_risk = self.calculate_volatility_risk(pool);
@@ -177,7 +188,7 @@ impl RiskCalculator {
         // - Audit status (would be stored in pool metadata)
         // - Code complexity
         
-        let age_seconds = current_timestamp().saturating_sub(pool.created_at);
+        let age_seconds = self.clock.now().saturating_sub(pool.created_at);
         let age_days = age_seconds / (24 * 3600);
 
         // Risk decreases with age, plateaus after 365 days
@@ -194,11 +205,7 @@ impl RiskCalculator {
     /// Calculate liquidity risk (impermanent loss potential, slippage)
     fn calculate_liquidity_risk(&self, pool: &PoolInfo) -> u16 {
         // Higher TVL = lower liquidity risk
-        let tvl_val = pool.tvl.inner()
-            .to_u64_digits()
-            .first()
-            .copied()
-            .unwrap_or(1) as f64;
+        let tvl_val = pool.tvl.to_f64_lossy();
 
         // Risk inversely proportional to TVL
         // Assume $10M TVL = very low risk (500), $100K TVL = high risk (5000)
@@ -250,17 +257,9 @@ impl RiskCalculator {
             return 0.0;
         }
 
-        let protocol_val = protocol_reward.inner()
-            .to_u64_digits()
-            .first()
-            .copied()
-            .unwrap_or(0) as f64;
+        let protocol_val = protocol_reward.to_f64_lossy();
 
-        let yield_val = defi_yield.inner()
-            .to_u64_digits()
-            .first()
-            .copied()
-            .unwrap_or(0) as f64;
+        let yield_val = defi_yield.to_f64_lossy();
 
         (protocol_val + yield_val) / (risk_score / 100.0)
     }
@@ -299,18 +298,12 @@ impl RiskCalculator {
         let total_rar: f64 = pool_scores.iter().map(|(_, rar)| rar).sum();
         let mut allocations = Vec::new();
 
-        let total_val = total_amount.inner()
-            .to_u64_digits()
-            .first()
-            .copied()
-            .unwrap_or(0) as f64;
 
         for (pool_id, rar) in pool_scores {
-            let percentage = (rar / total_rar) * 100.0;
-            let capped_percentage = percentage.min(risk_profile.max_single_pool_percentage);
+            let share = ((rar / total_rar) * BPS_DENOMINATOR as f64) as u64;
+            let capped_share = share.min(risk_profile.max_single_pool_share as u64);
             
-            let amount_val = (total_val * capped_percentage) / 100.0;
-            let amount = Amount::from_u64(amount_val as u64);
+            let amount = total_amount.mul_bps(capped_share);
 
             if !amount.is_zero() {
                 allocations.push((pool_id, amount));
@@ -332,14 +325,6 @@ impl Default for RiskCalculator {
     }
 }
 
-/// Helper to get current timestamp
-fn current_timestamp() -> blockchain_core::Timestamp {
-    std::time::SystemTime::now()
-        .duration_since(std::time::UNIX_EPOCH)
-        .unwrap()
-        .as_secs()
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,7 +1,10 @@
 // liquidity/src/treasury.rs
 
 use crate::{pool::LiquidityPool, LiquidityError, LiquidityResult};
-use blockchain_core::{Amount, Timestamp};
+use blockchain_core::{
+    clock::{Clock, SharedClock, SystemClock},
+    Amount, Timestamp,
+};
 use blockchain_crypto::{Address, Hash};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -34,6 +37,17 @@ pub struct Grant {
 impl Grant {
     /// Create new grant proposal
     pub fn new(recipient: Address, amount: Amount, purpose: String, milestones: Vec<Milestone>) -> Self {
+        Self::new_with_clock(recipient, amount, purpose, milestones, &SystemClock)
+    }
+
+    /// Create new grant proposal stamped with the given clock's time
+    pub fn new_with_clock(
+        recipient: Address,
+        amount: Amount,
+        purpose: String,
+        milestones: Vec<Milestone>,
+        clock: &dyn Clock,
+    ) -> Self {
         Self {
             id: Hash::zero(), // Would generate proper hash
             recipient,
@@ -42,7 +56,7 @@ impl Grant {
             milestones,
             current_milestone: 0,
             disbursed: Amount::zero(),
-            proposed_at: current_timestamp(),
+            proposed_at: clock.now(),
             approved_at: None,
             status: GrantStatus::Proposed,
         }
@@ -141,8 +155,8 @@ impl TreasuryAllocation {
         let used = self.spent.checked_add(&self.reserved)
             .unwrap_or_else(|| self.spent.clone());
 
-        let used_val = used.inner().to_u64_digits().first().copied().unwrap_or(0) as f64;
-        let allocated_val = self.allocated.inner().to_u64_digits().first().copied().unwrap_or(1) as f64;
+        let used_val = used.to_f64_lossy();
+        let allocated_val = self.allocated.to_f64_lossy();
 
         (used_val / allocated_val) * 100.0
     }
@@ -197,6 +211,11 @@ impl NetworkTreasury {
         }
     }
 
+    /// Replace the time source used for grant and milestone timestamps
+    pub fn set_clock(&mut self, clock: SharedClock) {
+        self.base.set_clock(clock);
+    }
+
     /// Deposit funds to treasury
     pub fn deposit(&mut self, depositor: Address, amount: Amount) -> LiquidityResult<()> {
         self.base.deposit(depositor, amount)
@@ -235,7 +254,7 @@ impl NetworkTreasury {
         }
 
         // Validate milestones sum to total
-        let mut grant = Grant::new(recipient, amount.clone(), purpose, milestones);
+        let mut grant = Grant::new_with_clock(recipient, amount.clone(), purpose, milestones, &**self.base.clock());
         let milestone_total = grant.total_milestone_amounts();
         
         if milestone_total.inner() != amount.inner() {
@@ -268,7 +287,7 @@ impl NetworkTreasury {
         }
 
         grant.status = GrantStatus::Approved;
-        grant.approved_at = Some(current_timestamp());
+        grant.approved_at = Some(self.base.clock().now());
 
         self.total_granted = self.total_granted.checked_add(&grant.amount)
             .ok_or_else(|| LiquidityError::CalculationError("Total granted overflow".into()))?;
@@ -304,7 +323,7 @@ impl NetworkTreasury {
 
         // Mark milestone as completed
         milestone.completed = true;
-        milestone.completed_at = Some(current_timestamp());
+        milestone.completed_at = Some(self.base.clock().now());
 
         let amount = milestone.amount.clone();
 
@@ -399,14 +418,6 @@ impl NetworkTreasury {
     }
 }
 
-/// Helper to get current timestamp
-fn current_timestamp() -> Timestamp {
-    std::time::SystemTime::now()
-        .duration_since(std::time::UNIX_EPOCH)
-        .unwrap()
-        .as_secs()
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -458,14 +469,14 @@ mod tests {
             Milestone {
                 description: "Phase 1".to_string(),
                 amount: Amount::from_u64(5000),
-                deadline: current_timestamp() + 30 * 24 * 3600,
+                deadline: SystemClock.now() + 30 * 24 * 3600,
                 completed: false,
                 completed_at: None,
             },
             Milestone {
                 description: "Phase 2".to_string(),
                 amount: Amount::from_u64(5000),
-                deadline: current_timestamp() + 60 * 24 * 3600,
+                deadline: SystemClock.now() + 60 * 24 * 3600,
                 completed: false,
                 completed_at: None,
             },
@@ -495,7 +506,7 @@ mod tests {
             Milestone {
                 description: "Phase 1".to_string(),
                 amount: Amount::from_u64(10000),
-                deadline: current_timestamp() + 30 * 24 * 3600,
+                deadline: SystemClock.now() + 30 * 24 * 3600,
                 completed: false,
                 completed_at: None,
             },
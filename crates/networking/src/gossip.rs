@@ -1,11 +1,12 @@
 // networking/src/gossip.rs
-use blockchain_core::{Block, Transaction};
+use blockchain_core::{Block, DoubleSignEvidence, Transaction};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum GossipTopic {
     NewBlocks,
     NewTransactions,
     Consensus,
+    Evidence,
 }
 
 pub struct GossipService {
@@ -19,6 +20,7 @@ impl GossipService {
                 GossipTopic::NewBlocks,
                 GossipTopic::NewTransactions,
                 GossipTopic::Consensus,
+                GossipTopic::Evidence,
             ],
         }
     }
@@ -30,6 +32,10 @@ impl GossipService {
     pub fn broadcast_transaction(&self, _tx: &Transaction) {
         tracing::debug!("Broadcasting transaction");
     }
+
+    pub fn broadcast_evidence(&self, _evidence: &DoubleSignEvidence) {
+        tracing::debug!("Broadcasting double-sign evidence");
+    }
 }
 
 impl Default for GossipService {
@@ -6,6 +6,7 @@ use std::sync::Arc;
 use tokio::sync::Mutex;
 use std::collections::{HashMap, HashSet};
 use blockchain_core::{Transaction, TransactionPool, fork::ForkResolver};
+use consensus::{evidence::lock_pool, SharedEvidencePool};
 use blockchain_crypto::Hash;
 use crate::peer::PeerId;
 
@@ -43,6 +44,8 @@ pub struct NetworkService {
     pub mempool: Option<Arc<Mutex<TransactionPool>>>,
     /// Optional fork resolver reference
     pub fork_resolver: Option<Arc<Mutex<ForkResolver>>>,
+    /// Optional handle to the evidence pool shared with consensus
+    pub evidence_pool: Option<SharedEvidencePool>,
 }
 
 impl NetworkService {
@@ -59,6 +62,7 @@ impl NetworkService {
             outbox: Arc::new(Mutex::new(HashMap::new())),
             mempool: None,
             fork_resolver: None,
+            evidence_pool: None,
         }
     }
 
@@ -72,6 +76,11 @@ impl NetworkService {
         self.fork_resolver = Some(resolver);
     }
 
+    /// Attach the evidence pool shared with consensus
+    pub fn set_evidence_pool(&mut self, pool: SharedEvidencePool) {
+        self.evidence_pool = Some(pool);
+    }
+
     /// Send a protocol message to a specific peer (outbox shim)
     pub async fn send_to_peer(&self, peer_id: PeerId, msg: ProtocolMessage) -> NetworkResult<()> {
         let mut out = self.outbox.lock().await;
@@ -104,12 +113,18 @@ impl NetworkService {
             RequestMempoolSync(req) => {
                 self.handle_mempool_sync_request(peer_id, req).await
             }
+            MempoolSyncResponse(resp) => {
+                self.handle_mempool_sync_response(peer_id, resp).await
+            }
             ForkDetected(fmsg) => {
                 self.handle_fork_detected(peer_id, fmsg).await
             }
             RequestChainSegment(req) => {
                 self.handle_request_chain_segment(peer_id, req).await
             }
+            NewEvidence(emsg) => {
+                self.handle_new_evidence(peer_id, emsg).await
+            }
             // Fallbacks - other messages are handled elsewhere
             _ => Ok(()),
         }
@@ -210,6 +225,38 @@ impl NetworkService {
         Ok(())
     }
 
+    async fn handle_mempool_sync_response(&self, peer_id: crate::peer::PeerId, resp: crate::protocol::MempoolSyncResponseMessage) -> NetworkResult<()> {
+        let Some(pool) = &self.mempool else {
+            return Ok(());
+        };
+
+        // Signatures are verified as one batch; best-effort current_nonce = 0
+        let received = resp.transactions.len();
+        let results = pool.lock().await.add_batch(resp.transactions, |_| 0);
+        let added = results.iter().filter(|r| r.is_ok()).count();
+        let bad_signatures = results
+            .iter()
+            .filter(|r| matches!(r, Err(blockchain_core::BlockchainError::InvalidSignature)))
+            .count();
+
+        if bad_signatures > 0 {
+            let mut pm = self.peer_manager.lock().await;
+            if let Some(peer) = pm.get_peer_mut(&peer_id) {
+                peer.decrease_reputation(5);
+            }
+        }
+
+        tracing::debug!(
+            "Mempool sync from {}: {} received, {} added, {} bad signatures",
+            peer_id.to_hex(),
+            received,
+            added,
+            bad_signatures
+        );
+
+        Ok(())
+    }
+
     async fn handle_fork_detected(&self, peer_id: crate::peer::PeerId, msg: crate::protocol::ForkDetectedMessage) -> NetworkResult<()> {
         tracing::info!("Received fork detected from {}: fork_point={}", peer_id.to_hex(), hex::encode(msg.fork_point_hash.as_bytes()));
 
@@ -229,6 +276,61 @@ impl NetworkService {
         Ok(())
     }
 
+    async fn handle_new_evidence(&self, peer_id: crate::peer::PeerId, msg: crate::protocol::NewEvidenceMessage) -> NetworkResult<()> {
+        let Some(pool) = &self.evidence_pool else {
+            return Ok(());
+        };
+
+        // The pool verifies the evidence and de-duplicates it by offense;
+        // new evidence is forwarded below rather than gossiped again
+        let added = {
+            let mut pool = lock_pool(pool);
+            let added = pool.add(msg.evidence.clone());
+            if let Ok(true) = added {
+                pool.mark_announced(&msg.evidence.id());
+            }
+            added
+        };
+        match added {
+            Ok(false) => return Ok(()),
+            Ok(true) => {
+                let mut pm = self.peer_manager.lock().await;
+                if let Some(peer) = pm.get_peer_mut(&peer_id) {
+                    peer.increase_reputation(1);
+                }
+            }
+            Err(e) => {
+                tracing::warn!("Invalid evidence from peer {}: {:?}", peer_id.to_hex(), e);
+                let mut pm = self.peer_manager.lock().await;
+                if let Some(peer) = pm.get_peer_mut(&peer_id) {
+                    peer.decrease_reputation(5);
+                }
+                return Err(crate::NetworkError::InvalidMessage(format!("Invalid evidence: {:?}", e)));
+            }
+        }
+
+        // Evidence is rare, so new evidence is forwarded to every other peer
+        let forward_msg = crate::protocol::ProtocolMessage::NewEvidence(msg);
+        self.broadcast(forward_msg, usize::MAX, Some(peer_id)).await
+    }
+
+    /// Gossip evidence pooled locally since the last call to every connected peer,
+    /// returning how many pieces of evidence were sent
+    pub async fn gossip_evidence(&self) -> NetworkResult<usize> {
+        let Some(pool) = &self.evidence_pool else {
+            return Ok(0);
+        };
+
+        let evidence = lock_pool(pool).take_unannounced();
+        for item in &evidence {
+            let msg = crate::protocol::ProtocolMessage::NewEvidence(crate::protocol::NewEvidenceMessage {
+                evidence: item.clone(),
+            });
+            self.broadcast(msg, usize::MAX, None).await?;
+        }
+        Ok(evidence.len())
+    }
+
     async fn handle_request_chain_segment(&self, peer_id: crate::peer::PeerId, req: crate::protocol::RequestChainSegmentMessage) -> NetworkResult<()> {
         tracing::debug!("Peer {} requested chain segment {}..{}", peer_id.to_hex(), req.start_block, req.end_block);
         // In a full implementation we'd fetch blocks and respond. Here we log.
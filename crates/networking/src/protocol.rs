@@ -1,9 +1,14 @@
 // networking/src/protocol.rs
 
-use blockchain_core::{Block, BlockNumber, Transaction};
+use crate::{NetworkError, NetworkResult};
+use bincode::Options;
+use blockchain_core::{Block, BlockNumber, DoubleSignEvidence, Transaction, MAX_BLOCK_BYTES, MAX_TX_BYTES};
 use blockchain_crypto::Hash;
 use serde::{Deserialize, Serialize};
 
+/// Maximum encoded size of a single protocol message
+pub const MAX_MESSAGE_BYTES: usize = 8 * MAX_BLOCK_BYTES;
+
 /// Protocol message types
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum ProtocolMessage {
@@ -35,11 +40,91 @@ pub enum ProtocolMessage {
     RequestChainSegment(RequestChainSegmentMessage),
     /// Response containing a chain segment (blocks)
     ChainSegmentResponse(ChainSegmentResponseMessage),
+    /// Double-sign evidence announcement
+    NewEvidence(NewEvidenceMessage),
     /// Ping/Pong for keepalive
     Ping,
     Pong,
 }
 
+impl ProtocolMessage {
+    /// Encode message for the wire
+    pub fn encode(&self) -> NetworkResult<Vec<u8>> {
+        let bytes = wire_options()
+            .serialize(self)
+            .map_err(|e| NetworkError::SerializationError(e.to_string()))?;
+        if bytes.len() > MAX_MESSAGE_BYTES {
+            return Err(NetworkError::InvalidMessage(format!(
+                "Message too large: {} bytes (max {})", bytes.len(), MAX_MESSAGE_BYTES
+            )));
+        }
+        Ok(bytes)
+    }
+
+    /// Decode message from the wire, rejecting oversized messages, blocks and transactions
+    pub fn decode(bytes: &[u8]) -> NetworkResult<Self> {
+        if bytes.len() > MAX_MESSAGE_BYTES {
+            return Err(NetworkError::InvalidMessage(format!(
+                "Message too large: {} bytes (max {})", bytes.len(), MAX_MESSAGE_BYTES
+            )));
+        }
+
+        let msg: Self = wire_options()
+            .deserialize(bytes)
+            .map_err(|e| NetworkError::SerializationError(e.to_string()))?;
+        msg.check_sizes()?;
+        Ok(msg)
+    }
+
+    /// Check contained blocks and transactions against protocol size limits
+    fn check_sizes(&self) -> NetworkResult<()> {
+        match self {
+            ProtocolMessage::Blocks(BlocksMessage { blocks })
+            | ProtocolMessage::ChainSegmentResponse(ChainSegmentResponseMessage { blocks }) => {
+                blocks.iter().try_for_each(check_block_size)
+            }
+            ProtocolMessage::NewBlock(NewBlockMessage { block }) => check_block_size(block),
+            ProtocolMessage::Transactions(TransactionsMessage { transactions })
+            | ProtocolMessage::MempoolSyncResponse(MempoolSyncResponseMessage { transactions }) => {
+                transactions.iter().try_for_each(check_tx_size)
+            }
+            ProtocolMessage::NewTransaction(NewTransactionMessage { transaction })
+            | ProtocolMessage::NewPendingTransaction(NewPendingTransactionMessage { transaction, .. }) => {
+                check_tx_size(transaction)
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Bincode options matching `bincode::serialize`, bounded to the message size limit
+fn wire_options() -> impl Options {
+    bincode::DefaultOptions::new()
+        .with_fixint_encoding()
+        .allow_trailing_bytes()
+        .with_limit(MAX_MESSAGE_BYTES as u64)
+}
+
+fn check_block_size(block: &Block) -> NetworkResult<()> {
+    let size = block.size();
+    if size > MAX_BLOCK_BYTES {
+        return Err(NetworkError::InvalidMessage(format!(
+            "Block too large: {} bytes (max {})", size, MAX_BLOCK_BYTES
+        )));
+    }
+    Ok(())
+}
+
+fn check_tx_size(tx: &Transaction) -> NetworkResult<()> {
+    let size = tx.size();
+    if size > MAX_TX_BYTES {
+        return Err(NetworkError::InvalidMessage(format!(
+            "Transaction too large: {} bytes (max {})", size, MAX_TX_BYTES
+        )));
+    }
+    Ok(())
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct HandshakeMessage {
     pub protocol_version: u32,
@@ -121,10 +206,16 @@ pub struct ChainSegmentResponseMessage {
     pub blocks: Vec<Block>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NewEvidenceMessage {
+    pub evidence: DoubleSignEvidence,
+}
+
 /// Message type for gossip protocol
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum MessageType {
     Block,
     Transaction,
     Status,
+    Evidence,
 }
\ No newline at end of file
@@ -56,9 +56,7 @@ fn test_mempool_sync_and_tx_gossip() {
             21000,
         );
         // Sign the transaction
-        let msg_hash = tx.hash();
-        let sig = key.sign(msg_hash.as_bytes()).unwrap();
-        tx.signature = Some(sig);
+        tx.sign(&key).unwrap();
 
         // Build message and handle
         let msg = networking::protocol::ProtocolMessage::NewPendingTransaction(networking::protocol::NewPendingTransactionMessage {
@@ -147,8 +145,7 @@ fn test_mempool_sync_and_tx_gossip() {
                 20,
                 21000,
             );
-            let sig = key.sign(tx.hash().as_bytes()).unwrap();
-            tx.signature = Some(sig);
+            tx.sign(&key).unwrap();
 
             let msg = networking::protocol::ProtocolMessage::NewPendingTransaction(
                 networking::protocol::NewPendingTransactionMessage {
@@ -205,8 +202,7 @@ fn test_mempool_sync_and_tx_gossip() {
                 15,
                 21000,
             );
-            let sig2 = key2.sign(tx2.hash().as_bytes()).unwrap();
-            tx2.signature = Some(sig2);
+            tx2.sign(&key2).unwrap();
 
             let msg2 = networking::protocol::ProtocolMessage::NewPendingTransaction(
                 networking::protocol::NewPendingTransactionMessage {
@@ -227,3 +223,108 @@ fn test_mempool_sync_and_tx_gossip() {
             assert_eq!(peer2_rep, Some(1)); // Started at 0, increased by 1 for valid tx
         });
     }
+
+#[test]
+fn test_decode_rejects_oversized_transaction() {
+    let tx = Transaction::new(
+        blockchain_crypto::Address::zero(),
+        0,
+        TransactionType::ContractCall {
+            contract: blockchain_crypto::Address::zero(),
+            data: vec![1u8; blockchain_core::MAX_TX_BYTES + 1],
+        },
+        1,
+        u64::MAX,
+    );
+    let msg = ProtocolMessage::NewTransaction(networking::protocol::NewTransactionMessage {
+        transaction: tx,
+    });
+
+    let bytes = msg.encode().unwrap();
+    assert!(ProtocolMessage::decode(&bytes).is_err());
+
+    let ping = ProtocolMessage::Ping.encode().unwrap();
+    assert!(matches!(ProtocolMessage::decode(&ping), Ok(ProtocolMessage::Ping)));
+}
+
+#[test]
+fn test_evidence_gossip() {
+    use blockchain_core::DoubleSignEvidence;
+    use consensus::{evidence::lock_pool, SharedEvidencePool, Vote, VoteType};
+    use networking::protocol::NewEvidenceMessage;
+
+    let rt = Runtime::new().unwrap();
+    rt.block_on(async {
+        let cfg = NetworkConfig {
+            listen_addr: "127.0.0.1:9010".parse().unwrap(),
+            max_peers: 10,
+            max_inbound: 5,
+            max_outbound: 5,
+            bootstrap_peers: vec![],
+            enable_tx_gossip: true,
+            mempool_sync_on_connect: false,
+            max_tx_propagate_peers: 4,
+            fork_detection_enabled: false,
+        };
+
+        let mut svc = NetworkService::new(cfg);
+        let pool = SharedEvidencePool::default();
+        svc.set_evidence_pool(pool.clone());
+
+        let peer1 = networking::peer::PeerInfo::new(PeerId::random(), "127.0.0.1:9011".parse().unwrap(), 1, "peer1/1.0".into(), true);
+        let peer2 = networking::peer::PeerInfo::new(PeerId::random(), "127.0.0.1:9012".parse().unwrap(), 1, "peer2/1.0".into(), true);
+        let peer1_id = peer1.id;
+        let peer2_id = peer2.id;
+        {
+            let pm_arc = svc.peer_manager_arc();
+            let mut pm = pm_arc.lock().await;
+            pm.add_peer(peer1).unwrap();
+            pm.add_peer(peer2).unwrap();
+            for id in [peer1_id, peer2_id] {
+                pm.get_peer_mut(&id).unwrap().status = networking::peer::PeerStatus::Connected;
+            }
+        }
+
+        let key = KeyPair::generate(blockchain_crypto::SignatureScheme::Ed25519).unwrap();
+        let vote = |byte| {
+            Box::new(Vote::sign(&key, VoteType::Precommit, 4, blockchain_crypto::Hash::new([byte; 32])).unwrap().to_signed())
+        };
+        let evidence = DoubleSignEvidence::ConflictingVotes { first: vote(1), second: vote(2) };
+        let msg = ProtocolMessage::NewEvidence(NewEvidenceMessage { evidence: evidence.clone() });
+
+        // New evidence is pooled and forwarded to the other peer only
+        svc.handle_incoming_message(peer1_id, msg.clone()).await.unwrap();
+        assert_eq!(lock_pool(&pool).len(), 1);
+        assert!(svc.drain_outbox_for(peer1_id).await.is_empty());
+        let forwarded = svc.drain_outbox_for(peer2_id).await;
+        assert!(matches!(forwarded.as_slice(), [ProtocolMessage::NewEvidence(_)]));
+
+        // Known evidence is not forwarded again
+        svc.handle_incoming_message(peer2_id, msg).await.unwrap();
+        assert!(svc.drain_outbox_for(peer1_id).await.is_empty());
+
+        // Forged evidence is rejected
+        let other = KeyPair::generate(blockchain_crypto::SignatureScheme::Ed25519).unwrap();
+        let same = Box::new(Vote::sign(&other, VoteType::Precommit, 4, blockchain_crypto::Hash::new([3u8; 32])).unwrap().to_signed());
+        let forged = DoubleSignEvidence::ConflictingVotes { first: same.clone(), second: same };
+        let msg = ProtocolMessage::NewEvidence(NewEvidenceMessage { evidence: forged });
+        assert!(svc.handle_incoming_message(peer1_id, msg).await.is_err());
+        assert_eq!(lock_pool(&pool).len(), 1);
+
+        // Forwarded evidence is not gossiped again
+        assert_eq!(svc.gossip_evidence().await.unwrap(), 0);
+
+        // Evidence pooled locally, e.g. by consensus, is gossiped to every peer once
+        let at_height_5 = |byte| {
+            Box::new(Vote::sign(&key, VoteType::Precommit, 5, blockchain_crypto::Hash::new([byte; 32])).unwrap().to_signed())
+        };
+        let local = DoubleSignEvidence::ConflictingVotes { first: at_height_5(1), second: at_height_5(2) };
+        lock_pool(&pool).add(local).unwrap();
+        assert_eq!(svc.gossip_evidence().await.unwrap(), 1);
+        for id in [peer1_id, peer2_id] {
+            let sent = svc.drain_outbox_for(id).await;
+            assert!(matches!(sent.as_slice(), [ProtocolMessage::NewEvidence(_)]));
+        }
+        assert_eq!(svc.gossip_evidence().await.unwrap(), 0);
+    });
+}
@@ -1,4 +1,6 @@
 // node/src/config.rs
+use blockchain_core::ChainConfig;
+use blockchain_crypto::{KeyPair, Keystore, SecretKey, SignatureScheme};
 use serde::{Deserialize, Serialize};
 use std::net::SocketAddr;
 use std::path::Path;
@@ -6,6 +8,9 @@ use std::path::Path;
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NodeConfig {
     pub data_dir: String,
+    /// Consensus-critical chain parameters; must match every other node
+    #[serde(default)]
+    pub chain: ChainConfig,
     pub network: NetworkConfig,
     pub rpc: RpcConfig,
     pub consensus: ConsensusConfig,
@@ -35,6 +40,10 @@ pub struct RpcConfig {
 pub struct ConsensusConfig {
     pub min_stake: u64,
     pub block_time_seconds: u64,
+    /// Unix time of the genesis block, where slot 0 starts; must match every other node.
+    /// Unset, the node starts its own chain when it first opens its database
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub genesis_time: Option<u64>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -48,6 +57,9 @@ pub struct StorageConfig {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ValidatorConfig {
     pub key_path: String,
+    /// File holding the keystore password; falls back to `KEYSTORE_PASSWORD_ENV`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub password_file: Option<String>,
     pub commission_rate: u16,
     pub auto_stake: bool,
     pub initial_stake: u64,
@@ -84,6 +96,7 @@ impl Default for NodeConfig {
     fn default() -> Self {
         Self {
             data_dir: "./data".into(),
+            chain: ChainConfig::default(),
             network: NetworkConfig {
                 listen_addr: "0.0.0.0:30303".parse().unwrap(),
                 max_peers: 50,
@@ -97,6 +110,7 @@ impl Default for NodeConfig {
             consensus: ConsensusConfig {
                 min_stake: 10_000,
                 block_time_seconds: 3,
+                genesis_time: None,
             },
             storage: StorageConfig {
                 cache_size_mb: 512,
@@ -143,3 +157,50 @@ impl NodeConfig {
         Ok(())
     }
 }
+
+/// Environment variable read for the keystore password
+pub const KEYSTORE_PASSWORD_ENV: &str = "UTILITY_NODE_KEY_PASSWORD";
+
+/// Read a keystore password from a file, or from `KEYSTORE_PASSWORD_ENV`
+pub fn read_password(password_file: Option<&str>) -> anyhow::Result<String> {
+    match password_file {
+        Some(path) => {
+            let contents = std::fs::read_to_string(path)?;
+            Ok(contents.trim_end_matches(['\r', '\n']).to_string())
+        }
+        None => std::env::var(KEYSTORE_PASSWORD_ENV).map_err(|_| {
+            anyhow::anyhow!(
+                "No keystore password: set password_file or {}",
+                KEYSTORE_PASSWORD_ENV
+            )
+        }),
+    }
+}
+
+impl ValidatorConfig {
+    /// Unlock the validator key
+    ///
+    /// Encrypted keystores are decrypted with the configured password. Legacy
+    /// plaintext key files written by older `keygen` still load, with a warning.
+    pub fn load_keypair(&self) -> anyhow::Result<KeyPair> {
+        let contents = std::fs::read_to_string(&self.key_path)?;
+        let json: serde_json::Value = serde_json::from_str(&contents)?;
+
+        if json.get("crypto").is_some() || json.get("Crypto").is_some() {
+            let keystore = Keystore::from_json(&contents)?;
+            let password = read_password(self.password_file.as_deref())?;
+            return Ok(keystore.decrypt(&password)?);
+        }
+
+        let secret = json
+            .get("secret_key")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow::anyhow!("{} is not a key file", self.key_path))?;
+        tracing::warn!(
+            "Validator key {} is stored unencrypted; regenerate it with `validator keygen`",
+            self.key_path
+        );
+        let secret = SecretKey::from_hex(SignatureScheme::Ed25519, secret)?;
+        Ok(KeyPair::from_secret_key(secret)?)
+    }
+}
@@ -82,6 +82,14 @@ enum ValidatorCommands {
         /// Output path
         #[arg(short, long)]
         output: String,
+
+        /// File holding the keystore password (defaults to $UTILITY_NODE_KEY_PASSWORD)
+        #[arg(short, long)]
+        password_file: Option<String>,
+
+        /// Write the secret key as plain hex instead of an encrypted keystore
+        #[arg(long)]
+        insecure_plaintext: bool,
     },
 }
 
@@ -210,20 +218,27 @@ async fn handle_validator_command(command: ValidatorCommands) -> anyhow::Result<
             tracing::info!("Unregistering validator");
             tracing::info!("Not yet implemented - requires running node");
         }
-        ValidatorCommands::Keygen { output } => {
-            use blockchain_crypto::{KeyPair, SignatureScheme};
+        ValidatorCommands::Keygen { output, password_file, insecure_plaintext } => {
+            use blockchain_crypto::{Kdf, KeyPair, Keystore, SignatureScheme};
             
             tracing::info!("Generating validator keypair");
             let keypair = KeyPair::generate(SignatureScheme::Ed25519)?;
             
-            let key_json = serde_json::json!({
-                "public_key": keypair.public_key().to_hex(),
-                "secret_key": keypair.secret_key().to_hex(),
-                "address": keypair.public_key().to_address().to_hex(),
-            });
+            let contents = if insecure_plaintext {
+                let key_json = serde_json::json!({
+                    "public_key": keypair.public_key().to_hex(),
+                    "secret_key": keypair.secret_key().export_hex().as_str(),
+                    "address": keypair.public_key().to_address().to_hex(),
+                });
+                serde_json::to_string_pretty(&key_json)?
+            } else {
+                let password = node::config::read_password(password_file.as_deref())?;
+                Keystore::encrypt(&keypair, &password, Kdf::default())?.to_json()?
+            };
             
-            std::fs::write(&output, serde_json::to_string_pretty(&key_json)?)?;
+            std::fs::write(&output, contents)?;
             tracing::info!("Keypair saved to {}", output);
+            tracing::info!("Address: {}", keypair.public_key().to_address().to_hex());
             tracing::warn!("Keep this file secure!");
         }
     }
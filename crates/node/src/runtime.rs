@@ -1,8 +1,8 @@
 // node/src/runtime.rs
 use crate::NodeConfig;
-use blockchain_core::{Block, Blockchain, TransactionPool, PoolConfig, ForkResolver, ForkChoice, ChainMetrics};
-use blockchain_crypto::Hash;
-use consensus::{PoASConsensus, ConsensusConfig as PoASConfig};
+use blockchain_core::{Block, Blockchain, TransactionType, TransactionPool, PoolConfig, ForkResolver, ForkChoice, ChainMetrics, MetricsCalculator, BlockExecution};
+use blockchain_crypto::{Hash, KeyPair};
+use consensus::{evidence::submit_evidence_transaction, PoASConsensus, ConsensusConfig as PoASConfig};
 use storage::{Database, DatabaseConfig, PruningMode};
 use networking::{NetworkService, NetworkConfig as NetConfig};
 use rpc::{RpcServer, RpcConfig as RpcCfg, RpcMethods};
@@ -11,6 +11,9 @@ use std::time::{SystemTime, UNIX_EPOCH};
 use tokio::sync::RwLock;
 use tokio::time::{interval, Duration};
 
+/// Most pieces of double-sign evidence a producer reports per block
+const MAX_EVIDENCE_PER_BLOCK: usize = 16;
+
 pub struct Node {
     config: NodeConfig,
     blockchain: Arc<RwLock<Blockchain>>,
@@ -20,11 +23,15 @@ pub struct Node {
     fork_resolver: Arc<RwLock<ForkResolver>>,
     network: Option<Arc<NetworkService>>,
     rpc: Option<Arc<RpcServer>>,
+    validator_key: Option<KeyPair>,
 }
 
 impl Node {
     pub fn new(config: NodeConfig) -> anyhow::Result<Self> {
         tracing::info!("Initializing node components");
+
+        // Fix the hash algorithm before anything is hashed
+        config.chain.install()?;
         
         // Initialize database
         let pruning_mode = if config.storage.pruning == "archive" {
@@ -48,9 +55,22 @@ impl Node {
         };
         let database = Arc::new(Database::open(db_config)?);
         
-        // Initialize blockchain
-        let genesis = Block::genesis(Hash::zero());
-        let blockchain = Arc::new(RwLock::new(Blockchain::new(genesis)?));
+        // Initialize blockchain from a genesis block at the configured genesis time,
+        // or the time this node first opened its database
+        let genesis_time = match config.consensus.genesis_time {
+            Some(time) => time,
+            None => match database.get_meta("genesis_time")? {
+                Some(bytes) => u64::from_be_bytes(bytes.as_slice().try_into()?),
+                None => {
+                    let now = unix_timestamp();
+                    database.store_meta("genesis_time", &now.to_be_bytes())?;
+                    now
+                }
+            },
+        };
+        let mut genesis = Block::genesis(Hash::zero());
+        genesis.header.timestamp = genesis_time;
+        let mut chain = Blockchain::new(genesis.clone())?;
         
         // Initialize consensus
         let consensus_config = PoASConfig {
@@ -58,7 +78,11 @@ impl Node {
             block_time: config.consensus.block_time_seconds,
             ..Default::default()
         };
-        let consensus = Arc::new(RwLock::new(PoASConsensus::new(consensus_config)));
+        chain.set_unbonding_period(consensus_config.unbonding_period);
+        let blockchain = Arc::new(RwLock::new(chain));
+        let mut consensus = PoASConsensus::new(consensus_config);
+        consensus.set_genesis(&genesis);
+        let consensus = Arc::new(RwLock::new(consensus));
         
         // Initialize transaction pool
         let pool_config = PoolConfig {
@@ -83,10 +107,17 @@ impl Node {
             _ => ForkChoice::LongestChain,
         };
 
-        let fork_resolver = Arc::new(RwLock::new(ForkResolver::new(
-            fork_choice,
-            config.fork_handling.max_reorg_depth,
-        )));
+        let mut resolver = ForkResolver::new(fork_choice, config.fork_handling.max_reorg_depth);
+        match database.get_block_tree()? {
+            Some(tree) => *resolver.tree_mut() = tree,
+            None => {
+                resolver.track_block(&genesis, 0)?;
+                let root: Vec<_> = resolver.tree().get(&genesis.hash()).cloned().into_iter().collect();
+                database.store_tree_nodes(&root)?;
+                database.prune_block_tree(&genesis.hash(), &[])?;
+            }
+        }
+        let fork_resolver = Arc::new(RwLock::new(resolver));
         
         tracing::info!(
             "✓ ForkResolver initialized: strategy={}, max_reorg_depth={}",
@@ -94,6 +125,19 @@ impl Node {
             config.fork_handling.max_reorg_depth
         );
         
+        // Unlock validator key
+        let validator_key = match &config.validator {
+            Some(validator) => {
+                let keypair = validator.load_keypair()?;
+                tracing::info!(
+                    "✓ Validator key unlocked: {}",
+                    keypair.public_key().to_address().to_hex()
+                );
+                Some(keypair)
+            }
+            None => None,
+        };
+        
         tracing::info!("Node components initialized");
         
         Ok(Self {
@@ -105,6 +149,7 @@ impl Node {
             fork_resolver,
             network: None,
             rpc: None,
+            validator_key,
         })
     }
 
@@ -132,7 +177,9 @@ impl Node {
         };
         
         let mut network = NetworkService::new(net_config);
+        network.set_evidence_pool(self.consensus.read().await.shared_evidence_pool());
         network.start().await?;
+        self.start_evidence_gossip(Arc::new(network));
         
         tracing::info!("✓ Network service started on {}", self.config.network.listen_addr);
         
@@ -206,6 +253,11 @@ impl Node {
         &self.fork_resolver
     }
 
+    /// Unlocked validator key, if this node is configured as a validator
+    pub fn validator_key(&self) -> Option<&KeyPair> {
+        self.validator_key.as_ref()
+    }
+
     // ==================== BACKGROUND TASKS ====================
 
     fn start_mempool_tasks(&self) {
@@ -248,21 +300,109 @@ impl Node {
         tracing::info!("✓ Mempool background tasks started");
     }
 
+    fn start_evidence_gossip(&self, network: Arc<NetworkService>) {
+        tokio::spawn(async move {
+            let mut ticker = interval(Duration::from_secs(1));
+            loop {
+                ticker.tick().await;
+                // Evidence found by consensus lands in the shared pool; peers' evidence is forwarded on receipt
+                match network.gossip_evidence().await {
+                    Ok(0) => {}
+                    Ok(count) => tracing::info!("Gossiped {} pieces of double-sign evidence", count),
+                    Err(e) => tracing::warn!("Failed to gossip evidence: {}", e),
+                }
+            }
+        });
+
+        tracing::info!("✓ Evidence gossip started");
+    }
+
     fn start_fork_monitor(&self) {
         let blockchain = self.blockchain.clone();
+        let consensus = self.consensus.clone();
         let fork_resolver = self.fork_resolver.clone();
         let database = self.database.clone();
         let alert_threshold = self.config.fork_handling.alert_threshold_depth;
 
         tokio::spawn(async move {
             let mut ticker = interval(Duration::from_secs(5));
+            // Blocks below the persisted root were pruned and are not re-tracked
+            let mut last_tracked = {
+                let resolver = fork_resolver.read().await;
+                let tree = resolver.tree();
+                tree.root().and_then(|root| tree.height(&root)).unwrap_or(0)
+            };
             loop {
                 ticker.tick().await;
-                // Check for fork candidates (simplified check)
+
+                // Feed newly imported canonical blocks into the block tree
                 let chain = blockchain.read().await;
                 let height = chain.height();
+                let head = chain.head_block().hash();
+                let new_blocks = if height > last_tracked {
+                    chain.get_block_range(last_tracked + 1, height)
+                } else {
+                    Vec::new()
+                };
                 drop(chain);
 
+                let consensus_guard = consensus.read().await;
+                let mut resolver = fork_resolver.write().await;
+                let mut tracked = Vec::new();
+                for block in &new_blocks {
+                    match resolver.track_block(block, block_weight(&consensus_guard, block)) {
+                        Ok(()) => tracked.extend(resolver.tree().get(&block.hash()).cloned()),
+                        Err(e) => tracing::warn!("Failed to track block #{}: {}", block.number(), e),
+                    }
+                }
+                let finalized = consensus_guard.finalized_checkpoint().map(|checkpoint| checkpoint.block_hash);
+                drop(consensus_guard);
+                last_tracked = height;
+
+                // Branches that do not descend from the finalized checkpoint can never win
+                let mut pruned = None;
+                if let Some(checkpoint) = finalized {
+                    let tree = resolver.tree();
+                    if tree.root() != Some(checkpoint) && tree.contains(&checkpoint) {
+                        match resolver.tree_mut().prune(&checkpoint) {
+                            Ok(removed) => pruned = Some((checkpoint, removed)),
+                            Err(e) => tracing::warn!("Failed to prune block tree: {}", e),
+                        }
+                    }
+                }
+
+                // Measure how far each competing branch diverges from the canonical head
+                let tree = resolver.tree();
+                for tip in tree.tips() {
+                    if tip == head {
+                        continue;
+                    }
+                    let depth = tree.common_ancestor(&head, &tip)
+                        .and_then(|ancestor| tree.height(&ancestor))
+                        .map(|fork_point| height.saturating_sub(fork_point))
+                        .unwrap_or(0);
+                    if depth > alert_threshold {
+                        tracing::warn!(
+                            "⚠️  Competing branch {} diverges {} blocks below head",
+                            tip.to_hex(),
+                            depth
+                        );
+                    }
+                }
+                drop(resolver);
+
+                if !tracked.is_empty() {
+                    if let Err(e) = database.store_tree_nodes(&tracked) {
+                        tracing::error!("Failed to persist block tree nodes: {}", e);
+                    }
+                }
+                if let Some((root, removed)) = pruned {
+                    match database.prune_block_tree(&root, &removed) {
+                        Ok(()) => tracing::debug!("Pruned {} block tree nodes below {}", removed.len(), root.to_hex()),
+                        Err(e) => tracing::error!("Failed to prune persisted block tree: {}", e),
+                    }
+                }
+
                 // Log fork statistics periodically
                 if let Ok(stats) = database.get_fork_statistics() {
                     if stats.total_forks > 0 {
@@ -288,10 +428,11 @@ impl Node {
     fn start_metrics_collector(&self) {
         let blockchain = self.blockchain.clone();
         let database = self.database.clone();
-        let snapshot_interval = self.config.metrics.snapshot_interval;
+        let mempool = self.mempool.clone();
         let window_size = self.config.metrics.window_size;
 
         tokio::spawn(async move {
+            let mut calculator = MetricsCalculator::new(window_size as usize);
             let mut last_block = 0u64;
             let mut ticker = interval(Duration::from_secs(5));
 
@@ -300,20 +441,58 @@ impl Node {
 
                 let chain = blockchain.read().await;
                 let current_block = chain.height();
+                if current_block <= last_block {
+                    continue;
+                }
+
+                // Collect new blocks with their receipts, burns and parent timestamps
+                let mut updates = Vec::new();
+                for number in last_block + 1..=current_block {
+                    let (Some(block), Some(parent)) = (
+                        chain.get_block_by_number(number),
+                        chain.get_block_by_number(number - 1),
+                    ) else {
+                        continue;
+                    };
+                    updates.push((
+                        block.clone(),
+                        parent.header.timestamp,
+                        chain.get_block_receipts(number),
+                        chain.get_block_burned(number),
+                    ));
+                }
                 drop(chain);
 
-                // Store metrics every N blocks
-                if current_block > last_block + snapshot_interval {
-                    if let Ok(Some(metrics)) = database.get_latest_metrics() {
-                        tracing::debug!(
-                            "Metrics: TPS={:.2}, block_time={:.2}s, gas_used={}",
-                            metrics.metrics.tps,
-                            metrics.metrics.avg_block_time,
-                            metrics.metrics.total_gas_used
-                        );
+                let pool = mempool.read().await;
+                for (block, prev_timestamp, receipts, burned) in updates {
+                    let added_at = block.transactions.iter()
+                        .filter_map(|tx| {
+                            let hash = tx.hash();
+                            pool.admission_time(&hash).map(|t| (hash, t))
+                        })
+                        .collect();
+                    let execution = BlockExecution {
+                        receipts,
+                        added_at,
+                        burned,
+                    };
+
+                    calculator.update_with_execution(&block, prev_timestamp, &execution);
+                    if let Err(e) = database.store_chain_metrics(block.number(), calculator.metrics()) {
+                        tracing::warn!("Failed to persist metrics for block #{}: {}", block.number(), e);
                     }
-                    last_block = current_block;
                 }
+                drop(pool);
+
+                let metrics = calculator.metrics();
+                tracing::debug!(
+                    "Metrics: TPS={:.2}, block_time p50/p99={:.2}s/{:.2}s, failed={:.2}%",
+                    metrics.tps,
+                    metrics.block_time_percentiles.p50,
+                    metrics.block_time_percentiles.p99,
+                    metrics.failed_tx_rate
+                );
+                last_block = current_block;
             }
         });
 
@@ -323,62 +502,99 @@ impl Node {
     // ==================== BLOCK PRODUCTION ====================
 
     pub async fn produce_block(&self) -> anyhow::Result<Block> {
-        let block_gas_limit = 8_000_000u64;
-        let max_tx_count = 1000usize;
-
-        // Step 1: Get pending transactions
-        let txs = self.mempool.read().await.get_pending(block_gas_limit, max_tx_count);
-        tracing::debug!("Selected {} transactions for block", txs.len());
-
-        // Step 2-3: Validate and execute (simplified - actual implementation would execute all)
-        let mut valid_txs = Vec::new();
-        let mut total_gas = 0u64;
-
-        for tx in txs {
-            let gas = tx.gas_limit;
-            if total_gas + gas <= block_gas_limit {
-                total_gas += gas;
-                valid_txs.push(tx);
-            }
-        }
+        let keypair = self.validator_key.as_ref()
+            .ok_or_else(|| anyhow::anyhow!("Block production requires a validator key"))?;
 
-        // Step 4: Create block using canonical constructor
-        let blockchain = self.blockchain.read().await;
-        let parent = blockchain.head_block().clone();
-        let next_number = parent.number() + 1;
-        let state_root = blockchain.state().state_root();
-        drop(blockchain);
+        let parent = self.blockchain.read().await.head_block().clone();
 
-        let block = Block::new(
-            next_number,
-            parent.hash(),
-            state_root,
-            blockchain_crypto::Address::zero(),
-            valid_txs,
-            block_gas_limit,
-        )?;
+        // Report pooled double-sign evidence so this block includes it
+        self.report_pending_evidence(keypair).await?;
+
+        // Consensus picks transactions from the mempool, checks this validator
+        // may propose in the current slot, attaches the election proof and the
+        // parent's certificate, and signs the block with the validator key
+        let mut mempool = self.mempool.write().await;
+        let block = self.consensus.write().await.produce_block(&parent, keypair, &mut mempool)?;
+        drop(mempool);
 
         tracing::info!("📦 Block #{} produced with {} transactions", block.number(), block.transactions.len());
 
         Ok(block)
     }
 
+    /// Submit pooled evidence not yet in the mempool as `SubmitEvidence`
+    /// transactions from the validator key
+    async fn report_pending_evidence(&self, reporter: &KeyPair) -> anyhow::Result<()> {
+        let evidence = self.consensus.read().await.evidence_pool().pending(MAX_EVIDENCE_PER_BLOCK);
+        if evidence.is_empty() {
+            return Ok(());
+        }
+
+        let address = reporter.public_key().to_address();
+        let state_nonce = self.blockchain.read().await.state().get_nonce(&address);
+        let mut pool = self.mempool.write().await;
+        let queued = pool.get_by_sender(&address);
+        let mut nonce = state_nonce + queued.len() as u64;
+        for item in evidence {
+            let id = item.id();
+            let reported = queued.iter().any(|tx| {
+                matches!(&tx.tx_type, TransactionType::SubmitEvidence { evidence } if evidence.id() == id)
+            });
+            if reported {
+                continue;
+            }
+
+            let tx = submit_evidence_transaction(&item, reporter, nonce, self.config.mempool.min_gas_price)?;
+            pool.add(tx, state_nonce)?;
+            tracing::info!("Reporting double-sign by {} at height {}", item.offender().to_hex(), item.height());
+            nonce += 1;
+        }
+        Ok(())
+    }
+
     // ==================== FORK HANDLING ====================
 
     pub async fn handle_incoming_block(&self, block: &Block) -> anyhow::Result<bool> {
         let blockchain = self.blockchain.read().await;
         let head = blockchain.head_block().clone();
+        drop(blockchain);
 
-        // Step 1: Detect fork
+        // Step 1: Track every received block, canonical or competing, weighted by its proposer's stake
+        let weight = block_weight(&*self.consensus.read().await, block);
+        let mut resolver = self.fork_resolver.write().await;
+        let tracked = match resolver.track_block(block, weight) {
+            Ok(()) => resolver.tree().get(&block.hash()).cloned(),
+            Err(e) => {
+                tracing::debug!("Block #{} not linked into block tree: {}", block.number(), e);
+                None
+            }
+        };
+        drop(resolver);
+        if let Some(node) = tracked {
+            if let Err(e) = self.database.store_tree_nodes(&[node]) {
+                tracing::error!("Failed to persist block tree node: {}", e);
+            }
+        }
+
+        // Step 2: Detect fork
         let is_fork = block.header.parent_hash != head.hash();
 
         if is_fork {
             tracing::warn!("🔄 Fork detected at block #{}", block.header.number);
 
-            // Step 2: Calculate reorg path (simplified)
-            let reorg_depth = 1u64;
+            // Step 3: Locate the fork point in the block tree
+            let resolver = self.fork_resolver.read().await;
+            let tree = resolver.tree();
+            let fork_point = tree.common_ancestor(&head.hash(), &block.hash())
+                .and_then(|ancestor| tree.get(&ancestor))
+                .map(|node| (node.hash, node.number));
+            drop(resolver);
+
+            let (fork_hash, fork_number) = fork_point.unwrap_or((head.hash(), head.number()));
+            let reorg_depth = head.number().saturating_sub(fork_number);
+            let fork_length = block.number().saturating_sub(fork_number);
 
-            // Step 3-5: Handle reorganization if necessary
+            // Step 4-6: Handle reorganization if necessary
             if reorg_depth <= self.config.fork_handling.max_reorg_depth {
                 if self.config.fork_handling.alert_threshold_depth > 0
                     && reorg_depth > self.config.fork_handling.alert_threshold_depth
@@ -388,12 +604,12 @@ impl Node {
 
                 // Record fork event with full audit trail
                 if let Err(e) = self.database.store_fork_event(
-                    head.number(),        // fork_point
-                    head.hash(),          // fork_hash (main fork point)
+                    fork_number,          // fork_point
+                    fork_hash,            // fork_hash (main fork point)
                     head.hash(),          // main_tip
                     block.hash(),         // fork_tip
-                    0u64,                 // main_length
-                    1u64,                 // fork_length
+                    reorg_depth,          // main_length
+                    fork_length,          // fork_length
                     &format!("fork_depth_{}", reorg_depth),
                     reorg_depth,
                 ) {
@@ -418,6 +634,14 @@ impl Node {
                 "avg_block_time": snapshot.metrics.avg_block_time,
                 "total_transactions": snapshot.metrics.total_transactions,
                 "total_gas_used": snapshot.metrics.total_gas_used,
+                "block_time_percentiles": snapshot.metrics.block_time_percentiles,
+                "block_fullness_percentiles": snapshot.metrics.block_fullness_percentiles,
+                "inclusion_latency_percentiles": snapshot.metrics.inclusion_latency_percentiles,
+                "tx_type_counts": snapshot.metrics.tx_type_counts,
+                "total_fees": snapshot.metrics.fee_stats.total_fees.to_string(),
+                "total_burned": snapshot.metrics.fee_stats.total_burned.to_string(),
+                "failed_transactions": snapshot.metrics.failed_transactions,
+                "failed_tx_rate": snapshot.metrics.failed_tx_rate,
             })),
             None => Ok(serde_json::json!(null)),
         }
@@ -444,9 +668,39 @@ impl Node {
             output.push_str(&format!(
                 "# HELP kai_gas_used Total gas used\n\
                  # TYPE kai_gas_used counter\n\
-                 kai_gas_used {}\n",
+                 kai_gas_used {}\n\n",
                 snapshot.metrics.total_gas_used
             ));
+
+            let distributions = [
+                ("kai_block_time_seconds", "Block time in seconds", &snapshot.metrics.block_time_percentiles),
+                ("kai_block_fullness_percent", "Block gas used as percent of limit", &snapshot.metrics.block_fullness_percentiles),
+                ("kai_inclusion_latency_seconds", "Seconds from mempool admission to inclusion", &snapshot.metrics.inclusion_latency_percentiles),
+            ];
+            for (name, help, p) in distributions {
+                output.push_str(&format!(
+                    "# HELP {name} {help}\n\
+                     # TYPE {name} summary\n\
+                     {name}{{quantile=\"0.5\"}} {}\n\
+                     {name}{{quantile=\"0.9\"}} {}\n\
+                     {name}{{quantile=\"0.99\"}} {}\n\n",
+                    p.p50, p.p90, p.p99
+                ));
+            }
+
+            output.push_str("# HELP kai_transactions_by_type Transactions processed per type\n\
+                             # TYPE kai_transactions_by_type counter\n");
+            for (tx_type, count) in &snapshot.metrics.tx_type_counts {
+                output.push_str(&format!("kai_transactions_by_type{{type=\"{}\"}} {}\n", tx_type, count));
+            }
+            output.push('\n');
+
+            output.push_str(&format!(
+                "# HELP kai_failed_tx_rate Failed transaction rate in percent\n\
+                 # TYPE kai_failed_tx_rate gauge\n\
+                 kai_failed_tx_rate {}\n",
+                snapshot.metrics.failed_tx_rate
+            ));
         }
 
         output
@@ -512,6 +766,14 @@ fn unix_timestamp() -> u64 {
         .unwrap_or(0)
 }
 
+/// Weight a block adds to its branch in the block tree: its proposer's stake
+fn block_weight(consensus: &PoASConsensus, block: &Block) -> u128 {
+    consensus
+        .validator_set()
+        .get(&block.header.proposer)
+        .map_or(0, |validator| validator.stake.saturating_to_u128())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -538,6 +800,51 @@ mod tests {
         assert!(result.is_ok() || result.is_err());
     }
 
+    #[tokio::test]
+    async fn test_first_block_jails_nobody() {
+        use blockchain_core::{ManualClock, StakeAmount};
+        use blockchain_crypto::SignatureScheme;
+        use consensus::validator::ValidatorStatus;
+
+        let config = NodeConfig {
+            data_dir: format!("/tmp/test-node-genesis-{}", std::process::id()),
+            ..Default::default()
+        };
+        let mut node = Node::new(config).unwrap();
+
+        // Slot 1 of the chain the node just started, with every fallback window open
+        let genesis_time = node.blockchain.read().await.genesis_block().header.timestamp;
+        let clock = ManualClock::new(genesis_time + 5);
+        let keys: Vec<KeyPair> = (1..=7u8)
+            .map(|seed| KeyPair::from_secret_bytes(SignatureScheme::Ed25519, &[seed; 32]).unwrap())
+            .collect();
+        {
+            let mut consensus = node.consensus.write().await;
+            consensus.set_clock(clock.shared());
+            for kp in &keys {
+                consensus.add_genesis_validator(kp.public_key().clone(), StakeAmount::from_u64(100_000), 100).unwrap();
+            }
+        }
+
+        let mut block = None;
+        for kp in keys {
+            node.validator_key = Some(kp);
+            if let Ok(produced) = node.produce_block().await {
+                block = Some(produced);
+                break;
+            }
+        }
+        let block = block.expect("a validator may propose in slot 1");
+        assert_eq!(block.header.slot, 1);
+
+        let mut consensus = node.consensus.write().await;
+        consensus.finalize_block(&block).unwrap();
+        for validator in consensus.validator_set().all_validators() {
+            assert_eq!(validator.status, ValidatorStatus::Active);
+            assert_eq!(validator.blocks_missed, 0);
+        }
+    }
+
     #[test]
     fn test_mempool_config() {
         let config = NodeConfig::default();
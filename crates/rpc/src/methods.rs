@@ -97,7 +97,7 @@ impl RpcMethods {
         let balance = chain.state().get_balance(&address);
         
         // Convert to hex string (wei)
-        let balance_hex = format!("0x{:x}", balance.inner().to_u64_digits().first().copied().unwrap_or(0));
+        let balance_hex = format!("0x{}", balance.inner().to_str_radix(16));
         Ok(serde_json::json!(balance_hex))
     }
 
@@ -1,7 +1,7 @@
 // smart-contracts/src/gas.rs
 
 use crate::{ContractError, ContractResult};
-use blockchain_core::Gas;
+use blockchain_core::{Gas, Transaction, TX_BASE_GAS, TX_CREATE_GAS, TX_DATA_NON_ZERO_GAS, TX_DATA_ZERO_GAS};
 use serde::{Deserialize, Serialize};
 
 /// Gas configuration (Ethereum-compatible)
@@ -51,10 +51,10 @@ impl GasConfig {
     /// Ethereum mainnet gas configuration
     pub fn mainnet() -> Self {
         Self {
-            tx_gas: 21000,
-            tx_data_zero_gas: 4,
-            tx_data_non_zero_gas: 16,
-            tx_create_gas: 32000,
+            tx_gas: TX_BASE_GAS,
+            tx_data_zero_gas: TX_DATA_ZERO_GAS,
+            tx_data_non_zero_gas: TX_DATA_NON_ZERO_GAS,
+            tx_create_gas: TX_CREATE_GAS,
             sstore_set_gas: 20000,
             sstore_reset_gas: 5000,
             sstore_clears_schedule: 15000,
@@ -135,6 +135,11 @@ impl GasCalculator {
         gas
     }
 
+    /// Calculate intrinsic gas for a transaction payload
+    pub fn calculate_intrinsic_gas(&self, tx: &Transaction) -> Gas {
+        self.calculate_base_tx_gas(tx.is_create(), &tx.payload())
+    }
+
     /// Calculate memory expansion gas
     pub fn calculate_memory_gas(&self, current_size: u64, new_size: u64) -> Gas {
         if new_size <= current_size {
@@ -309,6 +314,24 @@ mod tests {
         assert_eq!(gas_create, 21000 + 32000);
     }
 
+    #[test]
+    fn test_intrinsic_gas_matches_core() {
+        let calculator = GasCalculator::mainnet();
+        let tx = Transaction::new(
+            blockchain_crypto::Address::zero(),
+            0,
+            blockchain_core::TransactionType::ContractDeployment {
+                bytecode: vec![0x60, 0x00, 0x60],
+                constructor_args: vec![0, 0],
+            },
+            1,
+            100_000,
+        );
+
+        assert_eq!(calculator.calculate_intrinsic_gas(&tx), tx.intrinsic_gas());
+        assert_eq!(tx.intrinsic_gas(), 21000 + 32000 + 3 * 4 + 2 * 16);
+    }
+
     #[test]
     fn test_memory_expansion() {
         let calculator = GasCalculator::mainnet();
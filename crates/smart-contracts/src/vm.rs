@@ -6,7 +6,7 @@ use crate::{
     state::EVMState,
     ContractError, ContractResult,
 };
-use blockchain_core::{Amount, Gas};
+use blockchain_core::{Amount, Gas, MAX_CONTRACT_CODE_SIZE};
 use blockchain_crypto::{hash::Hashable, Address, Hash};
 use serde::{Deserialize, Serialize};
 
@@ -141,6 +141,14 @@ impl EVMExecutor {
         call: ContractCall,
         gas_meter: &mut GasMeter,
     ) -> ContractResult<ExecutionResult> {
+        if call.data.len() > MAX_CONTRACT_CODE_SIZE {
+            return Err(ContractError::InvalidBytecode(format!(
+                "Code size {} exceeds maximum {}",
+                call.data.len(),
+                MAX_CONTRACT_CODE_SIZE
+            )));
+        }
+
         // Calculate deployment address
         let nonce = self.state.get_nonce(&call.from);
         let contract_address = self.state.calculate_create_address(&call.from, nonce);
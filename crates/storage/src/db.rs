@@ -2,7 +2,8 @@
 
 use crate::{PruningMode, StorageError, StorageResult};
 use blockchain_core::{Block, BlockNumber, Transaction, TransactionReceipt, WorldState};
-use blockchain_crypto::{Address, Hash};
+use blockchain_core::fork::{BlockTree, TreeNode};
+use blockchain_crypto::{Address, CryptoError, CryptoResult, Hash, SmtNode, SmtStore};
 use smart_contracts::EVMState;
 use consensus::validator::{ValidatorInfo, ValidatorSet};
 use rocksdb::{DB, Options, WriteBatch, IteratorMode};
@@ -30,6 +31,8 @@ pub enum ColumnFamily {
     ChainMetrics,
     TransactionByAddress,
     MetricsByTime,
+    SmtNodes,
+    BlockTree,
 }
 
 impl ColumnFamily {
@@ -50,6 +53,8 @@ impl ColumnFamily {
             ColumnFamily::ChainMetrics => "chain_metrics",
             ColumnFamily::TransactionByAddress => "transaction_by_address",
             ColumnFamily::MetricsByTime => "metrics_by_time",
+            ColumnFamily::SmtNodes => "smt_nodes",
+            ColumnFamily::BlockTree => "block_tree",
         }
     }
 
@@ -70,6 +75,8 @@ impl ColumnFamily {
             Self::ChainMetrics,
             Self::TransactionByAddress,
             Self::MetricsByTime,
+            Self::SmtNodes,
+            Self::BlockTree,
         ]
     }
 }
@@ -144,6 +151,53 @@ pub struct MetricsSnapshot {
     pub metrics: blockchain_core::metrics::ChainMetrics,
 }
 
+impl MetricsSnapshot {
+    /// Decode a stored snapshot, accepting the layout written before distribution,
+    /// fee and failure metrics were tracked
+    fn decode(bytes: &[u8]) -> Option<Self> {
+        bincode::deserialize::<MetricsSnapshot>(bytes)
+            .or_else(|_| bincode::deserialize::<LegacyMetricsSnapshot>(bytes).map(Into::into))
+            .ok()
+    }
+}
+
+/// Metrics snapshot layout written before distribution, fee and failure metrics were tracked
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct LegacyMetricsSnapshot {
+    block_number: BlockNumber,
+    timestamp: u64,
+    height: BlockNumber,
+    total_transactions: u64,
+    total_gas_used: u64,
+    avg_block_time: f64,
+    avg_gas_price: f64,
+    tps: f64,
+    avg_tx_per_block: f64,
+    block_size_stats: blockchain_core::metrics::SizeStats,
+    gas_stats: blockchain_core::metrics::GasStats,
+}
+
+impl From<LegacyMetricsSnapshot> for MetricsSnapshot {
+    fn from(legacy: LegacyMetricsSnapshot) -> Self {
+        Self {
+            block_number: legacy.block_number,
+            timestamp: legacy.timestamp,
+            metrics: blockchain_core::metrics::ChainMetrics {
+                height: legacy.height,
+                total_transactions: legacy.total_transactions,
+                total_gas_used: legacy.total_gas_used,
+                avg_block_time: legacy.avg_block_time,
+                avg_gas_price: legacy.avg_gas_price,
+                tps: legacy.tps,
+                avg_tx_per_block: legacy.avg_tx_per_block,
+                block_size_stats: legacy.block_size_stats,
+                gas_stats: legacy.gas_stats,
+                ..Default::default()
+            },
+        }
+    }
+}
+
 /// Fork statistics aggregated from history
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct ForkStatistics {
@@ -403,6 +457,35 @@ impl Database {
         }
     }
 
+    // ==================== SPARSE MERKLE OPERATIONS ====================
+
+    /// Store sparse Merkle tree nodes in one batch
+    pub fn store_smt_nodes(&self, nodes: &[(Hash, SmtNode)]) -> StorageResult<()> {
+        let cf = self.cf(ColumnFamily::SmtNodes)?;
+        let mut batch = WriteBatch::default();
+        for (hash, node) in nodes {
+            let bytes = bincode::serialize(node)
+                .map_err(|e| StorageError::SerializationError(e.to_string()))?;
+            batch.put_cf(cf, hash.as_bytes(), &bytes);
+        }
+        self.db.write(batch)
+            .map_err(|e| StorageError::DatabaseError(e.to_string()))
+    }
+
+    /// Get a sparse Merkle tree node by hash
+    pub fn get_smt_node(&self, hash: &Hash) -> StorageResult<Option<SmtNode>> {
+        let cf = self.cf(ColumnFamily::SmtNodes)?;
+        match self.db.get_cf(cf, hash.as_bytes())
+            .map_err(|e| StorageError::DatabaseError(e.to_string()))? {
+            Some(bytes) => {
+                let node = bincode::deserialize(&bytes)
+                    .map_err(|e| StorageError::SerializationError(e.to_string()))?;
+                Ok(Some(node))
+            }
+            None => Ok(None),
+        }
+    }
+
     // ==================== VALIDATOR OPERATIONS ====================
 
     /// Store validator set
@@ -431,6 +514,56 @@ impl Database {
         }
     }
 
+    // ==================== BLOCK TREE ====================
+
+    /// Store fork-choice tree nodes, keyed by block hash
+    pub fn store_tree_nodes(&self, nodes: &[TreeNode]) -> StorageResult<()> {
+        let cf = self.cf(ColumnFamily::BlockTree)?;
+        let mut batch = WriteBatch::default();
+        for node in nodes {
+            let bytes = bincode::serialize(node)
+                .map_err(|e| StorageError::SerializationError(e.to_string()))?;
+            batch.put_cf(cf, node.hash.as_bytes(), &bytes);
+        }
+        self.db.write(batch)
+            .map_err(|e| StorageError::DatabaseError(e.to_string()))
+    }
+
+    /// Record the fork-choice tree root and delete the nodes pruned below it
+    pub fn prune_block_tree(&self, root: &Hash, removed: &[Hash]) -> StorageResult<()> {
+        let cf = self.cf(ColumnFamily::BlockTree)?;
+        let cf_meta = self.cf(ColumnFamily::Meta)?;
+        let mut batch = WriteBatch::default();
+        batch.put_cf(cf_meta, b"block_tree_root", root.as_bytes());
+        for hash in removed {
+            batch.delete_cf(cf, hash.as_bytes());
+        }
+        self.db.write(batch)
+            .map_err(|e| StorageError::DatabaseError(e.to_string()))
+    }
+
+    /// Load the fork-choice block tree, rebuilding its ancestor tables
+    pub fn get_block_tree(&self) -> StorageResult<Option<BlockTree>> {
+        let root = match self.get_meta("block_tree_root")? {
+            Some(bytes) => Hash::from_slice(&bytes)
+                .map_err(|_| StorageError::Corruption("Invalid block tree root".into()))?,
+            None => return Ok(None),
+        };
+
+        let cf = self.cf(ColumnFamily::BlockTree)?;
+        let mut nodes = Vec::new();
+        for item in self.db.iterator_cf(cf, IteratorMode::Start) {
+            let (_key, value) = item.map_err(|e| StorageError::DatabaseError(e.to_string()))?;
+            let node: TreeNode = bincode::deserialize(&value)
+                .map_err(|e| StorageError::SerializationError(e.to_string()))?;
+            nodes.push(node);
+        }
+
+        BlockTree::restore(root, nodes)
+            .map(Some)
+            .map_err(|e| StorageError::Corruption(e.to_string()))
+    }
+
     // ==================== MEMPOOL PERSISTENCE ====================
 
     /// Store pending transactions with metadata for mempool recovery across restarts
@@ -711,7 +844,7 @@ impl Database {
         for block_num in start_block..=end_block {
             if let Some(bytes) = self.db.get_cf(cf, &block_num.to_be_bytes())
                 .map_err(|e| StorageError::DatabaseError(e.to_string()))? {
-                if let Ok(snapshot) = bincode::deserialize::<MetricsSnapshot>(&bytes) {
+                if let Some(snapshot) = MetricsSnapshot::decode(&bytes) {
                     snapshots.push(snapshot);
                 }
             }
@@ -730,7 +863,7 @@ impl Database {
             for block_num in (0..=latest_block).rev() {
                 if let Some(bytes) = self.db.get_cf(cf, &block_num.to_be_bytes())
                     .map_err(|e| StorageError::DatabaseError(e.to_string()))? {
-                    if let Ok(snapshot) = bincode::deserialize::<MetricsSnapshot>(&bytes) {
+                    if let Some(snapshot) = MetricsSnapshot::decode(&bytes) {
                         tracing::debug!("Retrieved latest metrics from block #{}", block_num);
                         return Ok(Some(snapshot));
                     }
@@ -742,7 +875,7 @@ impl Database {
         let iter = self.db.iterator_cf(cf, IteratorMode::End);
         for item in iter {
             let (_k, v) = item.map_err(|e| StorageError::DatabaseError(e.to_string()))?;
-            if let Ok(snapshot) = bincode::deserialize::<MetricsSnapshot>(&v) {
+            if let Some(snapshot) = MetricsSnapshot::decode(&v) {
                 tracing::debug!("Retrieved latest metrics via CF scan (block #{})", snapshot.block_number);
                 return Ok(Some(snapshot));
             }
@@ -797,7 +930,7 @@ impl Database {
         let iter = self.db.iterator_cf(cf, IteratorMode::Start);
         for item in iter {
             let (_k, value) = item.map_err(|e| StorageError::DatabaseError(e.to_string()))?;
-            if let Ok(snapshot) = bincode::deserialize::<MetricsSnapshot>(&value) {
+            if let Some(snapshot) = MetricsSnapshot::decode(&value) {
                 if snapshot.timestamp < cutoff_timestamp {
                     batch.delete_cf(cf, &snapshot.block_number.to_be_bytes());
                     pruned_count += 1;
@@ -969,6 +1102,18 @@ impl Database {
     }
 }
 
+impl SmtStore for Database {
+    fn get_node(&self, hash: &Hash) -> CryptoResult<Option<SmtNode>> {
+        self.get_smt_node(hash)
+            .map_err(|e| CryptoError::MerkleError(e.to_string()))
+    }
+
+    fn put_nodes(&mut self, nodes: Vec<(Hash, SmtNode)>) -> CryptoResult<()> {
+        self.store_smt_nodes(&nodes)
+            .map_err(|e| CryptoError::MerkleError(e.to_string()))
+    }
+}
+
 /// Database statistics
 #[derive(Debug, Clone)]
 pub struct DatabaseStats {
@@ -1005,6 +1150,40 @@ mod tests {
         assert_eq!(retrieved.hash(), block.hash());
     }
 
+    #[test]
+    fn test_block_tree_persistence() {
+        let (db, _temp) = create_test_db();
+        assert!(db.get_block_tree().unwrap().is_none());
+
+        let genesis = Block::genesis(Hash::zero());
+        let mut chain = vec![genesis];
+        for number in 1..=6 {
+            let parent = chain[number as usize - 1].hash();
+            chain.push(Block::new(number, parent, Hash::zero(), Address::zero(), vec![], 10_000_000).unwrap());
+        }
+
+        let mut tree = BlockTree::default();
+        for block in &chain {
+            tree.insert(&block.header, 7).unwrap();
+            db.store_tree_nodes(&[tree.get(&block.hash()).unwrap().clone()]).unwrap();
+        }
+        db.prune_block_tree(&chain[0].hash(), &[]).unwrap();
+
+        let restored = db.get_block_tree().unwrap().unwrap();
+        assert_eq!(restored.len(), 7);
+        assert_eq!(restored.cumulative_weight(&chain[6].hash()), Some(49));
+        assert_eq!(restored.ancestor_at(&chain[6].hash(), 1), Some(chain[1].hash()));
+
+        // Pruning deletes the dropped nodes and moves the root
+        let removed = tree.prune(&chain[2].hash()).unwrap();
+        db.prune_block_tree(&chain[2].hash(), &removed).unwrap();
+
+        let restored = db.get_block_tree().unwrap().unwrap();
+        assert_eq!(restored.len(), 5);
+        assert_eq!(restored.root(), Some(chain[2].hash()));
+        assert_eq!(restored.cumulative_weight(&chain[6].hash()), Some(49));
+    }
+
     #[test]
     fn test_block_by_number() {
         let (db, _temp) = create_test_db();
@@ -1206,6 +1385,51 @@ mod tests {
         assert_eq!(latest.unwrap().block_number, 1);
     }
 
+    #[test]
+    fn test_metrics_distributions_persisted() {
+        let (db, _temp) = create_test_db();
+
+        let mut metrics = blockchain_core::metrics::ChainMetrics::new();
+        metrics.block_time_percentiles.p99 = 4.0;
+        metrics.tx_type_counts.insert("transfer".into(), 12);
+        metrics.fee_stats.total_burned = blockchain_core::Amount::from_u64(630);
+        metrics.failed_tx_rate = 2.5;
+        db.store_chain_metrics(1, &metrics).unwrap();
+
+        let latest = db.get_latest_metrics().unwrap().unwrap().metrics;
+        assert_eq!(latest.block_time_percentiles.p99, 4.0);
+        assert_eq!(latest.tx_type_counts.get("transfer"), Some(&12));
+        assert_eq!(latest.fee_stats.total_burned, blockchain_core::Amount::from_u64(630));
+        assert_eq!(latest.failed_tx_rate, 2.5);
+    }
+
+    #[test]
+    fn test_legacy_metrics_snapshot_decodes() {
+        let (db, _temp) = create_test_db();
+
+        let legacy = LegacyMetricsSnapshot {
+            block_number: 1,
+            timestamp: 1_700_000_000,
+            height: 1,
+            total_transactions: 3,
+            total_gas_used: 63_000,
+            avg_block_time: 3.0,
+            avg_gas_price: 10.0,
+            tps: 1.0,
+            avg_tx_per_block: 3.0,
+            block_size_stats: Default::default(),
+            gas_stats: Default::default(),
+        };
+        let cf = db.cf(ColumnFamily::ChainMetrics).unwrap();
+        db.db.put_cf(cf, 1u64.to_be_bytes(), bincode::serialize(&legacy).unwrap()).unwrap();
+
+        let latest = db.get_latest_metrics().unwrap().unwrap();
+        assert_eq!(latest.block_number, 1);
+        assert_eq!(latest.metrics.total_transactions, 3);
+        assert_eq!(latest.metrics.failed_transactions, 0);
+        assert!(latest.metrics.tx_type_counts.is_empty());
+    }
+
     #[test]
     fn test_get_metrics_range() {
         let (db, _temp) = create_test_db();
@@ -1320,4 +1544,22 @@ mod tests {
         // Should complete without errors
         db.compact().unwrap();
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_sparse_merkle_tree_persistence() {
+        use blockchain_crypto::{HashAlgorithm, Hashable, SparseMerkleTree};
+
+        let (db, _temp) = create_test_db();
+        let key = b"account".hash();
+
+        let mut tree = SparseMerkleTree::new(db, HashAlgorithm::Blake3);
+        tree.update(key, Some(b"balance")).unwrap();
+        let root = tree.root();
+
+        let reopened = SparseMerkleTree::with_root(tree.into_store(), root, HashAlgorithm::Blake3);
+        let proof = reopened.prove(&key).unwrap();
+        assert!(proof.verify_inclusion(root, &key, b"balance", HashAlgorithm::Blake3));
+        assert!(reopened.prove(&b"missing".hash()).unwrap()
+            .verify_exclusion(root, &b"missing".hash(), HashAlgorithm::Blake3));
+    }
+}
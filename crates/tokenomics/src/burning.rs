@@ -1,7 +1,7 @@
 // tokenomics/src/burning.rs
 
 use crate::{TokenomicsError, TokenomicsResult};
-use blockchain_core::{Amount, Transaction};
+use blockchain_core::{clock::{system_clock, SharedClock}, Amount, Fixed, Transaction};
 use serde::{Deserialize, Serialize};
 
 /// Burning mechanism configuration
@@ -12,7 +12,7 @@ pub struct BurnConfig {
     pub fee_burn_rate: u16,
     /// Utility index target for excess burning
     /// Default: 1.5 (50% above baseline)
-    pub utility_target: f64,
+    pub utility_target: Fixed,
     /// Enable excess utility burning
     pub enable_excess_burn: bool,
 }
@@ -21,7 +21,7 @@ impl Default for BurnConfig {
     fn default() -> Self {
         Self {
             fee_burn_rate: 3000,  // 30%
-            utility_target: 1.5,
+            utility_target: Fixed::from_bps(15000),
             enable_excess_burn: true,
         }
     }
@@ -37,7 +37,7 @@ pub enum BurnType {
     },
     /// Excess utility burning
     ExcessUtility {
-        utility_index: f64,
+        utility_index: Fixed,
         amount: Amount,
     },
     /// Slashing burn
@@ -78,6 +78,8 @@ pub struct BurningMechanism {
     slashing_burns: Amount,
     /// Buy-back burns
     buyback_burns: Amount,
+    /// Time source for burn records
+    clock: SharedClock,
 }
 
 impl BurningMechanism {
@@ -91,19 +93,18 @@ impl BurningMechanism {
             excess_burns: Amount::zero(),
             slashing_burns: Amount::zero(),
             buyback_burns: Amount::zero(),
+            clock: system_clock(),
         }
     }
 
+    /// Replace the time source
+    pub fn set_clock(&mut self, clock: SharedClock) {
+        self.clock = clock;
+    }
+
     /// Calculate burn amount from transaction fees
     pub fn calculate_fee_burn(&self, total_fees: &Amount) -> Amount {
-        let fees_val = total_fees.inner()
-            .to_u64_digits()
-            .first()
-            .copied()
-            .unwrap_or(0) as f64;
-
-        let burn_amount = (fees_val * self.config.fee_burn_rate as f64) / 10000.0;
-        Amount::from_u64(burn_amount as u64)
+        total_fees.mul_bps(self.config.fee_burn_rate as u64)
     }
 
     /// Burn transaction fees
@@ -116,8 +117,7 @@ impl BurningMechanism {
 
         for tx in transactions {
             // Calculate fee for this transaction
-            let gas_used = 21000; // Simplified
-            let fee = tx.calculate_fee(gas_used);
+            let fee = tx.calculate_fee(tx.intrinsic_gas());
             let burn_amount = self.calculate_fee_burn(&fee);
 
             // Record burn
@@ -127,7 +127,7 @@ impl BurningMechanism {
                     amount: burn_amount.clone(),
                 },
                 block_number,
-                timestamp: current_timestamp(),
+                timestamp: self.clock.now(),
             };
 
             self.history.push(record);
@@ -148,7 +148,7 @@ impl BurningMechanism {
     /// Burn excess utility (when network utility exceeds target)
     pub fn burn_excess_utility(
         &mut self,
-        utility_index: f64,
+        utility_index: Fixed,
         treasury_fees: &Amount,
         block_number: blockchain_core::BlockNumber,
     ) -> TokenomicsResult<Amount> {
@@ -161,16 +161,11 @@ impl BurningMechanism {
         }
 
         // Calculate excess: (UI - target) / target
-        let excess_ratio = (utility_index - self.config.utility_target) / self.config.utility_target;
-        
-        let treasury_val = treasury_fees.inner()
-            .to_u64_digits()
-            .first()
-            .copied()
-            .unwrap_or(0) as f64;
+        let excess_ratio = (utility_index - self.config.utility_target)
+            .checked_div(self.config.utility_target)
+            .unwrap_or(Fixed::ONE);
 
-        let burn_amount_val = (treasury_val * excess_ratio).min(treasury_val);
-        let burn_amount = Amount::from_u64(burn_amount_val as u64);
+        let burn_amount = treasury_fees.mul_fixed(excess_ratio.min(Fixed::ONE));
 
         // Record burn
         let record = BurnRecord {
@@ -179,7 +174,7 @@ impl BurningMechanism {
                 amount: burn_amount.clone(),
             },
             block_number,
-            timestamp: current_timestamp(),
+            timestamp: self.clock.now(),
         };
 
         self.history.push(record);
@@ -206,7 +201,7 @@ impl BurningMechanism {
                 amount: amount.clone(),
             },
             block_number,
-            timestamp: current_timestamp(),
+            timestamp: self.clock.now(),
         };
 
         self.history.push(record);
@@ -231,7 +226,7 @@ impl BurningMechanism {
                 amount: amount.clone(),
             },
             block_number,
-            timestamp: current_timestamp(),
+            timestamp: self.clock.now(),
         };
 
         self.history.push(record);
@@ -272,16 +267,8 @@ impl BurningMechanism {
             return 0.0;
         }
 
-        let burned = self.total_burned.inner()
-            .to_u64_digits()
-            .first()
-            .copied()
-            .unwrap_or(0) as f64;
-        let minted = total_minted.inner()
-            .to_u64_digits()
-            .first()
-            .copied()
-            .unwrap_or(1) as f64;
+        let burned = self.total_burned.to_f64_lossy();
+        let minted = total_minted.to_f64_lossy();
 
         (burned / minted) * 100.0
     }
@@ -307,14 +294,6 @@ pub struct BurnBreakdown {
     pub buyback_burns: Amount,
 }
 
-/// Helper to get current timestamp
-fn current_timestamp() -> blockchain_core::Timestamp {
-    std::time::SystemTime::now()
-        .duration_since(std::time::UNIX_EPOCH)
-        .unwrap()
-        .as_secs()
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -369,12 +348,16 @@ mod tests {
         let treasury = Amount::from_u64(10000);
         
         // No burn when below target
-        let burn1 = mechanism.burn_excess_utility(1.2, &treasury, 100).unwrap();
+        let burn1 = mechanism.burn_excess_utility(Fixed::from_bps(12000), &treasury, 100).unwrap();
         assert_eq!(burn1, Amount::zero());
 
-        // Burn when above target
-        let burn2 = mechanism.burn_excess_utility(2.0, &treasury, 101).unwrap();
-        assert!(burn2.inner() > &Amount::zero().inner());
+        // Burn when above target: (2.0 - 1.5) / 1.5 of the treasury fees
+        let burn2 = mechanism.burn_excess_utility(Fixed::from_int(2), &treasury, 101).unwrap();
+        assert_eq!(burn2, Amount::from_u64(3333));
+
+        // Capped at the full treasury fees
+        let burn3 = mechanism.burn_excess_utility(Fixed::from_int(10), &treasury, 102).unwrap();
+        assert_eq!(burn3, treasury);
     }
 
     #[test]
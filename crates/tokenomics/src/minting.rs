@@ -5,7 +5,7 @@ use crate::{
     utility_index::UtilityIndex,
     TokenomicsError, TokenomicsResult,
 };
-use blockchain_core::{Amount, BlockNumber};
+use blockchain_core::{Amount, BlockNumber, Fixed};
 use serde::{Deserialize, Serialize};
 
 /// Minting phases
@@ -23,26 +23,26 @@ pub struct MintingConfig {
     /// Base minting rate (tokens per block)
     pub base_rate: Amount,
     /// Bootstrap multiplier α (e.g., 2.0 for 3x initial rewards)
-    pub bootstrap_multiplier: f64,
+    pub bootstrap_multiplier: Fixed,
     /// Decay constant β (e.g., 0.0001 per block)
-    pub decay_constant: f64,
+    pub decay_constant: Fixed,
     /// Minimum minting rate for Phase 2
     pub min_rate: Amount,
     /// Maximum minting rate for Phase 2
     pub max_rate: Amount,
     /// Sensitivity parameter k for sigmoid function
-    pub sensitivity: f64,
+    pub sensitivity: Fixed,
 }
 
 impl Default for MintingConfig {
     fn default() -> Self {
         Self {
             base_rate: Amount::from_u64(1000),        // 1000 tokens/block
-            bootstrap_multiplier: 2.0,                 // 3x initial (1 + 2.0)
-            decay_constant: 0.0001,
+            bootstrap_multiplier: Fixed::from_int(2),  // 3x initial (1 + 2.0)
+            decay_constant: Fixed::from_bps(1),       // 0.0001
             min_rate: Amount::from_u64(100),          // 100 tokens/block minimum
             max_rate: Amount::from_u64(2000),         // 2000 tokens/block maximum
-            sensitivity: 2.0,
+            sensitivity: Fixed::from_int(2),
         }
     }
 }
@@ -90,18 +90,13 @@ impl MintingController {
     /// Calculate Phase 1 minting rate
     /// M₁(t) = M_base × (1 + α × e^(-βt))
     pub fn calculate_phase1_rate(&self, block_number: BlockNumber) -> Amount {
-        let t = block_number.saturating_sub(self.genesis_block) as f64;
-        let base = self.config.base_rate.inner()
-            .to_u64_digits()
-            .first()
-            .copied()
-            .unwrap_or(0) as f64;
-        
-        let decay = (-self.config.decay_constant * t).exp();
-        let multiplier = 1.0 + self.config.bootstrap_multiplier * decay;
-        let rate = base * multiplier;
-        
-        Amount::from_u64(rate as u64)
+        let elapsed = block_number.saturating_sub(self.genesis_block);
+        let t = Fixed::from_int(i64::try_from(elapsed).unwrap_or(i64::MAX));
+
+        let decay = (-(self.config.decay_constant * t)).exp();
+        let multiplier = Fixed::ONE + self.config.bootstrap_multiplier * decay;
+
+        self.config.base_rate.mul_fixed(multiplier)
     }
 
     /// Calculate Phase 2 minting rate
@@ -113,28 +108,19 @@ impl MintingController {
             ))?;
 
         let ui = utility_index.calculate();
-        let sigmoid_input = ui - 1.0;
+        let sigmoid_input = ui - Fixed::ONE;
         let sigmoid_value = self.sigmoid(sigmoid_input);
 
-        let min = self.config.min_rate.inner()
-            .to_u64_digits()
-            .first()
-            .copied()
-            .unwrap_or(0) as f64;
-        let max = self.config.max_rate.inner()
-            .to_u64_digits()
-            .first()
-            .copied()
-            .unwrap_or(0) as f64;
-
-        let rate = min + (max - min) * sigmoid_value;
-        
-        Ok(Amount::from_u64(rate as u64))
+        let min = &self.config.min_rate;
+        let range = self.config.max_rate.saturating_sub(min);
+
+        Ok(min.clone() + range.mul_fixed(sigmoid_value))
     }
 
     /// Sigmoid function: 1 / (1 + e^(-k×x))
-    fn sigmoid(&self, x: f64) -> f64 {
-        1.0 / (1.0 + (-self.config.sensitivity * x).exp())
+    fn sigmoid(&self, x: Fixed) -> Fixed {
+        let denominator = Fixed::ONE + (-(self.config.sensitivity * x)).exp();
+        Fixed::ONE.checked_div(denominator).unwrap_or(Fixed::ZERO)
     }
 
     /// Calculate blended minting rate (during transition)
@@ -148,20 +134,10 @@ impl MintingController {
         let phase1_rate = self.calculate_phase1_rate(block_number);
         let phase2_rate = self.calculate_phase2_rate()?;
 
-        let phase1_val = phase1_rate.inner()
-            .to_u64_digits()
-            .first()
-            .copied()
-            .unwrap_or(0) as f64;
-        let phase2_val = phase2_rate.inner()
-            .to_u64_digits()
-            .first()
-            .copied()
-            .unwrap_or(0) as f64;
-
-        let blended = phase1_val * blend_factor + phase2_val * (1.0 - blend_factor);
+        let blended = phase1_rate.mul_fixed(blend_factor)
+            + phase2_rate.mul_fixed(Fixed::ONE - blend_factor);
         
-        Ok(Amount::from_u64(blended as u64))
+        Ok(blended)
     }
 
     /// Get minting rate for current block
@@ -206,26 +182,22 @@ impl MintingController {
         self.utility_index.as_ref()
     }
 
-    /// Calculate projected annual inflation rate
-    pub fn calculate_annual_inflation(&mut self, block_number: BlockNumber, total_supply: &Amount) -> TokenomicsResult<f64> {
+    /// Calculate projected annual inflation rate, in percent
+    pub fn calculate_annual_inflation(&mut self, block_number: BlockNumber, total_supply: &Amount) -> TokenomicsResult<Fixed> {
         let rate_per_block = self.get_minting_rate(block_number)?;
         
         // Blocks per year (assuming 3s blocks)
         let blocks_per_year = 365 * 24 * 60 * 60 / 3;
         
-        let annual_minting = rate_per_block.inner()
-            .to_u64_digits()
-            .first()
-            .copied()
-            .unwrap_or(0) as f64 * blocks_per_year as f64;
-
-        let supply = total_supply.inner()
-            .to_u64_digits()
-            .first()
-            .copied()
-            .unwrap_or(1) as f64;
-
-        Ok((annual_minting / supply) * 100.0)
+        if total_supply.is_zero() {
+            return Err(TokenomicsError::MintingError("Total supply is zero".into()));
+        }
+
+        let annual_minting = rate_per_block.checked_mul_u64(blocks_per_year * 100)
+            .ok_or_else(|| TokenomicsError::OverflowError("Annual minting overflow".into()))?;
+
+        annual_minting.ratio_fixed(total_supply)
+            .ok_or_else(|| TokenomicsError::OverflowError("Inflation rate overflow".into()))
     }
 }
 
@@ -278,14 +250,20 @@ mod tests {
         let phase_manager = PhaseManager::new(IVTConfig::default());
         let controller = MintingController::new(config, phase_manager, 0);
 
-        // Sigmoid(0) should be 0.5
-        assert!((controller.sigmoid(0.0) - 0.5).abs() < 0.01);
-        
+        let half = Fixed::from_ratio(1, 2).unwrap();
+
+        // Sigmoid(0) is exactly 0.5
+        assert_eq!(controller.sigmoid(Fixed::ZERO), half);
+
         // Sigmoid of positive should be > 0.5
-        assert!(controller.sigmoid(1.0) > 0.5);
-        
+        assert!(controller.sigmoid(Fixed::ONE) > half);
+
         // Sigmoid of negative should be < 0.5
-        assert!(controller.sigmoid(-1.0) < 0.5);
+        assert!(controller.sigmoid(-Fixed::ONE) < half);
+
+        // Saturates instead of overflowing at the extremes
+        assert_eq!(controller.sigmoid(Fixed::from_int(1_000_000)), Fixed::ONE);
+        assert_eq!(controller.sigmoid(Fixed::from_int(-1_000_000)), Fixed::ZERO);
     }
 
     #[test]
@@ -306,10 +284,55 @@ mod tests {
         let phase_manager = PhaseManager::new(IVTConfig::default());
         let mut controller = MintingController::new(config, phase_manager, 0);
 
-        let total_supply = Amount::from_u64(100_000_000); // 100M tokens
+        let total_supply = Amount::from_tokens(100_000_000); // 100M tokens
         let inflation = controller.calculate_annual_inflation(0, &total_supply).unwrap();
 
-        assert!(inflation > 0.0);
-        assert!(inflation < 100.0); // Reasonable inflation rate
+        assert!(inflation > Fixed::ZERO);
+        assert!(inflation < Fixed::from_int(100)); // Reasonable inflation rate
+    }
+
+    #[test]
+    fn test_phase1_reference_values() {
+        let config = MintingConfig::default();
+        let phase_manager = PhaseManager::new(IVTConfig::default());
+        let controller = MintingController::new(config, phase_manager, 0);
+
+        // 1000 × (1 + 2) at genesis
+        assert_eq!(controller.calculate_phase1_rate(0), Amount::from_u64(3000));
+        // 1000 × (1 + 2e^-1) = 1735.758...
+        assert_eq!(controller.calculate_phase1_rate(10_000), Amount::from_u64(1735));
+        // Decay has vanished long after genesis
+        assert_eq!(controller.calculate_phase1_rate(u64::MAX), Amount::from_u64(1000));
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn prop_phase1_matches_float_reference(block in 0u64..500_000) {
+            let config = MintingConfig {
+                base_rate: Amount::from_tokens(1000),
+                ..MintingConfig::default()
+            };
+            let phase_manager = PhaseManager::new(IVTConfig::default());
+            let controller = MintingController::new(config, phase_manager, 0);
+
+            let reference = 1000.0 * (1.0 + 2.0 * (-0.0001 * block as f64).exp());
+            let rate = controller.calculate_phase1_rate(block).to_f64_lossy() / 1e18;
+            proptest::prop_assert!((rate - reference).abs() <= reference * 1e-12);
+        }
+
+        #[test]
+        fn prop_sigmoid_matches_float_reference(raw in -10_000_000_000_000_000_000i128..10_000_000_000_000_000_000) {
+            let config = MintingConfig::default();
+            let phase_manager = PhaseManager::new(IVTConfig::default());
+            let controller = MintingController::new(config, phase_manager, 0);
+
+            let x = Fixed::from_raw(raw);
+            let reference = 1.0 / (1.0 + (-2.0 * x.to_f64_lossy()).exp());
+            let value = controller.sigmoid(x).to_f64_lossy();
+            proptest::prop_assert!((value - reference).abs() <= 1e-12);
+            // Symmetric around zero: σ(x) + σ(-x) = 1
+            let sum = controller.sigmoid(x) + controller.sigmoid(-x);
+            proptest::prop_assert!((sum - Fixed::ONE).raw().abs() <= 2);
+        }
     }
 }
\ No newline at end of file
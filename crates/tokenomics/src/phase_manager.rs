@@ -5,7 +5,7 @@ use crate::{
     utility_index::{UtilityIndex, UtilityMetrics},
     TokenomicsError, TokenomicsResult,
 };
-use blockchain_core::{BlockNumber, Timestamp};
+use blockchain_core::{clock::{system_clock, SharedClock}, BlockNumber, Fixed, Timestamp};
 use serde::{Deserialize, Serialize};
 
 /// Initial Volume Threshold configuration
@@ -69,6 +69,8 @@ pub struct PhaseManager {
     blend_period_blocks: u64,
     /// Block when actual transition starts
     transition_start_block: Option<BlockNumber>,
+    /// Time source for transition records
+    clock: SharedClock,
 }
 
 impl PhaseManager {
@@ -84,9 +86,15 @@ impl PhaseManager {
             notice_block: None,
             blend_period_blocks: 864_000,   // ~30 days at 3s blocks
             transition_start_block: None,
+            clock: system_clock(),
         }
     }
 
+    /// Replace the time source
+    pub fn set_clock(&mut self, clock: SharedClock) {
+        self.clock = clock;
+    }
+
     /// Get current phase
     pub fn current_phase(&self) -> MintingPhase {
         self.current_phase
@@ -162,7 +170,7 @@ impl PhaseManager {
         // Create transition record
         let transition = PhaseTransition {
             block_number: current_block,
-            timestamp: current_timestamp(),
+            timestamp: self.clock.now(),
             metrics: metrics.clone(),
             governance_override: false,
         };
@@ -203,7 +211,7 @@ impl PhaseManager {
         
         let transition = PhaseTransition {
             block_number: current_block,
-            timestamp: current_timestamp(),
+            timestamp: self.clock.now(),
             metrics: metrics.clone(),
             governance_override: true,
         };
@@ -225,24 +233,25 @@ impl PhaseManager {
     /// Returns value between 0 and 1:
     /// - 1.0 = fully Phase 1
     /// - 0.0 = fully Phase 2
-    pub fn calculate_blend_factor(& mut self, current_block: BlockNumber) -> f64 {
+    pub fn calculate_blend_factor(& mut self, current_block: BlockNumber) -> Fixed {
         if let Some(start_block) = self.transition_start_block {
             if current_block < start_block {
-                return 1.0; // Still in Phase 1
+                return Fixed::ONE; // Still in Phase 1
             }
 
             let blocks_since_transition = current_block - start_block;
             
             if blocks_since_transition >= self.blend_period_blocks {
                 self.current_phase = MintingPhase::UtilityDriven;
-                return 0.0; // Fully Phase 2
+                return Fixed::ZERO; // Fully Phase 2
             }
 
             // Linear interpolation
-            let progress = blocks_since_transition as f64 / self.blend_period_blocks as f64;
-            1.0 - progress
+            let progress = Fixed::from_ratio(blocks_since_transition, self.blend_period_blocks)
+                .unwrap_or(Fixed::ONE);
+            Fixed::ONE - progress
         } else {
-            1.0 // No transition started
+            Fixed::ONE // No transition started
         }
     }
 
@@ -305,14 +314,6 @@ pub struct PhaseProgress {
     pub transition_start_block: Option<BlockNumber>,
 }
 
-/// Helper to get current timestamp
-fn current_timestamp() -> Timestamp {
-    std::time::SystemTime::now()
-        .duration_since(std::time::UNIX_EPOCH)
-        .unwrap()
-        .as_secs()
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -367,15 +368,15 @@ mod tests {
         manager.transition_start_block = Some(1000);
         
         // At start
-        assert_eq!(manager.calculate_blend_factor(1000), 1.0);
+        assert_eq!(manager.calculate_blend_factor(1000), Fixed::ONE);
         
         // Halfway through
         let halfway = 1000 + manager.blend_period_blocks / 2;
-        assert!((manager.calculate_blend_factor(halfway) - 0.5).abs() < 0.01);
+        assert_eq!(manager.calculate_blend_factor(halfway), Fixed::from_ratio(1, 2).unwrap());
         
         // After completion
         let end = 1000 + manager.blend_period_blocks;
-        assert_eq!(manager.calculate_blend_factor(end), 0.0);
+        assert_eq!(manager.calculate_blend_factor(end), Fixed::ZERO);
     }
 
     #[test]
@@ -5,7 +5,7 @@ use crate::{
     utility_index::UtilityIndex,
     TokenomicsError, TokenomicsResult,
 };
-use blockchain_core::{Amount, StakeAmount};
+use blockchain_core::{Amount, Fixed, Rounding, StakeAmount};
 use blockchain_crypto::Address;
 use consensus::validator::ValidatorInfo;
 use serde::{Deserialize, Serialize};
@@ -19,13 +19,13 @@ pub struct RewardCalculation {
     /// Base reward amount
     pub base_reward: Amount,
     /// Stake weight multiplier
-    pub stake_weight: f64,
+    pub stake_weight: Fixed,
     /// Time factor multiplier
-    pub time_factor: f64,
+    pub time_factor: Fixed,
     /// Block production bonus
-    pub block_bonus: f64,
+    pub block_bonus: Fixed,
     /// Utility contribution factor (Phase 2)
-    pub utility_factor: Option<f64>,
+    pub utility_factor: Option<Fixed>,
     /// Final reward amount
     pub final_reward: Amount,
     /// Commission deducted
@@ -75,41 +75,23 @@ impl RewardDistributor {
         produced_block: bool,
     ) -> TokenomicsResult<RewardCalculation> {
         // Calculate stake weight
-        let stake_weight = if !total_stake.is_zero() {
-            let validator_stake = validator.stake.inner()
-                .to_u64_digits()
-                .first()
-                .copied()
-                .unwrap_or(0) as f64;
-            let total = total_stake.inner()
-                .to_u64_digits()
-                .first()
-                .copied()
-                .unwrap_or(1) as f64;
-            validator_stake / total
-        } else {
-            0.0
-        };
+        let stake_weight = validator.stake.ratio_fixed(total_stake).unwrap_or(Fixed::ZERO);
 
         // Calculate time factor: min(1, blocks_staked / 100,000) T(i,t)
-        let time_factor = (blocks_staked as f64 / 100_000.0).min(1.0);
+        let time_factor = Fixed::from_ratio(blocks_staked.min(100_000), 100_000)
+            .unwrap_or(Fixed::ONE);
 
         // Block production bonus: 1.2 if produced block, else 1.0
-        let block_bonus = if produced_block { 1.2 } else { 1.0 };
-
-        // Calculate base reward
-        let network_mint_val = network_mint.inner()
-            .to_u64_digits()
-            .first()
-            .copied()
-            .unwrap_or(0) as f64;
+        let block_bonus = Self::block_bonus(produced_block);
 
-        let base_reward_val = network_mint_val * stake_weight * time_factor * block_bonus;
-        let base_reward = Amount::from_u64(base_reward_val as u64);
+        // Calculate base reward: stake share in exact integer math, then time and bonus factors
+        let stake_share = network_mint
+            .mul_div(&validator.stake, total_stake, Rounding::Down)
+            .unwrap_or_else(Amount::zero);
+        let base_reward = stake_share.mul_fixed(time_factor * block_bonus);
 
         // Calculate commission
-        let commission_val = (base_reward_val * validator.commission_rate as f64) / 10000.0;
-        let commission = Amount::from_u64(commission_val as u64);
+        let commission = base_reward.mul_bps(validator.commission_rate as u64);
 
         let net_reward = base_reward.checked_sub(&commission)
             .unwrap_or_else(|| base_reward.clone());
@@ -133,33 +115,25 @@ impl RewardDistributor {
         &self,
         validator: &ValidatorInfo,
         network_mint: &Amount,
-        total_utility: f64,
-        validator_utility: f64,
+        total_utility: Fixed,
+        validator_utility: Fixed,
         produced_block: bool,
     ) -> TokenomicsResult<RewardCalculation> {
         // Calculate utility contribution weight
-        let utility_weight = if total_utility > 0.0 {
-            validator_utility / total_utility
+        let utility_weight = if total_utility > Fixed::ZERO {
+            validator_utility.checked_div(total_utility).unwrap_or(Fixed::ZERO)
         } else {
-            0.0
+            Fixed::ZERO
         };
 
         // Block production bonus
-        let block_bonus = if produced_block { 1.2 } else { 1.0 };
+        let block_bonus = Self::block_bonus(produced_block);
 
         // Calculate base reward
-        let network_mint_val = network_mint.inner()
-            .to_u64_digits()
-            .first()
-            .copied()
-            .unwrap_or(0) as f64;
-
-        let base_reward_val = network_mint_val * utility_weight * block_bonus;
-        let base_reward = Amount::from_u64(base_reward_val as u64);
+        let base_reward = network_mint.mul_fixed(utility_weight * block_bonus);
 
         // Calculate commission
-        let commission_val = (base_reward_val * validator.commission_rate as f64) / 10000.0;
-        let commission = Amount::from_u64(commission_val as u64);
+        let commission = base_reward.mul_bps(validator.commission_rate as u64);
 
         let net_reward = base_reward.checked_sub(&commission)
             .unwrap_or_else(|| base_reward.clone());
@@ -168,7 +142,7 @@ impl RewardDistributor {
             validator: validator.address,
             base_reward: base_reward.clone(),
             stake_weight: utility_weight,
-            time_factor: 1.0,
+            time_factor: Fixed::ONE,
             block_bonus,
             utility_factor: Some(utility_weight),
             final_reward: base_reward.clone(),
@@ -177,6 +151,15 @@ impl RewardDistributor {
         })
     }
 
+    /// Block production bonus: 1.2 for the producer, 1.0 otherwise
+    fn block_bonus(produced_block: bool) -> Fixed {
+        if produced_block {
+            Fixed::from_bps(12000)
+        } else {
+            Fixed::ONE
+        }
+    }
+
     /// Calculate rewards for all validators
     pub fn distribute_rewards(
         &mut self,
@@ -212,7 +195,7 @@ impl RewardDistributor {
                     ))?;
 
                 // Calculate total utility contribution
-                let total_utility: f64 = validators.iter()
+                let total_utility: Fixed = validators.iter()
                     .map(|v| self.calculate_validator_utility(v, utility_index))
                     .sum();
 
@@ -243,30 +226,22 @@ impl RewardDistributor {
         &self,
         validator: &ValidatorInfo,
         _utility_index: &UtilityIndex,
-    ) -> f64 {
+    ) -> Fixed {
         // Simplified: based on validator's metrics
         // In production, this would calculate actual contribution to each metric
 
-        let base_contribution = validator.utility_score.value() as f64;
+        let base_contribution = Fixed::from_int(
+            i64::try_from(validator.utility_score.value()).unwrap_or(i64::MAX),
+        );
         let liquidity_factor = if !validator.stake.is_zero() {
-            let deployed = validator.liquidity_deployed.inner()
-                .to_u64_digits()
-                .first()
-                .copied()
-                .unwrap_or(0) as f64;
-            let staked = validator.stake.inner()
-                .to_u64_digits()
-                .first()
-                .copied()
-                .unwrap_or(1) as f64;
-            deployed / staked
+            validator.liquidity_deployed.ratio_fixed(&validator.stake).unwrap_or(Fixed::MAX)
         } else {
-            0.0
+            Fixed::ZERO
         };
 
-        let uptime_factor = validator.uptime as f64 / 10000.0;
+        let uptime_factor = validator.reliability_factor();
 
-        base_contribution * (1.0 + liquidity_factor) * uptime_factor
+        base_contribution * (Fixed::ONE + liquidity_factor) * uptime_factor
     }
 
     /// Get reward history
@@ -296,10 +271,11 @@ impl RewardDistributor {
 mod tests {
     use super::*;
     use blockchain_core::UtilityScore;
+    use blockchain_crypto::{PublicKey, SignatureScheme};
 
     fn create_test_validator(stake: u64, utility: u64, commission: u16) -> ValidatorInfo {
         let mut validator = ValidatorInfo::new(
-            Address::zero(),
+            PublicKey::new(SignatureScheme::Ed25519, vec![0u8; 32]),
             StakeAmount::from_u64(stake),
             commission,
         );
@@ -324,9 +300,11 @@ mod tests {
         ).unwrap();
 
         assert!(reward.final_reward.inner() > &Amount::zero().inner());
-        assert_eq!(reward.stake_weight, 0.1); // 10000/100000
-        assert_eq!(reward.time_factor, 1.0);
-        assert_eq!(reward.block_bonus, 1.2);
+        assert_eq!(reward.stake_weight, Fixed::from_ratio(1, 10).unwrap()); // 10000/100000
+        assert_eq!(reward.time_factor, Fixed::ONE);
+        assert_eq!(reward.block_bonus, Fixed::from_ratio(6, 5).unwrap());
+        // 1000 × 0.1 × 1.0 × 1.2
+        assert_eq!(reward.final_reward, Amount::from_u64(120));
     }
 
     #[test]
@@ -338,13 +316,13 @@ mod tests {
         let reward = distributor.calculate_phase2_reward(
             &validator,
             &network_mint,
-            10000.0,
-            5000.0,
+            Fixed::from_int(10000),
+            Fixed::from_int(5000),
             false,
         ).unwrap();
 
-        assert!(reward.final_reward.inner() > &Amount::zero().inner());
-        assert!(reward.utility_factor.is_some());
+        assert_eq!(reward.utility_factor, Some(Fixed::from_ratio(1, 2).unwrap()));
+        assert_eq!(reward.final_reward, Amount::from_u64(500));
     }
 
     #[test]
@@ -365,4 +343,33 @@ mod tests {
         assert!(reward.commission.inner() > &Amount::zero().inner());
         assert!(reward.net_reward.inner() < reward.final_reward.inner());
     }
-}
\ No newline at end of file
+
+    proptest::proptest! {
+        #[test]
+        fn prop_phase1_reward_matches_integer_reference(
+            stake in 1u64..1_000_000_000,
+            extra_stake in 0u64..1_000_000_000,
+            mint in 0u64..1_000_000_000_000,
+            blocks_staked in 0u64..200_000,
+            produced_block: bool,
+        ) {
+            let distributor = RewardDistributor::new(MintingPhase::Bootstrap);
+            let validator = create_test_validator(stake, 0, 0);
+            let total = stake + extra_stake;
+
+            let reward = distributor.calculate_phase1_reward(
+                &validator,
+                &Amount::from_u64(mint),
+                &StakeAmount::from_u64(total),
+                blocks_staked,
+                produced_block,
+            ).unwrap();
+
+            // floor(floor(mint × stake / total) × (blocks / 100000) × bonus)
+            let share = mint as u128 * stake as u128 / total as u128;
+            let bonus = if produced_block { 12 } else { 10 };
+            let reference = share * blocks_staked.min(100_000) as u128 * bonus / 1_000_000;
+            proptest::prop_assert_eq!(reward.final_reward, Amount::from_u128(reference));
+        }
+    }
+}
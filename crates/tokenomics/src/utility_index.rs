@@ -1,7 +1,7 @@
 // tokenomics/src/utility_index.rs
 
 use crate::{TokenomicsError, TokenomicsResult};
-use blockchain_core::{Amount, BlockNumber};
+use blockchain_core::{Amount, BlockNumber, Fixed};
 use serde::{Deserialize, Serialize};
 
 /// Utility metrics tracked by the network
@@ -58,36 +58,46 @@ impl UtilityMetrics {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MetricWeights {
     /// Transaction volume weight (default: 0.30)
-    pub tx_volume: f64,
+    pub tx_volume: Fixed,
     /// TVL weight (default: 0.25)
-    pub tvl: f64,
+    pub tvl: Fixed,
     /// Active users weight (default: 0.20)
-    pub active_users: f64,
+    pub active_users: Fixed,
     /// Contract interactions weight (default: 0.15)
-    pub contract_interactions: f64,
+    pub contract_interactions: Fixed,
     /// Bridge volume weight (default: 0.10)
-    pub bridge_volume: f64,
+    pub bridge_volume: Fixed,
 }
 
 impl MetricWeights {
     /// Create default weights as per whitepaper
     pub fn default_weights() -> Self {
         Self {
-            tx_volume: 0.30,
-            tvl: 0.25,
-            active_users: 0.20,
-            contract_interactions: 0.15,
-            bridge_volume: 0.10,
+            tx_volume: Fixed::from_bps(3000),
+            tvl: Fixed::from_bps(2500),
+            active_users: Fixed::from_bps(2000),
+            contract_interactions: Fixed::from_bps(1500),
+            bridge_volume: Fixed::from_bps(1000),
         }
     }
 
     /// Validate that weights sum to 1.0
     pub fn validate(&self) -> TokenomicsResult<()> {
-        let sum = self.tx_volume + self.tvl + self.active_users 
-                + self.contract_interactions + self.bridge_volume;
-        
-        // Allow small floating point error
-        if (sum - 1.0).abs() > 0.0001 {
+        let weights = [
+            self.tx_volume,
+            self.tvl,
+            self.active_users,
+            self.contract_interactions,
+            self.bridge_volume,
+        ];
+        if weights.iter().any(Fixed::is_negative) {
+            return Err(TokenomicsError::InvalidConfiguration(
+                "Metric weights must not be negative".into()
+            ));
+        }
+
+        let sum: Fixed = weights.into_iter().sum();
+        if sum != Fixed::ONE {
             return Err(TokenomicsError::InvalidConfiguration(
                 format!("Metric weights must sum to 1.0, got {}", sum)
             ));
@@ -130,8 +140,8 @@ impl UtilityIndex {
 
     /// Calculate utility index value
     /// UI(t) = Σ(w_k × [M_k(t) / M_k(baseline)])
-    pub fn calculate(&self) -> f64 {
-        let mut index = 0.0;
+    pub fn calculate(&self) -> Fixed {
+        let mut index = Fixed::ZERO;
 
         // Transaction volume contribution
         if !self.baseline.tx_volume.is_zero() {
@@ -139,7 +149,7 @@ impl UtilityIndex {
                 &self.current.tx_volume,
                 &self.baseline.tx_volume,
             );
-            index += self.weights.tx_volume * ratio;
+            index = index + self.weights.tx_volume * ratio;
         }
 
         // TVL contribution
@@ -148,19 +158,21 @@ impl UtilityIndex {
                 &self.current.total_value_locked,
                 &self.baseline.total_value_locked,
             );
-            index += self.weights.tvl * ratio;
+            index = index + self.weights.tvl * ratio;
         }
 
         // Active users contribution
         if self.baseline.unique_addresses > 0 {
-            let ratio = self.current.unique_addresses as f64 / self.baseline.unique_addresses as f64;
-            index += self.weights.active_users * ratio;
+            let ratio = Fixed::from_ratio(self.current.unique_addresses, self.baseline.unique_addresses)
+                .unwrap_or(Fixed::MAX);
+            index = index + self.weights.active_users * ratio;
         }
 
         // Contract interactions contribution
         if self.baseline.contract_interactions > 0 {
-            let ratio = self.current.contract_interactions as f64 / self.baseline.contract_interactions as f64;
-            index += self.weights.contract_interactions * ratio;
+            let ratio = Fixed::from_ratio(self.current.contract_interactions, self.baseline.contract_interactions)
+                .unwrap_or(Fixed::MAX);
+            index = index + self.weights.contract_interactions * ratio;
         }
 
         // Bridge volume contribution
@@ -169,31 +181,20 @@ impl UtilityIndex {
                 &self.current.bridge_volume,
                 &self.baseline.bridge_volume,
             );
-            index += self.weights.bridge_volume * ratio;
+            index = index + self.weights.bridge_volume * ratio;
         }
 
         index
     }
 
     /// Calculate ratio between two amounts
-    fn calculate_ratio(&self, current: &Amount, baseline: &Amount) -> f64 {
+    fn calculate_ratio(&self, current: &Amount, baseline: &Amount) -> Fixed {
         if baseline.is_zero() {
-            return 1.0;
+            return Fixed::ONE;
         }
 
-        // Convert to f64 for calculation (simplified)
-        let current_val = current.inner()
-            .to_u64_digits()
-            .first()
-            .copied()
-            .unwrap_or(0) as f64;
-        let baseline_val = baseline.inner()
-            .to_u64_digits()
-            .first()
-            .copied()
-            .unwrap_or(1) as f64;
-
-        current_val / baseline_val
+        // Saturate ratios too large to represent
+        current.ratio_fixed(baseline).unwrap_or(Fixed::MAX)
     }
 
     /// Get baseline metrics
@@ -220,20 +221,20 @@ impl UtilityIndex {
 
     /// Check if utility is above baseline
     pub fn is_above_baseline(&self) -> bool {
-        self.calculate() > 1.0
+        self.calculate() > Fixed::ONE
     }
 
     /// Get utility category
     pub fn utility_category(&self) -> UtilityCategory {
         let index = self.calculate();
         
-        if index < 0.5 {
+        if index < Fixed::from_bps(5000) {
             UtilityCategory::VeryLow
-        } else if index < 1.0 {
+        } else if index < Fixed::ONE {
             UtilityCategory::Low
-        } else if index < 1.5 {
+        } else if index < Fixed::from_bps(15000) {
             UtilityCategory::Normal
-        } else if index < 2.0 {
+        } else if index < Fixed::from_int(2) {
             UtilityCategory::High
         } else {
             UtilityCategory::VeryHigh
@@ -272,13 +273,22 @@ mod tests {
         assert!(weights.validate().is_ok());
 
         let invalid_weights = MetricWeights {
-            tx_volume: 0.5,
-            tvl: 0.5,
-            active_users: 0.5,
-            contract_interactions: 0.0,
-            bridge_volume: 0.0,
+            tx_volume: Fixed::from_bps(5000),
+            tvl: Fixed::from_bps(5000),
+            active_users: Fixed::from_bps(5000),
+            contract_interactions: Fixed::ZERO,
+            bridge_volume: Fixed::ZERO,
         };
         assert!(invalid_weights.validate().is_err());
+
+        let negative_weights = MetricWeights {
+            tx_volume: Fixed::from_bps(15000),
+            tvl: -Fixed::from_bps(5000),
+            active_users: Fixed::ZERO,
+            contract_interactions: Fixed::ZERO,
+            bridge_volume: Fixed::ZERO,
+        };
+        assert!(negative_weights.validate().is_err());
     }
 
     #[test]
@@ -290,8 +300,7 @@ mod tests {
         let mut index_mut = index;
         index_mut.update_metrics(baseline);
         
-        let value = index_mut.calculate();
-        assert!((value - 1.0).abs() < 0.1);
+        assert_eq!(index_mut.calculate(), Fixed::ONE);
     }
 
     #[test]
@@ -310,9 +319,7 @@ mod tests {
         );
         
         index.update_metrics(current);
-        let value = index.calculate();
-        
-        assert!(value > 1.9); // Should be close to 2.0
+        assert_eq!(index.calculate(), Fixed::from_int(2));
         assert!(index.is_above_baseline());
     }
 
@@ -345,4 +352,34 @@ mod tests {
         index.update_metrics(high_metrics);
         assert_eq!(index.utility_category(), UtilityCategory::High);
     }
-}
\ No newline at end of file
+
+    proptest::proptest! {
+        #[test]
+        fn prop_index_matches_float_reference(
+            tx in 0u64..100_000_000,
+            tvl in 0u64..100_000_000,
+            users in 0u64..1_000_000,
+            interactions in 0u64..1_000_000,
+            bridge in 0u64..100_000_000,
+        ) {
+            let baseline = create_baseline();
+            let mut index = UtilityIndex::with_baseline(baseline);
+            index.update_metrics(UtilityMetrics::with_values(
+                200000,
+                Amount::from_u64(tx),
+                Amount::from_u64(tvl),
+                users,
+                interactions,
+                Amount::from_u64(bridge),
+            ));
+
+            let reference = 0.30 * tx as f64 / 1_000_000.0
+                + 0.25 * tvl as f64 / 5_000_000.0
+                + 0.20 * users as f64 / 10_000.0
+                + 0.15 * interactions as f64 / 50_000.0
+                + 0.10 * bridge as f64 / 500_000.0;
+            let value = index.calculate().to_f64_lossy();
+            proptest::prop_assert!((value - reference).abs() <= reference * 1e-12 + 1e-17);
+        }
+    }
+}